@@ -2,62 +2,435 @@
 //!
 //! This module provides token resolution with fallback chain:
 //!
-//! 1. Repository-specific token (if configured)
-//! 2. Global `github_token` from config
-//! 3. `gh auth token` command (GitHub CLI)
-//! 4. Unauthenticated (returns `None`)
+//! 1. Repository-specific token, `token_env`, or `token_command` (if configured)
+//! 2. GitHub App installation token (if `github_app` is configured)
+//! 3. Environment variable named by `github_token_env` (if configured)
+//! 4. `WHIP_GITHUB_TOKEN` or `GITHUB_TOKEN` environment variables
+//! 5. Global `github_token_command` or `github_token` from config
+//! 6. `gh auth token` command (GitHub CLI)
+//! 7. Unauthenticated (returns `None`)
+//!
+//! [`resolve_token_with_source`] additionally reports which [`TokenSource`]
+//! provided the token, so it can be shown to the user.
+//!
+//! Token commands are run at most once per command until their token is
+//! rejected: the token they print is cached in memory, so fetching many
+//! repositories doesn't prompt a password manager once per repository.
+//! [`forget_rejected_token`] and [`forget_command_tokens`] drop cached
+//! tokens, so a rotated or expired token is picked up again.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use tokio::sync::OnceCell;
 
-use crate::Repository;
 use crate::error::{ConfigError, Result};
+use crate::{Config, Repository};
+
+/// Environment variable checked for a whip-specific GitHub token.
+pub const WHIP_GITHUB_TOKEN_ENV: &str = "WHIP_GITHUB_TOKEN";
+
+/// Environment variable checked for a generic GitHub token.
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// How long a token command may run before it is killed.
+pub const TOKEN_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Tokens printed by token commands so far, by command.
+///
+/// Each command has its own cell, so concurrent resolutions wait for a
+/// single run instead of starting one each. A failed run leaves the cell
+/// empty and the command is tried again next time; so does removing the
+/// command's entry.
+static COMMAND_TOKENS: LazyLock<Mutex<HashMap<String, Arc<OnceCell<String>>>>> =
+    LazyLock::new(Mutex::default);
+
+/// Where a resolved GitHub token came from.
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::auth::TokenSource;
+///
+/// let source = TokenSource::Env("GITHUB_TOKEN".to_string());
+/// assert_eq!(source.to_string(), "$GITHUB_TOKEN");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TokenSource {
+    /// The repository's plaintext `token`.
+    RepositoryToken,
+    /// The environment variable named by the repository's `token_env`.
+    RepositoryEnv(String),
+    /// The repository's `token_command`.
+    RepositoryCommand,
+    /// A GitHub App installation token.
+    GitHubApp,
+    /// An environment variable (`WHIP_GITHUB_TOKEN`, `GITHUB_TOKEN`, or the
+    /// one named by `github_token_env`).
+    Env(String),
+    /// The global `github_token_command`.
+    Command,
+    /// The global plaintext `github_token`.
+    ConfigToken,
+    /// The `gh auth token` command.
+    GhCli,
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RepositoryToken => f.write_str("repository token"),
+            Self::RepositoryEnv(name) => write!(f, "repository ${name}"),
+            Self::RepositoryCommand => f.write_str("repository token command"),
+            Self::GitHubApp => f.write_str("GitHub App"),
+            Self::Env(name) => write!(f, "${name}"),
+            Self::Command => f.write_str("token command"),
+            Self::ConfigToken => f.write_str("config github_token"),
+            Self::GhCli => f.write_str("gh CLI"),
+        }
+    }
+}
+
+/// A GitHub token together with the source that provided it.
+///
+/// The `Debug` implementation redacts the token.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResolvedToken {
+    /// The token value.
+    pub token: String,
+    /// Where the token came from.
+    pub source: TokenSource,
+}
+
+impl fmt::Debug for ResolvedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedToken")
+            .field("token", &"<redacted>")
+            .field("source", &self.source)
+            .finish()
+    }
+}
 
 /// Resolves the GitHub token for a specific repository.
 ///
+/// This is [`resolve_token_with_source`] without the source information.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::TokenSourceFailed`] if a configured token command
+/// or GitHub App fails to provide a token.
+///
+/// # Arguments
+///
+/// * `repo` - The repository configuration (may have a specific token)
+/// * `config` - The configuration holding the global token sources
+///
+/// # Returns
+///
+/// Returns `Some(token)` if a token is available, `None` if the repository
+/// is accessed without one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::{Config, Repository, auth::resolve_token};
+///
+/// # async fn example() -> whip_config::Result<()> {
+/// let repo = Repository::new("rust-lang", "rust");
+/// let config = Config {
+///     github_token: Some("ghp_global".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let token = resolve_token(&repo, &config).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn resolve_token(repo: &Repository, config: &Config) -> Result<Option<String>> {
+    Ok(resolve_token_with_source(repo, config)
+        .await?
+        .map(|resolved| resolved.token))
+}
+
+/// Resolves the GitHub token for a specific repository and reports its source.
+///
 /// Tries the following sources in order:
 ///
 /// 1. Repository-specific token (from `Repository::token()`)
-/// 2. Global token (from `global_token` parameter)
-/// 3. `gh auth token` command
+/// 2. Environment variable named by `Repository::token_env()`
+/// 3. Output of `Repository::token_command()`
+/// 4. GitHub App installation token (from `Config::github_app`), minted for
+///    the repository's installation on the repository's API host
+/// 5. Environment variable named by `Config::github_token_env`
+/// 6. `WHIP_GITHUB_TOKEN`, then `GITHUB_TOKEN` environment variables
+/// 7. Output of `Config::github_token_command`
+/// 8. Global token (from `Config::github_token`)
+/// 9. `gh auth token` command
+///
+/// The variable named by `github_token_env` is checked before the standard
+/// ones, since naming it is an explicit choice while `GITHUB_TOKEN` is often
+/// set for other tools. Empty environment variables are ignored. A
+/// configured token command or GitHub App that fails to provide a token
+/// (e.g. a locked password manager, a bad key, or an app not installed on
+/// the repository) is an error: falling back would run whip as someone
+/// else.
 ///
 /// # Arguments
 ///
-/// * `repo` - The repository configuration (may have a specific token)
-/// * `global_token` - The global GitHub token from config, if any
+/// * `repo` - The repository configuration (may have specific token sources)
+/// * `config` - The configuration holding the global token sources
 ///
 /// # Returns
 ///
-/// Returns `Some(token)` if a token is available, `None` otherwise.
+/// Returns the token and its source if one is available, `None` if the
+/// repository is accessed without one.
 ///
 /// # Errors
 ///
-/// This function does not return errors for missing tokens; it simply
-/// returns `None`. Errors are only returned if the `gh` CLI command
-/// exists but fails unexpectedly.
+/// Returns [`ConfigError::TokenSourceFailed`] if a configured token command
+/// or GitHub App fails to provide a token.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use whip_config::{Repository, auth::resolve_token};
+/// use whip_config::{Config, Repository, auth::resolve_token_with_source};
 ///
-/// # async fn example() {
-/// let repo = Repository::new("rust-lang", "rust");
-/// let global_token = Some("ghp_global".to_string());
+/// # async fn example() -> whip_config::Result<()> {
+/// let repo = Repository::new("rust-lang", "rust").with_token_command("pass show github/whip");
 ///
-/// let token = resolve_token(&repo, global_token.as_deref()).await;
+/// if let Some(resolved) = resolve_token_with_source(&repo, &Config::default()).await? {
+///     println!("Using token from {}", resolved.source);
+/// }
+/// # Ok(())
 /// # }
 /// ```
-pub async fn resolve_token(repo: &Repository, global_token: Option<&str>) -> Option<String> {
-    // 1. Repository-specific token
+pub async fn resolve_token_with_source(
+    repo: &Repository,
+    config: &Config,
+) -> Result<Option<ResolvedToken>> {
+    resolve_with_env(repo, config, |name| std::env::var(name).ok()).await
+}
+
+/// Implementation of [`resolve_token_with_source`] reading environment
+/// variables through `env`, so tests don't have to mutate the process
+/// environment.
+async fn resolve_with_env(
+    repo: &Repository,
+    config: &Config,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Option<ResolvedToken>> {
+    let found = |token: String, source: TokenSource| Ok(Some(ResolvedToken { token, source }));
+    let from_env = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+
+    // 1-3. Repository-specific sources
     if let Some(token) = repo.token() {
-        return Some(token.to_string());
+        return found(token.to_string(), TokenSource::RepositoryToken);
+    }
+    if let Some(name) = repo.token_env()
+        && let Some(token) = from_env(name)
+    {
+        return found(token, TokenSource::RepositoryEnv(name.to_string()));
+    }
+    if let Some(command) = repo.token_command() {
+        return match cached_token_command(command).await {
+            Ok(token) => found(token, TokenSource::RepositoryCommand),
+            Err(error) => Err(ConfigError::TokenSourceFailed {
+                token_source: TokenSource::RepositoryCommand,
+                error: Box::new(error),
+            }),
+        };
+    }
+
+    // 4. GitHub App installation token
+    if let Some(app) = &config.github_app {
+        let api_url = config.api_url_for(repo);
+        return match app
+            .installation_token(repo.owner(), repo.repo(), api_url)
+            .await
+        {
+            Ok(token) => found(token, TokenSource::GitHubApp),
+            Err(error) => Err(ConfigError::TokenSourceFailed {
+                token_source: TokenSource::GitHubApp,
+                error: Box::new(error),
+            }),
+        };
+    }
+
+    // 5-6. Environment variables
+    for name in config
+        .github_token_env
+        .as_deref()
+        .into_iter()
+        .chain([WHIP_GITHUB_TOKEN_ENV, GITHUB_TOKEN_ENV])
+    {
+        if let Some(token) = from_env(name) {
+            return found(token, TokenSource::Env(name.to_string()));
+        }
+    }
+
+    // 7. Global token command
+    if let Some(command) = config.github_token_command.as_deref() {
+        return match cached_token_command(command).await {
+            Ok(token) => found(token, TokenSource::Command),
+            Err(error) => Err(ConfigError::TokenSourceFailed {
+                token_source: TokenSource::Command,
+                error: Box::new(error),
+            }),
+        };
+    }
+
+    // 8. Global token
+    if let Some(token) = config.github_token.as_deref() {
+        return found(token.to_string(), TokenSource::ConfigToken);
+    }
+
+    // 9. Try gh CLI
+    match get_gh_token().await.ok().flatten() {
+        Some(token) => found(token, TokenSource::GhCli),
+        None => Ok(None),
+    }
+}
+
+/// Returns the token printed by a token command, running it only if it
+/// hasn't succeeded yet this session.
+///
+/// # Errors
+///
+/// Returns the error of [`run_token_command`] if the command is run and
+/// fails.
+pub async fn cached_token_command(command: &str) -> Result<String> {
+    let cell = Arc::clone(
+        lock_command_tokens()
+            .entry(command.to_string())
+            .or_default(),
+    );
+    cell.get_or_try_init(|| run_token_command(command))
+        .await
+        .cloned()
+}
+
+/// Drops the cached token of a token command, so it is run again next time.
+///
+/// # Arguments
+///
+/// * `command` - The token command, as configured
+pub fn forget_command_token(command: &str) {
+    lock_command_tokens().remove(command);
+}
+
+/// Drops all cached token command tokens, e.g. on a manual refresh.
+pub fn forget_command_tokens() {
+    lock_command_tokens().clear();
+}
+
+/// Drops the cached token behind `source` after GitHub rejected it.
+///
+/// Only tokens printed by token commands are cached; other sources are
+/// read afresh on every resolution, so nothing happens for them.
+///
+/// # Arguments
+///
+/// * `repo` - The repository the token was resolved for
+/// * `config` - The configuration it was resolved with
+/// * `source` - Where the rejected token came from
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::auth::{TokenSource, forget_rejected_token};
+/// use whip_config::{Config, Repository};
+///
+/// let repo = Repository::new("owner", "repo").with_token_command("pass show github");
+/// forget_rejected_token(&repo, &Config::default(), &TokenSource::RepositoryCommand);
+/// ```
+pub fn forget_rejected_token(repo: &Repository, config: &Config, source: &TokenSource) {
+    let command = match source {
+        TokenSource::RepositoryCommand => repo.token_command(),
+        TokenSource::Command => config.github_token_command.as_deref(),
+        _ => None,
+    };
+    if let Some(command) = command {
+        forget_command_token(command);
     }
+}
+
+fn lock_command_tokens() -> std::sync::MutexGuard<'static, HashMap<String, Arc<OnceCell<String>>>> {
+    // A panic while holding the lock cannot leave the map inconsistent
+    COMMAND_TOKENS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Runs a token command through the shell and returns the token it prints.
+///
+/// The first non-empty line of standard output, trimmed, is used as the
+/// token. A command still running after [`TOKEN_COMMAND_TIMEOUT`] is killed.
+///
+/// # Arguments
+///
+/// * `command` - The shell command, e.g. `pass show github/whip`
+///
+/// # Errors
+///
+/// Returns [`ConfigError::TokenCommand`] if the command cannot be started,
+/// times out, exits unsuccessfully, or prints nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::auth::run_token_command;
+///
+/// # async fn example() -> whip_config::Result<()> {
+/// let token = run_token_command("pass show github/whip").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run_token_command(command: &str) -> Result<String> {
+    run_token_command_with_timeout(command, TOKEN_COMMAND_TIMEOUT).await
+}
+
+/// Implementation of [`run_token_command`] with a configurable timeout, so
+/// tests don't have to wait for the real one.
+async fn run_token_command_with_timeout(command: &str, timeout: Duration) -> Result<String> {
+    use tokio::process::Command;
+
+    let failed = |reason: String| ConfigError::TokenCommand {
+        command: command.to_string(),
+        reason,
+    };
 
-    // 2. Global token
-    if let Some(token) = global_token {
-        return Some(token.to_string());
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    // Dropping the output future on timeout kills the command
+    let output = tokio::time::timeout(timeout, shell.arg(command).kill_on_drop(true).output())
+        .await
+        .map_err(|_| failed(format!("timed out after {}s", timeout.as_secs())))?
+        .map_err(|e| failed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(failed(format!(
+            "exited with {}: {}",
+            output.status,
+            stderr.trim()
+        )));
     }
 
-    // 3. Try gh CLI
-    get_gh_token().await.ok().flatten()
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+        .ok_or_else(|| failed("printed no token".to_string()))
 }
 
 /// Gets a GitHub token from the `gh` CLI.
@@ -131,40 +504,280 @@ pub async fn get_gh_token() -> Result<Option<String>> {
 /// # Arguments
 ///
 /// * `repo` - The repository configuration
-/// * `global_token` - The global GitHub token from config, if any
+/// * `config` - The configuration holding the global token sources
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use whip_config::{Repository, auth::has_token};
+/// use whip_config::{Config, Repository, auth::has_token};
 ///
 /// # async fn example() {
 /// let repo = Repository::new("rust-lang", "rust");
-/// if has_token(&repo, None).await {
+/// if has_token(&repo, &Config::default()).await {
 ///     println!("Authenticated access available");
 /// }
 /// # }
 /// ```
-pub async fn has_token(repo: &Repository, global_token: Option<&str>) -> bool {
-    resolve_token(repo, global_token).await.is_some()
+pub async fn has_token(repo: &Repository, config: &Config) -> bool {
+    matches!(resolve_token(repo, config).await, Ok(Some(_)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::GitHubAppConfig;
+
+    fn config_with_token(token: Option<&str>) -> Config {
+        Config {
+            github_token: token.map(String::from),
+            ..Default::default()
+        }
+    }
 
     #[tokio::test]
     async fn resolve_token_prefers_repo_token() {
         let repo = Repository::with_token("owner", "repo", "repo_token");
-        let token = resolve_token(&repo, Some("global_token")).await;
-        assert_eq!(token, Some("repo_token".to_string()));
+        let token = resolve_token(&repo, &config_with_token(Some("global_token"))).await;
+        assert_eq!(token.unwrap(), Some("repo_token".to_string()));
     }
 
     #[tokio::test]
     async fn resolve_token_falls_back_to_global() {
         let repo = Repository::new("owner", "repo");
-        let token = resolve_token(&repo, Some("global_token")).await;
-        assert_eq!(token, Some("global_token".to_string()));
+        let resolved = resolve_with_env(&repo, &config_with_token(Some("global_token")), no_env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "global_token");
+        assert_eq!(resolved.source, TokenSource::ConfigToken);
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[tokio::test]
+    async fn resolve_reports_repository_token_source() {
+        let repo = Repository::with_token("owner", "repo", "repo_token");
+        let resolved = resolve_with_env(&repo, &config_with_token(None), no_env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "repo_token");
+        assert_eq!(resolved.source, TokenSource::RepositoryToken);
+    }
+
+    #[tokio::test]
+    async fn resolve_prefers_env_over_config_token() {
+        let repo = Repository::new("owner", "repo");
+        let env = |name: &str| (name == GITHUB_TOKEN_ENV).then(|| "env_token".to_string());
+        let resolved = resolve_with_env(&repo, &config_with_token(Some("global_token")), env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "env_token");
+        assert_eq!(
+            resolved.source,
+            TokenSource::Env("GITHUB_TOKEN".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_prefers_whip_env_over_github_env() {
+        let repo = Repository::new("owner", "repo");
+        let env = |name: &str| Some(format!("{name}_value"));
+        let resolved = resolve_with_env(&repo, &config_with_token(None), env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "WHIP_GITHUB_TOKEN_value");
+        assert_eq!(
+            resolved.source,
+            TokenSource::Env(WHIP_GITHUB_TOKEN_ENV.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_ignores_empty_env() {
+        let repo = Repository::new("owner", "repo");
+        let env = |_: &str| Some("  ".to_string());
+        let resolved = resolve_with_env(&repo, &config_with_token(Some("global_token")), env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.source, TokenSource::ConfigToken);
+    }
+
+    #[tokio::test]
+    async fn resolve_uses_repository_token_env() {
+        let repo = Repository::new("owner", "repo").with_token_env("ORG_TOKEN");
+        let env = |name: &str| Some(format!("{name}_value"));
+        let resolved = resolve_with_env(&repo, &config_with_token(None), env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "ORG_TOKEN_value");
+        assert_eq!(
+            resolved.source,
+            TokenSource::RepositoryEnv("ORG_TOKEN".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_prefers_config_token_env_over_standard_env() {
+        let repo = Repository::new("owner", "repo");
+        let config = Config {
+            github_token_env: Some("CUSTOM_TOKEN".to_string()),
+            ..config_with_token(Some("global_token"))
+        };
+        let env = |name: &str| Some(format!("{name}_value"));
+        let resolved = resolve_with_env(&repo, &config, env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "CUSTOM_TOKEN_value");
+    }
+
+    #[tokio::test]
+    async fn resolve_uses_config_token_env() {
+        let repo = Repository::new("owner", "repo");
+        let config = Config {
+            github_token_env: Some("CUSTOM_TOKEN".to_string()),
+            ..config_with_token(Some("global_token"))
+        };
+        let env = |name: &str| (name == "CUSTOM_TOKEN").then(|| "custom".to_string());
+        let resolved = resolve_with_env(&repo, &config, env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "custom");
+        assert_eq!(
+            resolved.source,
+            TokenSource::Env("CUSTOM_TOKEN".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resolve_uses_token_commands() {
+        let repo = Repository::new("owner", "repo").with_token_command("echo repo_secret");
+        let resolved = resolve_with_env(&repo, &config_with_token(None), no_env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "repo_secret");
+        assert_eq!(resolved.source, TokenSource::RepositoryCommand);
+
+        let repo = Repository::new("owner", "repo");
+        let config = Config {
+            github_token_command: Some("printf '\\nglobal_secret\\nignored'".to_string()),
+            ..config_with_token(Some("global_token"))
+        };
+        let resolved = resolve_with_env(&repo, &config, no_env)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.token, "global_secret");
+        assert_eq!(resolved.source, TokenSource::Command);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resolve_reports_failing_token_command() {
+        let repo = Repository::new("owner", "repo").with_token_command("exit 3");
+        let err = resolve_with_env(&repo, &config_with_token(Some("global_token")), no_env)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::TokenSourceFailed {
+                token_source: TokenSource::RepositoryCommand,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("exit 3"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cached_token_command_runs_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let runs = dir.path().join("runs");
+        let command = format!("echo run >> '{}'; echo ghp_cached", runs.display());
+
+        assert_eq!(cached_token_command(&command).await.unwrap(), "ghp_cached");
+        assert_eq!(cached_token_command(&command).await.unwrap(), "ghp_cached");
+        assert_eq!(std::fs::read_to_string(&runs).unwrap(), "run\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn forgotten_token_command_runs_again() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let runs = dir.path().join("runs");
+        let command = format!("echo run >> '{}'; echo ghp_rotated", runs.display());
+        let repo = Repository::new("owner", "repo").with_token_command(&command);
+
+        assert_eq!(cached_token_command(&command).await.unwrap(), "ghp_rotated");
+        forget_rejected_token(&repo, &Config::default(), &TokenSource::ConfigToken);
+        assert_eq!(cached_token_command(&command).await.unwrap(), "ghp_rotated");
+        assert_eq!(std::fs::read_to_string(&runs).unwrap(), "run\n");
+
+        forget_rejected_token(&repo, &Config::default(), &TokenSource::RepositoryCommand);
+        assert_eq!(cached_token_command(&command).await.unwrap(), "ghp_rotated");
+        assert_eq!(std::fs::read_to_string(&runs).unwrap(), "run\nrun\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_token_command_times_out() {
+        let err = run_token_command_with_timeout("sleep 5", Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_token_command_reports_failures() {
+        let err = run_token_command("echo oops >&2; exit 2")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::TokenCommand { .. }));
+        assert!(err.to_string().contains("oops"));
+
+        let err = run_token_command("true").await.unwrap_err();
+        assert!(err.to_string().contains("printed no token"));
+    }
+
+    #[test]
+    fn resolved_token_debug_is_redacted() {
+        let resolved = ResolvedToken {
+            token: "ghp_secret".to_string(),
+            source: TokenSource::GhCli,
+        };
+        let debug = format!("{resolved:?}");
+        assert!(!debug.contains("ghp_secret"));
+        assert!(debug.contains("GhCli"));
+    }
+
+    #[tokio::test]
+    async fn resolve_token_reports_failing_github_app() {
+        let repo = Repository::new("owner", "repo");
+        let config = Config {
+            github_app: Some(GitHubAppConfig::new(1, "/nonexistent/key.pem")),
+            ..config_with_token(Some("global_token"))
+        };
+        let env = |name: &str| (name == GITHUB_TOKEN_ENV).then(|| "env_token".to_string());
+        let err = resolve_with_env(&repo, &config, env).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::TokenSourceFailed {
+                token_source: TokenSource::GitHubApp,
+                ..
+            }
+        ));
+        assert!(err.to_string().starts_with("GitHub App: "), "{err}");
     }
 
     // Note: Testing gh CLI integration requires the tool to be installed,
@@ -173,13 +786,13 @@ mod tests {
     #[tokio::test]
     async fn has_token_true_with_repo_token() {
         let repo = Repository::with_token("owner", "repo", "repo_token");
-        assert!(has_token(&repo, None).await);
+        assert!(has_token(&repo, &config_with_token(None)).await);
     }
 
     #[tokio::test]
     async fn has_token_true_with_global_token() {
         let repo = Repository::new("owner", "repo");
-        assert!(has_token(&repo, Some("global_token")).await);
+        assert!(has_token(&repo, &config_with_token(Some("global_token"))).await);
     }
 
     #[tokio::test]
@@ -188,6 +801,6 @@ mod tests {
         // This test verifies the fallback path doesn't panic regardless of
         // whether gh is installed or logged in on this machine.
         let repo = Repository::new("owner", "repo");
-        let _result = resolve_token(&repo, None).await;
+        let _result = resolve_token(&repo, &config_with_token(None)).await;
     }
 }
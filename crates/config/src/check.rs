@@ -0,0 +1,563 @@
+//! Locating problems in a configuration file.
+//!
+//! [`Config::validate`](crate::Config::validate) stops at the first problem
+//! and cannot tell where in the file it is. [`check_text`] and
+//! [`check_file`] instead report every problem they find as a
+//! [`Diagnostic`] with the line, column, and field path of the offending
+//! value:
+//!
+//! - JSON5 syntax errors
+//! - Values of the wrong type
+//! - Repository strings that are not `owner/repo`
+//! - Repositories listed more than once
+//! - Out-of-range values, such as the polling interval
+//! - Format versions newer than this whip supports
+//! - Malformed GitHub API URLs
+//! - Status labels without a name or a valid color, or listed twice
+//! - An empty list of lanes, or lanes without a title
+//! - Named filters without a name, or listed twice
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_config::check::check_text;
+//!
+//! let diagnostics = check_text(r#"{
+//!   repositories: ["rust-lang/rust", "rust-lang"],
+//! }"#);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].path, "repositories[1]");
+//! assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 36));
+//! ```
+
+use std::fmt;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use whip_protocol::{LabelDefinition, LaneDefinition, NamedFilter, api_host, repo_key};
+
+use crate::config::{Config, validate_api_url, validate_filter, validate_label, validate_lanes};
+use crate::error::{ConfigError, Result};
+use crate::json5_edit::{Json5Document, PathSegment};
+use crate::migrate::migrate_value;
+use crate::polling::PollingConfig;
+use crate::repository::Repository;
+
+/// A problem found in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The field path of the offending value, such as
+    /// `repositories[2].api_url`; empty for the document as a whole.
+    pub path: String,
+    /// The 1-based line where the offending value starts.
+    pub line: usize,
+    /// The 1-based column where the offending value starts.
+    pub column: usize,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Checks a configuration file.
+///
+/// # Arguments
+///
+/// * `path` - The configuration file to check
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read. Problems with its contents
+/// are returned as diagnostics instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::check::check_file;
+///
+/// # fn example() -> whip_config::Result<()> {
+/// for diagnostic in check_file("whip.json5")? {
+///     eprintln!("whip.json5:{diagnostic}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_file(path: impl AsRef<Path>) -> Result<Vec<Diagnostic>> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadFile {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(check_text(&text))
+}
+
+/// Checks the text of a configuration file.
+///
+/// Returns every problem found, in document order; an empty list means the
+/// configuration is valid. A syntax error is reported on its own, since
+/// nothing else can be checked past it.
+///
+/// # Arguments
+///
+/// * `text` - The JSON5 or JSON configuration text
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::check::check_text;
+///
+/// assert!(check_text(r#"{ repositories: ["rust-lang/rust"] }"#).is_empty());
+///
+/// let diagnostics = check_text("{ polling: { interval_secs: 5 } }");
+/// assert_eq!(diagnostics[0].path, "polling.interval_secs");
+/// ```
+#[must_use]
+pub fn check_text(text: &str) -> Vec<Diagnostic> {
+    let document = match Json5Document::parse(text) {
+        Ok(document) => document,
+        Err(ConfigError::EditJson5 {
+            line,
+            column,
+            reason,
+        }) => {
+            return vec![Diagnostic {
+                path: String::new(),
+                line,
+                column,
+                message: reason,
+            }];
+        }
+        Err(e) => {
+            return vec![Diagnostic {
+                path: String::new(),
+                line: 1,
+                column: 1,
+                message: e.to_string(),
+            }];
+        }
+    };
+    let mut checker = Checker {
+        document: &document,
+        diagnostics: Vec::new(),
+    };
+    match serde_json5::from_str::<Value>(text) {
+        Ok(mut value @ Value::Object(_)) => {
+            // Check what the file means once upgraded to the current format
+            if let Err(e) = migrate_value(&mut value) {
+                checker.report(&[PathSegment::Key("version")], e.to_string());
+            }
+            if let Value::Object(members) = &value {
+                checker.root(members);
+            }
+        }
+        Ok(_) => checker.report(&[], "expected an object"),
+        Err(e) => checker.report(&[], e.to_string()),
+    }
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+    diagnostics
+}
+
+/// Collects diagnostics, locating each in the document.
+struct Checker<'a> {
+    document: &'a Json5Document,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn root(&mut self, members: &Map<String, Value>) {
+        for (key, value) in members {
+            let path = [PathSegment::Key(key)];
+            match key.as_str() {
+                "repositories" => self.repositories(value),
+                "polling" => self.polling(value),
+                "labels" => self.labels(&path, value),
+                "lanes" => self.lanes(value),
+                "filters" => self.filters(value),
+                // Checked by the migration
+                "version" => {}
+                "github_api_url" => {
+                    if let Some(url) = self.parse::<Option<String>>(&path, value).flatten()
+                        && let Err(e) = validate_api_url(&url)
+                    {
+                        self.report(&path, e.to_string());
+                    }
+                }
+                _ => {
+                    // Type-check the member on its own, so the problem is
+                    // reported at the member rather than for the whole file
+                    let single = Value::Object(Map::from_iter([(key.clone(), value.clone())]));
+                    if let Err(e) = serde_json::from_value::<Config>(single) {
+                        self.report(&path, e.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    fn repositories(&mut self, value: &Value) {
+        let key = PathSegment::Key("repositories");
+        let Value::Array(items) = value else {
+            self.report(&[key], "expected an array of repositories");
+            return;
+        };
+        let mut seen: Vec<(String, usize)> = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            let path = [key, PathSegment::Index(index)];
+            let Some(repo) = self.parse::<Repository>(&path, item) else {
+                continue;
+            };
+            let name = repo_key(&api_host(repo.api_url()), repo.owner(), repo.repo());
+            if let Some((_, first)) = seen.iter().find(|(seen, _)| *seen == name) {
+                self.report(
+                    &path,
+                    format!("'{name}' is already listed at repositories[{first}]"),
+                );
+            } else {
+                seen.push((name, index));
+            }
+            if let Some(url) = repo.api_url()
+                && let Err(e) = validate_api_url(url)
+            {
+                self.report(
+                    &[key, PathSegment::Index(index), PathSegment::Key("api_url")],
+                    e.to_string(),
+                );
+            }
+            if let Value::Object(fields) = item
+                && let Some(labels) = fields.get("labels")
+            {
+                self.labels(
+                    &[key, PathSegment::Index(index), PathSegment::Key("labels")],
+                    labels,
+                );
+            }
+        }
+    }
+
+    fn labels(&mut self, path: &[PathSegment<'_>], value: &Value) {
+        let Value::Array(items) = value else {
+            // Let serde describe the expected shape
+            self.parse::<Vec<LabelDefinition>>(path, value);
+            return;
+        };
+        let mut labels = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            let mut label_path = path.to_vec();
+            label_path.push(PathSegment::Index(index));
+            let Some(label) = self.parse::<LabelDefinition>(&label_path, item) else {
+                continue;
+            };
+            if let Err(e) = validate_label(&label, &labels) {
+                self.report(&label_path, e.to_string());
+            }
+            labels.push(label);
+        }
+    }
+
+    fn lanes(&mut self, value: &Value) {
+        let key = PathSegment::Key("lanes");
+        let Value::Array(items) = value else {
+            // Let serde describe the expected shape
+            self.parse::<Vec<LaneDefinition>>(&[key], value);
+            return;
+        };
+        if items.is_empty()
+            && let Err(e) = validate_lanes(&[])
+        {
+            self.report(&[key], e.to_string());
+        }
+        for (index, item) in items.iter().enumerate() {
+            let path = [key, PathSegment::Index(index)];
+            if let Some(lane) = self.parse::<LaneDefinition>(&path, item)
+                && let Err(e) = validate_lanes(std::slice::from_ref(&lane))
+            {
+                self.report(
+                    &[key, PathSegment::Index(index), PathSegment::Key("title")],
+                    e.to_string(),
+                );
+            }
+        }
+    }
+
+    fn filters(&mut self, value: &Value) {
+        let key = PathSegment::Key("filters");
+        let Value::Array(items) = value else {
+            // Let serde describe the expected shape
+            self.parse::<Vec<NamedFilter>>(&[key], value);
+            return;
+        };
+        let mut filters = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            let path = [key, PathSegment::Index(index)];
+            let Some(filter) = self.parse::<NamedFilter>(&path, item) else {
+                continue;
+            };
+            if let Err(e) = validate_filter(&filter, &filters) {
+                self.report(&path, e.to_string());
+            }
+            filters.push(filter);
+        }
+    }
+
+    fn polling(&mut self, value: &Value) {
+        let path = [PathSegment::Key("polling")];
+        let Some(polling) = self.parse::<PollingConfig>(&path, value) else {
+            return;
+        };
+        if let Err(e) = polling.validate() {
+            self.report(&[path[0], PathSegment::Key("interval_secs")], e.to_string());
+        }
+    }
+
+    /// Deserializes the value at `path`, reporting it if it has the wrong shape.
+    fn parse<T: DeserializeOwned>(&mut self, path: &[PathSegment<'_>], value: &Value) -> Option<T> {
+        match serde_json::from_value(value.clone()) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                self.report(path, e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Reports a problem with the value at `path`, or with its closest
+    /// ancestor present in the document.
+    fn report(&mut self, path: &[PathSegment<'_>], message: impl Into<String>) {
+        let (line, column) = (0..=path.len())
+            .rev()
+            .find_map(|len| self.document.position(&path[..len]))
+            .unwrap_or((1, 1));
+        self.diagnostics.push(Diagnostic {
+            path: format_path(path),
+            line,
+            column,
+            message: message.into(),
+        });
+    }
+}
+
+/// Formats a path as `key.child[index]`.
+fn format_path(path: &[PathSegment<'_>]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                formatted.push_str(key);
+            }
+            PathSegment::Index(index) => formatted.push_str(&format!("[{index}]")),
+        }
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn located(diagnostics: &[Diagnostic]) -> Vec<(&str, usize, usize)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.path.as_str(), d.line, d.column))
+            .collect()
+    }
+
+    #[test]
+    fn valid_config_has_no_diagnostics() {
+        let text = r#"{
+            // Comments are fine
+            repositories: [
+                "rust-lang/rust",
+                { owner: "org", repo: "private", api_url: "https://ghe.example.com/api/v3" },
+            ],
+            polling: { interval_secs: 60 },
+            sync_labels: false,
+        }"#;
+        assert_eq!(check_text(text), vec![]);
+    }
+
+    #[test]
+    fn syntax_error_is_located() {
+        let diagnostics = check_text("{\n  repositories: [\n    \"a/b\"\n  \n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "");
+        assert_eq!(diagnostics[0].line, 5);
+    }
+
+    #[test]
+    fn invalid_repository_strings_are_located() {
+        let text = "{\n  repositories: [\n    \"a/b\",\n    \"missing-slash\",\n    \"too/many/parts\",\n  ],\n}";
+        let diagnostics = check_text(text);
+        assert_eq!(
+            located(&diagnostics),
+            vec![("repositories[1]", 4, 5), ("repositories[2]", 5, 5)]
+        );
+        assert!(diagnostics[0].message.contains("owner/repo"));
+    }
+
+    #[test]
+    fn repository_object_missing_field_is_located() {
+        let diagnostics = check_text("{ repositories: [{ owner: \"org\" }] }");
+        assert_eq!(located(&diagnostics), vec![("repositories[0]", 1, 18)]);
+        assert!(diagnostics[0].message.contains("repo"));
+    }
+
+    #[test]
+    fn invalid_labels_are_located() {
+        let text = r##"{
+  labels: [
+    { name: "status: doing", color: "FBCA04", lane: "in_progress" },
+    { name: "Status: Doing", color: "FBCA04", lane: "in_progress" },
+  ],
+  repositories: [
+    { owner: "a", repo: "b", labels: [{ name: "done", color: "#16A34A", lane: "done" }] },
+  ],
+}"##;
+        let diagnostics = check_text(text);
+        assert_eq!(
+            located(&diagnostics),
+            vec![("labels[1]", 4, 5), ("repositories[0].labels[0]", 7, 39)]
+        );
+        assert!(diagnostics[0].message.contains("more than once"));
+        assert!(diagnostics[1].message.contains("hex"));
+    }
+
+    #[test]
+    fn invalid_lanes_are_located() {
+        let text = "{\n  lanes: [\n    { title: \"Open\" },\n    { title: \"\", kinds: [\"done\"] },\n    { title: \"Bad\", kinds: [\"nope\"] },\n  ],\n}";
+        let diagnostics = check_text(text);
+        assert_eq!(
+            located(&diagnostics),
+            vec![("lanes[1].title", 4, 14), ("lanes[2]", 5, 5)]
+        );
+        assert!(diagnostics[0].message.contains("title"));
+
+        let diagnostics = check_text("{ lanes: [] }");
+        assert_eq!(located(&diagnostics), vec![("lanes", 1, 10)]);
+    }
+
+    #[test]
+    fn invalid_filters_are_located() {
+        let text = "{\n  filters: [\n    { name: \"api\", repositories: [\"acme/api\"] },\n    { name: \"API\" },\n    { name: \"bad\", states: [\"blocked\"] },\n  ],\n}";
+        let diagnostics = check_text(text);
+        assert_eq!(
+            located(&diagnostics),
+            vec![("filters[1]", 4, 5), ("filters[2]", 5, 5)]
+        );
+        assert!(diagnostics[0].message.contains("more than once"));
+        assert!(diagnostics[1].message.contains("blocked"));
+    }
+
+    #[test]
+    fn duplicate_repositories_are_located() {
+        let text = "{\n  repositories: [\n    \"a/b\",\n    \"c/d\",\n    { owner: \"a\", repo: \"b\" },\n  ],\n}";
+        let diagnostics = check_text(text);
+        assert_eq!(located(&diagnostics), vec![("repositories[2]", 5, 5)]);
+        assert_eq!(
+            diagnostics[0].message,
+            "'a/b' is already listed at repositories[0]"
+        );
+    }
+
+    #[test]
+    fn out_of_range_polling_interval_is_located() {
+        let diagnostics = check_text("{\n  polling: {\n    interval_secs: 7200,\n  },\n}");
+        assert_eq!(
+            located(&diagnostics),
+            vec![("polling.interval_secs", 3, 20)]
+        );
+        assert!(diagnostics[0].message.contains("exceeds maximum"));
+    }
+
+    #[test]
+    fn wrong_types_are_located() {
+        let text = "{\n  sync_labels: \"yes\",\n  polling: { interval_secs: -1 },\n  repositories: \"a/b\",\n}";
+        let diagnostics = check_text(text);
+        assert_eq!(
+            located(&diagnostics),
+            vec![
+                ("sync_labels", 2, 16),
+                ("polling", 3, 12),
+                ("repositories", 4, 17),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_api_urls_are_located() {
+        let text = "{\n  github_api_url: \"ghe.example.com\",\n  repositories: [{ owner: \"a\", repo: \"b\", api_url: \"ftp://x\" }],\n}";
+        let diagnostics = check_text(text);
+        assert_eq!(
+            located(&diagnostics),
+            vec![
+                ("github_api_url", 2, 19),
+                ("repositories[0].api_url", 3, 52)
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_version_is_located() {
+        let diagnostics = check_text("{\n  version: 99,\n}");
+        assert_eq!(located(&diagnostics), vec![("version", 2, 12)]);
+        assert!(
+            diagnostics[0]
+                .message
+                .contains("unsupported config version 99")
+        );
+    }
+
+    #[test]
+    fn unversioned_file_is_valid() {
+        assert_eq!(check_text("{ sync_labels: true }"), vec![]);
+        assert_eq!(check_text("{ version: 1, sync_labels: true }"), vec![]);
+    }
+
+    #[test]
+    fn non_object_document_is_reported() {
+        let diagnostics = check_text("[1, 2]");
+        assert_eq!(located(&diagnostics), vec![("", 1, 1)]);
+    }
+
+    #[test]
+    fn diagnostic_display() {
+        let diagnostic = Diagnostic {
+            path: "polling.interval_secs".to_string(),
+            line: 3,
+            column: 20,
+            message: "too large".to_string(),
+        };
+        assert_eq!(
+            diagnostic.to_string(),
+            "3:20: polling.interval_secs: too large"
+        );
+
+        let diagnostic = Diagnostic {
+            path: String::new(),
+            ..diagnostic
+        };
+        assert_eq!(diagnostic.to_string(), "3:20: too large");
+    }
+
+    #[test]
+    fn check_file_reads_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("whip.json5");
+        std::fs::write(&path, "{ repositories: [\"bad\"] }").unwrap();
+        assert_eq!(check_file(&path).unwrap().len(), 1);
+        assert!(check_file(dir.path().join("missing.json5")).is_err());
+    }
+}
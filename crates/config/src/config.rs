@@ -3,10 +3,16 @@
 //! This module provides the main [`Config`] struct which aggregates all
 //! configuration options for the whip application.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use whip_protocol::{
+    KeymapConfig, LabelDefinition, LaneDefinition, NamedFilter, StatusLabels, api_host, repo_key,
+};
 
-use crate::error::Result;
-use crate::persistence::{find_config_file, read_config_file, write_config_file};
+use crate::error::{ConfigError, Result};
+use crate::github_app::GitHubAppConfig;
+use crate::layers::{ConfigLayer, ConfigLoader};
+use crate::migrate::{CURRENT_VERSION, read_migrated};
 use crate::polling::PollingConfig;
 use crate::repository::Repository;
 
@@ -26,14 +32,35 @@ use crate::repository::Repository;
 ///
 /// // Create a custom config
 /// let config = Config {
+///     version: whip_config::migrate::CURRENT_VERSION,
 ///     repositories: vec![Repository::new("rust-lang", "rust")],
 ///     polling: PollingConfig::with_interval(120),
 ///     github_token: Some("ghp_xxx".to_string()),
+///     github_token_env: None,
+///     github_token_command: None,
+///     github_api_url: None,
+///     github_app: None,
+///     labels: None,
+///     lanes: None,
+///     filters: Vec::new(),
+///     keymap: Default::default(),
 ///     sync_labels: true,
+///     source: None,
 /// };
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "Configuration for whip.")]
 pub struct Config {
+    /// The config file format version.
+    ///
+    /// Files without a version predate versioning and are version 0; they
+    /// are upgraded when loaded (see [`migrate`](crate::migrate)).
+    #[serde(default)]
+    #[schemars(
+        description = "The config file format version. Files without one predate versioning and are upgraded when loaded."
+    )]
+    pub version: u32,
+
     /// List of GitHub repositories to monitor.
     ///
     /// Repositories can be specified in short format (`"owner/repo"`) or
@@ -54,15 +81,83 @@ pub struct Config {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub github_token: Option<String>,
 
-    /// Whether to sync whip labels to GitHub repositories on startup.
+    /// Environment variable holding the global GitHub token.
+    ///
+    /// Lets the token stay out of the configuration file. Takes precedence
+    /// over the `WHIP_GITHUB_TOKEN` and `GITHUB_TOKEN` environment variables
+    /// and over `github_token`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token_env: Option<String>,
+
+    /// Shell command printing the global GitHub token.
+    ///
+    /// For example `pass show github/whip`. The first line of its output is
+    /// used as the token. Takes precedence over `github_token`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token_command: Option<String>,
+
+    /// Global GitHub API base URL.
     ///
-    /// When enabled, whip will create or update the standard `whip/*` labels
-    /// in all configured repositories. This ensures consistent label naming
-    /// and colors across repositories.
+    /// Used for all repositories that don't have a specific API URL
+    /// configured. Set this to target a GitHub Enterprise Server instance
+    /// (e.g. `https://ghe.example.com/api/v3`). If not set, `api.github.com`
+    /// is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_api_url: Option<String>,
+
+    /// GitHub App credentials.
+    ///
+    /// When set, installation tokens minted for the App are used for all
+    /// repositories that don't have a specific token configured, so that
+    /// activity is attributed to the App's bot identity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_app: Option<GitHubAppConfig>,
+
+    /// Status labels, mapping GitHub label names to lanes and task states.
+    ///
+    /// Replaces the standard `whip/*` labels, so repositories that already
+    /// track status with their own labels can keep them. Labels listed
+    /// first take precedence when an issue has several. A repository's own
+    /// `labels` take precedence over these.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<LabelDefinition>>,
+
+    /// The lanes of the board, from left to right.
+    ///
+    /// Each task is shown in the first lane whose filters it passes.
+    /// Defaults to one lane per workflow stage: Backlog, In Progress, Under
+    /// Review, and Done.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lanes: Option<Vec<LaneDefinition>>,
+
+    /// Named board filters, picked from the filter bar of the board.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<NamedFilter>,
+
+    /// Key bindings of the TUI: a preset (`default` or `vim`), and keys
+    /// bound or unbound on top of it.
+    #[serde(default, skip_serializing_if = "KeymapConfig::is_default")]
+    pub keymap: KeymapConfig,
+
+    /// Whether to check whip labels on GitHub repositories on startup.
+    ///
+    /// When enabled, whip plans the changes needed to bring the status
+    /// labels (the standard `whip/*` ones, unless `labels` are configured)
+    /// in line across all configured repositories, and lists them in a
+    /// notification. Nothing is written on startup; `whip sync-labels`
+    /// shows the changes and applies them once confirmed.
     ///
     /// Defaults to `true`.
     #[serde(default = "default_sync_labels")]
     pub sync_labels: bool,
+
+    /// The config file this configuration was loaded from.
+    ///
+    /// Settings changes are saved back to this file. `None` for a
+    /// configuration that wasn't loaded from a file. Not part of the file
+    /// format.
+    #[serde(skip)]
+    pub source: Option<ConfigLayer>,
 }
 
 /// Default value for `sync_labels` configuration option.
@@ -70,13 +165,111 @@ fn default_sync_labels() -> bool {
     true
 }
 
+/// Checks that status labels have a name and a hex color, and that no name
+/// is listed twice (GitHub label names are case-insensitive).
+pub(crate) fn validate_labels(labels: &[LabelDefinition]) -> Result<()> {
+    for (i, label) in labels.iter().enumerate() {
+        validate_label(label, &labels[..i])?;
+    }
+    Ok(())
+}
+
+/// Checks a single status label, given the labels listed before it.
+pub(crate) fn validate_label(label: &LabelDefinition, previous: &[LabelDefinition]) -> Result<()> {
+    let invalid = |reason: &str| ConfigError::InvalidLabel {
+        name: label.name.clone(),
+        reason: reason.to_string(),
+    };
+    if label.name.trim().is_empty() {
+        return Err(invalid("name cannot be empty"));
+    }
+    if label.color.len() != 6 || !label.color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid("color must be 6 hex digits, without '#'"));
+    }
+    if previous
+        .iter()
+        .any(|other| other.name.eq_ignore_ascii_case(&label.name))
+    {
+        return Err(invalid("listed more than once"));
+    }
+    Ok(())
+}
+
+/// Checks that there is at least one lane and that every lane has a title.
+pub(crate) fn validate_lanes(lanes: &[LaneDefinition]) -> Result<()> {
+    if lanes.is_empty() {
+        return Err(ConfigError::InvalidLanes(
+            "at least one lane is required".to_string(),
+        ));
+    }
+    if lanes.iter().any(|lane| lane.title.trim().is_empty()) {
+        return Err(ConfigError::InvalidLanes(
+            "lane titles cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that named filters have a name, and that no name is used twice.
+pub(crate) fn validate_filters(filters: &[NamedFilter]) -> Result<()> {
+    for (i, filter) in filters.iter().enumerate() {
+        validate_filter(filter, &filters[..i])?;
+    }
+    Ok(())
+}
+
+/// Checks a single named filter, given the filters listed before it.
+pub(crate) fn validate_filter(filter: &NamedFilter, previous: &[NamedFilter]) -> Result<()> {
+    let invalid = |reason: &str| ConfigError::InvalidFilter {
+        name: filter.name.clone(),
+        reason: reason.to_string(),
+    };
+    if filter.name.trim().is_empty() {
+        return Err(invalid("name cannot be empty"));
+    }
+    if previous
+        .iter()
+        .any(|other| other.name.eq_ignore_ascii_case(&filter.name))
+    {
+        return Err(invalid("listed more than once"));
+    }
+    Ok(())
+}
+
+/// Checks that a GitHub API base URL is an absolute HTTP(S) URL with a host.
+pub(crate) fn validate_api_url(url: &str) -> Result<()> {
+    let invalid = |reason: &str| ConfigError::InvalidApiUrl {
+        url: url.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| invalid("must start with http:// or https://"))?;
+    if rest.split('/').next().is_none_or(str::is_empty) {
+        return Err(invalid("missing host"));
+    }
+    Ok(())
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: CURRENT_VERSION,
             repositories: Vec::new(),
             polling: PollingConfig::default(),
             github_token: None,
+            github_token_env: None,
+            github_token_command: None,
+            github_api_url: None,
+            github_app: None,
+            labels: None,
+            lanes: None,
+            filters: Vec::new(),
+            keymap: KeymapConfig::default(),
             sync_labels: default_sync_labels(),
+            source: None,
         }
     }
 }
@@ -101,17 +294,22 @@ impl Config {
 
     /// Loads configuration from the default file locations.
     ///
-    /// Searches for configuration files in the following order:
+    /// Merges, from lowest to highest priority:
     ///
-    /// 1. Local: `./whip.json5` or `./whip.json`
+    /// 1. Built-in defaults
     /// 2. User: `~/.config/whip/config.json5` or `~/.config/whip/config.json`
+    /// 3. Local: `./whip.json5` or `./whip.json`
+    /// 4. Environment overrides (`WHIP_*`)
     ///
-    /// If no configuration file is found, returns a default configuration.
+    /// Tokens stored in the user's credentials file (see
+    /// [`Credentials`](crate::Credentials)) are merged in. Use
+    /// [`ConfigLoader`](crate::ConfigLoader) to also learn which layer each
+    /// value came from.
     ///
     /// # Errors
     ///
-    /// Returns an error if a configuration file is found but cannot be
-    /// read or parsed.
+    /// Returns an error if a configuration or credentials file is found but
+    /// cannot be read or parsed, or if the merged configuration is invalid.
     ///
     /// # Examples
     ///
@@ -125,14 +323,7 @@ impl Config {
     /// # }
     /// ```
     pub async fn load() -> Result<Self> {
-        match find_config_file() {
-            Some(path) => {
-                let config: Config = read_config_file(&path)?;
-                config.validate()?;
-                Ok(config)
-            }
-            None => Ok(Self::default()),
-        }
+        Ok(ConfigLoader::discover().load()?.config)
     }
 
     /// Loads configuration from a specific file.
@@ -145,6 +336,9 @@ impl Config {
     ///
     /// Returns an error if the file cannot be read or parsed.
     ///
+    /// A file written for an older format version is upgraded in memory;
+    /// the file itself is left untouched.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -156,60 +350,208 @@ impl Config {
     /// # }
     /// ```
     pub fn load_from(path: impl AsRef<std::path::Path>) -> Result<Self> {
-        let config: Config = read_config_file(path)?;
+        let path = path.as_ref();
+        let (mut config, _) = read_migrated(path)?;
         config.validate()?;
+        config.source = Some(ConfigLayer::File(path.to_path_buf()));
         Ok(config)
     }
 
-    /// Saves the configuration to a file.
+    /// Validates the configuration.
     ///
-    /// # Arguments
+    /// Checks that all configuration values are within acceptable ranges,
+    /// that API URLs, status labels, lanes, and named filters are well
+    /// formed, and that no repository is listed twice.
     ///
-    /// * `path` - The path to save to
+    /// See [`check`](crate::check) to find where in a file a problem is.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written.
+    /// Returns an error if validation fails.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use whip_config::Config;
+    /// ```
+    /// use whip_config::{Config, PollingConfig};
+    ///
+    /// let mut config = Config::default();
+    /// assert!(config.validate().is_ok());
+    ///
+    /// // Invalid polling interval
+    /// config.polling = PollingConfig::fixed(5); // Below minimum
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        self.polling.validate()?;
+        for (i, repo) in self.repositories.iter().enumerate() {
+            let key = self.repo_key(repo);
+            if self.repositories[..i]
+                .iter()
+                .any(|other| self.repo_key(other) == key)
+            {
+                return Err(ConfigError::DuplicateRepository(key));
+            }
+        }
+        let api_urls = self
+            .github_api_url
+            .iter()
+            .map(String::as_str)
+            .chain(self.repositories.iter().filter_map(Repository::api_url));
+        for url in api_urls {
+            validate_api_url(url)?;
+        }
+        let label_sets = self
+            .labels
+            .as_deref()
+            .into_iter()
+            .chain(self.repositories.iter().filter_map(Repository::labels));
+        for labels in label_sets {
+            validate_labels(labels)?;
+        }
+        if let Some(lanes) = &self.lanes {
+            validate_lanes(lanes)?;
+        }
+        validate_filters(&self.filters)?;
+        Ok(())
+    }
+
+    /// Returns the GitHub API base URL to use for a repository.
+    ///
+    /// The repository's own `api_url` takes precedence over the global
+    /// `github_api_url`. Returns `None` when neither is set, meaning the
+    /// public `api.github.com` API.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The repository to look up
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::{Config, Repository};
+    ///
+    /// let config = Config {
+    ///     github_api_url: Some("https://ghe.example.com/api/v3".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let repo = Repository::new("org", "repo");
+    /// assert_eq!(config.api_url_for(&repo), Some("https://ghe.example.com/api/v3"));
+    ///
+    /// let repo = repo.with_api_url("https://other.example.com/api/v3");
+    /// assert_eq!(config.api_url_for(&repo), Some("https://other.example.com/api/v3"));
+    /// ```
+    #[must_use]
+    pub fn api_url_for<'a>(&'a self, repo: &'a Repository) -> Option<&'a str> {
+        repo.api_url().or(self.github_api_url.as_deref())
+    }
+
+    /// Returns the key identifying a repository across GitHub hosts.
+    ///
+    /// This is the full name (`"owner/repo"`) for repositories on
+    /// github.com, and is prefixed with the host for others (see
+    /// [`repo_key`](whip_protocol::repo_key)). Statuses, stored tokens, and
+    /// the tasks on the board are all tracked by this key, so same-named
+    /// repositories on different hosts stay apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The repository to identify
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::{Config, Repository};
     ///
-    /// # fn example() -> whip_config::Result<()> {
     /// let config = Config::default();
-    /// config.save_to("my-config.json")?;
-    /// # Ok(())
-    /// # }
+    /// let repo = Repository::new("org", "repo");
+    /// assert_eq!(config.repo_key(&repo), "org/repo");
+    ///
+    /// let repo = repo.with_api_url("https://ghe.example.com/api/v3");
+    /// assert_eq!(config.repo_key(&repo), "ghe.example.com/org/repo");
     /// ```
-    pub fn save_to(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
-        write_config_file(path, self)
+    #[must_use]
+    pub fn repo_key(&self, repo: &Repository) -> String {
+        repo_key(&api_host(self.api_url_for(repo)), repo.owner(), repo.repo())
     }
 
-    /// Validates the configuration.
+    /// Returns the lanes of the board: the configured ones, or else the
+    /// standard lanes.
     ///
-    /// Checks that all configuration values are within acceptable ranges
-    /// and that required fields are properly set.
+    /// # Examples
     ///
-    /// # Errors
+    /// ```
+    /// use whip_config::Config;
+    /// use whip_protocol::LaneDefinition;
     ///
-    /// Returns an error if validation fails.
+    /// assert_eq!(Config::default().lane_definitions(), LaneDefinition::standard());
+    /// ```
+    #[must_use]
+    pub fn lane_definitions(&self) -> Vec<LaneDefinition> {
+        self.lanes.clone().unwrap_or_else(LaneDefinition::standard)
+    }
+
+    /// Returns the named filter with the given name, ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the filter
     ///
     /// # Examples
     ///
     /// ```
-    /// use whip_config::{Config, PollingConfig};
+    /// use whip_config::Config;
+    /// use whip_protocol::{NamedFilter, TaskFilter};
     ///
-    /// let mut config = Config::default();
-    /// assert!(config.validate().is_ok());
+    /// let config = Config {
+    ///     filters: vec![NamedFilter::new("api", TaskFilter::default().with_repositories(["acme/api"]))],
+    ///     ..Default::default()
+    /// };
+    /// assert!(config.named_filter("API").is_some());
+    /// assert!(config.named_filter("web").is_none());
+    /// ```
+    #[must_use]
+    pub fn named_filter(&self, name: &str) -> Option<&NamedFilter> {
+        self.filters
+            .iter()
+            .find(|filter| filter.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the status labels to use for a repository.
+    ///
+    /// The repository's own `labels` take precedence over the global
+    /// `labels`; when neither is set, the standard `whip/*` labels are used.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The repository to look up
+    ///
+    /// # Examples
     ///
-    /// // Invalid polling interval
-    /// config.polling = PollingConfig::fixed(5); // Below minimum
-    /// assert!(config.validate().is_err());
     /// ```
-    pub fn validate(&self) -> Result<()> {
-        self.polling.validate()?;
-        Ok(())
+    /// use whip_config::{Config, Repository};
+    /// use whip_protocol::{LabelDefinition, LaneKind};
+    ///
+    /// let config = Config::default();
+    /// let repo = Repository::new("org", "repo");
+    /// assert!(config.status_labels_for(&repo).status("whip/done").is_some());
+    ///
+    /// let repo = repo.with_labels(vec![LabelDefinition::new(
+    ///     "status: done",
+    ///     "16A34A",
+    ///     "",
+    ///     LaneKind::Done,
+    /// )]);
+    /// let labels = config.status_labels_for(&repo);
+    /// assert_eq!(labels.status("status: done").unwrap().lane, LaneKind::Done);
+    /// assert!(labels.status("whip/done").is_none());
+    /// ```
+    #[must_use]
+    pub fn status_labels_for(&self, repo: &Repository) -> StatusLabels {
+        match repo.labels().or(self.labels.as_deref()) {
+            Some(labels) => StatusLabels::new(labels.to_vec()),
+            None => StatusLabels::standard(),
+        }
     }
 
     /// Returns whether the configuration has any repositories.
@@ -252,11 +594,15 @@ impl Config {
         self.repositories.push(repo);
     }
 
-    /// Removes a repository by its full name (`"owner/repo"`).
+    /// Removes a repository by its full name (`"owner/repo"`) or its
+    /// [`repo_key`](Self::repo_key).
+    ///
+    /// A full name removes the repository on every host it is configured
+    /// for; a key with a host removes only the repository on that host.
     ///
     /// # Arguments
     ///
-    /// * `full_name` - The full repository name to remove
+    /// * `name` - The full name or key of the repository to remove
     ///
     /// # Returns
     ///
@@ -268,17 +614,25 @@ impl Config {
     /// use whip_config::{Config, Repository};
     ///
     /// let mut config = Config {
-    ///     repositories: vec![Repository::new("rust-lang", "rust")],
+    ///     repositories: vec![
+    ///         Repository::new("rust-lang", "rust"),
+    ///         Repository::new("rust-lang", "rust").with_api_url("https://ghe.example.com/api/v3"),
+    ///     ],
     ///     ..Default::default()
     /// };
     ///
+    /// assert!(config.remove_repository("ghe.example.com/rust-lang/rust"));
+    /// assert_eq!(config.repositories.len(), 1);
     /// assert!(config.remove_repository("rust-lang/rust"));
     /// assert!(!config.remove_repository("nonexistent/repo"));
     /// ```
     #[must_use]
-    pub fn remove_repository(&mut self, full_name: &str) -> bool {
+    pub fn remove_repository(&mut self, name: &str) -> bool {
         let initial_len = self.repositories.len();
-        self.repositories.retain(|r| r.full_name() != full_name);
+        self.repositories = std::mem::take(&mut self.repositories)
+            .into_iter()
+            .filter(|r| r.full_name() != name && self.repo_key(r) != name)
+            .collect();
         self.repositories.len() < initial_len
     }
 }
@@ -286,7 +640,10 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Credentials;
+    use crate::migrate::MigrationPlan;
     use tempfile::TempDir;
+    use whip_protocol::{KeyContext, LaneKind, Message, SortKey, TaskFilter, TaskState};
 
     #[test]
     fn default_config() {
@@ -341,10 +698,20 @@ mod tests {
     #[test]
     fn validate_valid_config() {
         let config = Config {
+            version: CURRENT_VERSION,
             repositories: vec![Repository::new("owner", "repo")],
             polling: PollingConfig::with_interval(60),
             github_token: Some("ghp_xxx".to_string()),
+            github_token_env: None,
+            github_token_command: None,
+            github_api_url: None,
+            github_app: None,
+            labels: None,
+            lanes: None,
+            filters: Vec::new(),
+            keymap: KeymapConfig::default(),
             sync_labels: true,
+            source: None,
         };
         assert!(config.validate().is_ok());
     }
@@ -358,16 +725,297 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn load_from_upgrades_unversioned_file_in_memory() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.json5");
+        let text = "{ repositories: ['owner/repo'] }";
+        std::fs::write(&path, text).unwrap();
+
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.version, CURRENT_VERSION);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+    }
+
+    #[test]
+    fn load_from_rejects_newer_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.json");
+        std::fs::write(&path, r#"{ "version": 999 }"#).unwrap();
+
+        assert!(matches!(
+            Config::load_from(&path),
+            Err(ConfigError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn deserialize_ignores_schema_member() {
+        let json = r#"{ "$schema": "./config.schema.json", "repositories": ["org/repo"] }"#;
+        let config: Config = serde_json5::from_str(json).unwrap();
+        assert_eq!(config.repositories, vec![Repository::new("org", "repo")]);
+    }
+
+    #[test]
+    fn validate_rejects_duplicate_repositories() {
+        let config = Config {
+            repositories: vec![
+                Repository::new("org", "one"),
+                Repository::new("org", "two"),
+                Repository::with_token("org", "one", "ghp_xxx"),
+            ],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DuplicateRepository(name)) if name == "org/one"
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_api_urls() {
+        let config = Config {
+            github_api_url: Some("ghe.example.com/api/v3".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidApiUrl { .. })
+        ));
+
+        let config = Config {
+            repositories: vec![Repository::new("org", "repo").with_api_url("https:///api/v3")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidApiUrl { .. })
+        ));
+
+        let config = Config {
+            github_api_url: Some("http://127.0.0.1:8080".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn api_url_for_prefers_repository_url() {
+        let config = Config {
+            github_api_url: Some("https://global.example.com/api/v3".to_string()),
+            ..Default::default()
+        };
+        let plain = Repository::new("org", "plain");
+        let custom =
+            Repository::new("org", "custom").with_api_url("https://ghe.example.com/api/v3");
+
+        assert_eq!(
+            config.api_url_for(&plain),
+            Some("https://global.example.com/api/v3")
+        );
+        assert_eq!(
+            config.api_url_for(&custom),
+            Some("https://ghe.example.com/api/v3")
+        );
+        assert_eq!(Config::default().api_url_for(&plain), None);
+    }
+
+    #[test]
+    fn validate_rejects_invalid_labels() {
+        let label = |name: &str, color: &str| LabelDefinition::new(name, color, "", LaneKind::Done);
+        let invalid = |labels: Vec<LabelDefinition>| {
+            let config = Config {
+                labels: Some(labels),
+                ..Default::default()
+            };
+            matches!(config.validate(), Err(ConfigError::InvalidLabel { .. }))
+        };
+
+        assert!(invalid(vec![label(" ", "16A34A")]));
+        assert!(invalid(vec![label("done", "#16A34A")]));
+        assert!(invalid(vec![label("done", "green!")]));
+        assert!(invalid(vec![
+            label("done", "16A34A"),
+            label("Done", "16A34A")
+        ]));
+
+        let config = Config {
+            repositories: vec![
+                Repository::new("org", "repo").with_labels(vec![label("", "16A34A")]),
+            ],
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidLabel { .. })
+        ));
+
+        let config = Config {
+            labels: Some(vec![label("done", "16a34a"), label("shipped", "16A34A")]),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_lanes() {
+        let config = Config {
+            lanes: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidLanes(_))
+        ));
+
+        let config = Config {
+            lanes: Some(vec![LaneDefinition::new("Open"), LaneDefinition::new(" ")]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidLanes(_))
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_filters() {
+        let filter = |name: &str| NamedFilter::new(name, TaskFilter::default());
+        let invalid = |filters: Vec<NamedFilter>| {
+            let config = Config {
+                filters,
+                ..Default::default()
+            };
+            matches!(config.validate(), Err(ConfigError::InvalidFilter { .. }))
+        };
+        assert!(invalid(vec![filter(" ")]));
+        assert!(invalid(vec![filter("mine"), filter("Mine")]));
+        assert!(!invalid(vec![filter("mine"), filter("api")]));
+    }
+
+    #[test]
+    fn deserialize_filters() {
+        let config: Config = serde_json::from_str(
+            r#"{"filters": [
+                {"name": "api", "repositories": ["acme/api"]},
+                {"name": "mine", "authors": ["octocat"], "states": ["needs_attention"]}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        let mine = config.named_filter("mine").expect("filter");
+        assert_eq!(mine.filter.authors, ["octocat"]);
+        assert_eq!(mine.filter.states, [TaskState::NeedsAttention]);
+    }
+
+    #[test]
+    fn deserialize_lanes() {
+        let config: Config = serde_json::from_str(
+            r#"{"lanes": [
+                {"title": "Backlog", "kinds": ["backlog"], "sort": "priority"},
+                {"title": "Planning", "labels": ["whip/status/planning"]},
+                {"title": "Failed", "states": ["failed"]},
+                {"title": "Everything else"}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        let lanes = config.lane_definitions();
+        assert_eq!(lanes.len(), 4);
+        assert_eq!(lanes[0].sort, Some(SortKey::Priority));
+        assert_eq!(lanes[1].labels, ["whip/status/planning"]);
+        assert_eq!(lanes[2].states, [TaskState::Failed]);
+    }
+
+    #[test]
+    fn deserialize_keymap() {
+        let config: Config = serde_json::from_str(
+            r#"{"keymap": {
+                "preset": "vim",
+                "bindings": {"detail": {"ctrl+d": "navigate_last"}}
+            }}"#,
+        )
+        .unwrap();
+        let keymap = config.keymap.keymap();
+        let chord = |chord: &str| chord.parse().expect("chord");
+        assert_eq!(
+            keymap.message(KeyContext::Board, chord("q")),
+            Some(&Message::Quit)
+        );
+        assert_eq!(
+            keymap.message(KeyContext::Detail, chord("ctrl+d")),
+            Some(&Message::NavigateLast)
+        );
+
+        let invalid = serde_json::from_str::<Config>(r#"{"keymap": {"preset": "emacs"}}"#);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn status_labels_for_prefers_repository_labels() {
+        let global = LabelDefinition::new("status: done", "16A34A", "", LaneKind::Done);
+        let local = LabelDefinition::new("shipped", "16A34A", "", LaneKind::Done);
+        let config = Config {
+            labels: Some(vec![global]),
+            ..Default::default()
+        };
+        let plain = Repository::new("org", "plain");
+        let custom = Repository::new("org", "custom").with_labels(vec![local]);
+
+        let labels = config.status_labels_for(&plain);
+        assert!(labels.status("status: done").is_some());
+        assert!(labels.status("whip/done").is_none());
+
+        let labels = config.status_labels_for(&custom);
+        assert!(labels.status("shipped").is_some());
+        assert!(labels.status("status: done").is_none());
+
+        let labels = Config::default().status_labels_for(&plain);
+        assert_eq!(labels, StatusLabels::standard());
+    }
+
+    #[test]
+    fn deserialize_labels_with_states() {
+        let config: Config = serde_json::from_str(
+            r#"{"labels": [
+                {"name": "doing", "color": "FBCA04", "lane": "in_progress"},
+                {"name": "broken", "color": "D73A4A", "lane": "done", "state": "failed"}
+            ]}"#,
+        )
+        .unwrap();
+        let labels = config.status_labels_for(&Repository::new("org", "repo"));
+        let doing = labels.status("doing").unwrap();
+        assert_eq!(
+            (doing.lane, doing.state),
+            (LaneKind::InProgress, TaskState::InFlight)
+        );
+        let broken = labels.status("broken").unwrap();
+        assert_eq!(
+            (broken.lane, broken.state),
+            (LaneKind::Done, TaskState::Failed)
+        );
+    }
+
     #[test]
     fn serialize_deserialize_roundtrip() {
         let config = Config {
+            version: CURRENT_VERSION,
             repositories: vec![
                 Repository::new("rust-lang", "rust"),
                 Repository::with_token("private", "repo", "ghp_xxx"),
             ],
             polling: PollingConfig::with_interval(120),
             github_token: Some("ghp_global".to_string()),
+            github_token_env: None,
+            github_token_command: None,
+            github_api_url: Some("https://ghe.example.com/api/v3".to_string()),
+            github_app: None,
+            labels: None,
+            lanes: None,
+            filters: Vec::new(),
+            keymap: KeymapConfig::default(),
             sync_labels: true,
+            source: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -425,18 +1073,95 @@ mod tests {
         let path = dir.path().join("config.json");
 
         let original = Config {
+            version: CURRENT_VERSION,
             repositories: vec![Repository::new("owner", "repo")],
             polling: PollingConfig::with_interval(120),
             github_token: Some("ghp_xxx".to_string()),
+            github_token_env: None,
+            github_token_command: None,
+            github_api_url: None,
+            github_app: None,
+            labels: None,
+            lanes: None,
+            filters: vec![NamedFilter::new(
+                "mine",
+                TaskFilter::default().with_authors(["octocat"]),
+            )],
+            keymap: KeymapConfig {
+                preset: whip_protocol::KeymapPreset::Vim,
+                ..KeymapConfig::default()
+            },
             sync_labels: false, // test non-default value
+            source: Some(ConfigLayer::File(path.clone())),
         };
 
-        original.save_to(&path).unwrap();
-        let loaded = Config::load_from(&path).unwrap();
+        let credentials = dir.path().join("credentials.json");
+        original.save_with_credentials(&path, &credentials).unwrap();
+        let mut loaded = Config::load_from(&path).unwrap();
+        assert_eq!(loaded.github_token, None);
+        loaded.apply_credentials(&Credentials::load_from(&credentials).unwrap());
 
         assert_eq!(original, loaded);
     }
 
+    #[test]
+    fn save_preserves_hand_written_formatting() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("whip.json5");
+        let original = r#"{
+    // Repositories to monitor
+    repositories: [
+        "owner/repo",   // main project
+        "owner/legacy", // going away
+    ],
+
+    // Polling configuration
+    polling: {
+        interval_secs: 60, // seconds
+    },
+}
+"#;
+        std::fs::write(&path, original).unwrap();
+
+        let mut config = Config::load_from(&path).unwrap();
+        assert!(config.remove_repository("owner/legacy"));
+        config.add_repository(Repository::new("org", "new"));
+        config.polling = PollingConfig::with_interval(120);
+        config
+            .save_with_credentials(&path, dir.path().join("credentials.json"))
+            .unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap(), config);
+        assert!(written.contains("// Repositories to monitor"));
+        assert!(written.contains("\"owner/repo\",   // main project\n"));
+        assert!(!written.contains("legacy"));
+        assert!(written.contains("        \"org/new\",\n    ],"));
+        assert!(written.contains("interval_secs: 120, // seconds\n    },"));
+        assert!(!written.contains("sync_labels"));
+    }
+
+    #[test]
+    fn save_leaves_version_of_outdated_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("whip.json5");
+        std::fs::write(&path, "{ repositories: ['owner/repo'] }\n").unwrap();
+
+        let mut config = Config::load_from(&path).unwrap();
+        assert_eq!(config.version, CURRENT_VERSION);
+        config.add_repository(Repository::new("org", "new"));
+        config
+            .save_with_credentials(&path, dir.path().join("credentials.json"))
+            .unwrap();
+
+        // Upgrading the file is left to a migration plan
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{ repositories: ['owner/repo', \"org/new\"] }\n"
+        );
+        assert!(MigrationPlan::for_file(&path).unwrap().is_some());
+    }
+
     #[test]
     fn github_token_not_serialized_when_none() {
         let config = Config {
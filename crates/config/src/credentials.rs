@@ -0,0 +1,505 @@
+//! Secret storage, kept apart from the shareable configuration.
+//!
+//! Plaintext tokens entered in the settings panel are not written to the
+//! configuration file. They are stored in a separate credentials file
+//! (`~/.config/whip/credentials.json`) that only its owner can read, and
+//! merged back into the [`Config`] when it is loaded.
+//!
+//! Tokens referenced through `token_env`/`token_command` never need to be
+//! stored at all.
+//!
+//! # File Format
+//!
+//! ```json
+//! {
+//!   "github_token": "ghp_global",
+//!   "repositories": {
+//!     "private-org/secret-repo": "ghp_repo"
+//!   }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Config;
+use crate::error::{ConfigError, Result};
+use crate::persistence::{read_config_file, update_config_file, write_private_file};
+
+/// Tokens stored in the credentials file.
+///
+/// The `Debug` implementation redacts the tokens.
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::{Config, Credentials, Repository};
+///
+/// let config = Config {
+///     repositories: vec![Repository::with_token("org", "repo", "ghp_repo")],
+///     github_token: Some("ghp_global".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let (public, credentials) = config.split_credentials();
+/// assert!(public.github_token.is_none());
+/// assert!(public.repositories[0].token().is_none());
+/// assert_eq!(credentials.repositories["org/repo"], "ghp_repo");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Credentials {
+    /// The global GitHub token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
+
+    /// Repository-specific tokens, keyed by [`Config::repo_key`]: the full
+    /// name (`"owner/repo"`), prefixed with the host for repositories not on
+    /// github.com.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repositories: BTreeMap<String, String>,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field(
+                "github_token",
+                &self.github_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field(
+                "repositories",
+                &self.repositories.keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Credentials {
+    /// Returns `true` if no tokens are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.github_token.is_none() && self.repositories.is_empty()
+    }
+
+    /// Loads credentials from a file.
+    ///
+    /// A missing file holds no credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the credentials file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_config::Credentials;
+    ///
+    /// # fn example() -> whip_config::Result<()> {
+    /// let credentials = Credentials::load_from("credentials.json")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+        match read_config_file(path) {
+            Err(ConfigError::ReadFile { source, .. })
+                if source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                Ok(Self::default())
+            }
+            result => result,
+        }
+    }
+
+    /// Saves credentials to a file readable only by its owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the credentials file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_config::Credentials;
+    ///
+    /// # fn example() -> whip_config::Result<()> {
+    /// let credentials = Credentials {
+    ///     github_token: Some("ghp_xxx".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// credentials.save_to("credentials.json")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_private_file(path, self)
+    }
+
+    /// Edits the credentials file at `path` in place.
+    ///
+    /// The stored credentials are loaded, passed to `edit`, and written
+    /// back only if they changed.
+    pub(crate) fn update(path: &Path, edit: impl FnOnce(&mut Self)) -> Result<()> {
+        let mut stored = Self::load_from(path)?;
+        let before = stored.clone();
+        edit(&mut stored);
+        if stored != before {
+            stored.save_to(path)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the token changes from `old` to `new`.
+    ///
+    /// Tokens that differ are set, and tokens in `old` but not in `new` are
+    /// removed. Every other token is kept, so saving one configuration
+    /// never loses the tokens of repositories configured elsewhere.
+    pub(crate) fn apply_changes(&mut self, old: &Self, new: &Self) {
+        if new.github_token != old.github_token {
+            self.github_token.clone_from(&new.github_token);
+        }
+        for key in old.repositories.keys() {
+            if !new.repositories.contains_key(key) {
+                self.repositories.remove(key);
+            }
+        }
+        for (key, token) in &new.repositories {
+            if old.repositories.get(key) != Some(token) {
+                self.repositories.insert(key.clone(), token.clone());
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Separates plaintext tokens from the rest of the configuration.
+    ///
+    /// Returns a copy of the configuration without `github_token` or any
+    /// repository `token`, and the removed tokens. `token_env` and
+    /// `token_command` references are kept, since they hold no secret.
+    #[must_use]
+    pub fn split_credentials(&self) -> (Config, Credentials) {
+        let mut public = self.clone();
+        let mut credentials = Credentials {
+            github_token: public.github_token.take(),
+            ..Default::default()
+        };
+        let keys: Vec<String> = self
+            .repositories
+            .iter()
+            .map(|repo| self.repo_key(repo))
+            .collect();
+        for (repo, key) in public.repositories.iter_mut().zip(keys) {
+            if let Some(token) = repo.take_token() {
+                credentials.repositories.insert(key, token);
+            }
+        }
+        (public, credentials)
+    }
+
+    /// Fills in tokens from stored credentials.
+    ///
+    /// Tokens already present in the configuration take precedence.
+    /// Stored tokens for repositories that are not configured are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `credentials` - The stored credentials
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::{Config, Credentials, Repository};
+    ///
+    /// let mut config = Config {
+    ///     repositories: vec![Repository::new("org", "repo")],
+    ///     ..Default::default()
+    /// };
+    /// let credentials = Credentials {
+    ///     repositories: [("org/repo".to_string(), "ghp_repo".to_string())].into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// config.apply_credentials(&credentials);
+    /// assert_eq!(config.repositories[0].token(), Some("ghp_repo"));
+    /// ```
+    pub fn apply_credentials(&mut self, credentials: &Credentials) {
+        if self.github_token.is_none() {
+            self.github_token.clone_from(&credentials.github_token);
+        }
+        let keys: Vec<String> = self
+            .repositories
+            .iter()
+            .map(|repo| self.repo_key(repo))
+            .collect();
+        for (repo, key) in self.repositories.iter_mut().zip(keys) {
+            if repo.token().is_none()
+                && let Some(token) = credentials.repositories.get(&key)
+            {
+                repo.set_token(token.clone());
+            }
+        }
+    }
+
+    /// Saves the configuration without plaintext tokens.
+    ///
+    /// Tokens are moved to the credentials file at `credentials_path`,
+    /// which is readable only by its owner; everything else is written to
+    /// `config_path`. The credentials file is only created when there are
+    /// tokens to store. Stored tokens of repositories in this configuration
+    /// are replaced by its own (and dropped where it has none), while those
+    /// of other repositories are kept. A stored global token is replaced by
+    /// this configuration's, but kept if it has none: the configuration may
+    /// simply not have been loaded with credentials. Use
+    /// [`save_changes`](Config::save_changes) to remove it.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_path` - The path to save the configuration to
+    /// * `credentials_path` - The path to save the tokens to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_config::Config;
+    /// use whip_config::persistence::{default_credentials_path, default_user_config_path};
+    ///
+    /// # fn example() -> whip_config::Result<()> {
+    /// let config = Config {
+    ///     github_token: Some("ghp_xxx".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// config.save_with_credentials(default_user_config_path()?, default_credentials_path()?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_with_credentials(
+        &self,
+        config_path: impl AsRef<Path>,
+        credentials_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let credentials_path = credentials_path.as_ref();
+        let (public, credentials) = self.split_credentials();
+        // Only this configuration's tokens are replaced
+        let stored = Credentials::load_from(credentials_path)?;
+        let keys: Vec<String> = self
+            .repositories
+            .iter()
+            .map(|repo| self.repo_key(repo))
+            .collect();
+        let old = Credentials {
+            // Without a global token of its own, this configuration never
+            // held the stored one
+            github_token: None,
+            repositories: stored
+                .repositories
+                .into_iter()
+                .filter(|(key, _)| keys.contains(key))
+                .collect(),
+        };
+        Credentials::update(credentials_path, |stored| {
+            stored.apply_changes(&old, &credentials);
+        })?;
+        update_config_file(config_path, &public)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConfigLayer, Repository};
+    use tempfile::TempDir;
+
+    fn config_with_tokens() -> Config {
+        Config {
+            repositories: vec![
+                Repository::with_token("org", "secret", "ghp_repo"),
+                Repository::new("org", "public").with_token_env("ORG_TOKEN"),
+            ],
+            github_token: Some("ghp_global".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn split_credentials_removes_plaintext_tokens() {
+        let (public, credentials) = config_with_tokens().split_credentials();
+
+        assert!(public.github_token.is_none());
+        assert!(public.repositories[0].token().is_none());
+        assert_eq!(public.repositories[1].token_env(), Some("ORG_TOKEN"));
+        assert_eq!(credentials.github_token.as_deref(), Some("ghp_global"));
+        assert_eq!(credentials.repositories.len(), 1);
+        assert_eq!(credentials.repositories["org/secret"], "ghp_repo");
+    }
+
+    #[test]
+    fn apply_credentials_restores_split_config() {
+        let original = config_with_tokens();
+        let (mut public, credentials) = original.split_credentials();
+        public.apply_credentials(&credentials);
+        assert_eq!(public, original);
+    }
+
+    #[test]
+    fn apply_credentials_keeps_existing_tokens() {
+        let mut config = config_with_tokens();
+        let credentials = Credentials {
+            github_token: Some("ghp_stored".to_string()),
+            repositories: [
+                ("org/secret".to_string(), "ghp_stored".to_string()),
+                ("org/unknown".to_string(), "ghp_stored".to_string()),
+            ]
+            .into(),
+        };
+        config.apply_credentials(&credentials);
+
+        assert_eq!(config.github_token.as_deref(), Some("ghp_global"));
+        assert_eq!(config.repositories[0].token(), Some("ghp_repo"));
+        assert_eq!(config.repositories.len(), 2);
+    }
+
+    #[test]
+    fn credentials_are_keyed_by_host() {
+        let config = Config {
+            repositories: vec![
+                Repository::with_token("org", "repo", "ghp_public"),
+                Repository::with_token("org", "repo", "ghp_enterprise")
+                    .with_api_url("https://ghe.example.com/api/v3"),
+            ],
+            ..Default::default()
+        };
+        let (mut public, credentials) = config.split_credentials();
+
+        assert_eq!(credentials.repositories["org/repo"], "ghp_public");
+        assert_eq!(
+            credentials.repositories["ghe.example.com/org/repo"],
+            "ghp_enterprise"
+        );
+
+        public.apply_credentials(&credentials);
+        assert_eq!(public, config);
+    }
+
+    #[test]
+    fn load_missing_credentials_file_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let credentials = Credentials::load_from(dir.path().join("missing.json")).unwrap();
+        assert!(credentials.is_empty());
+    }
+
+    #[test]
+    fn save_with_credentials_keeps_tokens_out_of_config() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json5");
+        let credentials_path = dir.path().join("credentials.json");
+
+        let config = config_with_tokens();
+        config
+            .save_with_credentials(&config_path, &credentials_path)
+            .unwrap();
+
+        let written = std::fs::read_to_string(&config_path).unwrap();
+        assert!(!written.contains("ghp_"), "secret leaked: {written}");
+        assert!(written.contains("ORG_TOKEN"));
+
+        let mut loaded = Config::load_from(&config_path).unwrap();
+        loaded.apply_credentials(&Credentials::load_from(&credentials_path).unwrap());
+        assert_eq!(
+            loaded,
+            Config {
+                source: Some(ConfigLayer::File(config_path)),
+                ..config
+            }
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&credentials_path)
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn save_with_credentials_drops_removed_tokens() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json5");
+        let credentials_path = dir.path().join("credentials.json");
+
+        config_with_tokens()
+            .save_with_credentials(&config_path, &credentials_path)
+            .unwrap();
+        let mut config = config_with_tokens();
+        config.github_token = None;
+        config.repositories[0] = Repository::new("org", "secret");
+        config
+            .save_with_credentials(&config_path, &credentials_path)
+            .unwrap();
+
+        let stored = Credentials::load_from(&credentials_path).unwrap();
+        assert!(stored.repositories.is_empty());
+        assert_eq!(stored.github_token.as_deref(), Some("ghp_global"));
+    }
+
+    #[test]
+    fn save_with_credentials_keeps_tokens_of_other_repositories() {
+        let dir = TempDir::new().unwrap();
+        let config_path = dir.path().join("config.json5");
+        let credentials_path = dir.path().join("credentials.json");
+
+        config_with_tokens()
+            .save_with_credentials(&config_path, &credentials_path)
+            .unwrap();
+        let other = Config {
+            repositories: vec![Repository::with_token("me", "dotfiles", "ghp_mine")],
+            github_token: None,
+            ..Default::default()
+        };
+        other
+            .save_with_credentials(dir.path().join("proj.json5"), &credentials_path)
+            .unwrap();
+
+        let stored = Credentials::load_from(&credentials_path).unwrap();
+        assert_eq!(stored.repositories["org/secret"], "ghp_repo");
+        assert_eq!(stored.repositories["me/dotfiles"], "ghp_mine");
+        assert_eq!(stored.github_token.as_deref(), Some("ghp_global"));
+    }
+
+    #[test]
+    fn save_with_credentials_skips_empty_credentials_file() {
+        let dir = TempDir::new().unwrap();
+        let credentials_path = dir.path().join("credentials.json");
+
+        Config::default()
+            .save_with_credentials(dir.path().join("config.json5"), &credentials_path)
+            .unwrap();
+
+        assert!(!credentials_path.exists());
+    }
+
+    #[test]
+    fn debug_redacts_tokens() {
+        let (_, credentials) = config_with_tokens().split_credentials();
+        let debug = format!("{credentials:?}");
+        assert!(!debug.contains("ghp_"));
+        assert!(debug.contains("org/secret"));
+    }
+}
@@ -5,6 +5,8 @@
 
 use std::path::PathBuf;
 
+use crate::auth::TokenSource;
+
 /// Errors that can occur during configuration operations.
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -32,14 +34,115 @@ pub enum ConfigError {
     #[error("failed to parse config: {0}")]
     ParseJson5(#[from] serde_json5::Error),
 
+    /// A configuration document does not match the configuration format.
+    #[error("invalid config: {0}")]
+    InvalidConfig(#[source] serde_json::Error),
+
+    /// A configuration document has a format version this whip cannot read.
+    #[error("unsupported config version {version}: versions up to {supported} are supported")]
+    UnsupportedVersion {
+        /// The version found in the document.
+        version: String,
+        /// The newest version this whip reads.
+        supported: u32,
+    },
+
+    /// A JSON5 document could not be edited in place.
+    #[error("cannot edit config at line {line}, column {column}: {reason}")]
+    EditJson5 {
+        /// The 1-based line of the problem.
+        line: usize,
+        /// The 1-based column of the problem.
+        column: usize,
+        /// What went wrong.
+        reason: String,
+    },
+
     /// Failed to serialize configuration to JSON.
     #[error("failed to serialize config: {0}")]
     SerializeJson(#[from] serde_json::Error),
 
+    /// An environment variable override has an invalid value.
+    #[error("invalid value '{value}' for ${var}: {reason}")]
+    InvalidEnvOverride {
+        /// The environment variable.
+        var: String,
+        /// Its value.
+        value: String,
+        /// Why the value was rejected.
+        reason: String,
+    },
+
     /// Invalid repository format.
     #[error("invalid repository format: {0}")]
     InvalidRepository(String),
 
+    /// The same repository is listed more than once.
+    #[error("repository '{0}' is listed more than once")]
+    DuplicateRepository(String),
+
+    /// Invalid status label definition.
+    #[error("invalid label '{name}': {reason}")]
+    InvalidLabel {
+        /// The label name.
+        name: String,
+        /// Why the label was rejected.
+        reason: String,
+    },
+
+    /// Invalid named board filter.
+    #[error("invalid filter '{name}': {reason}")]
+    InvalidFilter {
+        /// The filter name.
+        name: String,
+        /// Why the filter was rejected.
+        reason: String,
+    },
+
+    /// Invalid board lanes.
+    #[error("invalid lanes: {0}")]
+    InvalidLanes(String),
+
+    /// Invalid GitHub API base URL.
+    #[error("invalid GitHub API URL '{url}': {reason}")]
+    InvalidApiUrl {
+        /// The configured URL.
+        url: String,
+        /// Why the URL was rejected.
+        reason: String,
+    },
+
+    /// Failed to read a GitHub App private key.
+    #[error("failed to read GitHub App private key at {path}: {source}")]
+    ReadPrivateKey {
+        /// The path that could not be read.
+        path: PathBuf,
+        /// The underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A GitHub App private key could not be used to sign a JWT.
+    #[error("invalid GitHub App private key: {0}")]
+    InvalidPrivateKey(#[source] jsonwebtoken::errors::Error),
+
+    /// A GitHub App installation token could not be obtained.
+    #[error("GitHub App token request failed: {0}")]
+    GitHubAppRequest(#[source] octocrab::Error),
+
+    /// A configured token source failed to produce a token.
+    ///
+    /// Resolution stops here rather than fall back to a token that acts as
+    /// someone else, such as a personal token in place of a GitHub App.
+    #[error("{token_source}: {error}")]
+    TokenSourceFailed {
+        /// The source that failed.
+        token_source: TokenSource,
+        /// Why it failed.
+        #[source]
+        error: Box<ConfigError>,
+    },
+
     /// Invalid polling interval.
     #[error("invalid polling interval: {reason}")]
     InvalidPollingInterval {
@@ -51,6 +154,15 @@ pub enum ConfigError {
     #[error("could not determine home directory")]
     NoHomeDirectory,
 
+    /// A configured token command could not be run or produced no token.
+    #[error("token command '{command}' failed: {reason}")]
+    TokenCommand {
+        /// The command that was run.
+        command: String,
+        /// Why no token was obtained.
+        reason: String,
+    },
+
     /// Failed to execute `gh auth token` command.
     #[error("failed to get GitHub token from gh CLI: {0}")]
     GhAuthFailed(#[source] std::io::Error),
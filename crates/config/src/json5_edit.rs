@@ -0,0 +1,1263 @@
+//! Format-preserving edits of JSON5 documents.
+//!
+//! Configuration files are written by hand, with comments, trailing commas
+//! and a deliberate ordering. Re-serializing a [`Config`](crate::Config)
+//! would throw all of that away, so [`Json5Document`] applies structured
+//! edits (set a value, insert or remove an array element, remove an object
+//! member) directly to the original text, touching only the edited parts.
+//!
+//! Inserted values follow the document's style: its indentation, whether
+//! object keys are quoted, and whether the last element of a container
+//! carries a trailing comma.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde_json::json;
+//! use whip_config::json5_edit::{Json5Document, PathSegment};
+//!
+//! let mut doc = Json5Document::parse(
+//!     r#"{
+//!     // Repositories to monitor
+//!     repositories: [
+//!         "owner/repo", // the main one
+//!     ],
+//! }"#,
+//! )?;
+//!
+//! doc.insert(&[PathSegment::Key("repositories")], 1, &json!("owner/other"))?;
+//!
+//! assert_eq!(
+//!     doc.as_str(),
+//!     r#"{
+//!     // Repositories to monitor
+//!     repositories: [
+//!         "owner/repo", // the main one
+//!         "owner/other",
+//!     ],
+//! }"#
+//! );
+//! # Ok::<(), whip_config::ConfigError>(())
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::error::{ConfigError, Result};
+
+/// Indentation used when the document has none to imitate.
+const DEFAULT_INDENT: &str = "    ";
+
+/// Maximum length of a container rendered on a single line.
+const MAX_INLINE_WIDTH: usize = 60;
+
+/// One step of a path into a JSON5 document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment<'a> {
+    /// An object member, by key.
+    Key(&'a str),
+    /// An array element, by index.
+    Index(usize),
+}
+
+/// A JSON5 document that can be edited without losing its formatting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Json5Document {
+    text: String,
+}
+
+/// A parsed value and its byte span in the document.
+#[derive(Debug)]
+struct Node {
+    start: usize,
+    end: usize,
+    kind: NodeKind,
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    Object(Vec<Entry>),
+    Array(Vec<Entry>),
+    Scalar,
+}
+
+/// An object member or array element.
+#[derive(Debug)]
+struct Entry {
+    /// Where the entry starts: its key for object members, its value otherwise.
+    start: usize,
+    /// The unescaped key, for object members.
+    key: Option<String>,
+    /// The raw key text, for object members.
+    raw_key: Option<String>,
+    value: Node,
+    /// Position of the comma following the entry, if any.
+    comma: Option<usize>,
+}
+
+impl Node {
+    fn entries(&self) -> Option<&[Entry]> {
+        match &self.kind {
+            NodeKind::Object(entries) | NodeKind::Array(entries) => Some(entries),
+            NodeKind::Scalar => None,
+        }
+    }
+
+    fn member(&self, key: &str) -> Option<(usize, &Entry)> {
+        match &self.kind {
+            NodeKind::Object(entries) => entries
+                .iter()
+                .enumerate()
+                .find(|(_, entry)| entry.key.as_deref() == Some(key)),
+            _ => None,
+        }
+    }
+
+    fn get(&self, path: &[PathSegment<'_>]) -> Option<&Node> {
+        let Some((first, rest)) = path.split_first() else {
+            return Some(self);
+        };
+        let child = match (first, &self.kind) {
+            (PathSegment::Key(key), NodeKind::Object(_)) => &self.member(key)?.1.value,
+            (PathSegment::Index(index), NodeKind::Array(entries)) => &entries.get(*index)?.value,
+            _ => return None,
+        };
+        child.get(rest)
+    }
+}
+
+impl Json5Document {
+    /// Parses a JSON5 document.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The document text
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::EditJson5`] if the text is not a single JSON5
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::json5_edit::Json5Document;
+    ///
+    /// assert!(Json5Document::parse("{ a: 1, // comment\n }").is_ok());
+    /// assert!(Json5Document::parse("{ a: ").is_err());
+    /// ```
+    pub fn parse(text: impl Into<String>) -> Result<Self> {
+        let doc = Self { text: text.into() };
+        doc.root()?;
+        Ok(doc)
+    }
+
+    /// Returns the document text.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the document text, consuming the document.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns `true` if a value exists at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::json5_edit::{Json5Document, PathSegment};
+    ///
+    /// let doc = Json5Document::parse("{ polling: { interval_secs: 60 } }")?;
+    /// assert!(doc.contains(&[PathSegment::Key("polling"), PathSegment::Key("interval_secs")]));
+    /// assert!(!doc.contains(&[PathSegment::Key("repositories")]));
+    /// # Ok::<(), whip_config::ConfigError>(())
+    /// ```
+    #[must_use]
+    pub fn contains(&self, path: &[PathSegment<'_>]) -> bool {
+        self.root().is_ok_and(|root| root.get(path).is_some())
+    }
+
+    /// Returns the 1-based line and column where the value at `path` starts.
+    ///
+    /// Returns `None` if there is no value at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::json5_edit::{Json5Document, PathSegment};
+    ///
+    /// let doc = Json5Document::parse("{\n  repositories: [\"a/b\", \"c/d\"],\n}")?;
+    /// let path = [PathSegment::Key("repositories"), PathSegment::Index(1)];
+    /// assert_eq!(doc.position(&path), Some((2, 25)));
+    /// # Ok::<(), whip_config::ConfigError>(())
+    /// ```
+    #[must_use]
+    pub fn position(&self, path: &[PathSegment<'_>]) -> Option<(usize, usize)> {
+        let root = self.root().ok()?;
+        let node = root.get(path)?;
+        Some(self.line_column(node.start))
+    }
+
+    /// Sets the value at `path`.
+    ///
+    /// An existing value is replaced in place. A missing object member is
+    /// appended to its parent object, which must exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Where to set the value; empty to replace the whole document
+    /// * `value` - The new value
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::EditJson5`] if the parent of `path` does not
+    /// exist, is not a container, or an array index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use whip_config::json5_edit::{Json5Document, PathSegment};
+    ///
+    /// let mut doc = Json5Document::parse("{\n  // How often to poll\n  polling: { interval_secs: 60 },\n}")?;
+    /// doc.set(&[PathSegment::Key("polling"), PathSegment::Key("interval_secs")], &json!(120))?;
+    /// doc.set(&[PathSegment::Key("sync_labels")], &json!(false))?;
+    ///
+    /// assert_eq!(
+    ///     doc.as_str(),
+    ///     "{\n  // How often to poll\n  polling: { interval_secs: 120 },\n  sync_labels: false,\n}"
+    /// );
+    /// # Ok::<(), whip_config::ConfigError>(())
+    /// ```
+    pub fn set(&mut self, path: &[PathSegment<'_>], value: &Value) -> Result<()> {
+        let root = self.root()?;
+        let Some((last, parent_path)) = path.split_last() else {
+            let text = self.render(value, "");
+            self.splice(root.start, root.end, &text);
+            return Ok(());
+        };
+        let parent = self.container_at(&root, parent_path)?;
+
+        match (last, &parent.kind) {
+            (PathSegment::Key(key), NodeKind::Object(entries)) => {
+                if let Some((_, entry)) = parent.member(key) {
+                    let text = self.render(value, self.indent_of(entry.start));
+                    self.splice(entry.value.start, entry.value.end, &text);
+                } else {
+                    let indent = self.entry_indent(parent);
+                    let entry =
+                        format!("{}: {}", self.render_key(key), self.render(value, &indent));
+                    let edits = self.insert_edits(parent, entries.len(), &indent, &entry);
+                    self.apply(edits);
+                }
+                Ok(())
+            }
+            (PathSegment::Index(index), NodeKind::Array(entries)) => {
+                let entry = entries
+                    .get(*index)
+                    .ok_or_else(|| self.error(parent.start, "array index out of bounds"))?;
+                let text = self.render(value, self.indent_of(entry.start));
+                self.splice(entry.value.start, entry.value.end, &text);
+                Ok(())
+            }
+            _ => Err(self.error(parent.start, "path does not match the document")),
+        }
+    }
+
+    /// Inserts a value into the array at `path`, before `index`.
+    ///
+    /// An `index` equal to the array length appends the value.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the array
+    /// * `index` - The position of the new element
+    /// * `value` - The value to insert
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::EditJson5`] if there is no array at `path` or
+    /// `index` is past its end.
+    pub fn insert(&mut self, path: &[PathSegment<'_>], index: usize, value: &Value) -> Result<()> {
+        let root = self.root()?;
+        let array = self.container_at(&root, path)?;
+        let NodeKind::Array(entries) = &array.kind else {
+            return Err(self.error(array.start, "expected an array"));
+        };
+        if index > entries.len() {
+            return Err(self.error(array.start, "array index out of bounds"));
+        }
+        let indent = self.entry_indent(array);
+        let entry = self.render(value, &indent);
+        let edits = self.insert_edits(array, index, &indent, &entry);
+        self.apply(edits);
+        Ok(())
+    }
+
+    /// Removes the object member or array element at `path`.
+    ///
+    /// Returns `false` if there was nothing to remove. Comments on the lines
+    /// of the removed entry go with it; comments on lines of their own stay.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the entry to remove
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::EditJson5`] if `path` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::json5_edit::{Json5Document, PathSegment};
+    ///
+    /// let mut doc = Json5Document::parse("[\n  \"a/b\", // first\n  \"c/d\",\n]")?;
+    /// assert!(doc.remove(&[PathSegment::Index(0)])?);
+    /// assert_eq!(doc.as_str(), "[\n  \"c/d\",\n]");
+    /// # Ok::<(), whip_config::ConfigError>(())
+    /// ```
+    pub fn remove(&mut self, path: &[PathSegment<'_>]) -> Result<bool> {
+        let root = self.root()?;
+        let Some((last, parent_path)) = path.split_last() else {
+            return Err(self.error(0, "cannot remove the document root"));
+        };
+        let Some(parent) = root.get(parent_path) else {
+            return Ok(false);
+        };
+        let index = match (last, &parent.kind) {
+            (PathSegment::Key(key), NodeKind::Object(_)) => parent.member(key).map(|(i, _)| i),
+            (PathSegment::Index(index), NodeKind::Array(entries)) => {
+                (*index < entries.len()).then_some(*index)
+            }
+            _ => None,
+        };
+        let Some(index) = index else {
+            return Ok(false);
+        };
+        let edits = self.remove_edits(parent, index);
+        self.apply(edits);
+        Ok(true)
+    }
+
+    /// Edits the document so the changes from `old` to `new` are applied.
+    ///
+    /// `old` must describe the document's current content (e.g. the
+    /// serialized value it was deserialized into), so that array elements
+    /// line up by index. Only what differs between `old` and `new` is
+    /// touched. Object members missing from `new` are removed, and array
+    /// elements are matched up so that unchanged elements, and the comments
+    /// around them, are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The value the document currently holds
+    /// * `new` - The value the document should hold
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::EditJson5`] if an edit cannot be applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use whip_config::json5_edit::Json5Document;
+    ///
+    /// let mut doc = Json5Document::parse("{\n  a: 1, // keep me\n  b: [1, 2],\n}")?;
+    /// doc.apply_diff(&json!({ "a": 1, "b": [1, 2] }), &json!({ "a": 1, "b": [1, 3] }))?;
+    /// assert_eq!(doc.as_str(), "{\n  a: 1, // keep me\n  b: [1, 3],\n}");
+    /// # Ok::<(), whip_config::ConfigError>(())
+    /// ```
+    pub fn apply_diff(&mut self, old: &Value, new: &Value) -> Result<()> {
+        self.diff_at(&mut Vec::new(), old, new)
+    }
+
+    fn diff_at(&mut self, path: &mut Vec<PathKey>, old: &Value, new: &Value) -> Result<()> {
+        if old == new {
+            return Ok(());
+        }
+        let root = self.root()?;
+        let segments = segments(path);
+        let node = root.get(&segments);
+
+        match (old, new, node.map(|node| &node.kind)) {
+            (Value::Object(old), Value::Object(new), Some(NodeKind::Object(_))) => {
+                for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                    path.push(PathKey::Key(key.clone()));
+                    self.remove(&segments_of(path))?;
+                    path.pop();
+                }
+                for (key, new_value) in new {
+                    path.push(PathKey::Key(key.clone()));
+                    match old.get(key) {
+                        // Unchanged, possibly left out of the document
+                        Some(old_value) if old_value == new_value => {}
+                        Some(old_value) if self.contains(&segments_of(path)) => {
+                            self.diff_at(path, old_value, new_value)?;
+                        }
+                        // Only write the members that changed, not the
+                        // ones the document leaves to their defaults
+                        Some(old_value @ Value::Object(_)) if new_value.is_object() => {
+                            self.set(&segments_of(path), &Value::Object(Map::new()))?;
+                            self.diff_at(path, old_value, new_value)?;
+                        }
+                        _ => self.set(&segments_of(path), new_value)?,
+                    }
+                    path.pop();
+                }
+                Ok(())
+            }
+            (Value::Array(old), Value::Array(new), Some(NodeKind::Array(entries)))
+                if entries.len() == old.len() =>
+            {
+                self.diff_array(path, old, new)
+            }
+            _ => self.set(&segments, new),
+        }
+    }
+
+    /// Applies the changes between two arrays element by element.
+    ///
+    /// Elements common to both (their longest common subsequence) are left
+    /// alone. Between them, objects and arrays that changed are edited in
+    /// place; other elements are removed and inserted, so comments on a
+    /// removed element don't end up describing its replacement.
+    fn diff_array(&mut self, path: &mut Vec<PathKey>, old: &[Value], new: &[Value]) -> Result<()> {
+        let common = longest_common_subsequence(old, new);
+        let (mut i, mut j, mut position) = (0, 0, 0);
+
+        for (next_i, next_j) in common
+            .into_iter()
+            .chain(std::iter::once((old.len(), new.len())))
+        {
+            let (removed, added) = (&old[i..next_i], &new[j..next_j]);
+            let paired = removed
+                .iter()
+                .zip(added)
+                .take_while(|(old, new)| {
+                    (old.is_object() && new.is_object()) || (old.is_array() && new.is_array())
+                })
+                .count();
+            for (old_value, new_value) in removed.iter().zip(added).take(paired) {
+                path.push(PathKey::Index(position));
+                self.diff_at(path, old_value, new_value)?;
+                path.pop();
+                position += 1;
+            }
+            for _ in &removed[paired..] {
+                path.push(PathKey::Index(position));
+                self.remove(&segments_of(path))?;
+                path.pop();
+            }
+            for value in &added[paired..] {
+                self.insert(&segments_of(path), position, value)?;
+                position += 1;
+            }
+            // Skip the common element
+            position += 1;
+            (i, j) = (next_i + 1, next_j + 1);
+        }
+        Ok(())
+    }
+
+    fn root(&self) -> Result<Node> {
+        let mut parser = Parser { doc: self, pos: 0 };
+        let root = parser.value()?;
+        parser.skip_trivia()?;
+        if parser.pos < self.text.len() {
+            return Err(self.error(parser.pos, "unexpected content after the document"));
+        }
+        Ok(root)
+    }
+
+    fn container_at<'n>(&self, root: &'n Node, path: &[PathSegment<'_>]) -> Result<&'n Node> {
+        let node = root
+            .get(path)
+            .ok_or_else(|| self.error(root.start, "path not found in the document"))?;
+        if node.entries().is_none() {
+            return Err(self.error(node.start, "expected an object or array"));
+        }
+        Ok(node)
+    }
+
+    fn error(&self, pos: usize, reason: &str) -> ConfigError {
+        let (line, column) = self.line_column(pos);
+        ConfigError::EditJson5 {
+            line,
+            column,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Returns the 1-based line and column of a byte offset.
+    fn line_column(&self, pos: usize) -> (usize, usize) {
+        let before = &self.text[..pos.min(self.text.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        (line, column)
+    }
+
+    fn splice(&mut self, start: usize, end: usize, text: &str) {
+        self.text.replace_range(start..end, text);
+    }
+
+    /// Applies non-overlapping `(start, end, text)` edits.
+    ///
+    /// Insertions at the same position keep their order in `edits`.
+    fn apply(&mut self, edits: Vec<(usize, usize, String)>) {
+        let mut edits: Vec<_> = edits.into_iter().enumerate().collect();
+        // Back to front, so earlier positions stay valid
+        edits.sort_by_key(|(order, (start, _, _))| std::cmp::Reverse((*start, *order)));
+        for (_, (start, end, text)) in edits {
+            self.splice(start, end, &text);
+        }
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    /// Returns the leading whitespace of the line containing `pos`.
+    fn indent_of(&self, pos: usize) -> &str {
+        let start = self.line_start(pos);
+        let line = &self.text[start..];
+        let len = line.len() - line.trim_start_matches([' ', '\t']).len();
+        &line[..len]
+    }
+
+    /// Returns `true` if only whitespace precedes `pos` on its line.
+    fn starts_line(&self, pos: usize) -> bool {
+        self.text[self.line_start(pos)..pos]
+            .chars()
+            .all(|c| c == ' ' || c == '\t')
+    }
+
+    /// Returns the position of the end of the line containing `pos`, if
+    /// only whitespace and comments follow `pos` on that line.
+    fn rest_of_line_is_trivia(&self, pos: usize) -> Option<usize> {
+        let bytes = self.text.as_bytes();
+        let mut i = pos;
+        while i < bytes.len() {
+            match bytes[i] {
+                b' ' | b'\t' | b'\r' => i += 1,
+                b'\n' => return Some(i),
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    return Some(self.text[i..].find('\n').map_or(bytes.len(), |n| i + n));
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    let close = self.text[i + 2..].find("*/")?;
+                    let comment = &self.text[i..i + 2 + close + 2];
+                    if comment.contains('\n') {
+                        return None;
+                    }
+                    i += comment.len();
+                }
+                _ => return None,
+            }
+        }
+        Some(i)
+    }
+
+    /// Returns `true` if the container spreads its entries over several lines.
+    fn is_multiline(&self, container: &Node) -> bool {
+        self.text[container.start..container.end].contains('\n')
+    }
+
+    /// Returns the indentation for entries of `container`.
+    fn entry_indent(&self, container: &Node) -> String {
+        let entries = container.entries().unwrap_or_default();
+        if let Some(first) = entries.first()
+            && self.starts_line(first.start)
+        {
+            return self.indent_of(first.start).to_string();
+        }
+        format!("{}{}", self.indent_of(container.start), self.indent_unit())
+    }
+
+    /// Returns the document's indentation unit.
+    fn indent_unit(&self) -> String {
+        let Ok(root) = self.root() else {
+            return DEFAULT_INDENT.to_string();
+        };
+        let mut stack = vec![&root];
+        while let Some(node) = stack.pop() {
+            let entries = node.entries().unwrap_or_default();
+            if let Some(first) = entries.first()
+                && self.starts_line(first.start)
+            {
+                let outer = self.indent_of(node.start);
+                if let Some(unit) = self.indent_of(first.start).strip_prefix(outer)
+                    && !unit.is_empty()
+                {
+                    return unit.to_string();
+                }
+            }
+            stack.extend(entries.iter().map(|entry| &entry.value));
+        }
+        DEFAULT_INDENT.to_string()
+    }
+
+    /// Returns `true` if the document quotes its object keys.
+    fn quotes_keys(&self) -> bool {
+        let Ok(root) = self.root() else {
+            return true;
+        };
+        let mut stack = vec![&root];
+        while let Some(node) = stack.pop() {
+            if let NodeKind::Object(entries) = &node.kind
+                && let Some(raw) = entries.iter().find_map(|entry| entry.raw_key.as_deref())
+            {
+                return raw.starts_with(['"', '\'']);
+            }
+            stack.extend(
+                node.entries()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|entry| &entry.value),
+            );
+        }
+        true
+    }
+
+    fn render_key(&self, key: &str) -> String {
+        let is_identifier = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        if is_identifier && !self.quotes_keys() {
+            key.to_string()
+        } else {
+            Value::from(key).to_string()
+        }
+    }
+
+    /// Renders a value in the document's style, for a line indented by `indent`.
+    fn render(&self, value: &Value, indent: &str) -> String {
+        let inline = self.render_inline(value);
+        let is_flat = match value {
+            Value::Object(map) => map.values().all(|v| !v.is_object() && !v.is_array()),
+            Value::Array(items) => items.iter().all(|v| !v.is_object() && !v.is_array()),
+            _ => true,
+        };
+        if is_flat && inline.len() <= MAX_INLINE_WIDTH {
+            return inline;
+        }
+
+        let inner = format!("{indent}{}", self.indent_unit());
+        let (open, close, items): (_, _, Vec<_>) = match value {
+            Value::Object(map) => (
+                '{',
+                '}',
+                map.iter()
+                    .map(|(k, v)| format!("{}: {}", self.render_key(k), self.render(v, &inner)))
+                    .collect(),
+            ),
+            Value::Array(items) => (
+                '[',
+                ']',
+                items.iter().map(|v| self.render(v, &inner)).collect(),
+            ),
+            _ => return inline,
+        };
+        let body: Vec<_> = items.iter().map(|item| format!("{inner}{item}")).collect();
+        format!("{open}\n{}\n{indent}{close}", body.join(",\n"))
+    }
+
+    fn render_inline(&self, value: &Value) -> String {
+        match value {
+            Value::Object(map) if map.is_empty() => "{}".to_string(),
+            Value::Object(map) => {
+                let members: Vec<_> = map
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", self.render_key(k), self.render_inline(v)))
+                    .collect();
+                format!("{{ {} }}", members.join(", "))
+            }
+            Value::Array(items) => {
+                let items: Vec<_> = items.iter().map(|v| self.render_inline(v)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            scalar => scalar.to_string(),
+        }
+    }
+
+    /// Computes the edits inserting `entry` into `container` before `index`.
+    fn insert_edits(
+        &self,
+        container: &Node,
+        index: usize,
+        indent: &str,
+        entry: &str,
+    ) -> Vec<(usize, usize, String)> {
+        let entries = container.entries().unwrap_or_default();
+        let open = container.start + 1;
+        let close = container.end - 1;
+
+        let Some(last) = entries.last() else {
+            let inner = &self.text[open..close];
+            if inner.trim().is_empty() {
+                let outer = self.indent_of(container.start);
+                return vec![(open, close, format!("\n{indent}{entry}\n{outer}"))];
+            }
+            // Keep comments inside the empty container
+            if self.starts_line(close) {
+                let line = self.line_start(close);
+                return vec![(line, line, format!("{indent}{entry}\n"))];
+            }
+            return match self.rest_of_line_is_trivia(open) {
+                Some(eol) if eol < close => vec![(eol, eol, format!("\n{indent}{entry}"))],
+                _ => vec![(close, close, entry.to_string())],
+            };
+        };
+
+        if self.is_multiline(container) {
+            if index == entries.len() {
+                let after = last.comma.map_or(last.value.end, |comma| comma + 1);
+                if let Some(eol) = self.rest_of_line_is_trivia(after)
+                    && eol < close
+                {
+                    return match last.comma {
+                        // Keep the trailing comma style
+                        Some(_) => vec![(eol, eol, format!("\n{indent}{entry},"))],
+                        None => vec![
+                            (last.value.end, last.value.end, ",".to_string()),
+                            (eol, eol, format!("\n{indent}{entry}")),
+                        ],
+                    };
+                }
+            } else {
+                let after = match index {
+                    0 => Some(open),
+                    _ => entries[index - 1].comma.map(|comma| comma + 1),
+                };
+                if let Some(eol) = after.and_then(|after| self.rest_of_line_is_trivia(after))
+                    && eol < entries[index].start
+                {
+                    return vec![(eol, eol, format!("\n{indent}{entry},"))];
+                }
+            }
+        }
+
+        // Entries share lines: insert inline
+        if index == entries.len() {
+            match last.comma {
+                Some(comma) => vec![(comma + 1, comma + 1, format!(" {entry},"))],
+                None => vec![(last.value.end, last.value.end, format!(", {entry}"))],
+            }
+        } else {
+            let start = entries[index].start;
+            vec![(start, start, format!("{entry}, "))]
+        }
+    }
+
+    /// Computes the edits removing the entry at `index` from `container`.
+    fn remove_edits(&self, container: &Node, index: usize) -> Vec<(usize, usize, String)> {
+        let entries = container.entries().unwrap_or_default();
+        let entry = &entries[index];
+        let after = entry.comma.map_or(entry.value.end, |comma| comma + 1);
+        let is_last = index + 1 == entries.len();
+
+        if self.starts_line(entry.start)
+            && let Some(eol) = self.rest_of_line_is_trivia(after)
+        {
+            let start = self.line_start(entry.start);
+            let end = if eol < self.text.len() { eol + 1 } else { eol };
+            let mut edits = vec![(start, end, String::new())];
+            // The new last entry must not keep a comma the old one lacked
+            if entry.comma.is_none()
+                && index > 0
+                && let Some(comma) = entries[index - 1].comma
+            {
+                edits.push((comma, comma + 1, String::new()));
+            }
+            return edits;
+        }
+
+        if is_last && index > 0 {
+            vec![(entries[index - 1].value.end, entry.value.end, String::new())]
+        } else if is_last {
+            vec![(entry.start, after, String::new())]
+        } else {
+            vec![(entry.start, entries[index + 1].start, String::new())]
+        }
+    }
+}
+
+/// An owned [`PathSegment`], used while walking a diff.
+#[derive(Debug, Clone)]
+enum PathKey {
+    Key(String),
+    Index(usize),
+}
+
+fn segments(path: &[PathKey]) -> Vec<PathSegment<'_>> {
+    path.iter()
+        .map(|key| match key {
+            PathKey::Key(key) => PathSegment::Key(key),
+            PathKey::Index(index) => PathSegment::Index(*index),
+        })
+        .collect()
+}
+
+fn segments_of(path: &[PathKey]) -> Vec<PathSegment<'_>> {
+    segments(path)
+}
+
+/// Returns the index pairs of a longest common subsequence of `a` and `b`.
+fn longest_common_subsequence(a: &[Value], b: &[Value]) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut pairs = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// A recursive-descent JSON5 parser recording spans.
+struct Parser<'a> {
+    doc: &'a Json5Document,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn text(&self) -> &str {
+        &self.doc.text
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text().as_bytes().get(self.pos).copied()
+    }
+
+    fn error(&self, reason: &str) -> ConfigError {
+        self.doc.error(self.pos, reason)
+    }
+
+    fn skip_trivia(&mut self) -> Result<()> {
+        loop {
+            let rest = &self.text()[self.pos..];
+            let Some(c) = rest.chars().next() else {
+                return Ok(());
+            };
+            if c.is_whitespace() || c == '\u{feff}' {
+                self.pos += c.len_utf8();
+            } else if rest.starts_with("//") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                let close = comment
+                    .find("*/")
+                    .ok_or_else(|| self.error("unterminated comment"))?;
+                self.pos += close + 4;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        self.skip_trivia()?;
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => self.container(b'}', true),
+            Some(b'[') => self.container(b']', false),
+            Some(quote @ (b'"' | b'\'')) => {
+                self.string(quote)?;
+                Ok(Node {
+                    start,
+                    end: self.pos,
+                    kind: NodeKind::Scalar,
+                })
+            }
+            Some(_) => {
+                let len = self.text()[start..]
+                    .find(|c: char| c.is_whitespace() || ",:{}[]/\"'".contains(c))
+                    .unwrap_or(self.text().len() - start);
+                if len == 0 {
+                    return Err(self.error("unexpected character"));
+                }
+                self.pos += len;
+                Ok(Node {
+                    start,
+                    end: self.pos,
+                    kind: NodeKind::Scalar,
+                })
+            }
+            None => Err(self.error("unexpected end of document")),
+        }
+    }
+
+    fn string(&mut self, quote: u8) -> Result<()> {
+        let bytes = self.text().as_bytes();
+        let mut i = self.pos + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b if b == quote => {
+                    self.pos = i + 1;
+                    return Ok(());
+                }
+                _ => i += 1,
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn key(&mut self) -> Result<(String, String)> {
+        let start = self.pos;
+        match self.peek() {
+            Some(quote @ (b'"' | b'\'')) => {
+                self.string(quote)?;
+                let raw = &self.text()[start..self.pos];
+                let key =
+                    serde_json5::from_str(raw).map_err(|_| self.doc.error(start, "invalid key"))?;
+                Ok((key, raw.to_string()))
+            }
+            _ => {
+                let len = self.text()[start..]
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .unwrap_or(self.text().len() - start);
+                if len == 0 {
+                    return Err(self.error("expected an object key"));
+                }
+                self.pos += len;
+                let raw = &self.text()[start..self.pos];
+                Ok((raw.to_string(), raw.to_string()))
+            }
+        }
+    }
+
+    fn container(&mut self, close: u8, is_object: bool) -> Result<Node> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut entries = Vec::new();
+
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some(close) {
+                self.pos += 1;
+                break;
+            }
+
+            let entry_start = self.pos;
+            let (key, raw_key) = if is_object {
+                let (key, raw) = self.key()?;
+                self.skip_trivia()?;
+                if self.peek() != Some(b':') {
+                    return Err(self.error("expected ':'"));
+                }
+                self.pos += 1;
+                (Some(key), Some(raw))
+            } else {
+                (None, None)
+            };
+            let value = self.value()?;
+
+            self.skip_trivia()?;
+            let comma = match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                    Some(self.pos - 1)
+                }
+                Some(c) if c == close => None,
+                _ => return Err(self.error("expected ',' or the end of the container")),
+            };
+            entries.push(Entry {
+                start: entry_start,
+                key,
+                raw_key,
+                value,
+                comma,
+            });
+        }
+
+        let kind = if is_object {
+            NodeKind::Object(entries)
+        } else {
+            NodeKind::Array(entries)
+        };
+        Ok(Node {
+            start,
+            end: self.pos,
+            kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CONFIG: &str = r#"{
+    // Repositories to monitor
+    repositories: [
+        "owner/repo",                                    // Short format
+        { owner: "org", repo: "name", token: "ghp_x" }, // Full format
+    ],
+
+    // Polling configuration
+    polling: {
+        interval_secs: 60,   // Polling interval in seconds
+        auto_adjust: true,
+    },
+}
+"#;
+
+    fn key(key: &str) -> PathSegment<'_> {
+        PathSegment::Key(key)
+    }
+
+    #[test]
+    fn parse_accepts_json5_syntax() {
+        let doc = Json5Document::parse(CONFIG).unwrap();
+        assert!(doc.contains(&[key("polling"), key("auto_adjust")]));
+        assert!(doc.contains(&[key("repositories"), PathSegment::Index(1), key("token")]));
+        assert!(!doc.contains(&[key("repositories"), PathSegment::Index(2)]));
+    }
+
+    #[test]
+    fn parse_reports_position_of_errors() {
+        let err = Json5Document::parse("{\n  a: 1\n  b: 2\n}").unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ConfigError::EditJson5 {
+                    line: 3,
+                    column: 3,
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn set_replaces_scalar_keeping_comments() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        doc.set(&[key("polling"), key("interval_secs")], &json!(120))
+            .unwrap();
+        assert_eq!(
+            doc.as_str(),
+            CONFIG.replace("interval_secs: 60,", "interval_secs: 120,")
+        );
+    }
+
+    #[test]
+    fn set_appends_missing_member_in_document_style() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        doc.set(&[key("sync_labels")], &json!(false)).unwrap();
+        assert!(
+            doc.as_str()
+                .ends_with("        auto_adjust: true,\n    },\n    sync_labels: false,\n}\n")
+        );
+    }
+
+    #[test]
+    fn set_quotes_keys_in_json_documents() {
+        let mut doc = Json5Document::parse("{\n  \"a\": 1\n}").unwrap();
+        doc.set(&[key("b")], &json!("x")).unwrap();
+        assert_eq!(doc.as_str(), "{\n  \"a\": 1,\n  \"b\": \"x\"\n}");
+    }
+
+    #[test]
+    fn set_into_empty_object() {
+        let mut doc = Json5Document::parse("{}").unwrap();
+        doc.set(&[key("a")], &json!({ "b": [1, 2] })).unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "{\n    \"a\": {\n        \"b\": [1, 2]\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn set_renders_long_values_over_several_lines() {
+        let mut doc = Json5Document::parse("{\n  a: 1,\n}").unwrap();
+        doc.set(&[key("b")], &json!({ "nested": { "x": 1 }, "list": ["a"] }))
+            .unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "{\n  a: 1,\n  b: {\n    list: [\"a\"],\n    nested: { x: 1 }\n  },\n}"
+        );
+    }
+
+    #[test]
+    fn insert_appends_after_trailing_comment() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        doc.insert(&[key("repositories")], 2, &json!("new/repo"))
+            .unwrap();
+        assert!(
+            doc.as_str()
+                .contains("token: \"ghp_x\" }, // Full format\n        \"new/repo\",\n    ],")
+        );
+    }
+
+    #[test]
+    fn insert_adds_comma_when_last_entry_has_none() {
+        let mut doc = Json5Document::parse("[\n  \"a\" // first\n]").unwrap();
+        doc.insert(&[], 1, &json!("b")).unwrap();
+        assert_eq!(doc.as_str(), "[\n  \"a\", // first\n  \"b\"\n]");
+    }
+
+    #[test]
+    fn insert_at_start_and_middle() {
+        let mut doc = Json5Document::parse("[\n  \"a\",\n  \"c\",\n]").unwrap();
+        doc.insert(&[], 1, &json!("b")).unwrap();
+        doc.insert(&[], 0, &json!("0")).unwrap();
+        assert_eq!(doc.as_str(), "[\n  \"0\",\n  \"a\",\n  \"b\",\n  \"c\",\n]");
+    }
+
+    #[test]
+    fn insert_into_inline_array() {
+        let mut doc = Json5Document::parse("{ list: [\"a\", \"c\"] }").unwrap();
+        doc.insert(&[key("list")], 1, &json!("b")).unwrap();
+        doc.insert(&[key("list")], 3, &json!("d")).unwrap();
+        assert_eq!(doc.as_str(), "{ list: [\"a\", \"b\", \"c\", \"d\"] }");
+    }
+
+    #[test]
+    fn insert_into_empty_array_with_comment() {
+        let mut doc = Json5Document::parse("{\n  list: [ // none yet\n  ],\n}").unwrap();
+        doc.insert(&[key("list")], 0, &json!("a")).unwrap();
+        assert_eq!(doc.as_str(), "{\n  list: [ // none yet\n    \"a\"\n  ],\n}");
+    }
+
+    #[test]
+    fn remove_entry_with_its_trailing_comment() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        assert!(
+            doc.remove(&[key("repositories"), PathSegment::Index(0)])
+                .unwrap()
+        );
+        assert_eq!(
+            doc.as_str(),
+            CONFIG.replace(
+                "        \"owner/repo\",                                    // Short format\n",
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn remove_last_entry_without_trailing_comma() {
+        let mut doc = Json5Document::parse("{\n  a: 1,\n  b: 2\n}").unwrap();
+        assert!(doc.remove(&[key("b")]).unwrap());
+        assert_eq!(doc.as_str(), "{\n  a: 1\n}");
+    }
+
+    #[test]
+    fn remove_inline_entries() {
+        let mut doc = Json5Document::parse("[1, 2, 3]").unwrap();
+        doc.remove(&[PathSegment::Index(1)]).unwrap();
+        assert_eq!(doc.as_str(), "[1, 3]");
+        doc.remove(&[PathSegment::Index(1)]).unwrap();
+        assert_eq!(doc.as_str(), "[1]");
+        doc.remove(&[PathSegment::Index(0)]).unwrap();
+        assert_eq!(doc.as_str(), "[]");
+    }
+
+    #[test]
+    fn remove_missing_entry_is_noop() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        assert!(!doc.remove(&[key("missing")]).unwrap());
+        assert!(!doc.remove(&[key("missing"), key("deeper")]).unwrap());
+        assert_eq!(doc.as_str(), CONFIG);
+    }
+
+    fn config_value(text: &str) -> Value {
+        serde_json5::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn apply_diff_adds_and_removes_array_elements() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        let old = config_value(CONFIG);
+        let mut new = old.clone();
+        let repos = new["repositories"].as_array_mut().unwrap();
+        repos.remove(0);
+        repos.push(json!("added/repo"));
+
+        doc.apply_diff(&old, &new).unwrap();
+
+        assert_eq!(config_value(doc.as_str()), new);
+        assert!(
+            doc.as_str()
+                .contains("// Full format\n        \"added/repo\",\n")
+        );
+        assert!(doc.as_str().contains("// Polling interval in seconds"));
+        assert!(!doc.as_str().contains("// Short format"));
+    }
+
+    #[test]
+    fn apply_diff_edits_changed_elements_in_place() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        let old = config_value(CONFIG);
+        let mut new = old.clone();
+        new["repositories"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("token");
+        new["polling"]["auto_adjust"] = json!(false);
+
+        doc.apply_diff(&old, &new).unwrap();
+
+        assert_eq!(config_value(doc.as_str()), new);
+        assert!(
+            doc.as_str()
+                .contains("{ owner: \"org\", repo: \"name\" }, // Full format")
+        );
+        assert!(doc.as_str().contains("auto_adjust: false,\n"));
+    }
+
+    #[test]
+    fn apply_diff_creates_missing_parents() {
+        let mut doc = Json5Document::parse("{\n  // empty\n}").unwrap();
+        doc.apply_diff(
+            &json!({ "polling": { "interval_secs": 60, "auto_adjust": true } }),
+            &json!({ "polling": { "interval_secs": 90, "auto_adjust": true } }),
+        )
+        .unwrap();
+        assert_eq!(
+            doc.as_str(),
+            "{\n  // empty\n    \"polling\": {\n        \"interval_secs\": 90\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn apply_diff_without_changes_keeps_text() {
+        let mut doc = Json5Document::parse(CONFIG).unwrap();
+        let value = config_value(CONFIG);
+        doc.apply_diff(&value, &value).unwrap();
+        assert_eq!(doc.as_str(), CONFIG);
+    }
+}
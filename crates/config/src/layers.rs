@@ -0,0 +1,1220 @@
+//! Layered configuration loading.
+//!
+//! The effective configuration is built by merging several layers, each
+//! overriding the ones before it:
+//!
+//! 1. Built-in defaults
+//! 2. User config (`~/.config/whip/config.json5` or `config.json`)
+//! 3. Local config (`./whip.json5` or `./whip.json`)
+//! 4. Environment overrides (`WHIP_*`, see [`ENV_OVERRIDES`])
+//!
+//! Tokens from the credentials file (see [`Credentials`]) then fill in any
+//! token no layer configured.
+//!
+//! # Merge Rules
+//!
+//! - Objects (such as `polling`) are merged member by member, so a local
+//!   config setting `polling.interval_secs` keeps the user's
+//!   `polling.auto_adjust`.
+//! - Any other value set by a later layer replaces the earlier one.
+//! - `repositories` lists are combined: repositories from later layers are
+//!   appended, and a repository listed again (same `owner/repo` on the same
+//!   host) keeps its position but takes the options (`token`, `token_env`,
+//!   ...) the later layer sets. Entries with different `api_url` hosts are
+//!   different repositories, even within one file. Entries without their
+//!   own `api_url` are on the host of the merged `github_api_url`, whichever
+//!   layer sets it.
+//!
+//! # Provenance
+//!
+//! [`Provenance`] records which [`ConfigLayer`] each effective value came
+//! from, keyed by dotted path (`polling.interval_secs`). Repositories are
+//! keyed by name: `repositories[owner/repo]` for the repository itself and
+//! `repositories[owner/repo].token_env` for its options. Repositories on
+//! another host, through their own `api_url` or the merged
+//! `github_api_url`, include it: `repositories[host/owner/repo]`.
+//!
+//! # Saving
+//!
+//! The loaded [`Config`] remembers the highest-priority file it was merged
+//! from in [`Config::source`]. [`Config::save_changes`] writes edits back to
+//! one file without copying values from the other layers into it.
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_config::layers::{ConfigLayer, ConfigLoader};
+//!
+//! let loaded = ConfigLoader::new()
+//!     .with_env_var("WHIP_POLL_INTERVAL_SECS", "120")
+//!     .load()?;
+//!
+//! assert_eq!(loaded.config.polling.interval_secs, 120);
+//! assert_eq!(
+//!     loaded.provenance.source("polling.interval_secs"),
+//!     Some(&ConfigLayer::Environment("WHIP_POLL_INTERVAL_SECS".to_string()))
+//! );
+//! assert_eq!(
+//!     loaded.provenance.source("sync_labels"),
+//!     Some(&ConfigLayer::Default)
+//! );
+//! # Ok::<(), whip_config::ConfigError>(())
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+use whip_protocol::{api_host, repo_key};
+
+use crate::credentials::Credentials;
+use crate::error::{ConfigError, Result};
+use crate::migrate::read_migrated;
+use crate::persistence::{
+    default_credentials_path, find_local_config_file, find_user_config_file, update_config_file,
+};
+use crate::{Config, Repository};
+
+/// Environment variable overriding `polling.interval_secs`.
+pub const POLL_INTERVAL_ENV: &str = "WHIP_POLL_INTERVAL_SECS";
+
+/// Environment variable overriding `github_api_url`.
+pub const API_URL_ENV: &str = "WHIP_GITHUB_API_URL";
+
+/// Environment variable overriding `sync_labels` (`true`/`false`/`1`/`0`).
+pub const SYNC_LABELS_ENV: &str = "WHIP_SYNC_LABELS";
+
+/// All environment variables that override configuration values.
+///
+/// Tokens are not among them: `WHIP_GITHUB_TOKEN` and `GITHUB_TOKEN` are
+/// part of token resolution (see [`auth`](crate::auth)).
+pub const ENV_OVERRIDES: &[&str] = &[POLL_INTERVAL_ENV, API_URL_ENV, SYNC_LABELS_ENV];
+
+/// Where a configuration value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// The built-in default.
+    Default,
+    /// The user config file.
+    User(PathBuf),
+    /// The project-local config file.
+    Local(PathBuf),
+    /// A config file loaded on its own, with [`Config::load_from`].
+    File(PathBuf),
+    /// An environment variable override.
+    Environment(String),
+    /// The credentials file.
+    Credentials(PathBuf),
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::User(path) => write!(f, "user config ({})", path.display()),
+            Self::Local(path) => write!(f, "local config ({})", path.display()),
+            Self::File(path) => write!(f, "config file ({})", path.display()),
+            Self::Environment(var) => write!(f, "${var}"),
+            Self::Credentials(path) => write!(f, "credentials ({})", path.display()),
+        }
+    }
+}
+
+impl ConfigLayer {
+    /// Returns the file behind this layer, if it is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use whip_config::ConfigLayer;
+    ///
+    /// let layer = ConfigLayer::Local("whip.json5".into());
+    /// assert_eq!(layer.path(), Some(Path::new("whip.json5")));
+    /// assert_eq!(ConfigLayer::Default.path(), None);
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::User(path) | Self::Local(path) | Self::File(path) | Self::Credentials(path) => {
+                Some(path)
+            }
+            Self::Default | Self::Environment(_) => None,
+        }
+    }
+}
+
+/// The layer each effective configuration value came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    sources: BTreeMap<String, ConfigLayer>,
+    /// The merged `github_api_url`, used by repositories without their own.
+    api_url: Option<String>,
+}
+
+impl Provenance {
+    /// Returns the layer the value at `key` came from.
+    ///
+    /// Returns `None` for values no layer sets (such as an unset
+    /// `github_token`) and for objects, whose members are tracked one by
+    /// one.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A dotted path such as `polling.interval_secs` or
+    ///   `repositories[owner/repo].token_env`
+    #[must_use]
+    pub fn source(&self, key: &str) -> Option<&ConfigLayer> {
+        self.sources.get(key)
+    }
+
+    /// Returns the layer that first listed a repository.
+    ///
+    /// A repository listed by several layers is listed by the lowest one;
+    /// higher layers only change its options.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The repository to look up
+    #[must_use]
+    pub fn repository_source(&self, repo: &Repository) -> Option<&ConfigLayer> {
+        self.source(&repository_key(repo, self.api_url.as_deref()))
+    }
+
+    /// Iterates over all tracked values and their layers, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ConfigLayer)> {
+        self.sources
+            .iter()
+            .map(|(key, layer)| (key.as_str(), layer))
+    }
+
+    /// Records `layer` as the source of `value` at `key`, replacing what
+    /// earlier layers recorded below `key`.
+    fn record(&mut self, key: &str, value: &Value, layer: &ConfigLayer) {
+        self.sources.retain(|existing, _| {
+            existing != key
+                && !existing
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with(['.', '[']))
+        });
+        match value {
+            Value::Object(members) if !members.is_empty() => {
+                for (name, member) in members {
+                    self.record(&child_key(key, name), member, layer);
+                }
+            }
+            _ => {
+                self.sources.insert(key.to_string(), layer.clone());
+            }
+        }
+    }
+}
+
+/// A configuration merged from its layers, with the source of each value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredConfig {
+    /// The effective configuration.
+    pub config: Config,
+    /// Where each value of `config` came from.
+    pub provenance: Provenance,
+}
+
+/// Builds a [`LayeredConfig`] from configuration layers.
+///
+/// [`ConfigLoader::discover`] picks up the standard locations; the `with_*`
+/// methods set layers explicitly.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct ConfigLoader {
+    user_file: Option<PathBuf>,
+    local_file: Option<PathBuf>,
+    credentials_file: Option<PathBuf>,
+    env: HashMap<String, String>,
+}
+
+impl ConfigLoader {
+    /// Creates a loader with only the built-in defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a loader for the standard layers.
+    ///
+    /// Uses the user and local config files that exist, the user's
+    /// credentials file, and the [`ENV_OVERRIDES`] set in the process
+    /// environment.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_config::layers::ConfigLoader;
+    ///
+    /// # fn example() -> whip_config::Result<()> {
+    /// let loaded = ConfigLoader::discover().load()?;
+    /// for (key, layer) in loaded.provenance.iter() {
+    ///     println!("{key}: {layer}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn discover() -> Self {
+        let loader = Self {
+            user_file: find_user_config_file(),
+            local_file: find_local_config_file(),
+            credentials_file: default_credentials_path().ok(),
+            env: HashMap::new(),
+        };
+        loader.with_process_env()
+    }
+
+    /// Sets the user config file.
+    pub fn with_user_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_file = Some(path.into());
+        self
+    }
+
+    /// Sets the project-local config file.
+    pub fn with_local_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.local_file = Some(path.into());
+        self
+    }
+
+    /// Sets the credentials file. A missing file holds no credentials.
+    pub fn with_credentials_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.credentials_file = Some(path.into());
+        self
+    }
+
+    /// Sets an environment override, as if `var` were set to `value`.
+    ///
+    /// Variables that are not in [`ENV_OVERRIDES`] and empty values are
+    /// ignored.
+    pub fn with_env_var(mut self, var: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(var.into(), value.into());
+        self
+    }
+
+    /// Sets the [`ENV_OVERRIDES`] that are set in the process environment.
+    pub fn with_process_env(mut self) -> Self {
+        for var in ENV_OVERRIDES {
+            if let Ok(value) = std::env::var(var) {
+                self = self.with_env_var(*var, value);
+            }
+        }
+        self
+    }
+
+    /// Returns the user config file, if any.
+    #[must_use]
+    pub fn user_file(&self) -> Option<&Path> {
+        self.user_file.as_deref()
+    }
+
+    /// Returns the project-local config file, if any.
+    #[must_use]
+    pub fn local_file(&self) -> Option<&Path> {
+        self.local_file.as_deref()
+    }
+
+    /// Returns the credentials file, if any.
+    #[must_use]
+    pub fn credentials_file(&self) -> Option<&Path> {
+        self.credentials_file.as_deref()
+    }
+
+    /// Loads and merges the layers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config or credentials file cannot be read or
+    /// parsed, if an environment override is invalid, or if the merged
+    /// configuration fails validation.
+    pub fn load(&self) -> Result<LayeredConfig> {
+        let mut provenance = Provenance::default();
+        let mut merged = serde_json::to_value(Config::default())?;
+        if let Value::Object(members) = &mut merged {
+            members.remove("repositories");
+        }
+        provenance.record("", &merged, &ConfigLayer::Default);
+
+        let mut layer_repositories = Vec::new();
+        let files = [
+            (
+                self.user_file.as_deref(),
+                ConfigLayer::User as fn(PathBuf) -> ConfigLayer,
+            ),
+            (self.local_file.as_deref(), ConfigLayer::Local),
+        ];
+        // Settings are saved to the highest-priority file
+        let mut source = None;
+        for (path, layer) in files {
+            let Some(path) = path else {
+                continue;
+            };
+            let layer = layer(path.to_path_buf());
+            source = Some(layer.clone());
+            // Read both as a `Config`, to check it, and as raw JSON, to know
+            // which values the file actually sets
+            let (config, mut value) = read_migrated(path)?;
+            if let Value::Object(members) = &mut value {
+                members.remove("repositories");
+            }
+            merge(&mut merged, value, "", &layer, &mut provenance);
+            layer_repositories.push((layer, config.repositories));
+        }
+
+        for var in ENV_OVERRIDES {
+            if let Some(value) = self.env.get(*var).filter(|value| !value.is_empty()) {
+                let layer = ConfigLayer::Environment((*var).to_string());
+                merge(
+                    &mut merged,
+                    env_override(var, value)?,
+                    "",
+                    &layer,
+                    &mut provenance,
+                );
+            }
+        }
+
+        // Repositories are merged once every layer had its say on the
+        // global API URL, which decides the host of those without their own
+        provenance.api_url = merged
+            .get("github_api_url")
+            .and_then(Value::as_str)
+            .map(String::from);
+        let mut repositories = Vec::new();
+        for (layer, layer_repositories) in layer_repositories {
+            merge_repositories(
+                &mut repositories,
+                layer_repositories,
+                &layer,
+                &mut provenance,
+            )?;
+        }
+
+        if let Value::Object(members) = &mut merged {
+            members.insert(
+                "repositories".to_string(),
+                serde_json::to_value(&repositories)?,
+            );
+        }
+        let mut config: Config = serde_json::from_value(merged)?;
+        config.source = source;
+
+        if let Some(path) = &self.credentials_file {
+            apply_credentials(&mut config, path, &mut provenance)?;
+        }
+
+        config.validate()?;
+        Ok(LayeredConfig { config, provenance })
+    }
+}
+
+impl Config {
+    /// Saves the changes made since `loaded` into a single config file.
+    ///
+    /// A layered configuration holds values from several files, so writing
+    /// it out whole would copy every layer into one of them. Instead, only
+    /// the values that differ between `loaded` and `self` are written, on
+    /// top of what the file at `config_path` already holds (keeping its
+    /// comments and formatting). Repositories are matched by name and host
+    /// (entries without their own `api_url` are on the host of the loaded
+    /// `github_api_url`): added or changed ones are written in full and
+    /// removed ones are dropped from the file. A removed repository that another layer also lists comes
+    /// back on the next load.
+    ///
+    /// As with [`save_with_credentials`](Config::save_with_credentials),
+    /// plaintext tokens go to the credentials file at `credentials_path`
+    /// and are removed from the config file. Only changed tokens are
+    /// written there; the tokens of repositories configured elsewhere stay.
+    ///
+    /// # Arguments
+    ///
+    /// * `loaded` - The configuration as it was loaded
+    /// * `config_path` - The config file to save the changes to
+    /// * `credentials_path` - The path to save the tokens to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existing config file cannot be parsed, or if
+    /// either file cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_config::layers::ConfigLoader;
+    /// use whip_config::persistence::default_credentials_path;
+    ///
+    /// # fn example() -> whip_config::Result<()> {
+    /// let loaded = ConfigLoader::discover().load()?.config;
+    /// let mut config = loaded.clone();
+    /// config.polling.interval_secs = 120;
+    ///
+    /// if let Some(path) = config.source.as_ref().and_then(|source| source.path()) {
+    ///     config.save_changes(&loaded, path, default_credentials_path()?)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_changes(
+        &self,
+        loaded: &Config,
+        config_path: impl AsRef<Path>,
+        credentials_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let config_path = config_path.as_ref();
+        let api_url = loaded.github_api_url.as_deref();
+        let same_repository = |a: &Repository, b: &Repository| same_repository(a, b, api_url);
+        let (public, credentials) = self.split_credentials();
+        let (loaded, loaded_credentials) = loaded.split_credentials();
+        let (file, file_credentials) = if config_path.exists() {
+            read_migrated(config_path)?.0.split_credentials()
+        } else {
+            (Config::default(), Credentials::default())
+        };
+
+        let mut repositories = file.repositories.clone();
+        for removed in loaded.repositories.iter().filter(|old| {
+            !public
+                .repositories
+                .iter()
+                .any(|new| same_repository(old, new))
+        }) {
+            repositories.retain(|repo| !same_repository(repo, removed));
+        }
+        for repo in &public.repositories {
+            let unchanged = loaded
+                .repositories
+                .iter()
+                .any(|old| same_repository(old, repo) && old == repo);
+            if unchanged {
+                continue;
+            }
+            match repositories
+                .iter_mut()
+                .find(|existing| same_repository(existing, repo))
+            {
+                Some(existing) => *existing = repo.clone(),
+                None => repositories.push(repo.clone()),
+            }
+        }
+
+        let mut value = settings_value(&file)?;
+        apply_changes(
+            &mut value,
+            &settings_value(&loaded)?,
+            &settings_value(&public)?,
+        );
+        let mut updated: Config = serde_json::from_value(value)?;
+        updated.repositories = repositories;
+
+        Credentials::update(credentials_path.as_ref(), |stored| {
+            // Plaintext tokens still in use move out of the file, which is
+            // written without them
+            if file_credentials.github_token.is_some()
+                && file_credentials.github_token == credentials.github_token
+            {
+                stored.github_token.clone_from(&credentials.github_token);
+            }
+            for (key, token) in &file_credentials.repositories {
+                if credentials.repositories.get(key) == Some(token) {
+                    stored.repositories.insert(key.clone(), token.clone());
+                }
+            }
+            stored.apply_changes(&loaded_credentials, &credentials);
+        })?;
+        update_config_file(config_path, &updated)
+    }
+}
+
+/// Returns `true` if both entries name the same repository on the same host.
+///
+/// Entries without their own `api_url` use the global `api_url`.
+fn same_repository(a: &Repository, b: &Repository, api_url: Option<&str>) -> bool {
+    a.full_name() == b.full_name()
+        && api_host(a.api_url().or(api_url)) == api_host(b.api_url().or(api_url))
+}
+
+/// Serializes everything but the repositories, which are merged by name.
+fn settings_value(config: &Config) -> Result<Value> {
+    let mut value = serde_json::to_value(config)?;
+    if let Value::Object(members) = &mut value {
+        members.remove("repositories");
+    }
+    Ok(value)
+}
+
+/// Applies the differences between `old` and `new` to `target`.
+fn apply_changes(target: &mut Value, old: &Value, new: &Value) {
+    match (target, old, new) {
+        (Value::Object(target), Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                target.remove(key);
+            }
+            for (key, value) in new {
+                match (target.get_mut(key), old.get(key)) {
+                    (_, Some(old)) if old == value => {}
+                    (Some(target), Some(old)) => apply_changes(target, old, value),
+                    _ => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, _, new) => *target = new.clone(),
+    }
+}
+
+fn child_key(key: &str, name: &str) -> String {
+    if key.is_empty() {
+        name.to_string()
+    } else {
+        format!("{key}.{name}")
+    }
+}
+
+/// Returns the provenance key of a repository, whose host is that of the
+/// global `api_url` unless the repository has its own.
+fn repository_key(repo: &Repository, api_url: Option<&str>) -> String {
+    let name = repo_key(
+        &api_host(repo.api_url().or(api_url)),
+        repo.owner(),
+        repo.repo(),
+    );
+    format!("repositories[{name}]")
+}
+
+/// Merges `value` into `target`, objects member by member.
+fn merge(
+    target: &mut Value,
+    value: Value,
+    key: &str,
+    layer: &ConfigLayer,
+    provenance: &mut Provenance,
+) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(members)) => {
+            for (name, member) in members {
+                let child = child_key(key, &name);
+                match target.get_mut(&name) {
+                    Some(existing) => merge(existing, member, &child, layer, provenance),
+                    None => {
+                        provenance.record(&child, &member, layer);
+                        target.insert(name, member);
+                    }
+                }
+            }
+        }
+        (target, value) => {
+            provenance.record(key, &value, layer);
+            *target = value;
+        }
+    }
+}
+
+/// Adds a layer's repositories, merging those already listed.
+///
+/// Repositories without their own `api_url` are on the host of the global
+/// one recorded in `provenance`.
+fn merge_repositories(
+    repositories: &mut Vec<Repository>,
+    layer_repositories: Vec<Repository>,
+    layer: &ConfigLayer,
+    provenance: &mut Provenance,
+) -> Result<()> {
+    let api_url = provenance.api_url.clone();
+    for repo in layer_repositories {
+        let key = repository_key(&repo, api_url.as_deref());
+        // Only the options the layer sets, not the owner and name
+        if let Value::Object(mut options) = serde_json::to_value(&repo)? {
+            options.remove("owner");
+            options.remove("repo");
+            for (name, option) in &options {
+                provenance.record(&child_key(&key, name), option, layer);
+            }
+        }
+
+        match repositories
+            .iter_mut()
+            .find(|existing| same_repository(existing, &repo, api_url.as_deref()))
+        {
+            Some(existing) => existing.merge(repo),
+            None => {
+                provenance.sources.insert(key, layer.clone());
+                repositories.push(repo);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts an environment override into the config value it sets.
+fn env_override(var: &str, value: &str) -> Result<Value> {
+    let invalid = |reason: &str| ConfigError::InvalidEnvOverride {
+        var: var.to_string(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
+    let (key, parsed) = match var {
+        POLL_INTERVAL_ENV => {
+            let secs: u64 = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid("expected a number of seconds"))?;
+            let mut polling = Map::new();
+            polling.insert("interval_secs".to_string(), secs.into());
+            ("polling", Value::Object(polling))
+        }
+        API_URL_ENV => ("github_api_url", value.trim().into()),
+        SYNC_LABELS_ENV => {
+            let enabled = match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" => true,
+                "false" | "0" | "no" => false,
+                _ => return Err(invalid("expected true or false")),
+            };
+            ("sync_labels", enabled.into())
+        }
+        _ => return Ok(Value::Object(Map::new())),
+    };
+    let mut members = Map::new();
+    members.insert(key.to_string(), parsed);
+    Ok(Value::Object(members))
+}
+
+/// Fills in stored tokens, recording the ones that were used.
+fn apply_credentials(config: &mut Config, path: &Path, provenance: &mut Provenance) -> Result<()> {
+    let before = config.clone();
+    config.apply_credentials(&Credentials::load_from(path)?);
+
+    let layer = ConfigLayer::Credentials(path.to_path_buf());
+    if before.github_token.is_none() && config.github_token.is_some() {
+        provenance
+            .sources
+            .insert("github_token".to_string(), layer.clone());
+    }
+    for (old, new) in before.repositories.iter().zip(&config.repositories) {
+        if old.token().is_none() && new.token().is_some() {
+            provenance.sources.insert(
+                child_key(
+                    &repository_key(new, config.github_api_url.as_deref()),
+                    "token",
+                ),
+                layer.clone(),
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct Files {
+        dir: TempDir,
+    }
+
+    impl Files {
+        fn new() -> Self {
+            Self {
+                dir: TempDir::new().unwrap(),
+            }
+        }
+
+        fn write(&self, name: &str, content: &str) -> PathBuf {
+            let path = self.dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        }
+    }
+
+    #[test]
+    fn defaults_only() {
+        let loaded = ConfigLoader::new().load().unwrap();
+        assert_eq!(loaded.config, Config::default());
+        assert_eq!(
+            loaded.provenance.source("polling.interval_secs"),
+            Some(&ConfigLayer::Default)
+        );
+        assert_eq!(loaded.provenance.source("github_token"), None);
+    }
+
+    #[test]
+    fn local_overrides_user_member_by_member() {
+        let files = Files::new();
+        let user = files.write(
+            "user.json5",
+            "{ github_token: 'ghp_user', polling: { interval_secs: 30, auto_adjust: false } }",
+        );
+        let local = files.write("whip.json5", "{ polling: { interval_secs: 90 } }");
+
+        let loaded = ConfigLoader::new()
+            .with_user_file(&user)
+            .with_local_file(&local)
+            .load()
+            .unwrap();
+
+        assert_eq!(loaded.config.github_token.as_deref(), Some("ghp_user"));
+        assert_eq!(loaded.config.polling.interval_secs, 90);
+        assert!(!loaded.config.polling.auto_adjust);
+
+        let source = |key| loaded.provenance.source(key).cloned();
+        assert_eq!(
+            source("github_token"),
+            Some(ConfigLayer::User(user.clone()))
+        );
+        assert_eq!(source("polling.auto_adjust"), Some(ConfigLayer::User(user)));
+        assert_eq!(
+            source("polling.interval_secs"),
+            Some(ConfigLayer::Local(local))
+        );
+        assert_eq!(source("sync_labels"), Some(ConfigLayer::Default));
+    }
+
+    #[test]
+    fn repositories_are_combined_by_name() {
+        let files = Files::new();
+        let user = files.write(
+            "user.json5",
+            r#"{ repositories: [
+                { owner: "org", repo: "shared", token_env: "ORG_TOKEN" },
+                "me/dotfiles",
+            ] }"#,
+        );
+        let local = files.write(
+            "whip.json5",
+            r#"{ repositories: [
+                "org/project",
+                { owner: "org", repo: "shared", token_command: "pass show org" },
+            ] }"#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .with_user_file(&user)
+            .with_local_file(&local)
+            .load()
+            .unwrap();
+
+        let names: Vec<_> = loaded
+            .config
+            .repositories
+            .iter()
+            .map(Repository::full_name)
+            .collect();
+        assert_eq!(names, ["org/shared", "me/dotfiles", "org/project"]);
+        let shared = &loaded.config.repositories[0];
+        assert_eq!(shared.token_env(), Some("ORG_TOKEN"));
+        assert_eq!(shared.token_command(), Some("pass show org"));
+
+        let source = |key| loaded.provenance.source(key).cloned();
+        assert_eq!(
+            source("repositories[org/shared]"),
+            Some(ConfigLayer::User(user.clone()))
+        );
+        assert_eq!(
+            loaded.provenance.repository_source(shared),
+            Some(&ConfigLayer::User(user.clone()))
+        );
+        assert_eq!(
+            source("repositories[org/shared].token_env"),
+            Some(ConfigLayer::User(user))
+        );
+        assert_eq!(
+            source("repositories[org/shared].token_command"),
+            Some(ConfigLayer::Local(local.clone()))
+        );
+        assert_eq!(
+            source("repositories[org/project]"),
+            Some(ConfigLayer::Local(local))
+        );
+    }
+
+    #[test]
+    fn repositories_on_different_hosts_are_kept_apart() {
+        let files = Files::new();
+        let local = files.write(
+            "whip.json5",
+            r#"{ repositories: [
+                "acme/api",
+                { owner: "acme", repo: "api", api_url: "https://ghe.example.com/api/v3" },
+            ] }"#,
+        );
+        let user = files.write(
+            "user.json5",
+            r#"{ repositories: [{ owner: "acme", repo: "api", token_env: "ACME_TOKEN" }] }"#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .with_user_file(&user)
+            .with_local_file(&local)
+            .load()
+            .unwrap();
+
+        let keys: Vec<_> = loaded
+            .config
+            .repositories
+            .iter()
+            .map(|repo| loaded.config.repo_key(repo))
+            .collect();
+        assert_eq!(keys, ["acme/api", "ghe.example.com/acme/api"]);
+        assert_eq!(
+            loaded.config.repositories[0].token_env(),
+            Some("ACME_TOKEN")
+        );
+        assert_eq!(loaded.config.repositories[1].token_env(), None);
+
+        let source = |key| loaded.provenance.source(key).cloned();
+        assert_eq!(
+            source("repositories[acme/api]"),
+            Some(ConfigLayer::User(user))
+        );
+        assert_eq!(
+            source("repositories[ghe.example.com/acme/api]"),
+            Some(ConfigLayer::Local(local.clone()))
+        );
+        assert_eq!(
+            loaded
+                .provenance
+                .repository_source(&loaded.config.repositories[1]),
+            Some(&ConfigLayer::Local(local))
+        );
+    }
+
+    #[test]
+    fn repositories_use_global_api_url_of_any_layer() {
+        let files = Files::new();
+        let user = files.write(
+            "user.json5",
+            r#"{ github_api_url: "https://ghe/api/v3", repositories: ["org/a"] }"#,
+        );
+        let local = files.write(
+            "whip.json5",
+            r#"{ repositories: [
+                { owner: "org", repo: "a", api_url: "https://ghe/api/v3", token_env: "GHE_TOKEN" },
+            ] }"#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .with_user_file(&user)
+            .with_local_file(&local)
+            .load()
+            .unwrap();
+
+        let repos = &loaded.config.repositories;
+        assert_eq!(repos.len(), 1, "{repos:?}");
+        assert_eq!(loaded.config.repo_key(&repos[0]), "ghe/org/a");
+        assert_eq!(repos[0].token_env(), Some("GHE_TOKEN"));
+
+        let source = |key| loaded.provenance.source(key).cloned();
+        assert_eq!(
+            loaded.provenance.repository_source(&repos[0]),
+            Some(&ConfigLayer::User(user))
+        );
+        assert_eq!(
+            source("repositories[ghe/org/a].token_env"),
+            Some(ConfigLayer::Local(local))
+        );
+        assert_eq!(source("repositories[org/a]"), None);
+    }
+
+    #[test]
+    fn environment_overrides_files() {
+        let files = Files::new();
+        let local = files.write(
+            "whip.json5",
+            "{ sync_labels: true, polling: { interval_secs: 90 } }",
+        );
+
+        let loaded = ConfigLoader::new()
+            .with_local_file(&local)
+            .with_env_var(POLL_INTERVAL_ENV, "300")
+            .with_env_var(SYNC_LABELS_ENV, "0")
+            .with_env_var(API_URL_ENV, "")
+            .with_env_var("WHIP_UNRELATED", "x")
+            .load()
+            .unwrap();
+
+        assert_eq!(loaded.config.polling.interval_secs, 300);
+        assert!(!loaded.config.sync_labels);
+        assert_eq!(loaded.config.github_api_url, None);
+        assert_eq!(
+            loaded.provenance.source("sync_labels"),
+            Some(&ConfigLayer::Environment(SYNC_LABELS_ENV.to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_environment_override_is_an_error() {
+        let err = ConfigLoader::new()
+            .with_env_var(POLL_INTERVAL_ENV, "soon")
+            .load()
+            .unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidEnvOverride { ref var, .. } if var == POLL_INTERVAL_ENV),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn merged_config_is_validated() {
+        let loaded = ConfigLoader::new()
+            .with_env_var(API_URL_ENV, "ghe.example.com")
+            .load();
+        assert!(matches!(loaded, Err(ConfigError::InvalidApiUrl { .. })));
+    }
+
+    #[test]
+    fn credentials_fill_missing_tokens() {
+        let files = Files::new();
+        let local = files.write(
+            "whip.json5",
+            "{ repositories: ['org/secret', 'org/public'] }",
+        );
+        let credentials = files.write(
+            "credentials.json",
+            r#"{ "github_token": "ghp_global", "repositories": { "org/secret": "ghp_repo" } }"#,
+        );
+
+        let loaded = ConfigLoader::new()
+            .with_local_file(&local)
+            .with_credentials_file(&credentials)
+            .load()
+            .unwrap();
+
+        assert_eq!(loaded.config.github_token.as_deref(), Some("ghp_global"));
+        assert_eq!(loaded.config.repositories[0].token(), Some("ghp_repo"));
+        let layer = Some(&ConfigLayer::Credentials(credentials.clone()));
+        assert_eq!(loaded.provenance.source("github_token"), layer);
+        assert_eq!(
+            loaded.provenance.source("repositories[org/secret].token"),
+            layer
+        );
+        assert_eq!(
+            loaded.provenance.source("repositories[org/public].token"),
+            None
+        );
+    }
+
+    #[test]
+    fn missing_credentials_file_is_ignored() {
+        let files = Files::new();
+        let loaded = ConfigLoader::new()
+            .with_credentials_file(files.dir.path().join("missing.json"))
+            .load()
+            .unwrap();
+        assert_eq!(loaded.config, Config::default());
+    }
+
+    #[test]
+    fn invalid_layer_file_is_an_error() {
+        let files = Files::new();
+        let local = files.write("whip.json5", "{ polling: 'often' }");
+        let err = ConfigLoader::new()
+            .with_local_file(local)
+            .load()
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidConfig(_)), "{err:?}");
+    }
+
+    #[test]
+    fn later_scalar_replaces_tracked_object() {
+        let mut provenance = Provenance::default();
+        let user = ConfigLayer::User(PathBuf::from("user.json5"));
+        provenance.record("github_app", &serde_json::json!({ "app_id": 1 }), &user);
+        assert_eq!(provenance.source("github_app.app_id"), Some(&user));
+
+        let local = ConfigLayer::Local(PathBuf::from("whip.json5"));
+        provenance.record("github_app", &Value::Null, &local);
+        assert_eq!(provenance.source("github_app.app_id"), None);
+        assert_eq!(provenance.source("github_app"), Some(&local));
+    }
+
+    #[test]
+    fn layer_display() {
+        assert_eq!(ConfigLayer::Default.to_string(), "default");
+        assert_eq!(
+            ConfigLayer::Local(PathBuf::from("whip.json5")).to_string(),
+            "local config (whip.json5)"
+        );
+        assert_eq!(
+            ConfigLayer::Environment(POLL_INTERVAL_ENV.to_string()).to_string(),
+            "$WHIP_POLL_INTERVAL_SECS"
+        );
+    }
+
+    #[test]
+    fn source_is_highest_priority_file() {
+        let files = Files::new();
+        let user = files.write("user.json5", "{}");
+        let local = files.write("whip.json5", "{}");
+
+        let loader = ConfigLoader::new().with_user_file(&user);
+        assert_eq!(
+            loader.load().unwrap().config.source,
+            Some(ConfigLayer::User(user))
+        );
+        let loader = loader.with_local_file(&local);
+        assert_eq!(
+            loader.load().unwrap().config.source,
+            Some(ConfigLayer::Local(local))
+        );
+        assert_eq!(ConfigLoader::new().load().unwrap().config.source, None);
+    }
+
+    #[test]
+    fn save_changes_writes_only_edits() {
+        let files = Files::new();
+        let user = files.write(
+            "user.json5",
+            "{ repositories: ['me/dotfiles'], polling: { interval_secs: 30 } }",
+        );
+        let local = files.write(
+            "whip.json5",
+            "{\n  // Project repositories\n  repositories: ['org/project', 'org/old'],\n}\n",
+        );
+        let credentials = files.dir.path().join("credentials.json");
+        let loader = ConfigLoader::new()
+            .with_user_file(&user)
+            .with_local_file(&local);
+        let loaded = loader.load().unwrap().config;
+
+        let mut config = loaded.clone();
+        config.polling.auto_adjust = false;
+        assert!(config.remove_repository("org/old"));
+        config.add_repository(Repository::with_token("org", "new", "ghp_new"));
+        config.save_changes(&loaded, &local, &credentials).unwrap();
+
+        let written = std::fs::read_to_string(&local).unwrap();
+        assert_eq!(
+            written,
+            "{\n  // Project repositories\n  repositories: ['org/project', \"org/new\"],\n  polling: {\n    auto_adjust: false\n  },\n}\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&user).unwrap(),
+            "{ repositories: ['me/dotfiles'], polling: { interval_secs: 30 } }"
+        );
+
+        let reloaded = loader.with_credentials_file(&credentials).load().unwrap();
+        assert_eq!(reloaded.config, config);
+    }
+
+    #[test]
+    fn save_changes_leaves_version_of_outdated_file() {
+        let files = Files::new();
+        let local = files.write("whip.json5", "{ repositories: ['org/project'] }");
+        let credentials = files.dir.path().join("credentials.json");
+        let loaded = ConfigLoader::new()
+            .with_local_file(&local)
+            .load()
+            .unwrap()
+            .config;
+
+        let mut config = loaded.clone();
+        config.polling.auto_adjust = false;
+        config.save_changes(&loaded, &local, &credentials).unwrap();
+
+        let written = std::fs::read_to_string(&local).unwrap();
+        assert!(!written.contains("version"), "{written}");
+        assert!(written.contains("auto_adjust: false"), "{written}");
+    }
+
+    #[test]
+    fn save_changes_keeps_tokens_of_other_configs() {
+        let files = Files::new();
+        let credentials = files.write(
+            "credentials.json",
+            r#"{ "repositories": { "me/dotfiles": "ghp_mine", "org/old": "ghp_old" } }"#,
+        );
+        let project = files.write(
+            "proj.json5",
+            "{ repositories: ['org/old', { owner: 'org', repo: 'legacy', token: 'ghp_legacy' }] }",
+        );
+        let loader = ConfigLoader::new()
+            .with_user_file(&project)
+            .with_credentials_file(&credentials);
+        let loaded = loader.load().unwrap().config;
+
+        let mut config = loaded.clone();
+        assert!(config.remove_repository("org/old"));
+        config.add_repository(Repository::with_token("org", "new", "ghp_new"));
+        config
+            .save_changes(&loaded, &project, &credentials)
+            .unwrap();
+
+        let stored = Credentials::load_from(&credentials).unwrap();
+        let keys: Vec<_> = stored.repositories.keys().collect();
+        assert_eq!(keys, ["me/dotfiles", "org/legacy", "org/new"]);
+        assert!(
+            !std::fs::read_to_string(&project)
+                .unwrap()
+                .contains("ghp_legacy")
+        );
+    }
+
+    #[test]
+    fn save_changes_removes_repository_on_every_host() {
+        let files = Files::new();
+        let local = files.write(
+            "whip.json5",
+            r#"{ repositories: [
+                "acme/api",
+                { owner: "acme", repo: "api", api_url: "https://ghe.example.com/api/v3" },
+                "acme/web",
+            ] }"#,
+        );
+        let credentials = files.dir.path().join("credentials.json");
+        let loader = ConfigLoader::new().with_local_file(&local);
+        let loaded = loader.load().unwrap().config;
+        assert_eq!(loaded.repositories.len(), 3);
+
+        let mut config = loaded.clone();
+        assert!(config.remove_repository("acme/api"));
+        assert_eq!(config.repositories.len(), 1);
+        config.save_changes(&loaded, &local, &credentials).unwrap();
+
+        let names: Vec<_> = loader
+            .load()
+            .unwrap()
+            .config
+            .repositories
+            .iter()
+            .map(Repository::full_name)
+            .collect();
+        assert_eq!(names, ["acme/web"]);
+    }
+
+    #[test]
+    fn save_changes_creates_missing_file() {
+        let files = Files::new();
+        let local = files.write("whip.json5", "{ repositories: ['org/project'] }");
+        let user = files.dir.path().join("config").join("config.json5");
+        let credentials = files.dir.path().join("credentials.json");
+        let loaded = ConfigLoader::new()
+            .with_local_file(&local)
+            .load()
+            .unwrap()
+            .config;
+
+        let mut config = loaded.clone();
+        config.sync_labels = false;
+        config.save_changes(&loaded, &user, &credentials).unwrap();
+
+        let saved = Config::load_from(&user).unwrap();
+        assert!(!saved.sync_labels);
+        assert!(saved.repositories.is_empty());
+        assert!(!credentials.exists());
+    }
+
+    #[test]
+    fn config_layer_path() {
+        let path = PathBuf::from("whip.json5");
+        assert_eq!(ConfigLayer::File(path.clone()).path(), Some(path.as_path()));
+        assert_eq!(
+            ConfigLayer::Environment(API_URL_ENV.to_string()).path(),
+            None
+        );
+    }
+}
@@ -11,12 +11,20 @@
 //! - [`repository`]: Repository configuration with flexible parsing
 //! - [`polling`]: Polling interval configuration with rate-limit awareness
 //! - [`auth`]: GitHub token resolution and authentication
+//! - [`github_app`]: GitHub App installation-token authentication
+//! - [`layers`]: Layered loading with per-value provenance
+//! - [`check`]: Locating problems in a configuration file
+//! - [`schema`]: JSON Schema for configuration files
 //! - [`persistence`]: Config file reading and writing
+//! - [`migrate`]: Upgrading config files written for older versions
+//! - [`json5_edit`]: Format-preserving edits of JSON5 config files
+//! - [`credentials`]: Token storage kept out of the configuration file
+//! - [`watch`]: Reloading configuration when its files change
 //! - [`error`]: Error types for configuration operations
 //!
 //! # Configuration Sources (Priority)
 //!
-//! Configuration is loaded from multiple sources with the following priority
+//! Configuration is merged from multiple sources with the following priority
 //! (highest to lowest):
 //!
 //! 1. Environment variables (`WHIP_*`)
@@ -24,6 +32,10 @@
 //! 3. User config (`~/.config/whip/config.json5` or `~/.config/whip/config.json`)
 //! 4. Built-in defaults
 //!
+//! Later layers override individual values rather than whole files, and
+//! `repositories` lists are combined. See [`layers`] for the merge rules
+//! and how to find out where a value came from.
+//!
 //! # Repository Format
 //!
 //! Repositories can be specified in two formats:
@@ -34,7 +46,9 @@
 //!     // Short format: "owner/repo"
 //!     "rust-lang/rust",
 //!     // Full format with optional token override
-//!     { "owner": "private-org", "repo": "secret-repo", "token": "ghp_xxx" }
+//!     { "owner": "private-org", "repo": "secret-repo", "token": "ghp_xxx" },
+//!     // Token read from an environment variable or a command instead
+//!     { "owner": "private-org", "repo": "other-repo", "token_command": "pass show github/org" }
 //!   ]
 //! }
 //! ```
@@ -43,10 +57,12 @@
 //!
 //! GitHub tokens are resolved in the following order:
 //!
-//! 1. Repository-specific token (if configured)
-//! 2. Global `github_token` from config
-//! 3. `gh auth token` command (GitHub CLI)
-//! 4. Unauthenticated (rate-limited)
+//! 1. Repository-specific `token`, `token_env`, or `token_command` (if configured)
+//! 2. GitHub App installation token (if `github_app` is configured)
+//! 3. `WHIP_GITHUB_TOKEN` or `GITHUB_TOKEN` environment variables
+//! 4. Global `github_token_env`, `github_token_command`, or `github_token` from config
+//! 5. `gh auth token` command (GitHub CLI)
+//! 6. Unauthenticated (rate-limited)
 //!
 //! # Examples
 //!
@@ -71,14 +87,25 @@
 //! ```
 
 pub mod auth;
+pub mod check;
 pub mod config;
+pub mod credentials;
 pub mod error;
+pub mod github_app;
+pub mod json5_edit;
+pub mod layers;
+pub mod migrate;
 pub mod persistence;
 pub mod polling;
 pub mod repository;
+pub mod schema;
+pub mod watch;
 
 // Re-export primary types at crate root for convenience
 pub use config::Config;
+pub use credentials::Credentials;
 pub use error::{ConfigError, Result};
+pub use github_app::GitHubAppConfig;
+pub use layers::{ConfigLayer, ConfigLoader, LayeredConfig, Provenance};
 pub use polling::PollingConfig;
 pub use repository::Repository;
@@ -0,0 +1,452 @@
+//! Upgrading configuration files written for older versions of whip.
+//!
+//! Every configuration file records its format version in a top-level
+//! `version` member; files without one predate versioning and are version 0.
+//! When the format changes, [`CURRENT_VERSION`] is bumped and a migration is
+//! added that upgrades documents from the previous version. Older documents
+//! are upgraded one version at a time, so each migration only needs to know
+//! about the version right before it.
+//!
+//! Loading a configuration upgrades it in memory only. Writing the upgraded
+//! file back is left to the caller, through a [`MigrationPlan`], so the user
+//! can confirm it first; the original file is kept as a backup.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde_json::json;
+//! use whip_config::migrate::{CURRENT_VERSION, migrate_value};
+//!
+//! let mut value = json!({ "repositories": ["rust-lang/rust"] });
+//! let applied = migrate_value(&mut value)?;
+//! assert_eq!(applied.len(), CURRENT_VERSION as usize);
+//! assert_eq!(value["version"], CURRENT_VERSION);
+//! # Ok::<(), whip_config::ConfigError>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::config::Config;
+use crate::error::{ConfigError, Result};
+use crate::json5_edit::Json5Document;
+
+/// The configuration format version written by this version of whip.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// One step of the migration pipeline, upgrading a document from version
+/// `from` to `from + 1`.
+#[derive(Debug)]
+pub struct Migration {
+    /// The version this migration upgrades from.
+    pub from: u32,
+    /// What the migration changes, shown before the user confirms it.
+    pub description: &'static str,
+    /// Rewrites the top-level members of the document.
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Every migration, in order; the one at index `n` upgrades from version `n`.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    description: "record the config format version",
+    apply: |_| {},
+}];
+
+/// Returns the format version of a configuration document.
+///
+/// # Arguments
+///
+/// * `value` - The parsed configuration document
+///
+/// # Errors
+///
+/// Returns [`ConfigError::UnsupportedVersion`] if `version` is not a
+/// non-negative integer, or is newer than [`CURRENT_VERSION`].
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use whip_config::migrate::document_version;
+///
+/// assert_eq!(document_version(&json!({}))?, 0);
+/// assert_eq!(document_version(&json!({ "version": 1 }))?, 1);
+/// assert!(document_version(&json!({ "version": 99 })).is_err());
+/// # Ok::<(), whip_config::ConfigError>(())
+/// ```
+pub fn document_version(value: &Value) -> Result<u32> {
+    let Some(version) = value.get("version") else {
+        return Ok(0);
+    };
+    version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .filter(|version| *version <= CURRENT_VERSION)
+        .ok_or_else(|| ConfigError::UnsupportedVersion {
+            version: version.to_string(),
+            supported: CURRENT_VERSION,
+        })
+}
+
+/// Upgrades a configuration document to [`CURRENT_VERSION`] in place.
+///
+/// Returns the migrations that were applied, oldest first; none if the
+/// document is already current. Documents that are not objects are left
+/// alone, for deserialization to reject.
+///
+/// # Arguments
+///
+/// * `value` - The parsed configuration document
+///
+/// # Errors
+///
+/// Returns an error if the document's version is invalid or too new; see
+/// [`document_version`].
+pub fn migrate_value(value: &mut Value) -> Result<Vec<&'static Migration>> {
+    let version = document_version(value)?;
+    let Value::Object(members) = value else {
+        return Ok(Vec::new());
+    };
+    let pending = &MIGRATIONS[version as usize..];
+    for migration in pending {
+        (migration.apply)(members);
+        members.insert("version".to_string(), Value::from(migration.from + 1));
+    }
+    Ok(pending.iter().collect())
+}
+
+/// Reads a configuration file, upgrading it to [`CURRENT_VERSION`] in memory.
+///
+/// Returns the configuration and the upgraded document it was read from.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, its version is
+/// invalid or too new, or it does not match the configuration format.
+pub(crate) fn read_migrated(path: &Path) -> Result<(Config, Value)> {
+    let text = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadFile {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    let mut value = serde_json5::from_str(&text)?;
+    migrate_value(&mut value)?;
+    let config = serde_json::from_value(value.clone()).map_err(ConfigError::InvalidConfig)?;
+    Ok((config, value))
+}
+
+/// An upgrade of a configuration file to [`CURRENT_VERSION`], not yet written.
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::migrate::MigrationPlan;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("config.json5");
+/// std::fs::write(&path, "{\n  // Mine\n  repositories: [\"rust-lang/rust\"],\n}\n").unwrap();
+///
+/// let plan = MigrationPlan::for_file(&path)?.expect("file is outdated");
+/// assert_eq!(plan.from_version(), 0);
+///
+/// let backup = plan.apply()?;
+/// assert!(std::fs::read_to_string(&path).unwrap().contains("// Mine"));
+/// assert!(backup.exists());
+/// assert!(MigrationPlan::for_file(&path)?.is_none());
+/// # Ok::<(), whip_config::ConfigError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MigrationPlan {
+    path: PathBuf,
+    from: u32,
+    steps: Vec<&'static str>,
+    original: String,
+    migrated: String,
+}
+
+impl MigrationPlan {
+    /// Plans the upgrade of a configuration file.
+    ///
+    /// Returns `None` if the file is already at [`CURRENT_VERSION`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The configuration file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or its
+    /// version is invalid or too new.
+    pub fn for_file(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        let original = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadFile {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let value: Value = serde_json5::from_str(&original)?;
+        let from = document_version(&value)?;
+        let mut migrated_value = value.clone();
+        let applied = migrate_value(&mut migrated_value)?;
+        if applied.is_empty() {
+            return Ok(None);
+        }
+
+        // Edit the text rather than rewrite it, so comments survive
+        let mut document = Json5Document::parse(original.clone())?;
+        document.apply_diff(&value, &migrated_value)?;
+        Ok(Some(Self {
+            path: path.to_path_buf(),
+            from,
+            steps: applied
+                .iter()
+                .map(|migration| migration.description)
+                .collect(),
+            original,
+            migrated: document.into_string(),
+        }))
+    }
+
+    /// Returns the file to upgrade.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the file's current format version.
+    #[must_use]
+    pub fn from_version(&self) -> u32 {
+        self.from
+    }
+
+    /// Returns what each migration changes, oldest first.
+    #[must_use]
+    pub fn steps(&self) -> &[&'static str] {
+        &self.steps
+    }
+
+    /// Returns the text the file will have once upgraded.
+    #[must_use]
+    pub fn migrated_text(&self) -> &str {
+        &self.migrated
+    }
+
+    /// Returns where the original file is kept once upgraded.
+    ///
+    /// This is the file path with `.v<version>.bak` appended, e.g.
+    /// `config.json5.v0.bak`. If that file already exists, e.g. from an
+    /// earlier upgrade, the first free `.v<version>.<n>.bak` is used
+    /// instead, so no backup is ever overwritten.
+    #[must_use]
+    pub fn backup_path(&self) -> PathBuf {
+        let file_name = self.path.file_name().unwrap_or_default();
+        let mut attempt = 0;
+        loop {
+            let mut name = file_name.to_os_string();
+            if attempt == 0 {
+                name.push(format!(".v{}.bak", self.from));
+            } else {
+                name.push(format!(".v{}.{attempt}.bak", self.from));
+            }
+            let path = self.path.with_file_name(name);
+            if !path.exists() {
+                return path;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Writes the upgraded file, keeping the original as a backup.
+    ///
+    /// The backup gets the original's permissions, so a file only its
+    /// owner may read does not leak through its backup. Returns the path of
+    /// the backup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backup or the upgraded file cannot be
+    /// written, or if the backup path was taken in the meantime. The file
+    /// is left untouched if the backup fails.
+    pub fn apply(&self) -> Result<PathBuf> {
+        use std::io::Write;
+
+        let backup = self.backup_path();
+        let backup_error = |e| ConfigError::WriteFile {
+            path: backup.clone(),
+            source: e,
+        };
+        let permissions = std::fs::metadata(&self.path)
+            .map_err(|e| ConfigError::ReadFile {
+                path: self.path.clone(),
+                source: e,
+            })?
+            .permissions();
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            // Private until the original's permissions are copied over
+            options.mode(0o600);
+        }
+        let mut file = options.open(&backup).map_err(backup_error)?;
+        file.set_permissions(permissions).map_err(backup_error)?;
+        file.write_all(self.original.as_bytes())
+            .map_err(backup_error)?;
+
+        std::fs::write(&self.path, &self.migrated).map_err(|e| ConfigError::WriteFile {
+            path: self.path.clone(),
+            source: e,
+        })?;
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn migrations_are_contiguous_up_to_current_version() {
+        assert_eq!(MIGRATIONS.len(), CURRENT_VERSION as usize);
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from as usize, index);
+        }
+    }
+
+    #[test]
+    fn unversioned_document_is_version_zero() {
+        assert_eq!(
+            document_version(&json!({ "sync_labels": false })).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn invalid_versions_are_rejected() {
+        for version in [
+            json!(-1),
+            json!("1"),
+            json!(1.5),
+            json!(CURRENT_VERSION + 1),
+        ] {
+            assert!(matches!(
+                document_version(&json!({ "version": version })),
+                Err(ConfigError::UnsupportedVersion { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn migrate_value_upgrades_old_documents() {
+        let mut value = json!({ "repositories": ["a/b"] });
+        let applied = migrate_value(&mut value).unwrap();
+        assert_eq!(applied[0].from, 0);
+        assert_eq!(
+            value,
+            json!({ "repositories": ["a/b"], "version": CURRENT_VERSION })
+        );
+    }
+
+    #[test]
+    fn migrate_value_keeps_current_documents() {
+        let mut value = json!({ "version": CURRENT_VERSION, "sync_labels": false });
+        let original = value.clone();
+        assert!(migrate_value(&mut value).unwrap().is_empty());
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn migrate_value_ignores_non_objects() {
+        let mut value = json!(["a/b"]);
+        assert!(migrate_value(&mut value).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plan_keeps_formatting_and_backs_up_original() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json5");
+        let original = "{\n    // Work repositories\n    repositories: [\"org/repo\"],\n}\n";
+        std::fs::write(&path, original).unwrap();
+
+        let plan = MigrationPlan::for_file(&path).unwrap().unwrap();
+        assert_eq!(plan.path(), path);
+        assert_eq!(plan.steps(), ["record the config format version"]);
+        assert_eq!(
+            plan.migrated_text(),
+            "{\n    // Work repositories\n    repositories: [\"org/repo\"],\n    version: 1,\n}\n"
+        );
+        // Nothing is written until the plan is applied
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        let backup = plan.apply().unwrap();
+        assert_eq!(backup, dir.path().join("config.json5.v0.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            plan.migrated_text()
+        );
+    }
+
+    #[test]
+    fn plan_keeps_existing_backups() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json5");
+        std::fs::write(dir.path().join("config.json5.v0.bak"), "earlier").unwrap();
+        std::fs::write(&path, "{}").unwrap();
+
+        let plan = MigrationPlan::for_file(&path).unwrap().unwrap();
+        assert_eq!(plan.backup_path(), dir.path().join("config.json5.v0.1.bak"));
+
+        let backup = plan.apply().unwrap();
+        assert_eq!(backup, dir.path().join("config.json5.v0.1.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "{}");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("config.json5.v0.bak")).unwrap(),
+            "earlier"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn plan_backup_keeps_original_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json5");
+        std::fs::write(&path, "{ github_token: 'ghp_secret' }").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        let backup = MigrationPlan::for_file(&path)
+            .unwrap()
+            .unwrap()
+            .apply()
+            .unwrap();
+
+        let mode = std::fs::metadata(&backup).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn plan_for_current_file_is_none() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("whip.json");
+        std::fs::write(&path, r#"{ "version": 1 }"#).unwrap();
+        assert!(MigrationPlan::for_file(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn plan_for_newer_file_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("whip.json");
+        std::fs::write(&path, r#"{ "version": 999 }"#).unwrap();
+        assert!(matches!(
+            MigrationPlan::for_file(&path),
+            Err(ConfigError::UnsupportedVersion { .. })
+        ));
+    }
+}
@@ -16,10 +16,18 @@
 //!
 //! 1. Local: `./whip.json5` or `./whip.json`
 //! 2. User: `~/.config/whip/config.json5` or `~/.config/whip/config.json`
+//!
+//! Saving an existing file edits its text in place, so comments and
+//! formatting written by hand are preserved.
+//!
+//! Tokens are kept out of these files, in `~/.config/whip/credentials.json`,
+//! which is only readable by its owner.
 
 use std::path::{Path, PathBuf};
 
 use crate::error::{ConfigError, Result};
+use crate::json5_edit::Json5Document;
+use crate::migrate::migrate_value;
 
 /// Configuration file names to search for, in priority order.
 const CONFIG_FILE_NAMES: &[&str] = &["whip.json5", "whip.json"];
@@ -37,6 +45,9 @@ const USER_CONFIG_FILE_NAMES: &[&str] = &["config.json5", "config.json"];
 /// 1. Local directory: `./whip.json5`, `./whip.json`
 /// 2. User config directory: `~/.config/whip/config.json5`, `~/.config/whip/config.json`
 ///
+/// Only the first file found is returned; see
+/// [`ConfigLoader`](crate::layers::ConfigLoader) to merge both.
+///
 /// # Returns
 ///
 /// Returns `Some(path)` if a config file is found, `None` otherwise.
@@ -52,26 +63,72 @@ const USER_CONFIG_FILE_NAMES: &[&str] = &["config.json5", "config.json"];
 /// ```
 #[must_use]
 pub fn find_config_file() -> Option<PathBuf> {
-    // Try local directory first
-    for name in CONFIG_FILE_NAMES {
-        let path = PathBuf::from(name);
-        if path.exists() {
-            return Some(path);
-        }
-    }
+    find_local_config_file().or_else(find_user_config_file)
+}
 
-    // Try user config directory
-    if let Some(config_dir) = dirs::config_dir() {
-        let whip_config_dir = config_dir.join(USER_CONFIG_DIR);
-        for name in USER_CONFIG_FILE_NAMES {
-            let path = whip_config_dir.join(name);
-            if path.exists() {
-                return Some(path);
-            }
-        }
-    }
+/// Finds the project-local configuration file (`./whip.json5` or `./whip.json`).
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::persistence::find_local_config_file;
+///
+/// if let Some(path) = find_local_config_file() {
+///     println!("Found local config at: {}", path.display());
+/// }
+/// ```
+#[must_use]
+pub fn find_local_config_file() -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.exists())
+}
+
+/// Finds the user configuration file (`~/.config/whip/config.json5` or
+/// `~/.config/whip/config.json`).
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::persistence::find_user_config_file;
+///
+/// if let Some(path) = find_user_config_file() {
+///     println!("Found user config at: {}", path.display());
+/// }
+/// ```
+#[must_use]
+pub fn find_user_config_file() -> Option<PathBuf> {
+    let whip_config_dir = user_config_dir().ok()?;
+    USER_CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| whip_config_dir.join(name))
+        .find(|path| path.exists())
+}
 
-    None
+/// Returns every path a config file is looked for at, in priority order.
+///
+/// Unlike [`find_config_file`], this includes files that do not exist yet,
+/// so a watcher can notice when one is created.
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::persistence::config_file_candidates;
+///
+/// for path in config_file_candidates() {
+///     println!("{} exists: {}", path.display(), path.exists());
+/// }
+/// ```
+#[must_use]
+pub fn config_file_candidates() -> Vec<PathBuf> {
+    let local = CONFIG_FILE_NAMES.iter().map(PathBuf::from);
+    let user = user_config_dir().ok().into_iter().flat_map(|dir| {
+        USER_CONFIG_FILE_NAMES
+            .iter()
+            .map(move |name| dir.join(name))
+    });
+    local.chain(user).collect()
 }
 
 /// Returns the default user configuration directory.
@@ -116,6 +173,27 @@ pub fn default_user_config_path() -> Result<PathBuf> {
     Ok(user_config_dir()?.join("config.json5"))
 }
 
+/// Returns the default credentials file path.
+///
+/// This is typically `~/.config/whip/credentials.json`. See
+/// [`Credentials`](crate::Credentials).
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::persistence::default_credentials_path;
+///
+/// let path = default_credentials_path().unwrap();
+/// println!("Credentials path: {}", path.display());
+/// ```
+pub fn default_credentials_path() -> Result<PathBuf> {
+    Ok(user_config_dir()?.join("credentials.json"))
+}
+
 /// Reads and parses a configuration file.
 ///
 /// Supports both JSON5 and JSON formats.
@@ -205,6 +283,160 @@ pub fn write_config_file<T: serde::Serialize>(path: impl AsRef<Path>, config: &T
     })
 }
 
+/// Writes a configuration to a file, preserving the file's formatting.
+///
+/// If the file already holds a configuration, only the values that changed
+/// are edited in its text (see [`Json5Document`]), so comments, ordering
+/// and trailing commas written by hand survive. If there is no file yet,
+/// this behaves like [`write_config_file`]. An existing file that cannot
+/// be read or parsed is left untouched rather than replaced.
+///
+/// The file's `version` is left as it is, even if the file is outdated:
+/// only a [`MigrationPlan`](crate::migrate::MigrationPlan) upgrades it,
+/// together with the rest of the file.
+///
+/// # Arguments
+///
+/// * `path` - The path to write to
+/// * `config` - The configuration to write
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The existing file cannot be read or parsed
+/// - The parent directory cannot be created
+/// - The file cannot be written
+/// - The configuration cannot be serialized
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::persistence::update_config_file;
+/// use whip_config::Config;
+///
+/// # fn main() -> whip_config::Result<()> {
+/// let mut config = Config::load_from("whip.json5")?;
+/// config.polling.interval_secs = 120;
+/// update_config_file("whip.json5", &config)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn update_config_file<T>(path: impl AsRef<Path>, config: &T) -> Result<()>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let path = path.as_ref();
+    let Some(content) = edit_config_text(path, config)? else {
+        return write_config_file(path, config);
+    };
+
+    std::fs::write(path, content).map_err(|e| ConfigError::WriteFile {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+/// Applies `config` to the text of the existing file at `path`.
+///
+/// Returns `None` if there is no existing file to edit.
+fn edit_config_text<T>(path: &Path, config: &T) -> Result<Option<String>>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(ConfigError::ReadFile {
+                path: path.to_path_buf(),
+                source: e,
+            });
+        }
+    };
+    // Compare against the file as it reads once upgraded, so that only
+    // the changes made since loading it are written
+    let mut value = serde_json5::from_str(&text)?;
+    migrate_value(&mut value)?;
+    let existing: T = serde_json::from_value(value).map_err(ConfigError::InvalidConfig)?;
+    let mut doc = Json5Document::parse(text)?;
+
+    // Compare serialized forms, so values the file leaves to their defaults
+    // are only written out when they change
+    let mut old = serde_json::to_value(&existing)?;
+    let mut new = serde_json::to_value(config)?;
+    // The version is only ever written by a `MigrationPlan`, which upgrades
+    // the rest of the file along with it
+    for side in [&mut old, &mut new] {
+        if let Some(members) = side.as_object_mut() {
+            members.remove("version");
+        }
+    }
+    doc.apply_diff(&old, &new)?;
+    Ok(Some(doc.into_string()))
+}
+
+/// Writes a file holding secrets, readable and writable only by its owner.
+///
+/// Like [`write_config_file`], but on Unix the file is created with mode
+/// `0600`, and the mode of an existing file is tightened to `0600` before
+/// it is overwritten.
+///
+/// # Arguments
+///
+/// * `path` - The path to write to
+/// * `value` - The value to write
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The parent directory cannot be created
+/// - The file cannot be written or its permissions cannot be set
+/// - The value cannot be serialized
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_config::Credentials;
+/// use whip_config::persistence::write_private_file;
+///
+/// # fn main() -> whip_config::Result<()> {
+/// write_private_file("credentials.json", &Credentials::default())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_private_file<T: serde::Serialize>(path: impl AsRef<Path>, value: &T) -> Result<()> {
+    use std::io::Write;
+
+    let path = path.as_ref();
+    let write_error = |e| ConfigError::WriteFile {
+        path: path.to_path_buf(),
+        source: e,
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.exists()) {
+        std::fs::create_dir_all(parent).map_err(write_error)?;
+    }
+
+    let content = serde_json::to_string_pretty(value)?;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        options.mode(0o600);
+        // `mode` only applies to newly created files
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .map_err(write_error)?;
+        }
+    }
+
+    let mut file = options.open(path).map_err(write_error)?;
+    file.write_all(content.as_bytes()).map_err(write_error)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,6 +527,50 @@ mod tests {
         assert!(path.exists());
     }
 
+    #[cfg(unix)]
+    fn mode(path: &Path) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn write_private_file_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("secret.json");
+
+        let original = TestConfig {
+            name: "secret".to_string(),
+            value: 7,
+        };
+
+        write_private_file(&path, &original).unwrap();
+        let loaded: TestConfig = read_config_file(&path).unwrap();
+        assert_eq!(original, loaded);
+        #[cfg(unix)]
+        assert_eq!(mode(&path), 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_file_tightens_existing_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("secret.json");
+        std::fs::write(&path, "a much longer previous content than the new one").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let config = TestConfig {
+            name: "s".to_string(),
+            value: 1,
+        };
+        write_private_file(&path, &config).unwrap();
+
+        assert_eq!(mode(&path), 0o600);
+        let loaded: TestConfig = read_config_file(&path).unwrap();
+        assert_eq!(loaded, config);
+    }
+
     #[test]
     fn user_config_dir_returns_path() {
         // This test may fail in environments without a home directory
@@ -328,4 +604,60 @@ mod tests {
             path.display()
         );
     }
+
+    #[test]
+    fn update_config_file_keeps_comments() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.json5");
+        std::fs::write(
+            &path,
+            "{\n  // The name\n  name: 'test', // inline\n  value: 42,\n}\n",
+        )
+        .unwrap();
+
+        let config = TestConfig {
+            name: "test".to_string(),
+            value: 7,
+        };
+        update_config_file(&path, &config).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\n  // The name\n  name: 'test', // inline\n  value: 7,\n}\n"
+        );
+    }
+
+    #[test]
+    fn update_config_file_writes_new_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("test.json");
+        let config = TestConfig {
+            name: "test".to_string(),
+            value: 42,
+        };
+
+        update_config_file(&path, &config).unwrap();
+
+        let loaded: TestConfig = read_config_file(&path).unwrap();
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn update_config_file_keeps_unparsable_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.json5");
+        std::fs::write(&path, "// Hand-written\n{ name: ").unwrap();
+        let config = TestConfig {
+            name: "test".to_string(),
+            value: 42,
+        };
+
+        let err = update_config_file(&path, &config).unwrap_err();
+
+        assert!(matches!(err, ConfigError::ParseJson5(_)), "{err:?}");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "// Hand-written\n{ name: "
+        );
+    }
 }
@@ -15,6 +15,7 @@
 //! - Unauthenticated: 300 seconds (5 minutes)
 //! - Authenticated: 60 seconds (1 minute)
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Default polling interval for unauthenticated requests (5 minutes).
@@ -47,13 +48,17 @@ pub const MAX_POLLING_INTERVAL: u32 = 3600;
 /// let config = PollingConfig::with_interval(120);
 /// assert_eq!(config.interval_secs, 120);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(
+    description = "How often repositories are polled, adjusted to the GitHub rate limit when `auto_adjust` is set."
+)]
 pub struct PollingConfig {
     /// The polling interval in seconds.
     ///
     /// This is the base interval; if `auto_adjust` is true, the actual
     /// interval may be adjusted based on authentication status.
     #[serde(default = "default_interval")]
+    #[schemars(range(min = MIN_POLLING_INTERVAL, max = MAX_POLLING_INTERVAL))]
     pub interval_secs: u32,
 
     /// Whether to automatically adjust the interval based on authentication.
@@ -3,7 +3,12 @@
 //! This module provides the [`Repository`] type which supports two formats:
 //!
 //! - Short format: `"owner/repo"` string
-//! - Full format: `{ "owner": "...", "repo": "...", "token": "..." }` object
+//! - Full format: `{ "owner": "...", "repo": "...", "token": "...", "api_url": "..." }` object
+//!
+//! Instead of a plaintext `token`, the full format also accepts `token_env`
+//! (the name of an environment variable) or `token_command` (a shell command
+//! printing the token), and `labels` replaces the status labels for that
+//! repository alone.
 //!
 //! # Examples
 //!
@@ -20,7 +25,11 @@
 //! assert_eq!(repo.full_name(), "org/repo");
 //! ```
 
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use whip_protocol::LabelDefinition;
 
 use crate::error::{ConfigError, Result};
 
@@ -28,7 +37,10 @@ use crate::error::{ConfigError, Result};
 ///
 /// Supports two serialization formats:
 /// - Short: `"owner/repo"` string
-/// - Full: `{ "owner": "...", "repo": "...", "token": "..." }` object
+/// - Full: `{ "owner": "...", "repo": "...", "token": "...", "api_url": "..." }` object
+///
+/// The optional `api_url` points the repository at a GitHub Enterprise
+/// Server instance instead of `github.com`.
 ///
 /// # Examples
 ///
@@ -47,6 +59,10 @@ pub struct Repository {
     owner: String,
     repo: String,
     token: Option<String>,
+    token_env: Option<String>,
+    token_command: Option<String>,
+    api_url: Option<String>,
+    labels: Option<Vec<LabelDefinition>>,
 }
 
 impl Repository {
@@ -72,6 +88,10 @@ impl Repository {
             owner: owner.into(),
             repo: repo.into(),
             token: None,
+            token_env: None,
+            token_command: None,
+            api_url: None,
+            labels: None,
         }
     }
 
@@ -101,9 +121,107 @@ impl Repository {
             owner: owner.into(),
             repo: repo.into(),
             token: Some(token.into()),
+            token_env: None,
+            token_command: None,
+            api_url: None,
+            labels: None,
         }
     }
 
+    /// Reads this repository's token from an environment variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The environment variable name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::Repository;
+    ///
+    /// let repo = Repository::new("org", "repo").with_token_env("ORG_GITHUB_TOKEN");
+    /// assert_eq!(repo.token_env(), Some("ORG_GITHUB_TOKEN"));
+    /// ```
+    #[must_use]
+    pub fn with_token_env(mut self, name: impl Into<String>) -> Self {
+        self.token_env = Some(name.into());
+        self
+    }
+
+    /// Obtains this repository's token by running a shell command.
+    ///
+    /// The first line the command prints is used as the token.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command, e.g. `pass show github/whip`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::Repository;
+    ///
+    /// let repo = Repository::new("org", "repo").with_token_command("pass show github/org");
+    /// assert_eq!(repo.token_command(), Some("pass show github/org"));
+    /// ```
+    #[must_use]
+    pub fn with_token_command(mut self, command: impl Into<String>) -> Self {
+        self.token_command = Some(command.into());
+        self
+    }
+
+    /// Sets the GitHub API base URL for this repository.
+    ///
+    /// Use this for repositories hosted on GitHub Enterprise Server, whose
+    /// API is usually served at `https://<host>/api/v3`.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_url` - The API base URL
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::Repository;
+    ///
+    /// let repo = Repository::new("org", "repo").with_api_url("https://ghe.example.com/api/v3");
+    /// assert_eq!(repo.api_url(), Some("https://ghe.example.com/api/v3"));
+    /// ```
+    #[must_use]
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = Some(api_url.into());
+        self
+    }
+
+    /// Uses its own status labels for this repository.
+    ///
+    /// They replace the global `labels` (or the standard `whip/*` labels)
+    /// for this repository only.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - The status labels, highest precedence first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::Repository;
+    /// use whip_protocol::{LabelDefinition, LaneKind};
+    ///
+    /// let repo = Repository::new("org", "repo").with_labels(vec![LabelDefinition::new(
+    ///     "status: doing",
+    ///     "FBCA04",
+    ///     "",
+    ///     LaneKind::InProgress,
+    /// )]);
+    /// assert_eq!(repo.labels().map(<[_]>::len), Some(1));
+    /// ```
+    #[must_use]
+    pub fn with_labels(mut self, labels: Vec<LabelDefinition>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
     /// Parses a repository from the short format `"owner/repo"`.
     ///
     /// # Errors
@@ -160,6 +278,60 @@ impl Repository {
         self.token.as_deref()
     }
 
+    /// Sets the repository-specific token.
+    pub(crate) fn set_token(&mut self, token: String) {
+        self.token = Some(token);
+    }
+
+    /// Removes and returns the repository-specific token.
+    pub(crate) fn take_token(&mut self) -> Option<String> {
+        self.token.take()
+    }
+
+    /// Overrides the optional settings that `other` configures.
+    ///
+    /// Settings `other` leaves unset are kept.
+    pub(crate) fn merge(&mut self, other: Repository) {
+        let overrides = [
+            (&mut self.token, other.token),
+            (&mut self.token_env, other.token_env),
+            (&mut self.token_command, other.token_command),
+            (&mut self.api_url, other.api_url),
+        ];
+        for (field, value) in overrides {
+            if value.is_some() {
+                *field = value;
+            }
+        }
+        if other.labels.is_some() {
+            self.labels = other.labels;
+        }
+    }
+
+    /// Returns the environment variable holding this repository's token, if configured.
+    #[must_use]
+    pub fn token_env(&self) -> Option<&str> {
+        self.token_env.as_deref()
+    }
+
+    /// Returns the command printing this repository's token, if configured.
+    #[must_use]
+    pub fn token_command(&self) -> Option<&str> {
+        self.token_command.as_deref()
+    }
+
+    /// Returns the repository-specific API base URL, if configured.
+    #[must_use]
+    pub fn api_url(&self) -> Option<&str> {
+        self.api_url.as_deref()
+    }
+
+    /// Returns the repository-specific status labels, if configured.
+    #[must_use]
+    pub fn labels(&self) -> Option<&[LabelDefinition]> {
+        self.labels.as_deref()
+    }
+
     /// Returns the full repository name in `"owner/repo"` format.
     ///
     /// # Examples
@@ -181,21 +353,77 @@ impl Serialize for Repository {
     where
         S: Serializer,
     {
-        // If there's no token, serialize as short format
-        if self.token.is_none() {
+        let optional_fields = [
+            ("token", &self.token),
+            ("token_env", &self.token_env),
+            ("token_command", &self.token_command),
+            ("api_url", &self.api_url),
+        ];
+
+        // If there are no optional fields, serialize as short format
+        if optional_fields.iter().all(|(_, value)| value.is_none()) && self.labels.is_none() {
             serializer.serialize_str(&self.full_name())
         } else {
             // Serialize as full format
             use serde::ser::SerializeStruct;
-            let mut state = serializer.serialize_struct("Repository", 3)?;
+            let mut state = serializer.serialize_struct("Repository", 3 + optional_fields.len())?;
             state.serialize_field("owner", &self.owner)?;
             state.serialize_field("repo", &self.repo)?;
-            state.serialize_field("token", &self.token)?;
+            for (name, value) in optional_fields {
+                if value.is_some() {
+                    state.serialize_field(name, value)?;
+                } else {
+                    state.skip_field(name)?;
+                }
+            }
+            match &self.labels {
+                Some(labels) => state.serialize_field("labels", labels)?,
+                None => state.skip_field("labels")?,
+            }
             state.end()
         }
     }
 }
 
+impl JsonSchema for Repository {
+    fn schema_name() -> Cow<'static, str> {
+        "Repository".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let optional =
+            |description: &str| json_schema!({ "type": "string", "description": description });
+        let mut labels = generator.subschema_for::<Vec<LabelDefinition>>();
+        labels.insert(
+            "description".into(),
+            "Status labels for this repository, replacing the global ones.".into(),
+        );
+        json_schema!({
+            "description": "A GitHub repository, as \"owner/repo\" or an object with options.",
+            "oneOf": [
+                {
+                    "type": "string",
+                    "pattern": "^[^/]+/[^/]+$",
+                    "description": "The repository full name, \"owner/repo\"."
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "owner": { "type": "string", "description": "The repository owner (user or organization)." },
+                        "repo": { "type": "string", "description": "The repository name." },
+                        "token": optional("GitHub token for this repository."),
+                        "token_env": optional("Environment variable holding the token for this repository."),
+                        "token_command": optional("Shell command printing the token for this repository."),
+                        "api_url": optional("GitHub API base URL for this repository, for GitHub Enterprise Server."),
+                        "labels": labels
+                    },
+                    "required": ["owner", "repo"]
+                }
+            ]
+        })
+    }
+}
+
 impl<'de> Deserialize<'de> for Repository {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
@@ -209,7 +437,7 @@ impl<'de> Deserialize<'de> for Repository {
             type Value = Repository;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("a string 'owner/repo' or an object with owner, repo, and optional token fields")
+                formatter.write_str("a string 'owner/repo' or an object with owner, repo, and optional token, token_env, token_command, api_url, and labels fields")
             }
 
             fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
@@ -226,6 +454,10 @@ impl<'de> Deserialize<'de> for Repository {
                 let mut owner: Option<String> = None;
                 let mut repo: Option<String> = None;
                 let mut token: Option<String> = None;
+                let mut token_env: Option<String> = None;
+                let mut token_command: Option<String> = None;
+                let mut api_url: Option<String> = None;
+                let mut labels: Option<Vec<LabelDefinition>> = None;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -247,6 +479,30 @@ impl<'de> Deserialize<'de> for Repository {
                             }
                             token = Some(map.next_value()?);
                         }
+                        "token_env" => {
+                            if token_env.is_some() {
+                                return Err(de::Error::duplicate_field("token_env"));
+                            }
+                            token_env = Some(map.next_value()?);
+                        }
+                        "token_command" => {
+                            if token_command.is_some() {
+                                return Err(de::Error::duplicate_field("token_command"));
+                            }
+                            token_command = Some(map.next_value()?);
+                        }
+                        "api_url" => {
+                            if api_url.is_some() {
+                                return Err(de::Error::duplicate_field("api_url"));
+                            }
+                            api_url = Some(map.next_value()?);
+                        }
+                        "labels" => {
+                            if labels.is_some() {
+                                return Err(de::Error::duplicate_field("labels"));
+                            }
+                            labels = Some(map.next_value()?);
+                        }
                         _ => {
                             // Ignore unknown fields for forward compatibility
                             let _: serde::de::IgnoredAny = map.next_value()?;
@@ -257,7 +513,15 @@ impl<'de> Deserialize<'de> for Repository {
                 let owner = owner.ok_or_else(|| de::Error::missing_field("owner"))?;
                 let repo = repo.ok_or_else(|| de::Error::missing_field("repo"))?;
 
-                Ok(Repository { owner, repo, token })
+                Ok(Repository {
+                    owner,
+                    repo,
+                    token,
+                    token_env,
+                    token_command,
+                    api_url,
+                    labels,
+                })
             }
         }
 
@@ -268,6 +532,7 @@ impl<'de> Deserialize<'de> for Repository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use whip_protocol::{LaneKind, TaskState};
 
     #[test]
     fn new_creates_repository_without_token() {
@@ -384,6 +649,62 @@ mod tests {
         assert_eq!(original, parsed);
     }
 
+    #[test]
+    fn deserialize_full_format_with_api_url() {
+        let repo: Repository = serde_json::from_str(
+            r#"{"owner": "org", "repo": "repo", "api_url": "https://ghe.example.com/api/v3"}"#,
+        )
+        .unwrap();
+        assert_eq!(repo.api_url(), Some("https://ghe.example.com/api/v3"));
+        assert!(repo.token().is_none());
+    }
+
+    #[test]
+    fn serialize_full_format_with_api_url_omits_missing_token() {
+        let repo = Repository::new("org", "repo").with_api_url("https://ghe.example.com/api/v3");
+        let json = serde_json::to_string(&repo).unwrap();
+        assert!(json.contains("api_url"));
+        assert!(!json.contains("token"));
+
+        let parsed: Repository = serde_json::from_str(&json).unwrap();
+        assert_eq!(repo, parsed);
+    }
+
+    #[test]
+    fn deserialize_token_references() {
+        let repo: Repository = serde_json::from_str(
+            r#"{"owner": "org", "repo": "repo", "token_env": "ORG_TOKEN", "token_command": "pass show gh"}"#,
+        )
+        .unwrap();
+        assert_eq!(repo.token_env(), Some("ORG_TOKEN"));
+        assert_eq!(repo.token_command(), Some("pass show gh"));
+        assert!(repo.token().is_none());
+    }
+
+    #[test]
+    fn roundtrip_token_references() {
+        let original = Repository::new("org", "repo")
+            .with_token_env("ORG_TOKEN")
+            .with_token_command("pass show gh");
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(!json.contains(r#""token":"#));
+        let parsed: Repository = serde_json::from_str(&json).unwrap();
+        assert_eq!(original, parsed);
+    }
+
+    #[test]
+    fn roundtrip_labels() {
+        let original = Repository::new("org", "repo").with_labels(vec![
+            LabelDefinition::new("status: doing", "FBCA04", "", LaneKind::InProgress),
+            LabelDefinition::new("status: broken", "D73A4A", "", LaneKind::Done)
+                .with_state(TaskState::Failed),
+        ]);
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains(r#""labels":["#));
+        let parsed: Repository = serde_json::from_str(&json).unwrap();
+        assert_eq!(original, parsed);
+    }
+
     #[test]
     fn roundtrip_full_format() {
         let original = Repository::with_token("org", "repo", "ghp_xxx");
@@ -0,0 +1,84 @@
+//! JSON Schema for configuration files.
+//!
+//! The schema is generated from the [`Config`] type, so it documents every
+//! option with its description, default, and allowed range. Editors use it
+//! for completion and inline validation.
+//!
+//! A generated copy is checked in as `crates/config/config.schema.json`.
+//! Point a JSON config at it with a `$schema` member; `whip` ignores that
+//! member when loading.
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_config::schema::config_schema;
+//!
+//! let schema = config_schema();
+//! let properties = schema.get("properties").unwrap();
+//! assert!(properties.get("repositories").is_some());
+//! ```
+
+use schemars::{Schema, schema_for};
+
+use crate::config::Config;
+
+/// Generates the JSON Schema of configuration files.
+#[must_use]
+pub fn config_schema() -> Schema {
+    schema_for!(Config)
+}
+
+/// Generates the JSON Schema of configuration files as pretty-printed JSON.
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::schema::config_schema_json;
+///
+/// let json = config_schema_json();
+/// assert!(json.contains("\"interval_secs\""));
+/// ```
+#[must_use]
+pub fn config_schema_json() -> String {
+    let mut json =
+        serde_json::to_string_pretty(&config_schema()).expect("a schema always serializes to JSON");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    #[test]
+    fn checked_in_schema_is_up_to_date() {
+        let checked_in = include_str!("../config.schema.json");
+        assert!(
+            checked_in == config_schema_json(),
+            "config.schema.json is out of date; regenerate it with `whip config schema > crates/config/config.schema.json`"
+        );
+    }
+
+    #[test]
+    fn polling_interval_is_bounded() {
+        let schema = serde_json::to_value(config_schema()).unwrap();
+        let interval = &schema["$defs"]["PollingConfig"]["properties"]["interval_secs"];
+        assert_eq!(interval["minimum"], json!(10));
+        assert_eq!(interval["maximum"], json!(3600));
+    }
+
+    #[test]
+    fn repository_accepts_both_formats() {
+        let schema = serde_json::to_value(config_schema()).unwrap();
+        let formats = schema["$defs"]["Repository"]["oneOf"].as_array().unwrap();
+        let types: Vec<&Value> = formats.iter().map(|format| &format["type"]).collect();
+        assert_eq!(types, [&json!("string"), &json!("object")]);
+    }
+
+    #[test]
+    fn source_is_not_part_of_the_schema() {
+        let schema = serde_json::to_value(config_schema()).unwrap();
+        assert!(schema["properties"].get("source").is_none());
+    }
+}
@@ -0,0 +1,325 @@
+//! Reloading configuration when its files change.
+//!
+//! [`ConfigWatcher`] checks the config and credentials files for changes
+//! and reloads the merged configuration (including validation) whenever one
+//! of them is created, modified, or removed. Each reload is reported as a
+//! [`ConfigChange`]; a file that fails to parse or validate is reported as
+//! [`ConfigChange::Invalid`] so the caller can keep using the last good
+//! configuration.
+//!
+//! Files are checked by polling their modification time and size, which
+//! works the same on every platform and also catches editors that save by
+//! replacing the file.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use tokio::sync::mpsc;
+//! use whip_config::watch::{ConfigChange, ConfigWatcher};
+//!
+//! # async fn example() {
+//! let (tx, mut rx) = mpsc::unbounded_channel();
+//! ConfigWatcher::discover().spawn(tx);
+//!
+//! while let Some(change) = rx.recv().await {
+//!     match change {
+//!         ConfigChange::Reloaded(config) => {
+//!             println!("{} repositories", config.repositories.len());
+//!         }
+//!         ConfigChange::Invalid(e) => eprintln!("Ignoring invalid config: {e}"),
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::error::ConfigError;
+use crate::layers::ConfigLoader;
+use crate::persistence::{config_file_candidates, default_credentials_path};
+
+/// How often files are checked for changes by default.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The outcome of reloading configuration after a file changed.
+#[derive(Debug)]
+pub enum ConfigChange {
+    /// The configuration was reloaded and validated.
+    Reloaded(Box<Config>),
+    /// The changed files could not be loaded; the previous configuration
+    /// should stay in use.
+    Invalid(ConfigError),
+}
+
+/// How the watcher builds the loader for each reload.
+#[derive(Debug, Clone)]
+enum Reload {
+    /// Always load the same layers.
+    Fixed(ConfigLoader),
+    /// Discover the layers again, picking up newly created files.
+    Discover,
+}
+
+impl Reload {
+    fn load(&self) -> ConfigChange {
+        let result = match self {
+            Self::Fixed(loader) => loader.load(),
+            Self::Discover => ConfigLoader::discover().load(),
+        };
+        match result {
+            Ok(loaded) => ConfigChange::Reloaded(Box::new(loaded.config)),
+            Err(e) => ConfigChange::Invalid(e),
+        }
+    }
+}
+
+/// The state of a watched file: its modification time and size, or `None`
+/// if it does not exist.
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+fn stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+/// Watches configuration files and reloads the configuration when they change.
+///
+/// The files are stamped when the watcher is created, so only later changes
+/// are reported.
+///
+/// # Examples
+///
+/// ```
+/// use whip_config::ConfigLoader;
+/// use whip_config::watch::ConfigWatcher;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("config.json5");
+/// std::fs::write(&path, "{}").unwrap();
+///
+/// let mut watcher = ConfigWatcher::new(ConfigLoader::new().with_user_file(&path));
+/// assert!(watcher.poll().is_none());
+///
+/// std::fs::write(&path, r#"{ repositories: ["rust-lang/rust"] }"#).unwrap();
+/// assert!(watcher.poll().is_some());
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ConfigWatcher {
+    reload: Reload,
+    paths: Vec<PathBuf>,
+    stamps: Vec<FileStamp>,
+    interval: Duration,
+}
+
+impl ConfigWatcher {
+    /// Creates a watcher for the files of a loader.
+    ///
+    /// Reloads use `loader` as is, so a file the loader was not given is
+    /// never picked up.
+    ///
+    /// # Arguments
+    ///
+    /// * `loader` - The loader whose user, local, and credentials files to watch
+    pub fn new(loader: ConfigLoader) -> Self {
+        let paths = [
+            loader.user_file(),
+            loader.local_file(),
+            loader.credentials_file(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::from)
+        .collect();
+        Self::with_paths(Reload::Fixed(loader), paths)
+    }
+
+    /// Creates a watcher for the standard config locations.
+    ///
+    /// Watches every path [`ConfigLoader::discover`] looks at, including
+    /// files that do not exist yet, and discovers the layers again on each
+    /// reload.
+    pub fn discover() -> Self {
+        let mut paths = config_file_candidates();
+        paths.extend(default_credentials_path().ok());
+        Self::with_paths(Reload::Discover, paths)
+    }
+
+    fn with_paths(reload: Reload, paths: Vec<PathBuf>) -> Self {
+        let stamps = paths.iter().map(|path| stamp(path)).collect();
+        Self {
+            reload,
+            paths,
+            stamps,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Sets how often [`spawn`](Self::spawn) checks the files.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the watched files.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter().map(PathBuf::as_path)
+    }
+
+    /// Checks the files once, reloading the configuration if any changed.
+    ///
+    /// Returns `None` if no file changed since the last check.
+    pub fn poll(&mut self) -> Option<ConfigChange> {
+        let stamps: Vec<_> = self.paths.iter().map(|path| stamp(path)).collect();
+        if stamps == self.stamps {
+            return None;
+        }
+        self.stamps = stamps;
+        Some(self.reload.load())
+    }
+
+    /// Checks the files in the background, sending every change to `changes`.
+    ///
+    /// The task stops once the receiving end of `changes` is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `changes` - Where to send reloaded configurations
+    pub fn spawn(mut self, changes: UnboundedSender<ConfigChange>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(self.interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                if changes.is_closed() {
+                    break;
+                }
+                if let Some(change) = self.poll()
+                    && changes.send(change).is_err()
+                {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn watcher(dir: &TempDir) -> (ConfigWatcher, PathBuf) {
+        let path = dir.path().join("config.json5");
+        std::fs::write(&path, r#"{ repositories: ["owner/one"] }"#).unwrap();
+        let loader = ConfigLoader::new()
+            .with_user_file(&path)
+            .with_credentials_file(dir.path().join("credentials.json"));
+        (ConfigWatcher::new(loader), path)
+    }
+
+    fn reloaded(change: Option<ConfigChange>) -> Config {
+        match change {
+            Some(ConfigChange::Reloaded(config)) => *config,
+            other => panic!("expected a reload, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn watches_loader_files() {
+        let dir = TempDir::new().unwrap();
+        let (watcher, path) = watcher(&dir);
+        let paths: Vec<_> = watcher.paths().collect();
+        assert_eq!(
+            paths,
+            [path.as_path(), &dir.path().join("credentials.json")]
+        );
+    }
+
+    #[test]
+    fn unchanged_files_are_not_reloaded() {
+        let dir = TempDir::new().unwrap();
+        let (mut watcher, _) = watcher(&dir);
+        assert!(watcher.poll().is_none());
+        assert!(watcher.poll().is_none());
+    }
+
+    #[test]
+    fn modified_file_is_reloaded() {
+        let dir = TempDir::new().unwrap();
+        let (mut watcher, path) = watcher(&dir);
+
+        std::fs::write(&path, r#"{ repositories: ["owner/one", "owner/two"] }"#).unwrap();
+        let config = reloaded(watcher.poll());
+        let names: Vec<_> = config
+            .repositories
+            .iter()
+            .map(|repo| repo.full_name())
+            .collect();
+        assert_eq!(names, ["owner/one", "owner/two"]);
+        assert!(watcher.poll().is_none());
+    }
+
+    #[test]
+    fn created_file_is_reloaded() {
+        let dir = TempDir::new().unwrap();
+        let (mut watcher, _) = watcher(&dir);
+
+        std::fs::write(
+            dir.path().join("credentials.json"),
+            r#"{ "repositories": { "owner/one": "ghp_test" } }"#,
+        )
+        .unwrap();
+        let config = reloaded(watcher.poll());
+        assert_eq!(config.repositories[0].token(), Some("ghp_test"));
+    }
+
+    #[test]
+    fn invalid_file_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let (mut watcher, path) = watcher(&dir);
+
+        std::fs::write(&path, "{ repositories: [").unwrap();
+        assert!(matches!(watcher.poll(), Some(ConfigChange::Invalid(_))));
+
+        std::fs::write(&path, r#"{ polling: { interval_secs: 0 } }"#).unwrap();
+        assert!(matches!(watcher.poll(), Some(ConfigChange::Invalid(_))));
+
+        std::fs::write(&path, "{}").unwrap();
+        assert!(reloaded(watcher.poll()).repositories.is_empty());
+    }
+
+    #[test]
+    fn removed_file_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let (mut watcher, path) = watcher(&dir);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(watcher.poll(), Some(ConfigChange::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn spawned_watcher_sends_changes() {
+        let dir = TempDir::new().unwrap();
+        let (watcher, path) = watcher(&dir);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = watcher.with_interval(Duration::from_millis(10)).spawn(tx);
+
+        std::fs::write(&path, r#"{ repositories: [] }"#).unwrap();
+        let change = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("change reported");
+        assert!(reloaded(change).repositories.is_empty());
+
+        drop(rx);
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .expect("task stops")
+            .unwrap();
+    }
+}
@@ -45,6 +45,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument, warn};
 
+use crate::client::DEFAULT_HOST;
 use crate::error::{Error, Result};
 
 /// Cached issues with metadata.
@@ -139,9 +140,11 @@ impl CachedIssues {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IssueCache {
     base_path: PathBuf,
+    /// The GitHub host cached issues belong to, if not [`DEFAULT_HOST`].
+    host: Option<String>,
 }
 
 impl IssueCache {
@@ -209,13 +212,47 @@ impl IssueCache {
             fs::create_dir_all(&base_path)?;
         }
 
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            host: None,
+        })
+    }
+
+    /// Scopes the cache to a GitHub host.
+    ///
+    /// Issues from different hosts are stored separately, so the same
+    /// `owner/repo` on `github.com` and on a GitHub Enterprise Server
+    /// instance never overwrite each other. Issues for [`DEFAULT_HOST`] are
+    /// stored at the top level of the cache directory, as they always were.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host name, as returned by [`api_host`](crate::api_host)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_github::{IssueCache, api_host};
+    ///
+    /// # fn example() -> whip_github::Result<()> {
+    /// let host = api_host(Some("https://ghe.example.com/api/v3"));
+    /// let cache = IssueCache::new()?.for_host(&host);
+    /// let cached = cache.load("owner", "repo")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn for_host(mut self, host: &str) -> Self {
+        self.host = (host != DEFAULT_HOST).then(|| host.to_string());
+        self
     }
 
     /// Loads cached issues for a repository.
     ///
-    /// Returns `None` if the cache file doesn't exist. Returns an error if
-    /// the file exists but cannot be read or parsed.
+    /// Returns `None` if the cache file doesn't exist, or if it holds tasks
+    /// recorded for a different host, as caches written before tasks
+    /// recorded their host do. Returns an error if the file exists but
+    /// cannot be read or parsed.
     ///
     /// # Errors
     ///
@@ -252,6 +289,15 @@ impl IssueCache {
                         format!("failed to parse cache file: {e}"),
                     ))
                 })?;
+                let other_host = cached.tasks.iter().any(|task| {
+                    task.github
+                        .as_ref()
+                        .is_some_and(|github| github.host != self.host)
+                });
+                if other_host {
+                    debug!(?path, "cached issues belong to another host");
+                    return Ok(None);
+                }
                 debug!(
                     tasks = cached.tasks.len(),
                     cached_at = %cached.cached_at,
@@ -303,6 +349,9 @@ impl IssueCache {
             ))
         })?;
 
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         fs::write(&path, content)?;
         debug!(?path, "cache saved successfully");
 
@@ -366,16 +415,71 @@ impl IssueCache {
         }
     }
 
+    /// Removes every cached repository, for all hosts.
+    ///
+    /// Returns the number of cache files removed. The cache directory itself
+    /// is kept, so the cache remains usable afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be read or an entry
+    /// cannot be removed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use whip_github::IssueCache;
+    ///
+    /// # fn example() -> whip_github::Result<()> {
+    /// let removed = IssueCache::new()?.clear()?;
+    /// println!("Removed {removed} cached repositories");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub fn clear(&self) -> Result<usize> {
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.base_path)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                // Issues cached for other hosts
+                removed += fs::read_dir(&path)?
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                    .count();
+                fs::remove_dir_all(&path)?;
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        debug!(removed, "cache cleared");
+        Ok(removed)
+    }
+
     /// Returns the cache file path for a repository.
     ///
     /// The filename is `{owner}_{repo}.json`, with owner and repo sanitized
-    /// to replace any path separators.
+    /// to replace any path separators. For hosts other than [`DEFAULT_HOST`],
+    /// the file is placed in a subdirectory named after the host.
     fn cache_path(&self, owner: &str, repo: &str) -> PathBuf {
         // Sanitize owner and repo to prevent path traversal
         let safe_owner = owner.replace(['/', '\\', '.'], "_");
         let safe_repo = repo.replace(['/', '\\', '.'], "_");
-        self.base_path
-            .join(format!("{safe_owner}_{safe_repo}.json"))
+        let dir = match &self.host {
+            Some(host) => {
+                // Host names may contain dots, but never path separators
+                let safe_host = host.replace(['/', '\\', ':'], "_");
+                let safe_host = if safe_host.starts_with('.') {
+                    format!("_{safe_host}")
+                } else {
+                    safe_host
+                };
+                self.base_path.join(safe_host)
+            }
+            None => self.base_path.clone(),
+        };
+        dir.join(format!("{safe_owner}_{safe_repo}.json"))
     }
 }
 
@@ -416,6 +520,90 @@ mod tests {
         assert!(path.ends_with("owner_name_repo_name.json"));
     }
 
+    #[test]
+    fn cache_path_is_keyed_by_host() {
+        let (cache, temp) = create_test_cache();
+
+        let default = cache
+            .clone()
+            .for_host(DEFAULT_HOST)
+            .cache_path("owner", "repo");
+        assert_eq!(default, temp.path().join("owner_repo.json"));
+
+        let enterprise = cache
+            .clone()
+            .for_host("ghe.example.com")
+            .cache_path("owner", "repo");
+        assert_eq!(
+            enterprise,
+            temp.path().join("ghe.example.com").join("owner_repo.json")
+        );
+
+        let local = cache
+            .clone()
+            .for_host("127.0.0.1:8080")
+            .cache_path("owner", "repo");
+        assert!(local.ends_with("127.0.0.1_8080/owner_repo.json"));
+
+        let traversal = cache.for_host("../evil").cache_path("owner", "repo");
+        assert!(traversal.starts_with(temp.path()));
+        assert!(traversal.ends_with("_.._evil/owner_repo.json"));
+    }
+
+    #[test]
+    fn same_repo_on_different_hosts_does_not_collide() {
+        let (cache, _temp) = create_test_cache();
+        let public = cache.clone();
+        let enterprise = cache.for_host("ghe.example.com");
+
+        public
+            .save(
+                "owner",
+                "repo",
+                &CachedIssues::new(vec![create_test_task("Public")], None),
+            )
+            .expect("save public");
+        enterprise
+            .save(
+                "owner",
+                "repo",
+                &CachedIssues::new(vec![create_test_task("Enterprise")], None),
+            )
+            .expect("save enterprise");
+
+        let public = public.load("owner", "repo").expect("load").expect("cached");
+        let enterprise = enterprise
+            .load("owner", "repo")
+            .expect("load")
+            .expect("cached");
+        assert_eq!(public.tasks[0].title, "Public");
+        assert_eq!(enterprise.tasks[0].title, "Enterprise");
+    }
+
+    #[test]
+    fn load_skips_tasks_recorded_for_another_host() {
+        let (cache, _temp) = create_test_cache();
+        let enterprise = cache.for_host("ghe.example.com");
+
+        // Written before tasks recorded their host
+        let mut task = create_test_task("Legacy");
+        task.github = Some(whip_protocol::GitHubSource {
+            host: None,
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            number: 1,
+            url: "https://ghe.example.com/owner/repo/issues/1".to_string(),
+            labels: vec![],
+            author: "author".to_string(),
+            comment_count: 0,
+        });
+        enterprise
+            .save("owner", "repo", &CachedIssues::new(vec![task], None))
+            .expect("save");
+
+        assert!(enterprise.load("owner", "repo").expect("load").is_none());
+    }
+
     #[test]
     fn load_returns_none_for_missing_cache() {
         let (cache, _temp) = create_test_cache();
@@ -611,4 +799,24 @@ mod tests {
         let cached = CachedIssues::new(vec![], None);
         cache.save("owner", "repo", &cached).expect("save");
     }
+
+    #[test]
+    fn clear_removes_every_host() {
+        let (cache, temp) = create_test_cache();
+        let cached = CachedIssues::new(vec![create_test_task("Task")], None);
+        cache.save("owner", "one", &cached).expect("save");
+        cache.save("owner", "two", &cached).expect("save");
+        let enterprise = cache.clone().for_host("ghe.example.com");
+        enterprise.save("owner", "one", &cached).expect("save");
+
+        assert_eq!(cache.clear().expect("clear"), 3);
+        assert!(cache.load("owner", "one").expect("load").is_none());
+        assert!(enterprise.load("owner", "one").expect("load").is_none());
+        assert!(temp.path().exists());
+
+        // The cache is still usable, and clearing it again is a no-op
+        cache.save("owner", "one", &cached).expect("save");
+        assert_eq!(cache.clear().expect("clear"), 1);
+        assert_eq!(cache.clear().expect("clear"), 0);
+    }
 }
@@ -10,6 +10,110 @@ use tracing::{debug, instrument, warn};
 use crate::error::{Error, Result};
 use crate::issue::FetchOptions;
 
+pub use whip_protocol::host::{DEFAULT_HOST, api_host};
+
+/// OAuth scopes whip needs for write access to issues, labels, and pull
+/// requests (label sync and lane moves).
+///
+/// `public_repo` grants the same access to public repositories only and is
+/// accepted in place of `repo`.
+pub const REQUIRED_SCOPES: &[&str] = &["repo"];
+
+/// Repository permissions whip needs for write access to issues, labels,
+/// and pull requests, as named in the `permissions` of a repository.
+///
+/// Checked instead of [`REQUIRED_SCOPES`] for GitHub App installation
+/// tokens, which carry no OAuth scopes.
+pub const REQUIRED_PERMISSIONS: &[&str] = &["push"];
+
+/// Identity and permissions of a GitHub token.
+///
+/// Returned by [`GitHubClient::token_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// The login of the user (or bot) the token belongs to.
+    pub login: String,
+    /// The OAuth scopes granted to the token, from the `x-oauth-scopes`
+    /// response header.
+    ///
+    /// `None` when GitHub does not report scopes, as for fine-grained
+    /// personal access tokens, whose permissions are configured per
+    /// repository instead. GitHub App installation tokens cannot call
+    /// `/user` at all; check them with
+    /// [`GitHubClient::repository_access`].
+    pub scopes: Option<Vec<String>>,
+}
+
+impl TokenInfo {
+    /// Returns the [`REQUIRED_SCOPES`] the token lacks.
+    ///
+    /// Always empty when the token's scopes are unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_github::TokenInfo;
+    ///
+    /// let info = TokenInfo {
+    ///     login: "octocat".to_string(),
+    ///     scopes: Some(vec!["read:org".to_string()]),
+    /// };
+    /// assert_eq!(info.missing_scopes(), vec!["repo"]);
+    /// ```
+    #[must_use]
+    pub fn missing_scopes(&self) -> Vec<&'static str> {
+        let Some(scopes) = &self.scopes else {
+            return Vec::new();
+        };
+        let has = |name: &str| scopes.iter().any(|scope| scope == name);
+        REQUIRED_SCOPES
+            .iter()
+            .copied()
+            .filter(|&scope| !(has(scope) || scope == "repo" && has("public_repo")))
+            .collect()
+    }
+}
+
+/// What a token may do in one repository.
+///
+/// Returned by [`GitHubClient::repository_access`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryAccess {
+    /// The repository permissions granted to the token, from the broadest
+    /// (`admin`) to the narrowest (`pull`).
+    ///
+    /// `None` when GitHub does not report permissions for the repository.
+    pub permissions: Option<Vec<String>>,
+}
+
+impl RepositoryAccess {
+    /// Returns the [`REQUIRED_PERMISSIONS`] the token lacks.
+    ///
+    /// Always empty when the token's permissions are unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_github::RepositoryAccess;
+    ///
+    /// let access = RepositoryAccess {
+    ///     permissions: Some(vec!["triage".to_string(), "pull".to_string()]),
+    /// };
+    /// assert_eq!(access.missing_permissions(), vec!["push"]);
+    /// ```
+    #[must_use]
+    pub fn missing_permissions(&self) -> Vec<&'static str> {
+        let Some(permissions) = &self.permissions else {
+            return Vec::new();
+        };
+        REQUIRED_PERMISSIONS
+            .iter()
+            .copied()
+            .filter(|&required| !permissions.iter().any(|granted| granted == required))
+            .collect()
+    }
+}
+
 /// GitHub API client with optional authentication.
 ///
 /// The client supports both authenticated and unauthenticated access.
@@ -44,6 +148,8 @@ pub struct GitHubClient {
     inner: Octocrab,
     /// Whether this client is authenticated.
     authenticated: bool,
+    /// The GitHub host this client talks to (see [`api_host`]).
+    host: String,
 }
 
 impl GitHubClient {
@@ -75,27 +181,69 @@ impl GitHubClient {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(token), fields(authenticated = token.is_some()))]
     pub async fn new(token: Option<SecretString>) -> Result<Self> {
-        let (inner, authenticated) = match token {
+        Self::with_base_url(token, None).await
+    }
+
+    /// Creates a new GitHub client targeting a specific API base URL.
+    ///
+    /// Use this to talk to a GitHub Enterprise Server instance, whose API
+    /// is usually served at `https://<host>/api/v3`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Optional GitHub personal access token (see [`GitHubClient::new`])
+    /// * `base_url` - The API base URL. If `None`, `https://api.github.com` is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `base_url` is not a valid URI or the octocrab
+    /// client fails to initialize.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use secrecy::SecretString;
+    /// use whip_github::GitHubClient;
+    ///
+    /// # async fn example() -> whip_github::Result<()> {
+    /// let token = SecretString::from("ghp_xxx".to_string());
+    /// let client =
+    ///     GitHubClient::with_base_url(Some(token), Some("https://ghe.example.com/api/v3")).await?;
+    /// assert_eq!(client.host(), "ghe.example.com");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(token), fields(authenticated = token.is_some()))]
+    pub async fn with_base_url(
+        token: Option<SecretString>,
+        base_url: Option<&str>,
+    ) -> Result<Self> {
+        let mut builder = Octocrab::builder();
+        if let Some(base_url) = base_url {
+            debug!(base_url, "using custom GitHub API base URL");
+            builder = builder.base_uri(base_url).map_err(Error::Api)?;
+        }
+
+        let authenticated = token.is_some();
+        let inner = match token {
             Some(token) => {
                 debug!("creating authenticated GitHub client");
-                let client = Octocrab::builder()
+                builder
                     .personal_token(token.expose_secret())
                     .build()
-                    .map_err(Error::Api)?;
-                (client, true)
+                    .map_err(Error::Api)?
             }
             None => {
                 debug!("creating unauthenticated GitHub client");
-                let client = Octocrab::builder().build().map_err(Error::Api)?;
-                (client, false)
+                builder.build().map_err(Error::Api)?
             }
         };
 
         Ok(Self {
             inner,
             authenticated,
+            host: api_host(base_url),
         })
     }
 
@@ -135,28 +283,181 @@ impl GitHubClient {
     /// ```
     #[instrument(skip(self))]
     pub async fn validate_token(&self) -> Result<bool> {
+        Ok(self.token_info().await?.is_some())
+    }
+
+    /// Looks up who the token belongs to and which scopes it grants.
+    ///
+    /// Calls the `/user` endpoint and reads the `x-oauth-scopes` response
+    /// header.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(info))` if authenticated and the token is valid
+    /// - `Ok(None)` if not authenticated (no token provided)
+    /// - `Err` if the API call fails (e.g., invalid token, network error)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TokenValidation`] if the token is invalid or expired.
+    /// Returns [`Error::Api`] for other API errors.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use secrecy::SecretString;
+    /// use whip_github::GitHubClient;
+    ///
+    /// # async fn example() -> whip_github::Result<()> {
+    /// let token = SecretString::from("ghp_xxx".to_string());
+    /// let client = GitHubClient::new(Some(token)).await?;
+    ///
+    /// if let Some(info) = client.token_info().await? {
+    ///     println!("Authenticated as {}", info.login);
+    ///     for scope in info.missing_scopes() {
+    ///         println!("Missing scope: {scope}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn token_info(&self) -> Result<Option<TokenInfo>> {
+        use octocrab::FromResponse;
+
+        #[derive(serde::Deserialize)]
+        struct User {
+            login: String,
+        }
+
         if !self.authenticated {
             debug!("client is not authenticated, skipping validation");
-            return Ok(false);
+            return Ok(None);
         }
 
         debug!("validating token by calling /user endpoint");
-        match self.inner.current().user().await {
-            Ok(user) => {
-                debug!(login = %user.login, "token validated successfully");
-                Ok(true)
-            }
-            Err(octocrab::Error::GitHub { source, .. }) => {
-                warn!(message = %source.message, "token validation failed");
-                Err(Error::TokenValidation {
-                    reason: source.message,
-                })
-            }
-            Err(e) => {
-                warn!(error = %e, "API error during token validation");
-                Err(Error::Api(e))
-            }
+        let response = match self.inner._get("/user").await {
+            Ok(response) => octocrab::map_github_error(response).await,
+            Err(e) => Err(e),
+        }
+        .map_err(validation_error)?;
+
+        let scopes = response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|scope| !scope.is_empty())
+                    .map(String::from)
+                    .collect()
+            });
+        let user = User::from_response(response).await?;
+
+        debug!(login = %user.login, "token validated successfully");
+        Ok(Some(TokenInfo {
+            login: user.login,
+            scopes,
+        }))
+    }
+
+    /// Looks up which permissions the token grants in a repository.
+    ///
+    /// Calls the `/repos/{owner}/{repo}` endpoint and reads the
+    /// repository's `permissions`. Unlike [`token_info`](Self::token_info),
+    /// this works for GitHub App installation tokens, which are not
+    /// allowed to call `/user`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(access))` if authenticated and the token can read the
+    ///   repository
+    /// - `Ok(None)` if not authenticated (no token provided)
+    /// - `Err` if the API call fails (e.g., invalid token, repository not
+    ///   accessible, network error)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TokenValidation`] if GitHub rejects the request,
+    /// e.g. because the token is invalid or cannot see the repository.
+    /// Returns [`Error::Api`] for other API errors.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use secrecy::SecretString;
+    /// use whip_github::GitHubClient;
+    ///
+    /// # async fn example() -> whip_github::Result<()> {
+    /// let token = SecretString::from("ghs_xxx".to_string());
+    /// let client = GitHubClient::new(Some(token)).await?;
+    ///
+    /// if let Some(access) = client.repository_access("owner", "repo").await? {
+    ///     for permission in access.missing_permissions() {
+    ///         println!("Missing permission: {permission}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn repository_access(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Option<RepositoryAccess>> {
+        use octocrab::FromResponse;
+
+        /// The `permissions` of a repository, in GitHub's order.
+        #[derive(serde::Deserialize)]
+        struct Permissions {
+            #[serde(default)]
+            admin: bool,
+            #[serde(default)]
+            maintain: bool,
+            #[serde(default)]
+            push: bool,
+            #[serde(default)]
+            triage: bool,
+            #[serde(default)]
+            pull: bool,
         }
+
+        #[derive(serde::Deserialize)]
+        struct Repo {
+            permissions: Option<Permissions>,
+        }
+
+        if !self.authenticated {
+            debug!("client is not authenticated, skipping validation");
+            return Ok(None);
+        }
+
+        debug!("validating token by calling the repository endpoint");
+        let response = match self.inner._get(format!("/repos/{owner}/{repo}")).await {
+            Ok(response) => octocrab::map_github_error(response).await,
+            Err(e) => Err(e),
+        }
+        .map_err(validation_error)?;
+        let repo = Repo::from_response(response).await?;
+
+        let permissions = repo.permissions.map(|p| {
+            [
+                ("admin", p.admin),
+                ("maintain", p.maintain),
+                ("push", p.push),
+                ("triage", p.triage),
+                ("pull", p.pull),
+            ]
+            .into_iter()
+            .filter(|&(_, granted)| granted)
+            .map(|(name, _)| name.to_string())
+            .collect()
+        });
+        debug!(?permissions, "token validated successfully");
+        Ok(Some(RepositoryAccess { permissions }))
     }
 
     /// Returns whether this client is authenticated.
@@ -190,6 +491,14 @@ impl GitHubClient {
         self.authenticated
     }
 
+    /// Returns the GitHub host this client talks to.
+    ///
+    /// This is [`DEFAULT_HOST`] for the public API.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
     /// Returns a reference to the underlying octocrab client.
     ///
     /// This allows direct access to the full octocrab API for operations
@@ -283,9 +592,28 @@ impl GitHubClient {
     }
 }
 
+/// Converts an error from a request made to validate a token.
+///
+/// Errors reported by GitHub become [`Error::TokenValidation`].
+fn validation_error(error: octocrab::Error) -> Error {
+    match error {
+        octocrab::Error::GitHub { source, .. } => {
+            warn!(message = %source.message, "token validation failed");
+            Error::TokenValidation {
+                reason: source.message,
+            }
+        }
+        e => {
+            warn!(error = %e, "API error during token validation");
+            Error::Api(e)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use whip_test_server::{Response, serve};
 
     #[tokio::test]
     async fn new_unauthenticated_client() {
@@ -309,6 +637,187 @@ mod tests {
         assert!(!result);
     }
 
+    /// Serves a single canned response, standing in for a GitHub API
+    /// server.
+    ///
+    /// Returns the base URL to point the client at, and a handle resolving
+    /// to the request line (e.g. `GET /repos/o/r/issues?... HTTP/1.1`).
+    async fn serve_once(response: Response) -> (String, tokio::task::JoinHandle<String>) {
+        let (base_url, server) = serve(vec![response]).await;
+        let handle = tokio::spawn(async move {
+            let requests = server.await.expect("server task");
+            requests[0].lines().next().unwrap_or_default().to_string()
+        });
+        (base_url, handle)
+    }
+
+    #[tokio::test]
+    async fn with_base_url_rejects_invalid_uri() {
+        let result = GitHubClient::with_base_url(None, Some("not a url")).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn with_base_url_sends_requests_to_custom_host() {
+        let (base_url, server) = serve_once(Response::json("[]")).await;
+        let client = GitHubClient::with_base_url(None, Some(&base_url))
+            .await
+            .expect("client");
+        assert_eq!(client.host(), api_host(Some(&base_url)));
+
+        let issues = client
+            .fetch_issues("owner", "repo", &FetchOptions::default())
+            .await
+            .expect("fetch from stand-in server");
+        assert!(issues.is_empty());
+
+        let request_line = server.await.expect("server task");
+        assert!(
+            request_line.starts_with("GET /repos/owner/repo/issues"),
+            "unexpected request: {request_line}"
+        );
+    }
+
+    async fn token_client(base_url: &str) -> GitHubClient {
+        let token = SecretString::from("ghp_test".to_string());
+        GitHubClient::with_base_url(Some(token), Some(base_url))
+            .await
+            .expect("client")
+    }
+
+    #[tokio::test]
+    async fn token_info_reads_login_and_scopes() {
+        let (base_url, server) = serve_once(
+            Response::json(r#"{"login": "octocat", "id": 1}"#)
+                .with_header("X-OAuth-Scopes", "repo, read:org"),
+        )
+        .await;
+        let client = token_client(&base_url).await;
+
+        let info = client
+            .token_info()
+            .await
+            .expect("token info")
+            .expect("authenticated");
+        assert_eq!(info.login, "octocat");
+        assert_eq!(
+            info.scopes,
+            Some(vec!["repo".to_string(), "read:org".to_string()])
+        );
+        assert!(info.missing_scopes().is_empty());
+
+        let request_line = server.await.expect("server task");
+        assert!(request_line.starts_with("GET /user "), "{request_line}");
+    }
+
+    #[tokio::test]
+    async fn token_info_without_scopes_header() {
+        let (base_url, _server) = serve_once(Response::json(r#"{"login": "app[bot]"}"#)).await;
+        let client = token_client(&base_url).await;
+
+        let info = client.token_info().await.unwrap().unwrap();
+        assert_eq!(info.scopes, None);
+        assert!(info.missing_scopes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn token_info_rejected_token_is_validation_error() {
+        let (base_url, _server) = serve_once(
+            Response::json(
+                r#"{"message": "Bad credentials", "documentation_url": "https://docs.github.com"}"#,
+            )
+            .with_status(401),
+        )
+        .await;
+        let client = token_client(&base_url).await;
+
+        let err = client.token_info().await.unwrap_err();
+        assert!(
+            matches!(&err, Error::TokenValidation { reason } if reason == "Bad credentials"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn repository_access_checks_installation_token_without_user() {
+        // Installation tokens may not call /user, but can read the repository
+        let (base_url, server) = serve(vec![
+            Response::json(
+                r#"{"message": "Resource not accessible by integration", "documentation_url": "https://docs.github.com"}"#,
+            )
+            .with_status(403),
+            Response::json(
+                r#"{"full_name": "owner/repo", "permissions": {"admin": false, "maintain": false, "push": true, "triage": true, "pull": true}}"#,
+            ),
+        ])
+        .await;
+        let client = token_client(&base_url).await;
+
+        let err = client.token_info().await.unwrap_err();
+        assert!(matches!(err, Error::TokenValidation { .. }), "{err:?}");
+
+        let access = client
+            .repository_access("owner", "repo")
+            .await
+            .expect("repository access")
+            .expect("authenticated");
+        assert_eq!(
+            access.permissions,
+            Some(vec![
+                "push".to_string(),
+                "triage".to_string(),
+                "pull".to_string()
+            ])
+        );
+        assert!(access.missing_permissions().is_empty());
+
+        let requests = server.await.expect("server task");
+        assert!(requests[0].starts_with("GET /user "), "{}", requests[0]);
+        assert!(
+            requests[1].starts_with("GET /repos/owner/repo "),
+            "{}",
+            requests[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn repository_access_unauthenticated() {
+        let client = GitHubClient::new(None).await.unwrap();
+        assert_eq!(
+            client.repository_access("owner", "repo").await.unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn missing_permissions_requires_push() {
+        let access = |permissions: &[&str]| RepositoryAccess {
+            permissions: Some(permissions.iter().map(|p| (*p).to_string()).collect()),
+        };
+        assert!(
+            access(&["admin", "push", "pull"])
+                .missing_permissions()
+                .is_empty()
+        );
+        assert_eq!(access(&["pull"]).missing_permissions(), vec!["push"]);
+        assert!(
+            RepositoryAccess { permissions: None }
+                .missing_permissions()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn missing_scopes_accepts_public_repo() {
+        let info = |scopes: &[&str]| TokenInfo {
+            login: "octocat".to_string(),
+            scopes: Some(scopes.iter().map(|s| (*s).to_string()).collect()),
+        };
+        assert!(info(&["public_repo"]).missing_scopes().is_empty());
+        assert_eq!(info(&["gist"]).missing_scopes(), vec!["repo"]);
+        assert_eq!(info(&[]).missing_scopes(), vec!["repo"]);
+    }
+
     #[tokio::test]
     async fn inner_returns_octocrab_reference() {
         let client = GitHubClient::new(None).await.unwrap();
@@ -5,6 +5,8 @@
 
 use std::time::Duration;
 
+use whip_protocol::FetchErrorKind;
+
 /// Errors that can occur during GitHub API operations.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -37,6 +39,58 @@ pub enum Error {
     Io(#[from] std::io::Error),
 }
 
+impl Error {
+    /// Classifies this error into a broad [`FetchErrorKind`].
+    ///
+    /// Used to group failures for display without exposing octocrab types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_github::Error;
+    /// use whip_protocol::FetchErrorKind;
+    ///
+    /// let err = Error::RateLimited { reset_after: None };
+    /// assert_eq!(err.kind(), FetchErrorKind::RateLimit);
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> FetchErrorKind {
+        match self {
+            Self::Api(octocrab::Error::GitHub { source, .. }) => {
+                classify_status(source.status_code.as_u16(), &source.message)
+            }
+            Self::Api(
+                octocrab::Error::Hyper { .. }
+                | octocrab::Error::Service { .. }
+                | octocrab::Error::Http { .. },
+            ) => FetchErrorKind::Network,
+            Self::Api(
+                octocrab::Error::Serde { .. }
+                | octocrab::Error::Json { .. }
+                | octocrab::Error::InvalidUtf8 { .. },
+            ) => FetchErrorKind::Parse,
+            Self::Api(_) => FetchErrorKind::Other,
+            Self::TokenValidation { .. } => FetchErrorKind::Auth,
+            Self::RateLimited { .. } => FetchErrorKind::RateLimit,
+            Self::Io(e) if e.kind() == std::io::ErrorKind::InvalidData => FetchErrorKind::Parse,
+            Self::Io(_) => FetchErrorKind::Other,
+        }
+    }
+}
+
+/// Classifies a GitHub API error response by its HTTP status and message.
+///
+/// GitHub reports primary rate limits as `403` with a "rate limit" message,
+/// so the message is checked before treating `403` as an auth failure.
+fn classify_status(status: u16, message: &str) -> FetchErrorKind {
+    match status {
+        429 => FetchErrorKind::RateLimit,
+        403 if message.to_lowercase().contains("rate limit") => FetchErrorKind::RateLimit,
+        401 | 403 => FetchErrorKind::Auth,
+        _ => FetchErrorKind::Other,
+    }
+}
+
 /// Formats the reset time for the rate limit error message.
 fn format_reset_time(reset_after: Option<Duration>) -> String {
     match reset_after {
@@ -77,6 +131,43 @@ mod tests {
         assert_eq!(err.to_string(), "token validation failed: token expired");
     }
 
+    #[test]
+    fn kind_classifies_own_variants() {
+        assert_eq!(
+            Error::TokenValidation {
+                reason: "bad".to_string()
+            }
+            .kind(),
+            FetchErrorKind::Auth
+        );
+        assert_eq!(
+            Error::RateLimited { reset_after: None }.kind(),
+            FetchErrorKind::RateLimit
+        );
+        let parse = std::io::Error::new(std::io::ErrorKind::InvalidData, "bad json");
+        assert_eq!(Error::Io(parse).kind(), FetchErrorKind::Parse);
+        let other = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert_eq!(Error::Io(other).kind(), FetchErrorKind::Other);
+    }
+
+    #[test]
+    fn classify_status_distinguishes_rate_limit_from_auth() {
+        assert_eq!(
+            classify_status(401, "Bad credentials"),
+            FetchErrorKind::Auth
+        );
+        assert_eq!(
+            classify_status(403, "Resource not accessible by integration"),
+            FetchErrorKind::Auth
+        );
+        assert_eq!(
+            classify_status(403, "API rate limit exceeded for 1.2.3.4."),
+            FetchErrorKind::RateLimit
+        );
+        assert_eq!(classify_status(429, ""), FetchErrorKind::RateLimit);
+        assert_eq!(classify_status(404, "Not Found"), FetchErrorKind::Other);
+    }
+
     #[test]
     fn error_display_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -13,9 +13,11 @@
 //!
 //! ```no_run
 //! use whip_github::{GitHubClient, FetchOptions, IssueState, issue_to_task};
+//! use whip_protocol::StatusLabels;
 //!
 //! # async fn example() -> whip_github::Result<()> {
 //! let client = GitHubClient::new(None).await?;
+//! let status_labels = StatusLabels::standard();
 //!
 //! let options = FetchOptions {
 //!     state: IssueState::Open,
@@ -26,7 +28,7 @@
 //! let issues = client.fetch_issues("rust-lang", "rust", &options).await?;
 //!
 //! for issue in &issues {
-//!     if let Some(task) = issue_to_task(issue, "rust-lang", "rust") {
+//!     if let Some(task) = issue_to_task(issue, client.host(), "rust-lang", "rust", &status_labels) {
 //!         println!("Task: {} - {}", task.id, task.title);
 //!     }
 //! }
@@ -35,7 +37,7 @@
 //! ```
 
 use uuid::Uuid;
-use whip_protocol::{GitHubSource, Task, TaskId, determine_status_from_labels};
+use whip_protocol::{DEFAULT_HOST, GitHubSource, StatusLabels, Task, TaskId, repo_key};
 
 /// UUID namespace for generating deterministic task IDs from GitHub issues.
 ///
@@ -119,15 +121,18 @@ impl IssueState {
 /// # ID Generation
 ///
 /// Generates a deterministic UUID v5 from a fixed namespace and the string
-/// `"{owner}/{repo}#{number}"`. This ensures:
+/// `"{key}#{number}"`, where `key` is the repository key returned by
+/// [`repo_key`] (`"owner/repo"` on github.com). This ensures:
 ///
 /// - Same issue always produces the same task ID
 /// - Different issues produce different task IDs (with astronomically high probability)
 /// - IDs are stable across application restarts
+/// - Issues of same-named repositories on different hosts get different IDs
 ///
 /// # Lane Assignment
 ///
-/// The task's lane is determined by the presence of `whip/*` labels:
+/// The task's lane and state are determined by the issue's status labels,
+/// as defined by `status_labels`. With the standard labels:
 ///
 /// | Label | Lane |
 /// |-------|------|
@@ -136,16 +141,18 @@ impl IssueState {
 /// | `whip/under-review` | Under Review |
 /// | `whip/done` | Done |
 ///
-/// If multiple whip labels are present, the first one in lane order takes precedence.
+/// If multiple status labels are present, the first one in `status_labels`
+/// takes precedence. Issues without a status label are skipped.
 ///
 /// # Example
 ///
 /// ```no_run
-/// use whip_github::issue_to_task;
+/// use whip_github::{DEFAULT_HOST, issue_to_task};
+/// use whip_protocol::StatusLabels;
 ///
 /// // Assuming `issue` is an octocrab issue with a whip/backlog label
 /// # fn example(issue: &octocrab::models::issues::Issue) {
-/// if let Some(task) = issue_to_task(issue, "owner", "repo") {
+/// if let Some(task) = issue_to_task(issue, DEFAULT_HOST, "owner", "repo", &StatusLabels::standard()) {
 ///     println!("Task: {} in lane {:?}", task.title, task.lane);
 /// }
 /// # }
@@ -153,19 +160,21 @@ impl IssueState {
 #[must_use]
 pub fn issue_to_task(
     issue: &octocrab::models::issues::Issue,
+    host: &str,
     owner: &str,
     repo: &str,
+    status_labels: &StatusLabels,
 ) -> Option<Task> {
     let number = issue.number;
 
     // Extract label names from issue labels
     let labels: Vec<String> = issue.labels.iter().map(|l| l.name.clone()).collect();
 
-    // Determine lane and state from whip/* labels - if no whip label, skip this issue
-    let status = determine_status_from_labels(&labels)?;
+    // Determine lane and state from status labels - if there is none, skip this issue
+    let status = status_labels.determine_status(&labels)?;
 
-    // Generate deterministic task ID from owner/repo#number
-    let id_input = format!("{owner}/{repo}#{number}");
+    // Generate deterministic task ID from the repository key and number
+    let id_input = format!("{}#{number}", repo_key(host, owner, repo));
     let id = Uuid::new_v5(&GITHUB_ISSUE_NAMESPACE, id_input.as_bytes());
 
     // Extract author login
@@ -173,6 +182,7 @@ pub fn issue_to_task(
 
     // Build the GitHub source metadata
     let github = GitHubSource {
+        host: (host != DEFAULT_HOST).then(|| host.to_string()),
         owner: owner.to_string(),
         repo: repo.to_string(),
         number,
@@ -361,8 +371,14 @@ mod tests {
         let issue: octocrab::models::issues::Issue =
             serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
 
-        let task = issue_to_task(&issue, "testowner", "testrepo")
-            .expect("Issue with whip label should produce a task");
+        let task = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "testowner",
+            "testrepo",
+            &StatusLabels::standard(),
+        )
+        .expect("Issue with whip label should produce a task");
 
         // Verify basic task fields
         assert_eq!(task.title, "Test Issue Title");
@@ -409,7 +425,13 @@ mod tests {
         let issue: octocrab::models::issues::Issue =
             serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
 
-        let task = issue_to_task(&issue, "owner", "repo");
+        let task = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "owner",
+            "repo",
+            &StatusLabels::standard(),
+        );
 
         // Should return None because there's no whip/* label
         assert!(task.is_none());
@@ -429,8 +451,14 @@ mod tests {
         let issue: octocrab::models::issues::Issue =
             serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
 
-        let task = issue_to_task(&issue, "owner", "repo")
-            .expect("Issue with whip label should produce a task");
+        let task = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "owner",
+            "repo",
+            &StatusLabels::standard(),
+        )
+        .expect("Issue with whip label should produce a task");
 
         // Body should default to empty string when null
         assert_eq!(task.description, "");
@@ -458,8 +486,22 @@ mod tests {
         let issue: octocrab::models::issues::Issue =
             serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
 
-        let task1 = issue_to_task(&issue, "myorg", "myrepo").unwrap();
-        let task2 = issue_to_task(&issue, "myorg", "myrepo").unwrap();
+        let task1 = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "myorg",
+            "myrepo",
+            &StatusLabels::standard(),
+        )
+        .unwrap();
+        let task2 = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "myorg",
+            "myrepo",
+            &StatusLabels::standard(),
+        )
+        .unwrap();
 
         assert_eq!(task1.id, task2.id);
     }
@@ -471,12 +513,84 @@ mod tests {
         let issue: octocrab::models::issues::Issue =
             serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
 
-        let task_a = issue_to_task(&issue, "org-a", "repo").unwrap();
-        let task_b = issue_to_task(&issue, "org-b", "repo").unwrap();
+        let task_a = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "org-a",
+            "repo",
+            &StatusLabels::standard(),
+        )
+        .unwrap();
+        let task_b = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "org-b",
+            "repo",
+            &StatusLabels::standard(),
+        )
+        .unwrap();
 
         assert_ne!(task_a.id, task_b.id);
     }
 
+    #[test]
+    fn issue_to_task_different_hosts_different_ids() {
+        let issue_json = mock_issue_json(1, "Same number", None, "user", &["whip/backlog"], 0);
+
+        let issue: octocrab::models::issues::Issue =
+            serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
+
+        let public = issue_to_task(
+            &issue,
+            DEFAULT_HOST,
+            "org",
+            "repo",
+            &StatusLabels::standard(),
+        )
+        .unwrap();
+        let enterprise = issue_to_task(
+            &issue,
+            "ghe.example.com",
+            "org",
+            "repo",
+            &StatusLabels::standard(),
+        )
+        .unwrap();
+
+        assert_ne!(public.id, enterprise.id);
+        assert_eq!(public.github.unwrap().host, None);
+        assert_eq!(
+            enterprise.github.unwrap().repo_key(),
+            "ghe.example.com/org/repo"
+        );
+    }
+
+    #[test]
+    fn issue_to_task_uses_configured_status_labels() {
+        use whip_protocol::LabelDefinition;
+
+        let status_labels = StatusLabels::new(vec![LabelDefinition::new(
+            "status: in progress",
+            "2563EB",
+            "",
+            LaneKind::InProgress,
+        )]);
+        let issue_json = mock_issue_json(1, "Test", None, "user", &["Status: In Progress"], 0);
+        let issue: octocrab::models::issues::Issue =
+            serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
+
+        let task = issue_to_task(&issue, DEFAULT_HOST, "owner", "repo", &status_labels)
+            .expect("Issue with a configured status label should produce a task");
+        assert_eq!(task.lane, LaneKind::InProgress);
+        assert_eq!(task.state, TaskState::InFlight);
+
+        // The standard labels no longer apply
+        let issue_json = mock_issue_json(2, "Test", None, "user", &["whip/in-progress"], 0);
+        let issue: octocrab::models::issues::Issue =
+            serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
+        assert!(issue_to_task(&issue, DEFAULT_HOST, "owner", "repo", &status_labels).is_none());
+    }
+
     #[test]
     fn issue_to_task_assigns_correct_lane_from_label() {
         // Test each label -> lane and state mapping
@@ -497,8 +611,14 @@ mod tests {
             let issue: octocrab::models::issues::Issue =
                 serde_json::from_str(&issue_json).expect("Failed to deserialize mock issue");
 
-            let task = issue_to_task(&issue, "owner", "repo")
-                .expect("Issue with whip label should produce a task");
+            let task = issue_to_task(
+                &issue,
+                DEFAULT_HOST,
+                "owner",
+                "repo",
+                &StatusLabels::standard(),
+            )
+            .expect("Issue with whip label should produce a task");
 
             assert_eq!(
                 task.lane, expected_lane,
@@ -10,13 +10,14 @@
 //! - [`GitHubClient::list_labels`]: List all labels on a repository
 //! - [`GitHubClient::create_label`]: Create a new label
 //! - [`GitHubClient::update_label`]: Update an existing label
-//! - [`sync_labels`]: Sync whip status labels to a repository
+//! - [`plan_label_sync`]: Plan the sync of status labels, without writing
+//! - [`sync_labels`]: Sync status labels to a repository
 //!
 //! # Example
 //!
 //! ```no_run
 //! use whip_github::GitHubClient;
-//! use whip_protocol::standard_status_labels;
+//! use whip_protocol::StatusLabels;
 //!
 //! # async fn example() -> whip_github::Result<()> {
 //! let client = GitHubClient::new(None).await?;
@@ -26,15 +27,17 @@
 //! println!("Found {} labels", labels.len());
 //!
 //! // Sync whip labels (requires write access)
-//! // sync_labels(&client, "owner", "repo").await?;
+//! // sync_labels(&client, "owner", "repo", &StatusLabels::standard()).await?;
 //! # Ok(())
 //! # }
 //! ```
 
+use std::fmt;
+
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument, warn};
-use whip_protocol::{LabelDefinition, standard_status_labels};
+use whip_protocol::{LabelDefinition, StatusLabels};
 
 use crate::client::GitHubClient;
 use crate::error::{Error, Result};
@@ -72,7 +75,9 @@ struct CreateLabelRequest<'a> {
 struct UpdateLabelRequest<'a> {
     new_name: &'a str,
     color: &'a str,
-    description: &'a str,
+    /// Left out to keep the current description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
 }
 
 impl GitHubClient {
@@ -236,7 +241,7 @@ impl GitHubClient {
         let body = UpdateLabelRequest {
             new_name: &label.name,
             color: &label.color,
-            description: &label.description,
+            description: (!label.description.is_empty()).then_some(label.description.as_str()),
         };
 
         let response: GitHubLabel = self
@@ -250,20 +255,320 @@ impl GitHubClient {
     }
 }
 
-/// Synchronizes whip status labels to a repository.
+/// A change to one of a repository's labels, as planned by [`LabelSyncPlan`].
+///
+/// The [`Display`](fmt::Display) output is a small diff: the action and
+/// label name on the first line, then each changed field with its old and
+/// new value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelChange {
+    /// The label does not exist and will be created.
+    Create(LabelDefinition),
+    /// The label exists with a different color or description.
+    Update {
+        /// The label as it is on the repository.
+        current: GitHubLabel,
+        /// The label as it will be.
+        target: LabelDefinition,
+    },
+    /// A label with the same name in a different case exists.
+    ///
+    /// GitHub label names are case-insensitive, so the existing label is
+    /// renamed rather than a new one created; issues keep it.
+    Rename {
+        /// The label as it is on the repository.
+        current: GitHubLabel,
+        /// The label as it will be.
+        target: LabelDefinition,
+    },
+}
+
+impl LabelChange {
+    /// Returns the label as it will be once the change is applied.
+    #[must_use]
+    pub fn target(&self) -> &LabelDefinition {
+        match self {
+            Self::Create(target) | Self::Update { target, .. } | Self::Rename { target, .. } => {
+                target
+            }
+        }
+    }
+}
+
+impl fmt::Display for LabelChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (current, target) = match self {
+            Self::Create(target) => {
+                write!(f, "create {}", target.name)?;
+                write!(f, "\n  color        #{}", target.color)?;
+                if !target.description.is_empty() {
+                    write!(f, "\n  description  {:?}", target.description)?;
+                }
+                return Ok(());
+            }
+            Self::Update { current, target } => {
+                write!(f, "update {}", target.name)?;
+                (current, target)
+            }
+            Self::Rename { current, target } => {
+                write!(f, "rename {} -> {}", current.name, target.name)?;
+                (current, target)
+            }
+        };
+        if !same_color(&current.color, &target.color) {
+            write!(
+                f,
+                "\n  color        #{} -> #{}",
+                current.color, target.color
+            )?;
+        }
+        if description_differs(current, target) {
+            write!(
+                f,
+                "\n  description  {:?} -> {:?}",
+                current.description.as_deref().unwrap_or_default(),
+                target.description
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// GitHub returns colors in lowercase, while definitions may use uppercase.
+fn same_color(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// An empty description leaves the existing one alone.
+fn description_differs(current: &GitHubLabel, target: &LabelDefinition) -> bool {
+    !target.description.is_empty()
+        && current.description.as_deref().unwrap_or_default() != target.description
+}
+
+/// The changes needed to bring a repository's labels in line with whip's.
+///
+/// Planning only reads labels, so a plan can be shown to the user before
+/// anything is written with [`apply`](Self::apply).
+///
+/// # Examples
+///
+/// ```
+/// use whip_github::{GitHubLabel, LabelChange, LabelSyncPlan};
+/// use whip_protocol::standard_status_labels;
+///
+/// let existing = vec![GitHubLabel {
+///     name: "Whip/Done".to_string(),
+///     color: "16a34a".to_string(),
+///     description: Some("Task has been completed successfully".to_string()),
+/// }];
+/// let plan = LabelSyncPlan::new("owner", "repo", &existing, &standard_status_labels());
+///
+/// assert_eq!(plan.changes().len(), 5);
+/// let rename = plan
+///     .changes()
+///     .iter()
+///     .find(|change| matches!(change, LabelChange::Rename { .. }))
+///     .unwrap();
+/// assert_eq!(rename.to_string(), "rename Whip/Done -> whip/done");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSyncPlan {
+    owner: String,
+    repo: String,
+    changes: Vec<LabelChange>,
+}
+
+impl LabelSyncPlan {
+    /// Plans the changes that turn `existing` labels into `wanted` ones.
+    ///
+    /// Labels that are not in `wanted` are left alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `existing` - The repository's labels
+    /// * `wanted` - The labels the repository should have
+    #[must_use]
+    pub fn new(
+        owner: impl Into<String>,
+        repo: impl Into<String>,
+        existing: &[GitHubLabel],
+        wanted: &[LabelDefinition],
+    ) -> Self {
+        let changes = wanted
+            .iter()
+            .filter_map(|target| {
+                let exact = existing.iter().find(|label| label.name == target.name);
+                let current = exact.or_else(|| {
+                    existing
+                        .iter()
+                        .find(|label| label.name.eq_ignore_ascii_case(&target.name))
+                });
+                let Some(current) = current else {
+                    return Some(LabelChange::Create(target.clone()));
+                };
+                let (current, target) = (current.clone(), target.clone());
+                if exact.is_none() {
+                    Some(LabelChange::Rename { current, target })
+                } else if !same_color(&current.color, &target.color)
+                    || description_differs(&current, &target)
+                {
+                    Some(LabelChange::Update { current, target })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        Self {
+            owner: owner.into(),
+            repo: repo.into(),
+            changes,
+        }
+    }
+
+    /// Returns the repository owner.
+    #[must_use]
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Returns the repository name.
+    #[must_use]
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    /// Returns the planned changes, in the order of the wanted labels.
+    #[must_use]
+    pub fn changes(&self) -> &[LabelChange] {
+        &self.changes
+    }
+
+    /// Returns `true` if the labels are already up to date.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Applies the planned changes to the repository.
+    ///
+    /// Renamed labels are counted as updated in the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - An authenticated GitHub client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any API call fails. Changes are applied in order
+    /// and are not rolled back, so the earlier ones remain.
+    #[instrument(skip(self, client), fields(owner = %self.owner, repo = %self.repo))]
+    pub async fn apply(&self, client: &GitHubClient) -> Result<SyncResult> {
+        let mut result = SyncResult {
+            created: 0,
+            updated: 0,
+        };
+        for change in &self.changes {
+            let label = change.target();
+            let applied = match change {
+                LabelChange::Create(target) => {
+                    debug!(label = %label.name, "creating label");
+                    client.create_label(&self.owner, &self.repo, target).await
+                }
+                LabelChange::Update { current, target }
+                | LabelChange::Rename { current, target } => {
+                    debug!(label = %label.name, "updating label");
+                    client
+                        .update_label(&self.owner, &self.repo, &current.name, target)
+                        .await
+                }
+            };
+            if let Err(e) = applied {
+                warn!(label = %label.name, error = %e, "failed to sync label");
+                return Err(e);
+            }
+            match change {
+                LabelChange::Create(_) => result.created += 1,
+                LabelChange::Update { .. } | LabelChange::Rename { .. } => result.updated += 1,
+            }
+        }
+
+        debug!(
+            created = result.created,
+            updated = result.updated,
+            "sync complete"
+        );
+        Ok(result)
+    }
+}
+
+/// Plans the sync of status labels to a repository.
+///
+/// Only lists the repository's labels; nothing is changed until the plan is
+/// [applied](LabelSyncPlan::apply).
+///
+/// # Arguments
 ///
-/// This function ensures that all standard whip labels exist on the repository
+/// * `client` - A GitHub client
+/// * `owner` - Repository owner
+/// * `repo` - Repository name
+/// * `status_labels` - The labels the repository should have
+///
+/// # Errors
+///
+/// Returns an error if the labels cannot be listed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use whip_github::{GitHubClient, plan_label_sync};
+/// use whip_protocol::StatusLabels;
+///
+/// # async fn example() -> whip_github::Result<()> {
+/// let client = GitHubClient::new(None).await?;
+///
+/// let plan = plan_label_sync(&client, "owner", "repo", &StatusLabels::standard()).await?;
+/// for change in plan.changes() {
+///     println!("{change}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[instrument(skip(client, status_labels), fields(owner = %owner, repo = %repo))]
+pub async fn plan_label_sync(
+    client: &GitHubClient,
+    owner: &str,
+    repo: &str,
+    status_labels: &StatusLabels,
+) -> Result<LabelSyncPlan> {
+    let existing = client.list_labels(owner, repo).await?;
+    Ok(LabelSyncPlan::new(
+        owner,
+        repo,
+        &existing,
+        status_labels.labels(),
+    ))
+}
+
+/// Synchronizes status labels to a repository.
+///
+/// This function ensures that all status labels exist on the repository
 /// with the correct colors and descriptions. It will:
 ///
 /// 1. Create labels that don't exist
 /// 2. Update labels that exist but have incorrect colors or descriptions
-/// 3. Leave correctly configured labels unchanged
+/// 3. Rename labels whose name only differs in case
+/// 4. Leave correctly configured labels unchanged
+///
+/// Use [`plan_label_sync`] to review the changes before making them.
 ///
 /// # Arguments
 ///
 /// * `client` - An authenticated GitHub client
 /// * `owner` - Repository owner
 /// * `repo` - Repository name
+/// * `status_labels` - The labels the repository should have
 ///
 /// # Returns
 ///
@@ -278,69 +583,30 @@ impl GitHubClient {
 ///
 /// ```no_run
 /// use whip_github::{GitHubClient, sync_labels};
+/// use whip_protocol::StatusLabels;
 /// use secrecy::SecretString;
 ///
 /// # async fn example() -> whip_github::Result<()> {
 /// let token = SecretString::from("ghp_xxx".to_string());
 /// let client = GitHubClient::new(Some(token)).await?;
 ///
-/// let result = sync_labels(&client, "owner", "repo").await?;
+/// let result = sync_labels(&client, "owner", "repo", &StatusLabels::standard()).await?;
 /// println!("Created: {}, Updated: {}", result.created, result.updated);
 /// # Ok(())
 /// # }
 /// ```
-#[instrument(skip(client), fields(owner = %owner, repo = %repo))]
-pub async fn sync_labels(client: &GitHubClient, owner: &str, repo: &str) -> Result<SyncResult> {
-    debug!("syncing whip labels");
-
-    // Get existing labels
-    let existing = client.list_labels(owner, repo).await?;
-    let existing_by_name: std::collections::HashMap<&str, &GitHubLabel> =
-        existing.iter().map(|l| (l.name.as_str(), l)).collect();
-
-    let mut created = 0;
-    let mut updated = 0;
-
-    // Sync each standard label
-    for label_def in standard_status_labels() {
-        match existing_by_name.get(label_def.name.as_str()) {
-            Some(existing_label) => {
-                // Check if update needed
-                let needs_update = existing_label.color != label_def.color
-                    || existing_label.description.as_deref() != Some(&label_def.description);
-
-                if needs_update {
-                    debug!(label = %label_def.name, "updating label");
-                    match client
-                        .update_label(owner, repo, &label_def.name, &label_def)
-                        .await
-                    {
-                        Ok(_) => updated += 1,
-                        Err(e) => {
-                            warn!(label = %label_def.name, error = %e, "failed to update label");
-                            return Err(e);
-                        }
-                    }
-                } else {
-                    debug!(label = %label_def.name, "label already up to date");
-                }
-            }
-            None => {
-                // Create the label
-                debug!(label = %label_def.name, "creating label");
-                match client.create_label(owner, repo, &label_def).await {
-                    Ok(_) => created += 1,
-                    Err(e) => {
-                        warn!(label = %label_def.name, error = %e, "failed to create label");
-                        return Err(e);
-                    }
-                }
-            }
-        }
-    }
-
-    debug!(created = created, updated = updated, "sync complete");
-    Ok(SyncResult { created, updated })
+#[instrument(skip(client, status_labels), fields(owner = %owner, repo = %repo))]
+pub async fn sync_labels(
+    client: &GitHubClient,
+    owner: &str,
+    repo: &str,
+    status_labels: &StatusLabels,
+) -> Result<SyncResult> {
+    debug!("syncing status labels");
+    plan_label_sync(client, owner, repo, status_labels)
+        .await?
+        .apply(client)
+        .await
 }
 
 /// Result of a label sync operation.
@@ -427,4 +693,125 @@ mod tests {
         assert_eq!(label.color, "FF0000");
         assert!(label.description.is_none());
     }
+
+    fn github_label(name: &str, color: &str, description: &str) -> GitHubLabel {
+        GitHubLabel {
+            name: name.to_string(),
+            color: color.to_string(),
+            description: Some(description.to_string()),
+        }
+    }
+
+    fn backlog() -> LabelDefinition {
+        LabelDefinition::new(
+            "whip/backlog",
+            "6B7280",
+            "Task is in the backlog",
+            whip_protocol::LaneKind::Backlog,
+        )
+    }
+
+    #[test]
+    fn plan_is_empty_when_labels_match() {
+        // GitHub reports colors in lowercase
+        let existing = [github_label(
+            "whip/backlog",
+            "6b7280",
+            "Task is in the backlog",
+        )];
+        let plan = LabelSyncPlan::new("owner", "repo", &existing, &[backlog()]);
+        assert!(plan.is_empty());
+        assert_eq!((plan.owner(), plan.repo()), ("owner", "repo"));
+    }
+
+    #[test]
+    fn plan_creates_missing_labels() {
+        let existing = [github_label("bug", "FF0000", "Something is broken")];
+        let plan = LabelSyncPlan::new("owner", "repo", &existing, &[backlog()]);
+        assert_eq!(plan.changes(), [LabelChange::Create(backlog())]);
+        assert_eq!(
+            plan.changes()[0].to_string(),
+            "create whip/backlog\n  color        #6B7280\n  description  \"Task is in the backlog\""
+        );
+    }
+
+    #[test]
+    fn plan_updates_changed_labels() {
+        let current = GitHubLabel {
+            description: None,
+            ..github_label("whip/backlog", "000000", "")
+        };
+        let plan = LabelSyncPlan::new(
+            "owner",
+            "repo",
+            std::slice::from_ref(&current),
+            &[backlog()],
+        );
+        assert_eq!(
+            plan.changes(),
+            [LabelChange::Update {
+                current,
+                target: backlog()
+            }]
+        );
+        assert_eq!(
+            plan.changes()[0].to_string(),
+            "update whip/backlog\n  color        #000000 -> #6B7280\n  description  \"\" -> \"Task is in the backlog\""
+        );
+    }
+
+    #[test]
+    fn plan_renames_labels_differing_in_case() {
+        let current = github_label("Whip/Backlog", "6B7280", "Old description");
+        let plan = LabelSyncPlan::new(
+            "owner",
+            "repo",
+            std::slice::from_ref(&current),
+            &[backlog()],
+        );
+        assert_eq!(
+            plan.changes(),
+            [LabelChange::Rename {
+                current,
+                target: backlog()
+            }]
+        );
+        assert_eq!(
+            plan.changes()[0].to_string(),
+            "rename Whip/Backlog -> whip/backlog\n  description  \"Old description\" -> \"Task is in the backlog\""
+        );
+    }
+
+    #[test]
+    fn plan_keeps_descriptions_when_none_is_configured() {
+        let target =
+            LabelDefinition::new("status: done", "16A34A", "", whip_protocol::LaneKind::Done);
+        let existing = [github_label("status: done", "16a34a", "Shipped")];
+        assert!(
+            LabelSyncPlan::new("owner", "repo", &existing, std::slice::from_ref(&target))
+                .is_empty()
+        );
+
+        let existing = [github_label("status: done", "000000", "Shipped")];
+        let plan = LabelSyncPlan::new("owner", "repo", &existing, std::slice::from_ref(&target));
+        assert_eq!(
+            plan.changes()[0].to_string(),
+            "update status: done\n  color        #000000 -> #16A34A"
+        );
+
+        let plan = LabelSyncPlan::new("owner", "repo", &[], &[target]);
+        assert_eq!(
+            plan.changes()[0].to_string(),
+            "create status: done\n  color        #16A34A"
+        );
+    }
+
+    #[test]
+    fn plan_prefers_exact_name_over_case_match() {
+        let existing = [
+            github_label("WHIP/BACKLOG", "000000", "Old"),
+            github_label("whip/backlog", "6B7280", "Task is in the backlog"),
+        ];
+        assert!(LabelSyncPlan::new("owner", "repo", &existing, &[backlog()]).is_empty());
+    }
 }
@@ -7,7 +7,8 @@
 //!
 //! The crate provides:
 //!
-//! - [`GitHubClient`]: The main API client with optional authentication
+//! - [`GitHubClient`]: The main API client with optional authentication,
+//!   targeting `github.com` or a GitHub Enterprise Server instance
 //! - [`FetchOptions`] and [`IssueState`]: Options for filtering issues
 //! - [`issue_to_task`]: Convert GitHub issues to whip tasks
 //! - [`IssueCache`] and [`CachedIssues`]: Persistent caching for issues
@@ -86,6 +87,7 @@
 //!
 //! ```no_run
 //! use whip_github::{GitHubClient, FetchOptions, IssueState, issue_to_task};
+//! use whip_protocol::StatusLabels;
 //!
 //! # async fn example() -> whip_github::Result<()> {
 //! let client = GitHubClient::new(None).await?;
@@ -98,7 +100,7 @@
 //!
 //! let issues = client.fetch_issues("owner", "repo", &options).await?;
 //! let tasks: Vec<_> = issues.iter()
-//!     .map(|issue| issue_to_task(issue, "owner", "repo"))
+//!     .map(|issue| issue_to_task(issue, client.host(), "owner", "repo", &StatusLabels::standard()))
 //!     .collect();
 //! # Ok(())
 //! # }
@@ -111,7 +113,12 @@ pub mod issue;
 pub mod label;
 
 pub use cache::{CachedIssues, IssueCache};
-pub use client::GitHubClient;
+pub use client::{
+    DEFAULT_HOST, GitHubClient, REQUIRED_PERMISSIONS, REQUIRED_SCOPES, RepositoryAccess, TokenInfo,
+    api_host,
+};
 pub use error::{Error, Result};
 pub use issue::{FetchOptions, IssueState, issue_to_task};
-pub use label::{GitHubLabel, SyncResult, sync_labels};
+pub use label::{
+    GitHubLabel, LabelChange, LabelSyncPlan, SyncResult, plan_label_sync, sync_labels,
+};
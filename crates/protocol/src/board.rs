@@ -2,15 +2,23 @@
 //!
 //! This module defines the board layout types including lanes and the
 //! overall board structure that organizes tasks.
+//!
+//! A task's [`LaneKind`] is its workflow stage. Which column of the board
+//! it shows up in is decided by the board's [`LaneDefinition`]s, so a board
+//! can split a stage into several lanes (for example failed tasks apart
+//! from done ones) or merge several stages into one.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::task::{Task, TaskId};
+use crate::sort::SortKey;
+use crate::task::{Task, TaskId, TaskState};
 
-/// The type of lane on a Kanban board.
+/// The workflow stage of a task.
 ///
 /// Represents the workflow stages that tasks move through.
-/// The order reflects the typical progression of work.
+/// The order reflects the typical progression of work. The standard board
+/// has one lane per stage.
 ///
 /// # Examples
 ///
@@ -20,8 +28,9 @@ use crate::task::{Task, TaskId};
 /// let lane = LaneKind::InProgress;
 /// assert_eq!(lane.display_name(), "In Progress");
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
+#[schemars(description = "A Kanban board lane.")]
 pub enum LaneKind {
     /// Tasks waiting to be started.
     #[default]
@@ -153,9 +162,154 @@ impl LaneKind {
     }
 }
 
+/// Which tasks a lane of the board holds.
+///
+/// A task matches a lane when its stage, state, and labels pass every
+/// filter the lane sets; an empty filter accepts anything. Lanes are
+/// matched in board order, so a narrow lane (such as failed tasks) must
+/// come before a broader one (such as every done task).
+///
+/// A lane with a [`SortKey`] keeps its tasks in that order; otherwise tasks
+/// are shown in the order they arrived.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{LaneDefinition, LaneKind, Task, TaskState};
+///
+/// let failed = LaneDefinition::new("Failed")
+///     .with_kinds([LaneKind::Done])
+///     .with_states([TaskState::Failed]);
+///
+/// let mut task = Task::new("Task", "Description");
+/// task.lane = LaneKind::Done;
+/// assert!(!failed.matches(&task));
+///
+/// task.state = TaskState::Failed;
+/// assert!(failed.matches(&task));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "A lane of the board and the tasks it holds.")]
+pub struct LaneDefinition {
+    /// The title shown in the lane header.
+    pub title: String,
+    /// Workflow stages of the tasks this lane holds; empty holds any stage.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<LaneKind>,
+    /// Task states this lane holds; empty holds any state.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub states: Vec<TaskState>,
+    /// Issue labels, one of which a task needs to be in this lane; empty
+    /// holds tasks regardless of their labels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// What the tasks of this lane are sorted by; unset keeps the order
+    /// tasks arrived in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortKey>,
+}
+
+impl LaneDefinition {
+    /// Creates a lane that holds every task.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The title shown in the lane header
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            kinds: Vec::new(),
+            states: Vec::new(),
+            labels: Vec::new(),
+            sort: None,
+        }
+    }
+
+    /// Creates the standard lane for a workflow stage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{LaneDefinition, LaneKind};
+    ///
+    /// let lane = LaneDefinition::for_kind(LaneKind::UnderReview);
+    /// assert_eq!(lane.title, "Under Review");
+    /// assert_eq!(lane.kinds, vec![LaneKind::UnderReview]);
+    /// ```
+    #[must_use]
+    pub fn for_kind(kind: LaneKind) -> Self {
+        Self::new(kind.display_name()).with_kinds([kind])
+    }
+
+    /// Returns the standard lanes, one per workflow stage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::LaneDefinition;
+    ///
+    /// let titles: Vec<_> = LaneDefinition::standard()
+    ///     .into_iter()
+    ///     .map(|lane| lane.title)
+    ///     .collect();
+    /// assert_eq!(titles, ["Backlog", "In Progress", "Under Review", "Done"]);
+    /// ```
+    #[must_use]
+    pub fn standard() -> Vec<Self> {
+        LaneKind::all().into_iter().map(Self::for_kind).collect()
+    }
+
+    /// Restricts this lane to tasks in the given workflow stages.
+    #[must_use]
+    pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = LaneKind>) -> Self {
+        self.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    /// Restricts this lane to tasks in the given states.
+    #[must_use]
+    pub fn with_states(mut self, states: impl IntoIterator<Item = TaskState>) -> Self {
+        self.states = states.into_iter().collect();
+        self
+    }
+
+    /// Restricts this lane to tasks having at least one of the given labels.
+    #[must_use]
+    pub fn with_labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        self.labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sorts the tasks of this lane by the given key.
+    #[must_use]
+    pub const fn with_sort(mut self, sort: SortKey) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Returns `true` if the task passes every filter of this lane.
+    ///
+    /// Labels are compared case-insensitively, as on GitHub.
+    #[must_use]
+    pub fn matches(&self, task: &Task) -> bool {
+        let kind_matches = self.kinds.is_empty() || self.kinds.contains(&task.lane);
+        let state_matches = self.states.is_empty() || self.states.contains(&task.state);
+        let label_matches = self.labels.is_empty()
+            || task.github.as_ref().is_some_and(|github| {
+                github.labels.iter().any(|label| {
+                    self.labels
+                        .iter()
+                        .any(|wanted| wanted.eq_ignore_ascii_case(label))
+                })
+            });
+        kind_matches && state_matches && label_matches
+    }
+}
+
 /// A single lane (column) on the Kanban board.
 ///
-/// Contains tasks that share the same workflow stage.
+/// Contains the tasks its [`LaneDefinition`] matches.
 ///
 /// # Examples
 ///
@@ -164,22 +318,19 @@ impl LaneKind {
 ///
 /// let lane = Lane::new(LaneKind::Backlog);
 /// assert!(lane.is_empty());
-/// assert_eq!(lane.kind, LaneKind::Backlog);
+/// assert_eq!(lane.title(), "Backlog");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Lane {
-    /// The type of this lane.
-    pub kind: LaneKind,
-    /// Tasks currently in this lane, ordered by position.
+    /// Which tasks this lane holds.
+    #[serde(flatten)]
+    pub definition: LaneDefinition,
+    /// Tasks currently in this lane, in display order.
     pub tasks: Vec<Task>,
 }
 
 impl Lane {
-    /// Creates a new empty lane of the specified kind.
-    ///
-    /// This function is `const` despite returning a `Vec`. In Rust 2024 edition,
-    /// [`Vec::new()`] is a const function that performs no heap allocation until
-    /// elements are added, making this safe for use in const contexts.
+    /// Creates a new empty lane for a workflow stage.
     ///
     /// # Examples
     ///
@@ -187,26 +338,38 @@ impl Lane {
     /// use whip_protocol::{Lane, LaneKind};
     ///
     /// let lane = Lane::new(LaneKind::InProgress);
-    /// assert_eq!(lane.kind, LaneKind::InProgress);
+    /// assert_eq!(lane.definition.kinds, vec![LaneKind::InProgress]);
     /// assert!(lane.tasks.is_empty());
     /// ```
+    #[must_use]
+    pub fn new(kind: LaneKind) -> Self {
+        Self::with_definition(LaneDefinition::for_kind(kind))
+    }
+
+    /// Creates a new empty lane from its definition.
     ///
-    /// # Const Usage
+    /// # Examples
     ///
     /// ```
-    /// use whip_protocol::{Lane, LaneKind};
+    /// use whip_protocol::{Lane, LaneDefinition};
     ///
-    /// const EMPTY_BACKLOG: Lane = Lane::new(LaneKind::Backlog);
-    /// assert!(EMPTY_BACKLOG.tasks.is_empty());
+    /// let lane = Lane::with_definition(LaneDefinition::new("Everything"));
+    /// assert_eq!(lane.title(), "Everything");
     /// ```
     #[must_use]
-    pub const fn new(kind: LaneKind) -> Self {
+    pub const fn with_definition(definition: LaneDefinition) -> Self {
         Self {
-            kind,
+            definition,
             tasks: Vec::new(),
         }
     }
 
+    /// Returns the title shown in the lane header.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.definition.title
+    }
+
     /// Returns the number of tasks in this lane.
     ///
     /// # Examples
@@ -240,10 +403,12 @@ impl Lane {
         self.tasks.is_empty()
     }
 
-    /// Adds a task to the end of this lane.
+    /// Adds a task to this lane, at its place in the lane's sort order, or
+    /// at the end if the lane is not sorted.
     ///
-    /// Note: This does not update the task's `lane` field. Use
-    /// [`KanbanBoard::move_task`] for proper task movement.
+    /// Note: This neither checks that the lane matches the task nor updates
+    /// the task's `lane` field. Use [`KanbanBoard::add_task`] and
+    /// [`KanbanBoard::move_task`] to place tasks.
     ///
     /// # Examples
     ///
@@ -255,7 +420,41 @@ impl Lane {
     /// assert_eq!(lane.len(), 1);
     /// ```
     pub fn add_task(&mut self, task: Task) {
-        self.tasks.push(task);
+        match self.definition.sort {
+            Some(sort) => {
+                let idx = self
+                    .tasks
+                    .partition_point(|other| sort.compare(other, &task).is_le());
+                self.tasks.insert(idx, task);
+            }
+            None => self.tasks.push(task),
+        }
+    }
+
+    /// Changes what the tasks of this lane are sorted by, and sorts them.
+    ///
+    /// With `None`, tasks stay in their current order and new tasks are
+    /// added at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{Lane, LaneKind, SortKey, Task, TaskState};
+    ///
+    /// let mut lane = Lane::new(LaneKind::Backlog);
+    /// lane.add_task(Task::new("Idle", "Description"));
+    /// let mut failed = Task::new("Failed", "Description");
+    /// failed.state = TaskState::Failed;
+    /// lane.add_task(failed);
+    ///
+    /// lane.set_sort(Some(SortKey::Severity));
+    /// assert_eq!(lane.tasks[0].title, "Failed");
+    /// ```
+    pub fn set_sort(&mut self, sort: Option<SortKey>) {
+        self.definition.sort = sort;
+        if let Some(sort) = sort {
+            self.tasks.sort_by(|a, b| sort.compare(a, b));
+        }
     }
 
     /// Removes and returns a task by ID, if found.
@@ -305,10 +504,11 @@ impl Lane {
     }
 }
 
-/// A Kanban board with four fixed lanes.
+/// A Kanban board with an ordered list of lanes.
 ///
-/// The board organizes tasks across workflow stages: Backlog, In Progress,
-/// Under Review, and Done.
+/// By default the board has the standard lanes: Backlog, In Progress,
+/// Under Review, and Done. Each task is shown in the first lane that
+/// [matches](LaneDefinition::matches) it.
 ///
 /// # Examples
 ///
@@ -324,8 +524,8 @@ impl Lane {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KanbanBoard {
-    /// The four lanes of the board, indexed by [`LaneKind::index`].
-    pub lanes: [Lane; 4],
+    /// The lanes of the board, in display order; never empty.
+    pub lanes: Vec<Lane>,
 }
 
 impl Default for KanbanBoard {
@@ -335,7 +535,7 @@ impl Default for KanbanBoard {
 }
 
 impl KanbanBoard {
-    /// Creates a new empty Kanban board with four lanes.
+    /// Creates a new empty Kanban board with the standard lanes.
     ///
     /// # Examples
     ///
@@ -347,54 +547,154 @@ impl KanbanBoard {
     /// ```
     #[must_use]
     pub fn new() -> Self {
+        Self::with_lanes(LaneDefinition::standard())
+    }
+
+    /// Creates a new empty Kanban board with the given lanes.
+    ///
+    /// An empty list gives the standard lanes, since a board needs at least
+    /// one lane.
+    ///
+    /// # Arguments
+    ///
+    /// * `definitions` - The lanes, in display order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, LaneDefinition, LaneKind, Task, TaskState};
+    ///
+    /// let mut board = KanbanBoard::with_lanes(vec![
+    ///     LaneDefinition::new("Open").with_kinds([LaneKind::Backlog, LaneKind::InProgress]),
+    ///     LaneDefinition::new("Failed").with_states([TaskState::Failed]),
+    ///     LaneDefinition::new("Closed"),
+    /// ]);
+    ///
+    /// let mut task = Task::new("Task", "Description");
+    /// task.lane = LaneKind::Done;
+    /// task.state = TaskState::Failed;
+    /// board.add_task(task);
+    /// assert_eq!(board.lanes[1].len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_lanes(definitions: Vec<LaneDefinition>) -> Self {
+        let definitions = if definitions.is_empty() {
+            LaneDefinition::standard()
+        } else {
+            definitions
+        };
         Self {
-            lanes: [
-                Lane::new(LaneKind::Backlog),
-                Lane::new(LaneKind::InProgress),
-                Lane::new(LaneKind::UnderReview),
-                Lane::new(LaneKind::Done),
-            ],
+            lanes: definitions.into_iter().map(Lane::with_definition).collect(),
         }
     }
 
-    /// Returns a reference to the lane of the specified kind.
+    /// Replaces the lanes of the board, placing every task again.
+    ///
+    /// An empty list gives the standard lanes. Tasks keep their relative
+    /// order.
+    ///
+    /// # Arguments
+    ///
+    /// * `definitions` - The new lanes, in display order
     ///
     /// # Examples
     ///
     /// ```
-    /// use whip_protocol::{KanbanBoard, LaneKind};
+    /// use whip_protocol::{KanbanBoard, LaneDefinition, Task};
+    ///
+    /// let mut board = KanbanBoard::new();
+    /// board.add_task(Task::new("Task", "Description"));
+    ///
+    /// board.set_lanes(vec![LaneDefinition::new("All")]);
+    /// assert_eq!(board.lanes.len(), 1);
+    /// assert_eq!(board.lanes[0].len(), 1);
+    /// ```
+    pub fn set_lanes(&mut self, definitions: Vec<LaneDefinition>) {
+        let old = std::mem::replace(self, Self::with_lanes(definitions));
+        for task in old.lanes.into_iter().flat_map(|lane| lane.tasks) {
+            self.add_task(task);
+        }
+    }
+
+    /// Returns the lane definitions of the board, in display order.
+    #[must_use]
+    pub fn lane_definitions(&self) -> Vec<LaneDefinition> {
+        self.lanes
+            .iter()
+            .map(|lane| lane.definition.clone())
+            .collect()
+    }
+
+    /// Returns a reference to the lane at the given position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::KanbanBoard;
     ///
     /// let board = KanbanBoard::new();
-    /// let backlog = board.lane(LaneKind::Backlog);
-    /// assert_eq!(backlog.kind, LaneKind::Backlog);
+    /// assert_eq!(board.lane(0).unwrap().title(), "Backlog");
+    /// assert!(board.lane(4).is_none());
     /// ```
     #[must_use]
-    pub fn lane(&self, kind: LaneKind) -> &Lane {
-        &self.lanes[kind.index()]
+    pub fn lane(&self, index: usize) -> Option<&Lane> {
+        self.lanes.get(index)
     }
 
-    /// Returns a mutable reference to the lane of the specified kind.
+    /// Returns a mutable reference to the lane at the given position.
     #[must_use]
-    pub fn lane_mut(&mut self, kind: LaneKind) -> &mut Lane {
-        &mut self.lanes[kind.index()]
+    pub fn lane_mut(&mut self, index: usize) -> Option<&mut Lane> {
+        self.lanes.get_mut(index)
     }
 
-    /// Adds a task to its designated lane based on `task.lane`.
+    /// Returns the position of the lane a task belongs in.
+    ///
+    /// That is the first lane matching the task. A task no lane matches
+    /// goes to the first lane holding its workflow stage, or else to the
+    /// first lane, so that no task is ever hidden.
     ///
     /// # Examples
     ///
     /// ```
     /// use whip_protocol::{KanbanBoard, LaneKind, Task};
     ///
+    /// let board = KanbanBoard::new();
+    /// let mut task = Task::new("Task", "Description");
+    /// task.lane = LaneKind::UnderReview;
+    /// assert_eq!(board.lane_index_for(&task), 2);
+    /// ```
+    #[must_use]
+    pub fn lane_index_for(&self, task: &Task) -> usize {
+        self.lanes
+            .iter()
+            .position(|lane| lane.definition.matches(task))
+            .or_else(|| {
+                self.lanes
+                    .iter()
+                    .position(|lane| lane.definition.kinds.contains(&task.lane))
+            })
+            .unwrap_or(0)
+    }
+
+    /// Adds a task to the end of the lane it belongs in.
+    ///
+    /// See [`lane_index_for`](Self::lane_index_for) for how the lane is
+    /// chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, Task};
+    ///
     /// let mut board = KanbanBoard::new();
     /// let task = Task::new("Task", "Description");
     ///
     /// board.add_task(task);
-    /// assert_eq!(board.lane(LaneKind::Backlog).len(), 1);
+    /// assert_eq!(board.lanes[0].len(), 1);
     /// ```
     pub fn add_task(&mut self, task: Task) {
-        let lane_kind = task.lane;
-        self.lane_mut(lane_kind).add_task(task);
+        let index = self.lane_index_for(&task);
+        self.lanes[index].add_task(task);
     }
 
     /// Finds and returns a reference to a task by ID across all lanes.
@@ -422,7 +722,8 @@ impl KanbanBoard {
         self.lanes.iter_mut().find_map(|lane| lane.get_task_mut(id))
     }
 
-    /// Moves a task from its current lane to a new lane.
+    /// Moves a task to a new workflow stage, and to the lane it then
+    /// belongs in.
     ///
     /// Returns `true` if the task was found and moved, `false` otherwise.
     ///
@@ -438,27 +739,16 @@ impl KanbanBoard {
     ///
     /// assert!(board.move_task(id, LaneKind::InProgress));
     /// assert_eq!(board.get_task(id).unwrap().lane, LaneKind::InProgress);
+    /// assert_eq!(board.lanes[1].len(), 1);
     /// ```
     pub fn move_task(&mut self, id: TaskId, to_lane: LaneKind) -> bool {
-        // Find which lane contains the task
-        let from_lane = self
-            .lanes
-            .iter()
-            .find(|lane| lane.get_task(id).is_some())
-            .map(|lane| lane.kind);
-
-        let Some(from_kind) = from_lane else {
+        let Some(mut task) = self.remove_task(id) else {
             return false;
         };
 
-        // Remove from current lane
-        let Some(mut task) = self.lane_mut(from_kind).remove_task(id) else {
-            return false;
-        };
-
-        // Update task's lane field and add to new lane
+        // Update task's lane field and add it to the lane it now belongs in
         task.move_to_lane(to_lane);
-        self.lane_mut(to_lane).add_task(task);
+        self.add_task(task);
 
         true
     }
@@ -508,6 +798,28 @@ impl KanbanBoard {
         }
         None
     }
+
+    /// Retains only the tasks for which the predicate returns `true`.
+    ///
+    /// Tasks are visited lane by lane, preserving their relative order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, Task};
+    ///
+    /// let mut board = KanbanBoard::new();
+    /// board.add_task(Task::new("Keep", "Description"));
+    /// board.add_task(Task::new("Drop", "Description"));
+    ///
+    /// board.retain_tasks(|task| task.title == "Keep");
+    /// assert_eq!(board.total_tasks(), 1);
+    /// ```
+    pub fn retain_tasks(&mut self, mut predicate: impl FnMut(&Task) -> bool) {
+        for lane in &mut self.lanes {
+            lane.tasks.retain(|task| predicate(task));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -590,7 +902,7 @@ mod tests {
         let json = serde_json::to_string(&lane).expect("serialize");
         let parsed: Lane = serde_json::from_str(&json).expect("deserialize");
 
-        assert_eq!(lane.kind, parsed.kind);
+        assert_eq!(lane.definition, parsed.definition);
         assert_eq!(lane.len(), parsed.len());
     }
 
@@ -599,8 +911,8 @@ mod tests {
         let board = KanbanBoard::new();
         assert_eq!(board.lanes.len(), 4);
 
-        for (i, lane) in board.lanes.iter().enumerate() {
-            assert_eq!(lane.kind.index(), i);
+        for (lane, kind) in board.lanes.iter().zip(LaneKind::all()) {
+            assert_eq!(lane.definition, LaneDefinition::for_kind(kind));
             assert!(lane.is_empty());
         }
     }
@@ -615,7 +927,7 @@ mod tests {
 
         assert_eq!(board.total_tasks(), 1);
         assert!(board.get_task(id).is_some());
-        assert_eq!(board.lane(LaneKind::Backlog).len(), 1);
+        assert_eq!(board.lanes[0].len(), 1);
     }
 
     #[test]
@@ -626,8 +938,8 @@ mod tests {
         board.add_task(task);
 
         assert!(board.move_task(id, LaneKind::InProgress));
-        assert_eq!(board.lane(LaneKind::Backlog).len(), 0);
-        assert_eq!(board.lane(LaneKind::InProgress).len(), 1);
+        assert_eq!(board.lanes[0].len(), 0);
+        assert_eq!(board.lanes[1].len(), 1);
 
         let task = board.get_task(id).expect("task should exist");
         assert_eq!(task.lane, LaneKind::InProgress);
@@ -653,6 +965,192 @@ mod tests {
         assert_eq!(board.total_tasks(), 0);
     }
 
+    #[test]
+    fn kanban_board_retain_tasks_across_lanes() {
+        let mut board = KanbanBoard::new();
+        board.add_task(Task::new("Keep", "Description"));
+        let mut done = Task::new("Drop", "Description");
+        done.lane = LaneKind::Done;
+        board.add_task(done);
+        let mut kept_done = Task::new("Keep", "Description");
+        kept_done.lane = LaneKind::Done;
+        board.add_task(kept_done);
+
+        board.retain_tasks(|task| task.title == "Keep");
+
+        assert_eq!(board.total_tasks(), 2);
+        assert_eq!(board.lanes[0].len(), 1);
+        assert_eq!(board.lanes[3].len(), 1);
+    }
+
+    fn failed_task() -> Task {
+        let mut task = Task::new("Failed", "Description");
+        task.lane = LaneKind::Done;
+        task.state = TaskState::Failed;
+        task
+    }
+
+    #[test]
+    fn lane_definition_matches_every_filter() {
+        let lane = LaneDefinition::new("Blocked")
+            .with_kinds([LaneKind::InProgress])
+            .with_states([TaskState::NeedsAttention])
+            .with_labels(["Blocked"]);
+
+        let mut task = Task::new("Task", "Description");
+        task.lane = LaneKind::InProgress;
+        task.state = TaskState::NeedsAttention;
+        assert!(!lane.matches(&task), "task without labels");
+
+        task.github = Some(crate::GitHubSource {
+            host: None,
+            owner: "org".into(),
+            repo: "repo".into(),
+            number: 1,
+            url: String::new(),
+            labels: vec!["blocked".into()],
+            author: String::new(),
+            comment_count: 0,
+        });
+        assert!(lane.matches(&task));
+
+        task.state = TaskState::InFlight;
+        assert!(!lane.matches(&task));
+
+        assert!(LaneDefinition::new("All").matches(&task));
+    }
+
+    #[test]
+    fn kanban_board_places_tasks_in_first_matching_lane() {
+        let mut board = KanbanBoard::with_lanes(vec![
+            LaneDefinition::for_kind(LaneKind::Backlog),
+            LaneDefinition::new("Failed").with_states([TaskState::Failed]),
+            LaneDefinition::for_kind(LaneKind::Done),
+        ]);
+        board.add_task(failed_task());
+        let mut done = Task::new("Done", "Description");
+        done.lane = LaneKind::Done;
+        board.add_task(done);
+
+        assert_eq!(board.lanes[1].tasks[0].title, "Failed");
+        assert_eq!(board.lanes[2].tasks[0].title, "Done");
+    }
+
+    #[test]
+    fn kanban_board_falls_back_for_unmatched_tasks() {
+        let board = KanbanBoard::with_lanes(vec![
+            LaneDefinition::new("Started").with_states([TaskState::InFlight]),
+            LaneDefinition::new("Finished")
+                .with_kinds([LaneKind::Done])
+                .with_states([TaskState::Success]),
+        ]);
+
+        // Same stage as the "Finished" lane
+        assert_eq!(board.lane_index_for(&failed_task()), 1);
+        // Nothing in common with any lane
+        assert_eq!(board.lane_index_for(&Task::new("Idle", "Description")), 0);
+    }
+
+    #[test]
+    fn kanban_board_without_lanes_uses_standard_lanes() {
+        let board = KanbanBoard::with_lanes(Vec::new());
+        assert_eq!(board.lane_definitions(), LaneDefinition::standard());
+    }
+
+    #[test]
+    fn kanban_board_set_lanes_places_tasks_again() {
+        let mut board = KanbanBoard::new();
+        board.add_task(Task::new("First", "Description"));
+        board.add_task(failed_task());
+        board.add_task(Task::new("Second", "Description"));
+
+        board.set_lanes(vec![
+            LaneDefinition::new("Failed").with_states([TaskState::Failed]),
+            LaneDefinition::new("Other"),
+        ]);
+
+        assert_eq!(board.total_tasks(), 3);
+        assert_eq!(board.lanes[0].tasks[0].title, "Failed");
+        let others: Vec<_> = board.lanes[1].tasks.iter().map(|t| &t.title).collect();
+        assert_eq!(others, ["First", "Second"]);
+    }
+
+    #[test]
+    fn kanban_board_move_task_follows_lane_filters() {
+        let mut board = KanbanBoard::with_lanes(vec![
+            LaneDefinition::new("Open").with_kinds([LaneKind::Backlog, LaneKind::InProgress]),
+            LaneDefinition::new("Closed").with_kinds([LaneKind::Done]),
+        ]);
+        let task = Task::new("Task", "Description");
+        let id = task.id;
+        board.add_task(task);
+
+        assert!(board.move_task(id, LaneKind::InProgress));
+        assert_eq!(board.lanes[0].len(), 1);
+
+        assert!(board.move_task(id, LaneKind::Done));
+        assert_eq!(board.lanes[1].len(), 1);
+    }
+
+    #[test]
+    fn lane_definition_json_format() {
+        let lane: LaneDefinition = serde_json::from_str(
+            r#"{"title": "Blocked", "kinds": ["in_progress"], "states": ["needs_attention"]}"#,
+        )
+        .expect("deserialize");
+        assert_eq!(
+            lane,
+            LaneDefinition::new("Blocked")
+                .with_kinds([LaneKind::InProgress])
+                .with_states([TaskState::NeedsAttention])
+        );
+
+        let json = serde_json::to_string(&LaneDefinition::new("All")).expect("serialize");
+        assert_eq!(json, r#"{"title":"All"}"#);
+
+        let lane: LaneDefinition =
+            serde_json::from_str(r#"{"title": "Open", "sort": "updated_at"}"#).expect("sorted");
+        assert_eq!(lane.sort, Some(SortKey::UpdatedAt));
+    }
+
+    fn task_in_state(title: &str, state: TaskState) -> Task {
+        let mut task = Task::new(title, "Description");
+        task.state = state;
+        task
+    }
+
+    #[test]
+    fn sorted_lane_inserts_tasks_in_order() {
+        let mut board = KanbanBoard::with_lanes(vec![
+            LaneDefinition::new("All").with_sort(SortKey::Severity),
+        ]);
+        board.add_task(task_in_state("Done", TaskState::Success));
+        board.add_task(task_in_state("Failed", TaskState::Failed));
+        board.add_task(task_in_state("Idle", TaskState::Idle));
+
+        let titles: Vec<_> = board.lanes[0]
+            .tasks
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Failed", "Idle", "Done"]);
+    }
+
+    #[test]
+    fn lane_set_sort_keeps_order_when_unsorted() {
+        let mut lane = Lane::new(LaneKind::Backlog);
+        lane.add_task(task_in_state("Idle", TaskState::Idle));
+        lane.add_task(task_in_state("Failed", TaskState::Failed));
+
+        lane.set_sort(Some(SortKey::Severity));
+        assert_eq!(lane.tasks[0].title, "Failed");
+
+        lane.set_sort(None);
+        lane.add_task(task_in_state("Attention", TaskState::NeedsAttention));
+        assert_eq!(lane.tasks[0].title, "Failed");
+        assert_eq!(lane.tasks[2].title, "Attention");
+    }
+
     #[test]
     fn kanban_board_serialization_roundtrip() {
         let mut board = KanbanBoard::new();
@@ -0,0 +1,233 @@
+//! Differences between two versions of a board.
+//!
+//! A [`BoardDiff`] tells what a refresh changed: which tasks appeared,
+//! disappeared, moved to another lane, or were updated (a newer
+//! `updated_at`, or a different comment count).
+
+use std::collections::{HashMap, HashSet};
+
+use crate::board::KanbanBoard;
+use crate::task::{Task, TaskId};
+
+/// How a task changed between two versions of a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskChange {
+    /// The task is new on the board.
+    Added,
+    /// The task is shown in another lane.
+    Moved,
+    /// The task was updated or commented on, and stayed in its lane.
+    Updated,
+}
+
+impl TaskChange {
+    /// Returns a short name for the change, as shown on task cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::TaskChange;
+    ///
+    /// assert_eq!(TaskChange::Added.display_name(), "new");
+    /// ```
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Added => "new",
+            Self::Moved => "moved",
+            Self::Updated => "updated",
+        }
+    }
+}
+
+/// The tasks that changed between two versions of a board.
+///
+/// Each list holds task IDs in the order of the board they come from.
+/// A task that moved and was updated counts as moved.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{BoardDiff, KanbanBoard, LaneKind, Task};
+///
+/// let kept = Task::new("Kept", "Description");
+/// let mut before = KanbanBoard::new();
+/// before.add_task(kept.clone());
+///
+/// let mut after = before.clone();
+/// after.move_task(kept.id, LaneKind::Done);
+/// let added = Task::new("Added", "Description");
+/// after.add_task(added.clone());
+///
+/// let diff = BoardDiff::between(&before, &after);
+/// assert_eq!(diff.added, [added.id]);
+/// assert_eq!(diff.moved, [kept.id]);
+/// assert!(diff.removed.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardDiff {
+    /// Tasks only on the new board.
+    pub added: Vec<TaskId>,
+    /// Tasks only on the old board.
+    pub removed: Vec<TaskId>,
+    /// Tasks shown in another lane on the new board.
+    pub moved: Vec<TaskId>,
+    /// Tasks updated or commented on since the old board.
+    pub updated: Vec<TaskId>,
+}
+
+impl BoardDiff {
+    /// Compares two versions of a board.
+    ///
+    /// Lanes are compared by title, so a task counts as moved when the lane
+    /// showing it has another title.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The board before the refresh
+    /// * `new` - The board after the refresh
+    #[must_use]
+    pub fn between(old: &KanbanBoard, new: &KanbanBoard) -> Self {
+        let previous: HashMap<TaskId, (&str, &Task)> = placed_tasks(old)
+            .map(|(lane, task)| (task.id, (lane, task)))
+            .collect();
+
+        let mut diff = Self::default();
+        let mut seen = HashSet::new();
+        for (lane, task) in placed_tasks(new) {
+            seen.insert(task.id);
+            match previous.get(&task.id) {
+                None => diff.added.push(task.id),
+                Some((old_lane, _)) if *old_lane != lane => diff.moved.push(task.id),
+                Some((_, old_task)) if was_updated(old_task, task) => diff.updated.push(task.id),
+                Some(_) => {}
+            }
+        }
+        diff.removed = placed_tasks(old)
+            .map(|(_, task)| task.id)
+            .filter(|id| !seen.contains(id))
+            .collect();
+        diff
+    }
+
+    /// Returns `true` if nothing changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.updated.is_empty()
+    }
+
+    /// Returns how a task of the new board changed, if it did.
+    #[must_use]
+    pub fn change(&self, id: TaskId) -> Option<TaskChange> {
+        if self.added.contains(&id) {
+            Some(TaskChange::Added)
+        } else if self.moved.contains(&id) {
+            Some(TaskChange::Moved)
+        } else if self.updated.contains(&id) {
+            Some(TaskChange::Updated)
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterates over the tasks of a board, with the title of their lane.
+fn placed_tasks(board: &KanbanBoard) -> impl Iterator<Item = (&str, &Task)> {
+    board
+        .lanes
+        .iter()
+        .flat_map(|lane| lane.tasks.iter().map(move |task| (lane.title(), task)))
+}
+
+fn was_updated(old: &Task, new: &Task) -> bool {
+    let comments = |task: &Task| task.github.as_ref().map(|github| github.comment_count);
+    new.updated_at > old.updated_at || comments(new) != comments(old)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::GitHubSource;
+    use chrono::Duration;
+
+    fn issue(title: &str) -> Task {
+        let mut task = Task::new(title, "Description");
+        task.github = Some(GitHubSource {
+            host: None,
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            number: 1,
+            url: "https://github.com/owner/repo/issues/1".to_string(),
+            labels: vec![],
+            author: "author".to_string(),
+            comment_count: 0,
+        });
+        task
+    }
+
+    fn board(tasks: &[&Task]) -> KanbanBoard {
+        let mut board = KanbanBoard::new();
+        for task in tasks {
+            board.add_task((*task).clone());
+        }
+        board
+    }
+
+    #[test]
+    fn identical_boards_have_no_changes() {
+        let task = issue("Task");
+        let diff = BoardDiff::between(&board(&[&task]), &board(&[&task]));
+        assert!(diff.is_empty());
+        assert_eq!(diff.change(task.id), None);
+    }
+
+    #[test]
+    fn detects_removed_tasks() {
+        let gone = issue("Gone");
+        let kept = issue("Kept");
+        let diff = BoardDiff::between(&board(&[&gone, &kept]), &board(&[&kept]));
+        assert_eq!(diff.removed, [gone.id]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn detects_updates_and_comments() {
+        let edited = issue("Edited");
+        let commented = issue("Commented");
+        let older = issue("Older");
+
+        let mut edited_after = edited.clone();
+        edited_after.updated_at += Duration::minutes(5);
+        let mut commented_after = commented.clone();
+        commented_after
+            .github
+            .as_mut()
+            .expect("github")
+            .comment_count = 3;
+        let mut older_after = older.clone();
+        older_after.updated_at -= Duration::minutes(5);
+
+        let diff = BoardDiff::between(
+            &board(&[&edited, &commented, &older]),
+            &board(&[&edited_after, &commented_after, &older_after]),
+        );
+        assert_eq!(diff.updated, [edited.id, commented.id]);
+        assert_eq!(diff.change(commented.id), Some(TaskChange::Updated));
+    }
+
+    #[test]
+    fn moved_takes_precedence_over_updated() {
+        let task = issue("Task");
+        let mut after = task.clone();
+        after.lane = crate::LaneKind::InProgress;
+        after.updated_at += Duration::minutes(5);
+
+        let diff = BoardDiff::between(&board(&[&task]), &board(&[&after]));
+        assert_eq!(diff.moved, [task.id]);
+        assert!(diff.updated.is_empty());
+        assert_eq!(diff.change(task.id), Some(TaskChange::Moved));
+    }
+}
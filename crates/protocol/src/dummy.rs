@@ -78,10 +78,10 @@ impl TaskBuilder {
 /// let board = dummy_board();
 ///
 /// // Check task distribution
-/// assert_eq!(board.lane(LaneKind::Backlog).len(), 3);
-/// assert_eq!(board.lane(LaneKind::InProgress).len(), 2);
-/// assert_eq!(board.lane(LaneKind::UnderReview).len(), 1);
-/// assert_eq!(board.lane(LaneKind::Done).len(), 2);
+/// assert_eq!(board.lanes[LaneKind::Backlog.index()].len(), 3);
+/// assert_eq!(board.lanes[LaneKind::InProgress.index()].len(), 2);
+/// assert_eq!(board.lanes[LaneKind::UnderReview.index()].len(), 1);
+/// assert_eq!(board.lanes[LaneKind::Done.index()].len(), 2);
 /// ```
 #[must_use]
 pub fn dummy_board() -> KanbanBoard {
@@ -321,10 +321,10 @@ mod tests {
     fn dummy_board_has_tasks_in_all_lanes() {
         let board = dummy_board();
 
-        assert_eq!(board.lane(LaneKind::Backlog).len(), 3);
-        assert_eq!(board.lane(LaneKind::InProgress).len(), 2);
-        assert_eq!(board.lane(LaneKind::UnderReview).len(), 1);
-        assert_eq!(board.lane(LaneKind::Done).len(), 2);
+        assert_eq!(board.lanes[LaneKind::Backlog.index()].len(), 3);
+        assert_eq!(board.lanes[LaneKind::InProgress.index()].len(), 2);
+        assert_eq!(board.lanes[LaneKind::UnderReview.index()].len(), 1);
+        assert_eq!(board.lanes[LaneKind::Done.index()].len(), 2);
     }
 
     #[test]
@@ -332,13 +332,13 @@ mod tests {
         let board = dummy_board();
 
         // Check Backlog tasks are Idle
-        for task in &board.lane(LaneKind::Backlog).tasks {
+        for task in &board.lanes[LaneKind::Backlog.index()].tasks {
             assert_eq!(task.state, TaskState::Idle);
             assert_eq!(task.lane, LaneKind::Backlog);
         }
 
         // Check In Progress has both InFlight and NeedsAttention
-        let in_progress = &board.lane(LaneKind::InProgress).tasks;
+        let in_progress = &board.lanes[LaneKind::InProgress.index()].tasks;
         assert!(in_progress.iter().any(|t| t.state == TaskState::InFlight));
         assert!(
             in_progress
@@ -347,11 +347,11 @@ mod tests {
         );
 
         // Check Under Review task is InFlight
-        let under_review = &board.lane(LaneKind::UnderReview).tasks;
+        let under_review = &board.lanes[LaneKind::UnderReview.index()].tasks;
         assert!(under_review.iter().all(|t| t.state == TaskState::InFlight));
 
         // Check Done has both Success and Failed
-        let done = &board.lane(LaneKind::Done).tasks;
+        let done = &board.lanes[LaneKind::Done.index()].tasks;
         assert!(done.iter().any(|t| t.state == TaskState::Success));
         assert!(done.iter().any(|t| t.state == TaskState::Failed));
     }
@@ -360,8 +360,7 @@ mod tests {
     fn dummy_board_task_lanes_match_lane_kind() {
         let board = dummy_board();
 
-        for lane_kind in LaneKind::all() {
-            let lane = board.lane(lane_kind);
+        for (lane, lane_kind) in board.lanes.iter().zip(LaneKind::all()) {
             for task in &lane.tasks {
                 assert_eq!(
                     task.lane, lane_kind,
@@ -27,6 +27,14 @@ pub enum ProtocolError {
     /// A task title was empty or invalid.
     #[error("invalid task title: title cannot be empty")]
     InvalidTaskTitle,
+
+    /// A filter expression could not be parsed.
+    #[error("invalid filter {0}")]
+    InvalidFilter(String),
+
+    /// A key chord could not be parsed.
+    #[error("invalid key chord '{0}'")]
+    InvalidKeyChord(String),
 }
 
 /// A specialized Result type for protocol operations.
@@ -0,0 +1,77 @@
+//! Classification of failures when fetching data from remote sources.
+//!
+//! Fetch errors originate in backend crates (e.g. `whip-github`) but are
+//! displayed by the TUI. [`FetchErrorKind`] is the shared vocabulary that
+//! lets the TUI group and describe failures without depending on backend
+//! error types.
+
+use serde::{Deserialize, Serialize};
+
+/// The broad category of a failed fetch.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::FetchErrorKind;
+///
+/// assert_eq!(FetchErrorKind::RateLimit.label(), "rate limit");
+/// assert_eq!(FetchErrorKind::Auth.to_string(), "auth");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchErrorKind {
+    /// Missing, invalid, or insufficiently scoped credentials.
+    Auth,
+    /// The remote API rate limit was exceeded.
+    RateLimit,
+    /// The remote could not be reached (DNS, connection, timeout).
+    Network,
+    /// A response or cached payload could not be parsed.
+    Parse,
+    /// Any other failure.
+    Other,
+}
+
+impl FetchErrorKind {
+    /// Returns a short human-readable label for this kind.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::RateLimit => "rate limit",
+            Self::Network => "network",
+            Self::Parse => "parse",
+            Self::Other => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for FetchErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_distinct() {
+        let kinds = [
+            FetchErrorKind::Auth,
+            FetchErrorKind::RateLimit,
+            FetchErrorKind::Network,
+            FetchErrorKind::Parse,
+            FetchErrorKind::Other,
+        ];
+        let labels: std::collections::HashSet<_> = kinds.iter().map(|k| k.label()).collect();
+        assert_eq!(labels.len(), kinds.len());
+    }
+
+    #[test]
+    fn serialization_uses_snake_case() {
+        let json = serde_json::to_string(&FetchErrorKind::RateLimit).expect("serialize");
+        assert_eq!(json, r#""rate_limit""#);
+    }
+}
@@ -0,0 +1,406 @@
+//! Task filters, narrowing the board to some repositories, labels, authors,
+//! or states.
+//!
+//! A [`TaskFilter`] combines criteria: a task is shown when it matches
+//! every non-empty criterion, and a criterion matches when any of its
+//! values does. Filters can be written as short expressions, which is how
+//! they are edited in the TUI:
+//!
+//! ```text
+//! repo:acme/api,acme/web state:needs_attention author:octocat label:bug
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_protocol::{GitHubSource, Task, TaskFilter, TaskState};
+//!
+//! let filter: TaskFilter = "repo:acme/api state:needs_attention".parse().unwrap();
+//!
+//! let mut task = Task::new("Fix login bug", "Users couldn't log in");
+//! task.state = TaskState::NeedsAttention;
+//! task.github = Some(GitHubSource {
+//!     host: None,
+//!     owner: "acme".into(),
+//!     repo: "api".into(),
+//!     number: 42,
+//!     url: "https://github.com/acme/api/issues/42".into(),
+//!     labels: vec![],
+//!     author: "octocat".into(),
+//!     comment_count: 0,
+//! });
+//! assert!(filter.matches(&task));
+//!
+//! task.state = TaskState::Idle;
+//! assert!(!filter.matches(&task));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ProtocolError;
+use crate::task::{Task, TaskState};
+
+/// Criteria narrowing the tasks shown on the board.
+///
+/// Values are compared ignoring case. A task without a GitHub source never
+/// matches a repository, label, or author criterion.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{TaskFilter, TaskState};
+///
+/// let filter = TaskFilter::default()
+///     .with_repositories(["acme/api"])
+///     .with_states([TaskState::Failed]);
+/// assert_eq!(filter.to_string(), "repo:acme/api state:failed");
+/// assert!(TaskFilter::default().is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(
+    description = "Criteria narrowing the tasks shown on the board. A task is shown when it matches every non-empty criterion, and a criterion matches when any of its values does."
+)]
+pub struct TaskFilter {
+    /// Repositories to show, as `owner/repo`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repositories: Vec<String>,
+    /// Labels to show; a task matches when it has any of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Authors to show, as GitHub logins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    /// Task states to show.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub states: Vec<TaskState>,
+}
+
+impl TaskFilter {
+    /// Adds repositories (as `owner/repo`) to show.
+    #[must_use]
+    pub fn with_repositories<S: Into<String>>(
+        mut self,
+        repositories: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.repositories
+            .extend(repositories.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds labels to show.
+    #[must_use]
+    pub fn with_labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        self.labels.extend(labels.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds authors to show.
+    #[must_use]
+    pub fn with_authors<S: Into<String>>(mut self, authors: impl IntoIterator<Item = S>) -> Self {
+        self.authors.extend(authors.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds task states to show.
+    #[must_use]
+    pub fn with_states(mut self, states: impl IntoIterator<Item = TaskState>) -> Self {
+        self.states.extend(states);
+        self
+    }
+
+    /// Returns `true` if the filter has no criteria, and so shows every task.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.repositories.is_empty()
+            && self.labels.is_empty()
+            && self.authors.is_empty()
+            && self.states.is_empty()
+    }
+
+    /// Returns `true` if the task matches every criterion of the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task to check
+    #[must_use]
+    pub fn matches(&self, task: &Task) -> bool {
+        if !self.states.is_empty() && !self.states.contains(&task.state) {
+            return false;
+        }
+        if self.repositories.is_empty() && self.labels.is_empty() && self.authors.is_empty() {
+            return true;
+        }
+        let Some(github) = &task.github else {
+            return false;
+        };
+
+        let repository = format!("{}/{}", github.owner, github.repo);
+        let any = |values: &[String], actual: &str| {
+            values.is_empty() || values.iter().any(|v| v.eq_ignore_ascii_case(actual))
+        };
+        any(&self.repositories, &repository)
+            && any(&self.authors, &github.author)
+            && (self.labels.is_empty()
+                || github.labels.iter().any(|label| any(&self.labels, label)))
+    }
+}
+
+/// Parses a filter expression.
+///
+/// An expression is a list of `key:value` terms separated by whitespace,
+/// where `key` is one of `repo`, `label`, `author` or `state`, and `value`
+/// may list several values separated by commas. Repeating a key adds values.
+/// States are written in snake case (`needs_attention`); dashes are
+/// accepted too.
+///
+/// # Errors
+///
+/// Returns [`ProtocolError::InvalidFilter`] for terms without a value, with
+/// an unknown key, or naming an unknown state.
+impl FromStr for TaskFilter {
+    type Err = ProtocolError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+        for term in expression.split_whitespace() {
+            let invalid =
+                |reason: &str| ProtocolError::InvalidFilter(format!("'{term}': {reason}"));
+            let Some((key, values)) = term.split_once(':') else {
+                return Err(invalid("expected key:value"));
+            };
+            let values: Vec<&str> = values.split(',').filter(|v| !v.is_empty()).collect();
+            if values.is_empty() {
+                return Err(invalid("missing value"));
+            }
+            match key.to_ascii_lowercase().as_str() {
+                "repo" => filter = filter.with_repositories(values),
+                "label" => filter = filter.with_labels(values),
+                "author" => filter = filter.with_authors(values),
+                "state" => {
+                    for value in values {
+                        let state = parse_state(value).ok_or_else(|| invalid("unknown state"))?;
+                        filter.states.push(state);
+                    }
+                }
+                _ => {
+                    return Err(invalid(
+                        "unknown key, expected repo, label, author or state",
+                    ));
+                }
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Parses a task state from its snake case name.
+fn parse_state(value: &str) -> Option<TaskState> {
+    let name = value.to_ascii_lowercase().replace('-', "_");
+    serde_json::from_value(serde_json::Value::String(name)).ok()
+}
+
+/// Formats a task state as its snake case name.
+fn state_name(state: TaskState) -> String {
+    match serde_json::to_value(state) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => format!("{state:?}"),
+    }
+}
+
+/// Writes the filter as an expression that parses back to it.
+impl fmt::Display for TaskFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let states: Vec<String> = self.states.iter().copied().map(state_name).collect();
+        let terms = [
+            ("repo", self.repositories.join(",")),
+            ("label", self.labels.join(",")),
+            ("author", self.authors.join(",")),
+            ("state", states.join(",")),
+        ];
+        let mut first = true;
+        for (key, values) in terms.iter().filter(|(_, values)| !values.is_empty()) {
+            if !first {
+                f.write_str(" ")?;
+            }
+            write!(f, "{key}:{values}")?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// A filter saved under a name, so it can be picked from the filter bar.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{NamedFilter, TaskFilter};
+///
+/// let filter = NamedFilter::new("api", TaskFilter::default().with_repositories(["acme/api"]));
+/// let json = serde_json::to_string(&filter).unwrap();
+/// assert_eq!(json, r#"{"name":"api","repositories":["acme/api"]}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "A board filter saved under a name.")]
+pub struct NamedFilter {
+    /// The name of the filter, shown in the header while it is active.
+    pub name: String,
+    /// The criteria of the filter.
+    #[serde(flatten)]
+    pub filter: TaskFilter,
+}
+
+impl NamedFilter {
+    /// Creates a named filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the filter
+    /// * `filter` - The criteria of the filter
+    #[must_use]
+    pub fn new(name: impl Into<String>, filter: TaskFilter) -> Self {
+        Self {
+            name: name.into(),
+            filter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::GitHubSource;
+
+    fn github_task(repo: &str, author: &str, labels: &[&str], state: TaskState) -> Task {
+        let (owner, name) = repo.split_once('/').expect("owner/repo");
+        let mut task = Task::new("Task", "Description");
+        task.state = state;
+        task.github = Some(GitHubSource {
+            host: None,
+            owner: owner.to_string(),
+            repo: name.to_string(),
+            number: 1,
+            url: format!("https://github.com/{repo}/issues/1"),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            author: author.to_string(),
+            comment_count: 0,
+        });
+        task
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = TaskFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches(&Task::new("Local", "Task")));
+        assert!(filter.matches(&github_task("a/b", "c", &[], TaskState::Idle)));
+    }
+
+    #[test]
+    fn criteria_are_combined() {
+        let filter = TaskFilter::default()
+            .with_repositories(["acme/api", "acme/web"])
+            .with_states([TaskState::NeedsAttention]);
+
+        assert!(filter.matches(&github_task(
+            "acme/web",
+            "a",
+            &[],
+            TaskState::NeedsAttention
+        )));
+        assert!(!filter.matches(&github_task("acme/web", "a", &[], TaskState::Idle)));
+        assert!(!filter.matches(&github_task(
+            "acme/cli",
+            "a",
+            &[],
+            TaskState::NeedsAttention
+        )));
+    }
+
+    #[test]
+    fn values_ignore_case() {
+        let filter = TaskFilter::default()
+            .with_repositories(["ACME/Api"])
+            .with_authors(["OctoCat"])
+            .with_labels(["BUG"]);
+        assert!(filter.matches(&github_task(
+            "acme/api",
+            "octocat",
+            &["feature", "bug"],
+            TaskState::Idle
+        )));
+        assert!(!filter.matches(&github_task(
+            "acme/api",
+            "octocat",
+            &["feature"],
+            TaskState::Idle
+        )));
+    }
+
+    #[test]
+    fn github_criteria_skip_local_tasks() {
+        let filter = TaskFilter::default().with_authors(["octocat"]);
+        assert!(!filter.matches(&Task::new("Local", "Task")));
+
+        let filter = TaskFilter::default().with_states([TaskState::Idle]);
+        assert!(filter.matches(&Task::new("Local", "Task")));
+    }
+
+    #[test]
+    fn parse_expression() {
+        let filter: TaskFilter = "repo:acme/api,acme/web  state:needs-attention label:bug repo:x/y"
+            .parse()
+            .expect("valid expression");
+        assert_eq!(filter.repositories, vec!["acme/api", "acme/web", "x/y"]);
+        assert_eq!(filter.labels, vec!["bug"]);
+        assert_eq!(filter.states, vec![TaskState::NeedsAttention]);
+        assert!(filter.authors.is_empty());
+
+        let empty: TaskFilter = "   ".parse().expect("valid expression");
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_terms() {
+        for expression in ["acme/api", "repo:", "owner:acme", "state:blocked"] {
+            let err = expression.parse::<TaskFilter>().expect_err(expression);
+            assert!(matches!(err, ProtocolError::InvalidFilter(_)), "{err}");
+        }
+    }
+
+    #[test]
+    fn display_roundtrips() {
+        let filter = TaskFilter::default()
+            .with_states([TaskState::InFlight, TaskState::Failed])
+            .with_authors(["octocat"])
+            .with_labels(["bug", "p1"]);
+        let expression = filter.to_string();
+        assert_eq!(
+            expression,
+            "label:bug,p1 author:octocat state:in_flight,failed"
+        );
+        assert_eq!(expression.parse::<TaskFilter>().expect("parses"), filter);
+        assert_eq!(TaskFilter::default().to_string(), "");
+    }
+
+    #[test]
+    fn named_filter_roundtrip() {
+        let named = NamedFilter::new(
+            "mine",
+            TaskFilter::default()
+                .with_authors(["octocat"])
+                .with_states([TaskState::NeedsAttention]),
+        );
+        let json = serde_json::to_string(&named).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"name":"mine","authors":["octocat"],"states":["needs_attention"]}"#
+        );
+        let parsed: NamedFilter = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, named);
+    }
+}
@@ -0,0 +1,779 @@
+//! Key bindings.
+//!
+//! A [`Keymap`] maps key chords to [`Message`]s, separately for each
+//! [`KeyContext`]. It starts from a built-in [`KeymapPreset`], and a
+//! [`KeymapConfig`] can bind further keys or unbind preset ones:
+//!
+//! ```json5
+//! keymap: {
+//!   preset: "vim",
+//!   bindings: {
+//!     board: { "ctrl+n": "start_search", "q": null },
+//!     settings: { "J": { "settings_navigate": { "delta": 5 } } },
+//!   },
+//! }
+//! ```
+//!
+//! A chord is a character (`g`, `G`, `?`) or a key name (`enter`, `esc`,
+//! `space`, `tab`, `backspace`, `delete`, `left`, `right`, `up`, `down`,
+//! `home`, `end`, `pageup`, `pagedown`, `f1`-`f12`), optionally preceded
+//! by `ctrl+`, `alt+`, or `shift+`. Characters are case-sensitive, and
+//! `shift+g` is the same chord as `G`.
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_protocol::{KeyChord, KeyContext, KeymapConfig, KeymapPreset, Message};
+//!
+//! let config = KeymapConfig {
+//!     preset: KeymapPreset::Vim,
+//!     ..KeymapConfig::default()
+//! };
+//! let keymap = config.keymap();
+//!
+//! let j: KeyChord = "j".parse().unwrap();
+//! assert_eq!(keymap.message(KeyContext::Board, j), Some(&Message::NavigateDown));
+//! ```
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ProtocolError;
+use crate::message::Message;
+
+/// A key, without modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    /// A character key, including space.
+    Char(char),
+    /// The Enter key.
+    Enter,
+    /// The Escape key.
+    Esc,
+    /// The Backspace key.
+    Backspace,
+    /// The Tab key.
+    Tab,
+    /// Shift+Tab.
+    BackTab,
+    /// The Delete key.
+    Delete,
+    /// The left arrow key.
+    Left,
+    /// The right arrow key.
+    Right,
+    /// The up arrow key.
+    Up,
+    /// The down arrow key.
+    Down,
+    /// The Home key.
+    Home,
+    /// The End key.
+    End,
+    /// The Page Up key.
+    PageUp,
+    /// The Page Down key.
+    PageDown,
+    /// A function key, `F1` to `F12`.
+    F(u8),
+}
+
+/// Named keys, as written in chords.
+const KEY_NAMES: [(&str, Key); 15] = [
+    ("enter", Key::Enter),
+    ("esc", Key::Esc),
+    ("backspace", Key::Backspace),
+    ("tab", Key::Tab),
+    ("backtab", Key::BackTab),
+    ("delete", Key::Delete),
+    ("left", Key::Left),
+    ("right", Key::Right),
+    ("up", Key::Up),
+    ("down", Key::Down),
+    ("home", Key::Home),
+    ("end", Key::End),
+    ("pageup", Key::PageUp),
+    ("pagedown", Key::PageDown),
+    ("space", Key::Char(' ')),
+];
+
+/// A key with its modifiers, such as `ctrl+r`.
+///
+/// Chords are normalized when built, so equal key presses compare equal:
+/// Shift on a character key is folded into the character (`shift+g` is
+/// `G`), and Shift+Tab is [`Key::BackTab`].
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{Key, KeyChord};
+///
+/// let chord: KeyChord = "ctrl+r".parse().unwrap();
+/// assert_eq!(chord, KeyChord::new(Key::Char('r')).with_ctrl());
+/// assert_eq!(chord.to_string(), "ctrl+r");
+/// assert_eq!(chord.label(), "Ctrl+R");
+///
+/// let chord: KeyChord = "shift+g".parse().unwrap();
+/// assert_eq!(chord, KeyChord::new(Key::Char('G')));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyChord {
+    key: Key,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+}
+
+impl KeyChord {
+    /// Creates a chord of a key without modifiers.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key
+    #[must_use]
+    pub const fn new(key: Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    /// Adds the Ctrl modifier.
+    #[must_use]
+    pub const fn with_ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Adds the Alt modifier.
+    #[must_use]
+    pub const fn with_alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Adds the Shift modifier, folding it into character keys and Tab.
+    #[must_use]
+    pub const fn with_shift(mut self) -> Self {
+        match self.key {
+            Key::Char(ch) => self.key = Key::Char(ch.to_ascii_uppercase()),
+            Key::Tab | Key::BackTab => self.key = Key::BackTab,
+            _ => self.shift = true,
+        }
+        self
+    }
+
+    /// Returns the key of the chord.
+    #[must_use]
+    pub const fn key(self) -> Key {
+        self.key
+    }
+
+    /// Returns the chord as shown in help texts, such as `Ctrl+R`,
+    /// `Shift+G`, or `←`.
+    #[must_use]
+    pub fn label(self) -> String {
+        let mut label = String::new();
+        if self.ctrl {
+            label.push_str("Ctrl+");
+        }
+        if self.alt {
+            label.push_str("Alt+");
+        }
+        if self.shift {
+            label.push_str("Shift+");
+        }
+        match self.key {
+            Key::Char(' ') => label.push_str("Space"),
+            // Letters are shown as on the keyboard, with Shift for capitals
+            Key::Char(ch) if ch.is_ascii_uppercase() => {
+                label.push_str("Shift+");
+                label.push(ch);
+            }
+            Key::Char(ch) if self.ctrl => label.push(ch.to_ascii_uppercase()),
+            Key::Char(ch) => label.push(ch),
+            Key::Enter => label.push_str("Enter"),
+            Key::Esc => label.push_str("Esc"),
+            Key::Backspace => label.push_str("Backspace"),
+            Key::Tab => label.push_str("Tab"),
+            Key::BackTab => label.push_str("Shift+Tab"),
+            Key::Delete => label.push_str("Del"),
+            Key::Left => label.push('←'),
+            Key::Right => label.push('→'),
+            Key::Up => label.push('↑'),
+            Key::Down => label.push('↓'),
+            Key::Home => label.push_str("Home"),
+            Key::End => label.push_str("End"),
+            Key::PageUp => label.push_str("PgUp"),
+            Key::PageDown => label.push_str("PgDn"),
+            Key::F(n) => {
+                let _ = write!(label, "F{n}");
+            }
+        }
+        label
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = ProtocolError;
+
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        let invalid = || ProtocolError::InvalidKeyChord(chord.to_string());
+        // The last `+` separates the key, unless the key is `+` itself
+        let (modifiers, key) = match chord.strip_suffix('+') {
+            Some("") => ("", "+"),
+            Some(rest) if rest.ends_with('+') => (&rest[..rest.len() - 1], "+"),
+            _ => chord.rsplit_once('+').unwrap_or(("", chord)),
+        };
+        let mut parsed = Self::new(parse_key(key).ok_or_else(invalid)?);
+        if modifiers.is_empty() {
+            return Ok(parsed);
+        }
+        for modifier in modifiers.split('+') {
+            parsed = match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => parsed.with_ctrl(),
+                "alt" => parsed.with_alt(),
+                "shift" => parsed.with_shift(),
+                _ => return Err(invalid()),
+            };
+        }
+        Ok(parsed)
+    }
+}
+
+/// Reads a key: a single character, or a key name ignoring case.
+fn parse_key(key: &str) -> Option<Key> {
+    let mut chars = key.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(ch));
+    }
+    let key = key.to_ascii_lowercase();
+    if let Some(n) = key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+        return (1..=12).contains(&n).then_some(Key::F(n));
+    }
+    KEY_NAMES
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, key)| *key)
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("alt+")?;
+        }
+        if self.shift {
+            f.write_str("shift+")?;
+        }
+        match self.key {
+            Key::Char(' ') => f.write_str("space"),
+            Key::Char(ch) => write!(f, "{ch}"),
+            Key::F(n) => write!(f, "f{n}"),
+            key => {
+                let name = KEY_NAMES
+                    .iter()
+                    .find(|(_, named)| *named == key)
+                    .map_or("", |(name, _)| name);
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = ProtocolError;
+
+    fn try_from(chord: String) -> Result<Self, Self::Error> {
+        chord.parse()
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(chord: KeyChord) -> Self {
+        chord.to_string()
+    }
+}
+
+impl JsonSchema for KeyChord {
+    fn schema_name() -> Cow<'static, str> {
+        "KeyChord".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A key with optional ctrl+, alt+, or shift+ modifiers, such as \"g\", \"G\", \"ctrl+r\", or \"enter\"."
+        })
+    }
+}
+
+/// Where a key is pressed.
+///
+/// Keys not bound in the detail view or the help overlay fall back to
+/// their board binding.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[schemars(description = "Where a key is pressed.")]
+pub enum KeyContext {
+    /// The board.
+    Board,
+    /// The task detail view.
+    Detail,
+    /// The settings panel, when not editing a value.
+    Settings,
+    /// The help overlay.
+    Help,
+}
+
+impl KeyContext {
+    /// Returns the context whose bindings apply to keys this one doesn't
+    /// bind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::KeyContext;
+    ///
+    /// assert_eq!(KeyContext::Detail.fallback(), Some(KeyContext::Board));
+    /// assert_eq!(KeyContext::Settings.fallback(), None);
+    /// ```
+    #[must_use]
+    pub const fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Detail | Self::Help => Some(Self::Board),
+            Self::Board | Self::Settings => None,
+        }
+    }
+}
+
+/// A built-in set of key bindings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(description = "A built-in set of key bindings.")]
+pub enum KeymapPreset {
+    /// Arrow keys and single-letter actions.
+    #[default]
+    Default,
+    /// The default bindings, plus `hjkl` to navigate, `g`/`G` for the first
+    /// and last task, and `q` to quit or close.
+    Vim,
+}
+
+/// The keymap section of the configuration.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{KeyContext, KeymapConfig, Message};
+///
+/// let config: KeymapConfig = serde_json::from_str(
+///     r#"{ "bindings": { "board": { "x": "toggle_errors", "e": null } } }"#,
+/// )
+/// .unwrap();
+/// let keymap = config.keymap();
+///
+/// let key = |chord: &str| keymap.message(KeyContext::Board, chord.parse().unwrap());
+/// assert_eq!(key("x"), Some(&Message::ToggleErrors));
+/// assert_eq!(key("e"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(
+    description = "Key bindings: a preset, and per-context bindings from key chords to messages (null unbinds a key)."
+)]
+pub struct KeymapConfig {
+    /// The built-in bindings to start from.
+    #[serde(default)]
+    pub preset: KeymapPreset,
+
+    /// Bindings added to the preset, by context. A `null` message unbinds
+    /// the key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub bindings: BTreeMap<KeyContext, BTreeMap<KeyChord, Option<Message>>>,
+}
+
+impl KeymapConfig {
+    /// Returns `true` if this is the default configuration, which is not
+    /// written to config files.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Builds the keymap: the preset's bindings with the configured ones
+    /// applied on top.
+    #[must_use]
+    pub fn keymap(&self) -> Keymap {
+        let mut keymap = Keymap::preset(self.preset);
+        for (context, bindings) in &self.bindings {
+            for (chord, message) in bindings {
+                keymap.bind(*context, *chord, message.clone());
+            }
+        }
+        keymap
+    }
+}
+
+/// Key bindings, by context.
+///
+/// Bindings keep the order they were added in, so the first key bound to
+/// a message is the one to show in hints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(KeyContext, KeyChord, Message)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(KeymapPreset::Default)
+    }
+}
+
+impl Keymap {
+    /// Returns the bindings of a preset.
+    ///
+    /// # Arguments
+    ///
+    /// * `preset` - The preset
+    #[must_use]
+    pub fn preset(preset: KeymapPreset) -> Self {
+        let mut keymap = Self {
+            bindings: Vec::new(),
+        };
+        for (context, bindings) in [
+            (KeyContext::Board, DEFAULT_BOARD.as_slice()),
+            (KeyContext::Settings, DEFAULT_SETTINGS.as_slice()),
+        ] {
+            keymap.bind_all(context, bindings);
+        }
+        if preset == KeymapPreset::Vim {
+            for (context, bindings) in [
+                (KeyContext::Board, VIM_BOARD.as_slice()),
+                (KeyContext::Detail, VIM_DETAIL.as_slice()),
+                (KeyContext::Settings, VIM_SETTINGS.as_slice()),
+                (KeyContext::Help, VIM_HELP.as_slice()),
+            ] {
+                keymap.bind_all(context, bindings);
+            }
+        }
+        keymap
+    }
+
+    fn bind_all(&mut self, context: KeyContext, bindings: &[(KeyChord, Message)]) {
+        for (chord, message) in bindings {
+            self.bind(context, *chord, Some(message.clone()));
+        }
+    }
+
+    /// Binds a key in a context, replacing its previous binding there.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Where the binding applies
+    /// * `chord` - The key
+    /// * `message` - The message the key sends, or `None` to unbind it
+    pub fn bind(&mut self, context: KeyContext, chord: KeyChord, message: Option<Message>) {
+        let existing = self
+            .bindings
+            .iter()
+            .position(|(bound_context, bound, _)| *bound_context == context && *bound == chord);
+        match (existing, message) {
+            (Some(idx), Some(message)) => self.bindings[idx].2 = message,
+            (Some(idx), None) => {
+                self.bindings.remove(idx);
+            }
+            (None, Some(message)) => self.bindings.push((context, chord, message)),
+            (None, None) => {}
+        }
+    }
+
+    /// Returns the message a key sends in a context, falling back to the
+    /// bindings of [`KeyContext::fallback`].
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Where the key is pressed
+    /// * `chord` - The key
+    #[must_use]
+    pub fn message(&self, context: KeyContext, chord: KeyChord) -> Option<&Message> {
+        self.bindings
+            .iter()
+            .find(|(bound_context, bound, _)| *bound_context == context && *bound == chord)
+            .map(|(_, _, message)| message)
+            .or_else(|| {
+                context
+                    .fallback()
+                    .and_then(|fallback| self.message(fallback, chord))
+            })
+    }
+
+    /// Returns the keys sending a message in a context, in binding order.
+    ///
+    /// Keys of the fallback context are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Where the keys are pressed
+    /// * `message` - The message
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KeyContext, Keymap, Message};
+    ///
+    /// let keymap = Keymap::default();
+    /// let keys: Vec<String> = keymap
+    ///     .keys(KeyContext::Board, &Message::Select)
+    ///     .map(|chord| chord.label())
+    ///     .collect();
+    /// assert_eq!(keys, ["Enter", "Space"]);
+    /// ```
+    pub fn keys<'a>(
+        &'a self,
+        context: KeyContext,
+        message: &'a Message,
+    ) -> impl Iterator<Item = KeyChord> + 'a {
+        self.bindings
+            .iter()
+            .filter(move |(bound_context, _, bound)| *bound_context == context && bound == message)
+            .map(|(_, chord, _)| *chord)
+    }
+}
+
+const fn ch(ch: char) -> KeyChord {
+    KeyChord::new(Key::Char(ch))
+}
+
+const fn key(key: Key) -> KeyChord {
+    KeyChord::new(key)
+}
+
+const DEFAULT_BOARD: [(KeyChord, Message); 20] = [
+    (ch('c').with_ctrl(), Message::Quit),
+    (key(Key::Esc), Message::Escape),
+    (key(Key::Left), Message::NavigateLeft),
+    (key(Key::Right), Message::NavigateRight),
+    (key(Key::Up), Message::NavigateUp),
+    (key(Key::Down), Message::NavigateDown),
+    (key(Key::Home), Message::NavigateFirst),
+    (key(Key::End), Message::NavigateLast),
+    (key(Key::Enter), Message::Select),
+    (ch(' '), Message::Select),
+    (key(Key::Backspace), Message::Back),
+    (ch('o'), Message::OpenInBrowser),
+    (ch('S'), Message::OpenSettings),
+    (ch('r').with_ctrl(), Message::Refresh),
+    (ch('s'), Message::CycleSort),
+    (ch('e'), Message::ToggleErrors),
+    (ch('n'), Message::ToggleNotifications),
+    (ch('?'), Message::ToggleHelp),
+    (ch('/'), Message::StartSearch),
+    (ch('f'), Message::EditFilter),
+];
+
+const DEFAULT_SETTINGS: [(KeyChord, Message); 10] = [
+    (ch('c').with_ctrl(), Message::Quit),
+    (key(Key::Esc), Message::CloseSettings),
+    (key(Key::Left), Message::SettingsPrevSection),
+    (key(Key::Right), Message::SettingsNextSection),
+    (key(Key::Up), Message::SettingsNavigate { delta: -1 }),
+    (key(Key::Down), Message::SettingsNavigate { delta: 1 }),
+    (key(Key::Enter), Message::SettingsEdit),
+    (ch('d'), Message::SettingsDelete),
+    (ch('t'), Message::SettingsTestCredentials),
+    (ch('u'), Message::SettingsSaveToUserConfig),
+];
+
+const VIM_BOARD: [(KeyChord, Message); 7] = [
+    (ch('h'), Message::NavigateLeft),
+    (ch('l'), Message::NavigateRight),
+    (ch('k'), Message::NavigateUp),
+    (ch('j'), Message::NavigateDown),
+    (ch('g'), Message::NavigateFirst),
+    (ch('G'), Message::NavigateLast),
+    (ch('q'), Message::Quit),
+];
+
+const VIM_DETAIL: [(KeyChord, Message); 1] = [(ch('q'), Message::Back)];
+
+const VIM_SETTINGS: [(KeyChord, Message); 5] = [
+    (ch('h'), Message::SettingsPrevSection),
+    (ch('l'), Message::SettingsNextSection),
+    (ch('k'), Message::SettingsNavigate { delta: -1 }),
+    (ch('j'), Message::SettingsNavigate { delta: 1 }),
+    (ch('q'), Message::CloseSettings),
+];
+
+const VIM_HELP: [(KeyChord, Message); 1] = [(ch('q'), Message::ToggleHelp)];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(chord: &str) -> KeyChord {
+        chord.parse().expect("valid chord")
+    }
+
+    #[test]
+    fn chords_roundtrip_through_strings() {
+        for text in [
+            "g",
+            "G",
+            "?",
+            "+",
+            "ctrl++",
+            "ctrl+r",
+            "alt+x",
+            "space",
+            "enter",
+            "shift+left",
+            "f5",
+            "ctrl+alt+delete",
+        ] {
+            assert_eq!(chord(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn chords_are_normalized() {
+        assert_eq!(chord("shift+g"), chord("G"));
+        assert_eq!(chord("Ctrl+R"), chord("ctrl+R"));
+        assert_eq!(chord("CTRL+r"), chord("ctrl+r"));
+        assert_eq!(chord("shift+tab"), chord("backtab"));
+        assert_eq!(chord("Enter"), chord("enter"));
+        assert_eq!(chord(" "), chord("space"));
+    }
+
+    #[test]
+    fn invalid_chords_are_rejected() {
+        for text in ["", "ctrl+", "hyper+x", "f13", "enterr", "ctrl+ab"] {
+            assert!(text.parse::<KeyChord>().is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn labels_match_the_keyboard() {
+        assert_eq!(chord("ctrl+c").label(), "Ctrl+C");
+        assert_eq!(chord("ctrl+C").label(), "Ctrl+Shift+C");
+        assert_eq!(chord("S").label(), "Shift+S");
+        assert_eq!(chord("s").label(), "s");
+        assert_eq!(chord("left").label(), "←");
+        assert_eq!(chord("space").label(), "Space");
+        assert_eq!(chord("shift+tab").label(), "Shift+Tab");
+    }
+
+    #[test]
+    fn default_preset_has_no_vim_keys() {
+        let keymap = Keymap::default();
+        for key in ["h", "j", "k", "l", "g", "G", "q"] {
+            assert_eq!(keymap.message(KeyContext::Board, chord(key)), None, "{key}");
+        }
+        assert_eq!(
+            keymap.message(KeyContext::Board, chord("S")),
+            Some(&Message::OpenSettings)
+        );
+    }
+
+    #[test]
+    fn vim_preset_binds_per_context() {
+        let keymap = Keymap::preset(KeymapPreset::Vim);
+        let q = chord("q");
+        assert_eq!(keymap.message(KeyContext::Board, q), Some(&Message::Quit));
+        assert_eq!(keymap.message(KeyContext::Detail, q), Some(&Message::Back));
+        assert_eq!(
+            keymap.message(KeyContext::Help, q),
+            Some(&Message::ToggleHelp)
+        );
+        assert_eq!(
+            keymap.message(KeyContext::Settings, q),
+            Some(&Message::CloseSettings)
+        );
+        // Detail falls back to the board bindings
+        assert_eq!(
+            keymap.message(KeyContext::Detail, chord("j")),
+            Some(&Message::NavigateDown)
+        );
+        // Arrow keys keep working
+        assert_eq!(
+            keymap.message(KeyContext::Board, chord("down")),
+            Some(&Message::NavigateDown)
+        );
+    }
+
+    #[test]
+    fn settings_do_not_fall_back_to_board() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.message(KeyContext::Settings, chord("/")), None);
+    }
+
+    #[test]
+    fn configured_bindings_override_preset() {
+        let config: KeymapConfig = serde_json::from_str(
+            r#"{
+                "preset": "vim",
+                "bindings": {
+                    "board": { "q": null, "Q": "quit", "x": "toggle_errors" },
+                    "settings": { "J": { "settings_navigate": { "delta": 5 } } }
+                }
+            }"#,
+        )
+        .expect("valid keymap");
+        let keymap = config.keymap();
+
+        assert_eq!(keymap.message(KeyContext::Board, chord("q")), None);
+        assert_eq!(
+            keymap.message(KeyContext::Board, chord("shift+q")),
+            Some(&Message::Quit)
+        );
+        assert_eq!(
+            keymap.message(KeyContext::Settings, chord("J")),
+            Some(&Message::SettingsNavigate { delta: 5 })
+        );
+        // Rebinding keeps the position of the key in hints
+        let quit: Vec<_> = keymap.keys(KeyContext::Board, &Message::Quit).collect();
+        assert_eq!(quit, [chord("ctrl+c"), chord("Q")]);
+    }
+
+    #[test]
+    fn invalid_chord_fails_to_deserialize() {
+        let result = serde_json::from_str::<KeymapConfig>(
+            r#"{ "bindings": { "board": { "meta+x": "quit" } } }"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn config_roundtrip() {
+        let mut config = KeymapConfig {
+            preset: KeymapPreset::Vim,
+            ..KeymapConfig::default()
+        };
+        config
+            .bindings
+            .entry(KeyContext::Detail)
+            .or_default()
+            .insert(chord("ctrl+d"), Some(Message::NavigateDown));
+
+        let json = serde_json::to_string(&config).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"preset":"vim","bindings":{"detail":{"ctrl+d":"navigate_down"}}}"#
+        );
+        let parsed: KeymapConfig = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, config);
+        assert!(!parsed.is_default());
+        assert!(KeymapConfig::default().is_default());
+    }
+}
@@ -7,7 +7,8 @@
 //! # Overview
 //!
 //! The whip application uses GitHub labels to determine which lane a task
-//! belongs to. The standard labels are:
+//! belongs to. Which labels those are is configurable through
+//! [`StatusLabels`]; by default, the standard labels are:
 //!
 //! - `whip/backlog` - Tasks waiting to be started
 //! - `whip/in-progress` - Tasks currently being worked on
@@ -31,6 +32,7 @@
 //! assert_eq!(in_progress.name, "whip/in-progress");
 //! ```
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::board::LaneKind;
@@ -53,7 +55,8 @@ pub struct StatusFromLabel {
 /// A label definition that maps a GitHub label to a Kanban lane.
 ///
 /// This struct defines both the label metadata (name, color, description)
-/// for GitHub and the corresponding lane in the whip application.
+/// for GitHub and the corresponding lane and task state in the whip
+/// application.
 ///
 /// # Examples
 ///
@@ -65,11 +68,13 @@ pub struct StatusFromLabel {
 ///     color: "0052CC".to_string(),
 ///     description: "Task is in the backlog".to_string(),
 ///     lane: LaneKind::Backlog,
+///     state: None,
 /// };
 ///
 /// assert!(label.is_whip_label());
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "A status label, mapping a GitHub label to a lane and task state.")]
 pub struct LabelDefinition {
     /// The full label name (e.g., "whip/in-progress").
     pub name: String,
@@ -78,10 +83,17 @@ pub struct LabelDefinition {
     pub color: String,
 
     /// A brief description of the label's purpose.
+    ///
+    /// Empty leaves the description of an existing label alone.
+    #[serde(default)]
     pub description: String,
 
     /// The Kanban lane this label maps to.
     pub lane: LaneKind,
+
+    /// The task state this label implies; `None` for the lane's usual state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<TaskState>,
 }
 
 impl LabelDefinition {
@@ -118,6 +130,51 @@ impl LabelDefinition {
             color: color.into(),
             description: description.into(),
             lane,
+            state: None,
+        }
+    }
+
+    /// Sets the task state this label implies, instead of the lane's usual one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{LabelDefinition, LaneKind, TaskState};
+    ///
+    /// let label = LabelDefinition::new("blocked", "DC2626", "", LaneKind::InProgress)
+    ///     .with_state(TaskState::NeedsAttention);
+    /// assert_eq!(label.status().state, TaskState::NeedsAttention);
+    /// ```
+    #[must_use]
+    pub fn with_state(mut self, state: TaskState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Returns the lane and task state of issues carrying this label.
+    ///
+    /// Without an explicit state, tasks in progress are in flight, done
+    /// tasks succeeded, and all others are idle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{LabelDefinition, LaneKind, TaskState};
+    ///
+    /// let label = LabelDefinition::new("status: done", "16A34A", "", LaneKind::Done);
+    /// assert_eq!(label.status().lane, LaneKind::Done);
+    /// assert_eq!(label.status().state, TaskState::Success);
+    /// ```
+    #[must_use]
+    pub fn status(&self) -> StatusFromLabel {
+        let state = self.state.unwrap_or(match self.lane {
+            LaneKind::InProgress => TaskState::InFlight,
+            LaneKind::Done => TaskState::Success,
+            LaneKind::Backlog | LaneKind::UnderReview => TaskState::Idle,
+        });
+        StatusFromLabel {
+            lane: self.lane,
+            state,
         }
     }
 
@@ -200,14 +257,106 @@ pub fn standard_status_labels() -> Vec<LabelDefinition> {
             "DC2626", // Red - error/failure
             "Task failed and needs attention",
             LaneKind::Done,
-        ),
+        )
+        .with_state(TaskState::Failed),
     ]
 }
 
+/// The labels whip reads task status from, in priority order.
+///
+/// When an issue carries several status labels, the one listed first wins.
+/// Label names are matched case-insensitively, as GitHub does.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{LabelDefinition, LaneKind, StatusLabels, TaskState};
+///
+/// let labels = StatusLabels::new(vec![
+///     LabelDefinition::new("status: in progress", "2563EB", "", LaneKind::InProgress),
+///     LabelDefinition::new("status: done", "16A34A", "", LaneKind::Done),
+/// ]);
+///
+/// let issue_labels = vec!["bug".to_string(), "Status: Done".to_string()];
+/// let status = labels.determine_status(&issue_labels).unwrap();
+/// assert_eq!(status.lane, LaneKind::Done);
+/// assert_eq!(status.state, TaskState::Success);
+///
+/// // The standard `whip/*` labels are not part of a custom set
+/// assert!(labels.status("whip/done").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusLabels {
+    labels: Vec<LabelDefinition>,
+}
+
+impl Default for StatusLabels {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl StatusLabels {
+    /// Creates a set of status labels, highest priority first.
+    #[must_use]
+    pub fn new(labels: Vec<LabelDefinition>) -> Self {
+        Self { labels }
+    }
+
+    /// Returns the [standard `whip/*` labels](standard_status_labels).
+    #[must_use]
+    pub fn standard() -> Self {
+        Self::new(standard_status_labels())
+    }
+
+    /// Returns the labels, highest priority first.
+    #[must_use]
+    pub fn labels(&self) -> &[LabelDefinition] {
+        &self.labels
+    }
+
+    /// Returns the label definition named `label_name`, if it is a status label.
+    #[must_use]
+    pub fn get(&self, label_name: &str) -> Option<&LabelDefinition> {
+        self.labels
+            .iter()
+            .find(|label| label.name.eq_ignore_ascii_case(label_name))
+    }
+
+    /// Parses a label name into its lane and state.
+    ///
+    /// Returns `None` if `label_name` is not a status label.
+    #[must_use]
+    pub fn status(&self, label_name: &str) -> Option<StatusFromLabel> {
+        self.get(label_name).map(LabelDefinition::status)
+    }
+
+    /// Determines both lane and state from an issue's labels.
+    ///
+    /// Returns `None` if none of `labels` is a status label.
+    #[must_use]
+    pub fn determine_status(&self, labels: &[String]) -> Option<StatusFromLabel> {
+        self.labels
+            .iter()
+            .find(|label| {
+                labels
+                    .iter()
+                    .any(|name| label.name.eq_ignore_ascii_case(name))
+            })
+            .map(LabelDefinition::status)
+    }
+
+    /// Returns `true` if any of `labels` is a status label.
+    #[must_use]
+    pub fn has_status_label(&self, labels: &[String]) -> bool {
+        self.determine_status(labels).is_some()
+    }
+}
+
 /// Finds the lane kind for a given label name.
 ///
 /// Returns `Some(LaneKind)` if the label matches a standard whip label,
-/// `None` otherwise.
+/// `None` otherwise. Use [`StatusLabels`] for a configured set of labels.
 ///
 /// # Examples
 ///
@@ -227,7 +376,7 @@ pub fn label_to_lane(label_name: &str) -> Option<LaneKind> {
 /// Parses a label name into its lane and state.
 ///
 /// Returns `Some(StatusFromLabel)` if the label matches a standard whip label,
-/// `None` otherwise.
+/// `None` otherwise. Use [`StatusLabels`] for a configured set of labels.
 ///
 /// # Examples
 ///
@@ -244,29 +393,7 @@ pub fn label_to_lane(label_name: &str) -> Option<LaneKind> {
 /// ```
 #[must_use]
 pub fn label_to_status(label_name: &str) -> Option<StatusFromLabel> {
-    match label_name {
-        "whip/backlog" => Some(StatusFromLabel {
-            lane: LaneKind::Backlog,
-            state: TaskState::Idle,
-        }),
-        "whip/in-progress" => Some(StatusFromLabel {
-            lane: LaneKind::InProgress,
-            state: TaskState::InFlight,
-        }),
-        "whip/under-review" => Some(StatusFromLabel {
-            lane: LaneKind::UnderReview,
-            state: TaskState::Idle,
-        }),
-        "whip/done" => Some(StatusFromLabel {
-            lane: LaneKind::Done,
-            state: TaskState::Success,
-        }),
-        "whip/failed" => Some(StatusFromLabel {
-            lane: LaneKind::Done,
-            state: TaskState::Failed,
-        }),
-        _ => None,
-    }
+    StatusLabels::standard().status(label_name)
 }
 
 /// Determines the lane from a list of labels.
@@ -318,21 +445,7 @@ pub fn determine_lane_from_labels(labels: &[String]) -> Option<LaneKind> {
 /// ```
 #[must_use]
 pub fn determine_status_from_labels(labels: &[String]) -> Option<StatusFromLabel> {
-    // Standard whip labels in priority order
-    const WHIP_LABELS: &[&str] = &[
-        "whip/backlog",
-        "whip/in-progress",
-        "whip/under-review",
-        "whip/done",
-        "whip/failed",
-    ];
-
-    for &whip_label in WHIP_LABELS {
-        if labels.iter().any(|l| l == whip_label) {
-            return label_to_status(whip_label);
-        }
-    }
-    None
+    StatusLabels::standard().determine_status(labels)
 }
 
 /// Checks if any of the provided labels is a whip status label.
@@ -521,4 +634,39 @@ mod tests {
 
         assert_eq!(label, parsed);
     }
+
+    #[test]
+    fn label_definition_deserializes_with_defaults() {
+        let label: LabelDefinition =
+            serde_json::from_str(r#"{"name": "status: done", "color": "16A34A", "lane": "done"}"#)
+                .expect("deserialize");
+        assert_eq!(label.description, "");
+        assert_eq!(label.state, None);
+        assert_eq!(label.status().state, crate::task::TaskState::Success);
+    }
+
+    #[test]
+    fn custom_status_labels_follow_their_own_order() {
+        use crate::task::TaskState;
+
+        let labels = StatusLabels::new(vec![
+            LabelDefinition::new("blocked", "DC2626", "", LaneKind::InProgress)
+                .with_state(TaskState::NeedsAttention),
+            LabelDefinition::new("status: in progress", "2563EB", "", LaneKind::InProgress),
+        ]);
+        let issue_labels = vec!["status: in progress".to_string(), "Blocked".to_string()];
+        let status = labels.determine_status(&issue_labels).unwrap();
+        assert_eq!(status.state, TaskState::NeedsAttention);
+
+        assert!(labels.has_status_label(&["STATUS: IN PROGRESS".to_string()]));
+        assert!(!labels.has_status_label(&["whip/in-progress".to_string()]));
+    }
+
+    #[test]
+    fn default_status_labels_are_standard() {
+        assert_eq!(
+            StatusLabels::default().labels(),
+            standard_status_labels().as_slice()
+        );
+    }
 }
@@ -8,8 +8,14 @@
 //! The crate is organized into the following modules:
 //!
 //! - [`task`]: Task identifiers, states, and the `Task` struct
-//! - [`board`]: Kanban board lanes and the `KanbanBoard` struct
+//! - [`board`]: Kanban board lanes, their definitions, and the `KanbanBoard` struct
+//! - [`diff`]: Differences between two versions of a board
+//! - [`filter`]: Task filters narrowing the board
+//! - [`sort`]: Task ordering within lanes
 //! - [`message`]: TUI event messages
+//! - [`keymap`]: Key bindings sending messages
+//! - [`fetch`]: Classification of remote fetch failures
+//! - [`host`]: GitHub host identification
 //! - [`error`]: Error types for protocol operations
 //!
 //! # Examples
@@ -37,19 +43,31 @@
 //! ```
 
 pub mod board;
+pub mod diff;
 pub mod dummy;
 pub mod error;
+pub mod fetch;
+pub mod filter;
+pub mod host;
+pub mod keymap;
 pub mod label;
 pub mod message;
+pub mod sort;
 pub mod task;
 
 // Re-export primary types at crate root for convenience
-pub use board::{KanbanBoard, Lane, LaneKind};
+pub use board::{KanbanBoard, Lane, LaneDefinition, LaneKind};
+pub use diff::{BoardDiff, TaskChange};
 pub use error::{ProtocolError, Result};
+pub use fetch::FetchErrorKind;
+pub use filter::{NamedFilter, TaskFilter};
+pub use host::{DEFAULT_HOST, api_host, repo_key};
+pub use keymap::{Key, KeyChord, KeyContext, Keymap, KeymapConfig, KeymapPreset};
 pub use label::{
-    LABEL_PREFIX, LabelDefinition, StatusFromLabel, determine_lane_from_labels,
+    LABEL_PREFIX, LabelDefinition, StatusFromLabel, StatusLabels, determine_lane_from_labels,
     determine_status_from_labels, has_whip_status_label, label_to_lane, label_to_status,
     standard_status_labels,
 };
 pub use message::Message;
+pub use sort::SortKey;
 pub use task::{GitHubSource, Task, TaskId, TaskState};
@@ -3,6 +3,7 @@
 //! This module defines the message enum used for communication between
 //! the TUI input handler and the application state.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Messages that represent user actions in the TUI.
@@ -18,8 +19,9 @@ use serde::{Deserialize, Serialize};
 /// let msg = Message::NavigateRight;
 /// assert!(matches!(msg, Message::NavigateRight));
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[schemars(description = "A user action, as sent by a key binding.")]
 pub enum Message {
     /// Move selection to the left lane.
     NavigateLeft,
@@ -29,6 +31,10 @@ pub enum Message {
     NavigateUp,
     /// Move selection down within the current lane.
     NavigateDown,
+    /// Move selection to the first task of the current lane.
+    NavigateFirst,
+    /// Move selection to the last task of the current lane.
+    NavigateLast,
     /// Select the currently highlighted item.
     Select,
     /// Go back to the previous view or cancel current action.
@@ -41,8 +47,14 @@ pub enum Message {
     Refresh,
     /// Toggle help overlay.
     ToggleHelp,
+    /// Toggle the errors pane.
+    ToggleErrors,
+    /// Toggle the notification history overlay.
+    ToggleNotifications,
     /// Open the current item in the default browser.
     OpenInBrowser,
+    /// Sort the selected lane by the next sort key.
+    CycleSort,
     /// Mouse click at coordinates (column, row).
     ClickAt {
         /// Column (x coordinate) of the click.
@@ -50,6 +62,22 @@ pub enum Message {
         /// Row (y coordinate) of the click.
         row: u16,
     },
+    /// Mouse right click at coordinates (column, row).
+    RightClickAt {
+        /// Column (x coordinate) of the click.
+        column: u16,
+        /// Row (y coordinate) of the click.
+        row: u16,
+    },
+    /// Mouse wheel scrolled at coordinates (column, row).
+    ScrollAt {
+        /// Column (x coordinate) of the pointer.
+        column: u16,
+        /// Row (y coordinate) of the pointer.
+        row: u16,
+        /// Steps scrolled, negative scrolls up.
+        delta: i32,
+    },
 
     // --- Settings messages ---
     /// Open the settings panel.
@@ -73,6 +101,11 @@ pub enum Message {
     SettingsCancel,
     /// Delete the selected item (e.g., a repository).
     SettingsDelete,
+    /// Test the credentials of the selected repository.
+    SettingsTestCredentials,
+    /// Toggle saving settings to the user config instead of the file they
+    /// were loaded from.
+    SettingsSaveToUserConfig,
     /// Save settings to file.
     SettingsSave,
     /// Input a character while editing.
@@ -88,6 +121,47 @@ pub enum Message {
     SettingsCursorLeft,
     /// Move cursor right within the current text field.
     SettingsCursorRight,
+
+    // Search messages
+    /// Start typing a search query.
+    StartSearch,
+    /// Input a character into the search query.
+    SearchInput {
+        /// The character that was input.
+        ch: char,
+    },
+    /// Delete the last character of the search query.
+    SearchBackspace,
+    /// Stop typing and keep the search results highlighted.
+    SearchConfirm,
+    /// End the search and clear its highlights.
+    SearchCancel,
+    /// Select the next search result.
+    SearchNext,
+    /// Select the previous search result.
+    SearchPrevious,
+
+    // Filter messages
+    /// Open the filter bar to edit the board filter.
+    EditFilter,
+    /// Input a character into the filter bar.
+    FilterInput {
+        /// The character that was input.
+        ch: char,
+    },
+    /// Delete the last character of the filter bar.
+    FilterBackspace,
+    /// Put the next or previous saved filter in the filter bar.
+    FilterCycleNamed {
+        /// Direction to cycle (positive = next, negative = previous).
+        delta: i32,
+    },
+    /// Apply the filter bar input to the board.
+    FilterApply,
+    /// Save the filter bar input as a named filter, asking for its name.
+    FilterSave,
+    /// Close the filter bar without changing the filter.
+    FilterCancel,
 }
 
 impl Message {
@@ -110,6 +184,8 @@ impl Message {
                 | Self::NavigateRight
                 | Self::NavigateUp
                 | Self::NavigateDown
+                | Self::NavigateFirst
+                | Self::NavigateLast
                 | Self::SettingsNavigate { .. }
                 | Self::SettingsNextSection
                 | Self::SettingsPrevSection
@@ -155,6 +231,8 @@ impl Message {
                 | Self::SettingsConfirm
                 | Self::SettingsCancel
                 | Self::SettingsDelete
+                | Self::SettingsTestCredentials
+                | Self::SettingsSaveToUserConfig
                 | Self::SettingsSave
                 | Self::SettingsInput { .. }
                 | Self::SettingsBackspace
@@ -163,6 +241,56 @@ impl Message {
                 | Self::SettingsCursorRight
         )
     }
+
+    /// Returns `true` if this message is a search-related action.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::Message;
+    ///
+    /// assert!(Message::StartSearch.is_search());
+    /// assert!(Message::SearchNext.is_search());
+    /// assert!(!Message::NavigateDown.is_search());
+    /// ```
+    #[must_use]
+    pub fn is_search(&self) -> bool {
+        matches!(
+            self,
+            Self::StartSearch
+                | Self::SearchInput { .. }
+                | Self::SearchBackspace
+                | Self::SearchConfirm
+                | Self::SearchCancel
+                | Self::SearchNext
+                | Self::SearchPrevious
+        )
+    }
+
+    /// Returns `true` if this message is a filter-related action.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::Message;
+    ///
+    /// assert!(Message::EditFilter.is_filter());
+    /// assert!(Message::FilterApply.is_filter());
+    /// assert!(!Message::SearchConfirm.is_filter());
+    /// ```
+    #[must_use]
+    pub fn is_filter(&self) -> bool {
+        matches!(
+            self,
+            Self::EditFilter
+                | Self::FilterInput { .. }
+                | Self::FilterBackspace
+                | Self::FilterCycleNamed { .. }
+                | Self::FilterApply
+                | Self::FilterSave
+                | Self::FilterCancel
+        )
+    }
 }
 
 #[cfg(test)]
@@ -175,6 +303,8 @@ mod tests {
         assert!(Message::NavigateRight.is_navigation());
         assert!(Message::NavigateUp.is_navigation());
         assert!(Message::NavigateDown.is_navigation());
+        assert!(Message::NavigateFirst.is_navigation());
+        assert!(Message::NavigateLast.is_navigation());
         assert!(Message::SettingsNavigate { delta: 1 }.is_navigation());
         assert!(Message::SettingsNextSection.is_navigation());
         assert!(Message::SettingsPrevSection.is_navigation());
@@ -201,6 +331,7 @@ mod tests {
         assert!(Message::SettingsConfirm.is_settings());
         assert!(Message::SettingsCancel.is_settings());
         assert!(Message::SettingsDelete.is_settings());
+        assert!(Message::SettingsTestCredentials.is_settings());
         assert!(Message::SettingsSave.is_settings());
         assert!(Message::SettingsInput { ch: 'a' }.is_settings());
         assert!(Message::SettingsBackspace.is_settings());
@@ -211,6 +342,32 @@ mod tests {
         assert!(!Message::Quit.is_settings());
     }
 
+    #[test]
+    fn message_search_detection() {
+        assert!(Message::StartSearch.is_search());
+        assert!(Message::SearchInput { ch: 'a' }.is_search());
+        assert!(Message::SearchBackspace.is_search());
+        assert!(Message::SearchConfirm.is_search());
+        assert!(Message::SearchCancel.is_search());
+        assert!(Message::SearchNext.is_search());
+        assert!(Message::SearchPrevious.is_search());
+        assert!(!Message::SettingsInput { ch: 'a' }.is_search());
+        assert!(!Message::Escape.is_search());
+    }
+
+    #[test]
+    fn message_filter_detection() {
+        assert!(Message::EditFilter.is_filter());
+        assert!(Message::FilterInput { ch: 'a' }.is_filter());
+        assert!(Message::FilterBackspace.is_filter());
+        assert!(Message::FilterCycleNamed { delta: 1 }.is_filter());
+        assert!(Message::FilterApply.is_filter());
+        assert!(Message::FilterSave.is_filter());
+        assert!(Message::FilterCancel.is_filter());
+        assert!(!Message::SearchInput { ch: 'a' }.is_filter());
+        assert!(!Message::Escape.is_filter());
+    }
+
     #[test]
     fn message_serialization_roundtrip() {
         let messages = vec![
@@ -218,13 +375,23 @@ mod tests {
             Message::NavigateRight,
             Message::NavigateUp,
             Message::NavigateDown,
+            Message::NavigateFirst,
+            Message::NavigateLast,
             Message::Select,
             Message::Back,
             Message::Escape,
             Message::Quit,
             Message::Refresh,
             Message::ToggleHelp,
+            Message::ToggleErrors,
+            Message::ToggleNotifications,
             Message::ClickAt { column: 10, row: 5 },
+            Message::RightClickAt { column: 10, row: 5 },
+            Message::ScrollAt {
+                column: 10,
+                row: 5,
+                delta: -1,
+            },
             Message::OpenSettings,
             Message::CloseSettings,
             Message::SettingsNextSection,
@@ -240,6 +407,21 @@ mod tests {
             Message::SettingsSwitchField,
             Message::SettingsCursorLeft,
             Message::SettingsCursorRight,
+            Message::StartSearch,
+            Message::SearchInput { ch: '/' },
+            Message::SearchBackspace,
+            Message::SearchConfirm,
+            Message::SearchCancel,
+            Message::SearchNext,
+            Message::SearchPrevious,
+            Message::CycleSort,
+            Message::EditFilter,
+            Message::FilterInput { ch: ':' },
+            Message::FilterBackspace,
+            Message::FilterCycleNamed { delta: -1 },
+            Message::FilterApply,
+            Message::FilterSave,
+            Message::FilterCancel,
         ];
 
         for msg in messages {
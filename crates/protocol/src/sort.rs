@@ -0,0 +1,275 @@
+//! Task ordering within lanes.
+//!
+//! Lanes hold their tasks in the order they arrived unless their
+//! [`LaneDefinition`](crate::LaneDefinition) names a [`SortKey`]. Each key
+//! sorts in the direction that puts the tasks most worth looking at first;
+//! ties are broken by the most recently updated task, then by ID, so the
+//! order never depends on fetch order.
+
+use std::cmp::Ordering;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::task::{Task, TaskState};
+
+/// What the tasks of a lane are sorted by.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use whip_protocol::{SortKey, Task, TaskState};
+///
+/// let mut failed = Task::new("Failed", "Description");
+/// failed.state = TaskState::Failed;
+/// let idle = Task::new("Idle", "Description");
+///
+/// assert_eq!(SortKey::Severity.compare(&failed, &idle), Ordering::Less);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(description = "What the tasks of a lane are sorted by.")]
+pub enum SortKey {
+    /// Most recently updated first.
+    UpdatedAt,
+    /// Most recently created first.
+    CreatedAt,
+    /// Lowest issue number first; tasks without an issue last.
+    Number,
+    /// Highest priority label first (`P0`, `priority: high`, ...); tasks
+    /// without one last.
+    Priority,
+    /// Most severe state first: failed, needs attention, in flight, idle,
+    /// then successful.
+    Severity,
+}
+
+impl SortKey {
+    /// Returns all sort keys, in the order [`next`](Self::next) cycles
+    /// through them.
+    #[must_use]
+    pub const fn all() -> [Self; 5] {
+        [
+            Self::UpdatedAt,
+            Self::CreatedAt,
+            Self::Number,
+            Self::Priority,
+            Self::Severity,
+        ]
+    }
+
+    /// Returns a short name for the key, as shown in lane headers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::SortKey;
+    ///
+    /// assert_eq!(SortKey::UpdatedAt.display_name(), "updated");
+    /// ```
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::UpdatedAt => "updated",
+            Self::CreatedAt => "created",
+            Self::Number => "number",
+            Self::Priority => "priority",
+            Self::Severity => "severity",
+        }
+    }
+
+    /// Returns the sort following `current`, cycling through every key and
+    /// back to arrival order (`None`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::SortKey;
+    ///
+    /// assert_eq!(SortKey::next(None), Some(SortKey::UpdatedAt));
+    /// assert_eq!(SortKey::next(Some(SortKey::UpdatedAt)), Some(SortKey::CreatedAt));
+    /// assert_eq!(SortKey::next(Some(SortKey::Severity)), None);
+    /// ```
+    #[must_use]
+    pub fn next(current: Option<Self>) -> Option<Self> {
+        let all = Self::all();
+        match current {
+            None => Some(all[0]),
+            Some(key) => all
+                .iter()
+                .position(|candidate| *candidate == key)
+                .and_then(|idx| all.get(idx + 1).copied()),
+        }
+    }
+
+    /// Compares two tasks; the task to show first is [`Ordering::Less`].
+    ///
+    /// Ties are broken by the most recently updated task, then by ID.
+    #[must_use]
+    pub fn compare(self, a: &Task, b: &Task) -> Ordering {
+        let by_key = match self {
+            Self::UpdatedAt => Ordering::Equal,
+            Self::CreatedAt => b.created_at.cmp(&a.created_at),
+            Self::Number => last_if_none(issue_number(a), issue_number(b)),
+            Self::Priority => last_if_none(priority_rank(a), priority_rank(b)),
+            Self::Severity => severity_rank(a.state).cmp(&severity_rank(b.state)),
+        };
+        by_key
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+            .then_with(|| a.id.cmp(&b.id))
+    }
+}
+
+/// Orders present values ascending, before missing ones.
+fn last_if_none<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn issue_number(task: &Task) -> Option<u64> {
+    task.github.as_ref().map(|github| github.number)
+}
+
+const fn severity_rank(state: TaskState) -> u8 {
+    match state {
+        TaskState::Failed => 0,
+        TaskState::NeedsAttention => 1,
+        TaskState::InFlight => 2,
+        TaskState::Idle => 3,
+        TaskState::Success => 4,
+    }
+}
+
+/// Returns the rank of a task's highest priority label, `0` being the
+/// highest priority.
+fn priority_rank(task: &Task) -> Option<u8> {
+    task.github
+        .as_ref()?
+        .labels
+        .iter()
+        .filter_map(|label| label_priority(label))
+        .min()
+}
+
+/// Reads a priority label: `P0`-`P9`, or `priority` followed by a level
+/// (`priority: high`, `priority/P1`, `priority-2`, ...).
+fn label_priority(label: &str) -> Option<u8> {
+    let label = label.trim().to_ascii_lowercase();
+    let (level, prefixed) = match label.strip_prefix("priority") {
+        Some(rest) => (
+            rest.trim_start_matches(|c: char| c.is_whitespace() || ":/-_".contains(c)),
+            true,
+        ),
+        None => (label.as_str(), false),
+    };
+    if let Some(digit) = level.strip_prefix('p').filter(|d| d.len() == 1) {
+        return digit.parse().ok();
+    }
+    if !prefixed {
+        return None;
+    }
+    match level {
+        "critical" | "urgent" => Some(0),
+        "high" => Some(1),
+        "medium" | "normal" => Some(2),
+        "low" => Some(3),
+        _ if level.len() == 1 => level.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::GitHubSource;
+    use chrono::{Duration, Utc};
+
+    fn issue(number: u64, labels: &[&str]) -> Task {
+        let mut task = Task::new(format!("Issue {number}"), "Description");
+        task.github = Some(GitHubSource {
+            host: None,
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            number,
+            url: format!("https://github.com/owner/repo/issues/{number}"),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            author: "author".to_string(),
+            comment_count: 0,
+        });
+        task
+    }
+
+    #[test]
+    fn next_cycles_through_every_key() {
+        let mut sort = None;
+        let mut seen = Vec::new();
+        loop {
+            sort = SortKey::next(sort);
+            let Some(key) = sort else { break };
+            seen.push(key);
+        }
+        assert_eq!(seen, SortKey::all());
+    }
+
+    #[test]
+    fn dates_sort_newest_first() {
+        let mut old = issue(1, &[]);
+        old.created_at = Utc::now() - Duration::days(2);
+        old.updated_at = Utc::now() + Duration::days(1);
+        let new = issue(2, &[]);
+
+        assert_eq!(SortKey::CreatedAt.compare(&new, &old), Ordering::Less);
+        assert_eq!(SortKey::UpdatedAt.compare(&old, &new), Ordering::Less);
+    }
+
+    #[test]
+    fn numbers_sort_ascending_with_local_tasks_last() {
+        let local = Task::new("Local", "Description");
+        assert_eq!(
+            SortKey::Number.compare(&issue(2, &[]), &issue(10, &[])),
+            Ordering::Less
+        );
+        assert_eq!(
+            SortKey::Number.compare(&local, &issue(10, &[])),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn priority_labels_are_recognized() {
+        assert_eq!(label_priority("P0"), Some(0));
+        assert_eq!(label_priority("priority: high"), Some(1));
+        assert_eq!(label_priority("Priority/P2"), Some(2));
+        assert_eq!(label_priority("priority-3"), Some(3));
+        assert_eq!(label_priority("priority:critical"), Some(0));
+        assert_eq!(label_priority("high"), None);
+        assert_eq!(label_priority("p10"), None);
+        assert_eq!(label_priority("bug"), None);
+    }
+
+    #[test]
+    fn priority_uses_highest_label() {
+        let urgent = issue(1, &["bug", "priority: low", "P0"]);
+        let low = issue(2, &["priority: low"]);
+        let none = issue(3, &["bug"]);
+
+        assert_eq!(SortKey::Priority.compare(&urgent, &low), Ordering::Less);
+        assert_eq!(SortKey::Priority.compare(&none, &low), Ordering::Greater);
+    }
+
+    #[test]
+    fn ties_are_broken_deterministically() {
+        let a = issue(1, &[]);
+        let mut b = a.clone();
+        b.id = crate::TaskId::new_v4();
+
+        let order = SortKey::Severity.compare(&a, &b);
+        assert_ne!(order, Ordering::Equal);
+        assert_eq!(SortKey::Severity.compare(&b, &a), order.reverse());
+    }
+}
@@ -4,9 +4,11 @@
 //! including task identifiers, states, and the task structure itself.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::board::LaneKind;
+use crate::host::{DEFAULT_HOST, repo_key};
 
 /// Unique identifier for a task.
 ///
@@ -26,8 +28,9 @@ pub type TaskId = uuid::Uuid;
 /// let state = TaskState::InFlight;
 /// assert!(matches!(state, TaskState::InFlight));
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
+#[schemars(description = "The execution state of a task.")]
 pub enum TaskState {
     /// Task is waiting to be worked on.
     #[default]
@@ -84,6 +87,9 @@ impl TaskState {
 /// and supplementary information like labels and comment count.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GitHubSource {
+    /// The GitHub host of the repository, or `None` for github.com.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
     /// Repository owner (e.g., "rust-lang").
     pub owner: String,
     /// Repository name (e.g., "rust").
@@ -100,6 +106,41 @@ pub struct GitHubSource {
     pub comment_count: u32,
 }
 
+impl GitHubSource {
+    /// Returns the key of the repository the issue belongs to.
+    ///
+    /// See [`repo_key`] for the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::GitHubSource;
+    ///
+    /// let mut source = GitHubSource {
+    ///     host: None,
+    ///     owner: "acme".into(),
+    ///     repo: "api".into(),
+    ///     number: 7,
+    ///     url: "https://github.com/acme/api/issues/7".into(),
+    ///     labels: vec![],
+    ///     author: "octocat".into(),
+    ///     comment_count: 0,
+    /// };
+    /// assert_eq!(source.repo_key(), "acme/api");
+    ///
+    /// source.host = Some("ghe.example.com".into());
+    /// assert_eq!(source.repo_key(), "ghe.example.com/acme/api");
+    /// ```
+    #[must_use]
+    pub fn repo_key(&self) -> String {
+        repo_key(
+            self.host.as_deref().unwrap_or(DEFAULT_HOST),
+            &self.owner,
+            &self.repo,
+        )
+    }
+}
+
 /// A task on the Kanban board.
 ///
 /// Represents a unit of work that can be tracked through the board's lanes.
@@ -283,6 +324,7 @@ mod proptest_tests {
         ) -> GitHubSource {
             let url = format!("https://github.com/{owner}/{repo}/issues/{number}");
             GitHubSource {
+                host: None,
                 owner,
                 repo,
                 number,
@@ -495,6 +537,7 @@ mod unit_tests {
     fn task_with_github_includes_field_in_json() {
         let mut task = Task::new("Test task", "A description");
         task.github = Some(GitHubSource {
+            host: None,
             owner: "rust-lang".to_string(),
             repo: "rust".to_string(),
             number: 12345,
@@ -526,6 +569,7 @@ mod unit_tests {
     #[test]
     fn github_source_serialization_roundtrip() {
         let source = GitHubSource {
+            host: None,
             owner: "owner".to_string(),
             repo: "repo".to_string(),
             number: 1,
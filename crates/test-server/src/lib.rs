@@ -0,0 +1,120 @@
+//! A stand-in for the GitHub API in tests.
+//!
+//! [`serve`] answers each connection on a local port with the next canned
+//! [`Response`], and reports the requests it received, so tests can point
+//! a client at it and check what was sent. It only understands as much
+//! HTTP as the GitHub clients under test need: one request per
+//! connection, without a body.
+//!
+//! # Examples
+//!
+//! ```
+//! use tokio::io::{AsyncReadExt, AsyncWriteExt};
+//! use whip_test_server::{Response, serve};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let (base_url, server) = serve(vec![Response::json(r#"{"id": 1}"#)]).await;
+//!
+//! let addr = base_url.strip_prefix("http://").unwrap();
+//! let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+//! stream.write_all(b"GET /user HTTP/1.1\r\n\r\n").await.unwrap();
+//! let mut response = String::new();
+//! stream.read_to_string(&mut response).await.unwrap();
+//! assert!(response.ends_with(r#"{"id": 1}"#));
+//!
+//! let requests = server.await.unwrap();
+//! assert!(requests[0].starts_with("GET /user "));
+//! # }
+//! ```
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::task::JoinHandle;
+
+/// A canned response served by [`serve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Response {
+    /// Creates a `200 OK` response with a JSON body.
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Sets the status code.
+    #[must_use]
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Adds a response header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn to_http(&self) -> String {
+        let headers: String = self
+            .headers
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}\r\n"))
+            .collect();
+        format!(
+            "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+/// Serves `responses` on a local port, one per connection, in order.
+///
+/// Returns the base URL to point a client at, and a handle resolving to
+/// the head (request line and headers) of each request received once all
+/// responses have been served.
+///
+/// # Panics
+///
+/// Panics if no local port can be bound. The server task panics if it
+/// cannot talk to a client; awaiting the handle then fails the test.
+pub async fn serve(responses: Vec<Response>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind stand-in server");
+    let addr = listener.local_addr().expect("local addr");
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut chunk).await.expect("read request");
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&chunk[..n]);
+            }
+            stream
+                .write_all(response.to_http().as_bytes())
+                .await
+                .expect("write response");
+            requests.push(String::from_utf8_lossy(&request).to_string());
+        }
+        requests
+    });
+
+    (format!("http://{addr}"), handle)
+}
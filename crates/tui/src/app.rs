@@ -3,6 +3,11 @@
 //! This module provides the `App` struct which orchestrates the TUI
 //! application lifecycle including event handling, state updates, and rendering.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use chrono::Local;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -10,20 +15,42 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
-use whip_config::Config;
-use whip_protocol::{KanbanBoard, Message};
+use tokio::sync::mpsc::UnboundedReceiver;
+use whip_config::auth::TokenSource;
+use whip_config::watch::ConfigChange;
+use whip_config::{Config, ConfigLayer, Repository};
+use whip_protocol::{BoardDiff, KanbanBoard, KeyContext, Keymap, Message, NamedFilter, Task};
 
-// Note: render_board is used via self.render_board() wrapper, not directly
+// Note: render_board is used via self.render_board_area() wrapper, not directly
 
 use crate::{
     AppState, Focus,
-    event::{event_to_message, key_to_settings_message, poll_event},
-    layout::{HEADER_HEIGHT, MIN_HEIGHT, MIN_HEIGHT_WITH_HEADER, MIN_WIDTH, TASK_CARD_HEIGHT},
+    context_menu::ContextMenu,
+    credentials::CredentialCheck,
+    event::{
+        event_to_message, key_to_filter_message, key_to_search_message, key_to_settings_message,
+        poll_event,
+    },
+    layout::{HEADER_HEIGHT, MIN_HEIGHT, MIN_HEIGHT_WITH_HEADER, MIN_WIDTH},
+    notification::{ErrorLog, Notifications, RepoError, Severity},
+    repo_status::{RepoEvent, RepoStatus, RepoStatuses},
     settings_state::SettingsState,
     terminal::AppTerminal,
     widgets::{
-        calculate_metadata_height, description_area_dimensions, max_scroll_offset, render_board,
-        render_detail_panel, render_help_overlay, render_settings_panel,
+        board::lane_areas,
+        board::render_board_with_highlights,
+        calculate_metadata_height,
+        context_menu::{context_menu_item_at, render_context_menu},
+        description_area_dimensions,
+        help::{help_area, help_message_at},
+        lane::task_index_at,
+        max_scroll_offset,
+        notifications::history_visible_rows,
+        render_detail_panel, render_errors_pane, render_filter_bar, render_help_overlay,
+        render_notification_history, render_repo_status, render_search_bar, render_settings_panel,
+        render_toasts,
+        settings::{SettingsTarget, settings_target_at},
+        task_card::Highlights,
     },
 };
 
@@ -34,26 +61,61 @@ pub enum RunResult {
     Quit,
     /// User requested to refresh data (Ctrl+R).
     RefreshRequested,
+    /// User requested to test a repository's credentials from the settings
+    /// panel; see [`App::take_credential_test`].
+    CredentialTestRequested,
+    /// Repositories were added to the configuration and need fetching;
+    /// see [`App::take_pending_fetches`].
+    FetchRequested,
 }
 
 /// Function type for opening URLs in a browser.
 pub type BrowserOpener = fn(&str) -> std::io::Result<()>;
 
 /// Function type for saving configuration to disk.
-pub type ConfigSaver = fn(&Config) -> anyhow::Result<()>;
+///
+/// Receives the configuration as it was before the settings panel was
+/// opened, then the edited configuration.
+pub type ConfigSaver = fn(&Config, &Config) -> anyhow::Result<()>;
+
+/// Lines the detail view scrolls per step of the mouse wheel.
+const DETAIL_WHEEL_LINES: i32 = 3;
 
 /// Default browser opener that uses the system's default browser.
 fn default_browser_opener(url: &str) -> std::io::Result<()> {
     open::that(url)
 }
 
-/// Default config saver that writes to the user's config file.
-fn default_config_saver(config: &Config) -> anyhow::Result<()> {
-    let path = whip_config::persistence::default_user_config_path()?;
-    config.save_to(&path)?;
+/// Default config saver that writes the changes to the config's source file.
+///
+/// Falls back to the user's config file for a configuration that wasn't
+/// loaded from a file. Plaintext tokens are never written there; they go to
+/// the user's owner-only credentials file instead.
+fn default_config_saver(loaded: &Config, config: &Config) -> anyhow::Result<()> {
+    use whip_config::persistence::{default_credentials_path, default_user_config_path};
+
+    let config_path = match config.source.as_ref().and_then(ConfigLayer::path) {
+        Some(path) => path.to_path_buf(),
+        None => default_user_config_path()?,
+    };
+    config.save_changes(loaded, config_path, default_credentials_path()?)?;
     Ok(())
 }
 
+/// Returns the user config file that settings can be saved to instead of
+/// the file they were loaded from.
+fn user_config_location() -> Option<PathBuf> {
+    use whip_config::persistence::{default_user_config_path, find_user_config_file};
+
+    find_user_config_file().or_else(|| default_user_config_path().ok())
+}
+
+/// Returns `true` if the task is an issue of the repository, given by its
+/// [`Config::repo_key`].
+fn is_repo_task(task: &Task, repo: &str) -> bool {
+    task.github.as_ref().is_some_and(|gh| gh.repo_key() == repo)
+}
+
 /// The main application struct.
 ///
 /// Manages the application state and provides the main event loop.
@@ -70,10 +132,34 @@ pub struct App {
     settings_state: Option<SettingsState>,
     /// The application configuration.
     config: Config,
+    /// Key bindings, built from the configured keymap.
+    keymap: Keymap,
     /// Function to open URLs in a browser (injectable for testing).
     browser_opener: BrowserOpener,
     /// Function to save config to disk (injectable for testing).
     config_saver: ConfigSaver,
+    /// The user config file, offered in settings as a place to save to.
+    user_config_path: Option<PathBuf>,
+    /// Loading status of each configured repository, shown in the header.
+    repo_statuses: RepoStatuses,
+    /// Receiver for repository updates from background fetches, if any.
+    repo_events: Option<UnboundedReceiver<RepoEvent>>,
+    /// Receiver for configuration reloaded from disk, if any.
+    config_changes: Option<UnboundedReceiver<ConfigChange>>,
+    /// A configuration reloaded while the settings panel was open.
+    deferred_config: Option<Config>,
+    /// Repositories added to the configuration, not yet fetched.
+    pending_fetches: Vec<Repository>,
+    /// Log of repository errors, shown in the errors pane.
+    errors: ErrorLog,
+    /// Notifications shown as toasts and kept in the history overlay.
+    notifications: Notifications,
+    /// The token source that won for each repository, shown in settings.
+    token_sources: HashMap<String, Option<TokenSource>>,
+    /// The latest credential test of each repository, shown in settings.
+    credential_checks: HashMap<String, CredentialCheck>,
+    /// A credential test requested from the settings panel, not yet started.
+    credential_test: Option<(Repository, Config)>,
 }
 
 impl App {
@@ -102,8 +188,20 @@ impl App {
             header_visible: true,
             settings_state: None,
             config: Config::default(),
+            keymap: Keymap::default(),
             browser_opener: default_browser_opener,
             config_saver: default_config_saver,
+            user_config_path: user_config_location(),
+            repo_statuses: RepoStatuses::default(),
+            repo_events: None,
+            config_changes: None,
+            deferred_config: None,
+            pending_fetches: Vec::new(),
+            errors: ErrorLog::default(),
+            notifications: Notifications::default(),
+            token_sources: HashMap::new(),
+            credential_checks: HashMap::new(),
+            credential_test: None,
         }
     }
 
@@ -134,9 +232,21 @@ impl App {
             last_area: Rect::default(),
             header_visible: true,
             settings_state: None,
+            keymap: config.keymap.keymap(),
             config,
             browser_opener: default_browser_opener,
             config_saver: default_config_saver,
+            user_config_path: user_config_location(),
+            repo_statuses: RepoStatuses::default(),
+            repo_events: None,
+            config_changes: None,
+            deferred_config: None,
+            pending_fetches: Vec::new(),
+            errors: ErrorLog::default(),
+            notifications: Notifications::default(),
+            token_sources: HashMap::new(),
+            credential_checks: HashMap::new(),
+            credential_test: None,
         }
     }
 
@@ -160,6 +270,42 @@ impl App {
         self
     }
 
+    /// Sets the user config file offered in settings as a place to save to.
+    ///
+    /// Defaults to the user's config file (`~/.config/whip/config.json5`).
+    #[must_use]
+    pub fn with_user_config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_config_path = Some(path.into());
+        self
+    }
+
+    /// Sets the initial loading status of each repository.
+    #[must_use]
+    pub fn with_repo_statuses(mut self, statuses: RepoStatuses) -> Self {
+        self.repo_statuses = statuses;
+        self
+    }
+
+    /// Sets the channel on which background fetches deliver repository updates.
+    ///
+    /// Pending events are drained on every iteration of [`run`](Self::run),
+    /// so fresh data appears on the board as soon as it arrives.
+    #[must_use]
+    pub fn with_repo_events(mut self, events: UnboundedReceiver<RepoEvent>) -> Self {
+        self.repo_events = Some(events);
+        self
+    }
+
+    /// Sets the channel on which configuration reloaded from disk arrives.
+    ///
+    /// See [`apply_config_change`](Self::apply_config_change) for how each
+    /// change is applied.
+    #[must_use]
+    pub fn with_config_changes(mut self, changes: UnboundedReceiver<ConfigChange>) -> Self {
+        self.config_changes = Some(changes);
+        self
+    }
+
     /// Returns a reference to the application state.
     #[must_use]
     pub fn state(&self) -> &AppState {
@@ -172,6 +318,37 @@ impl App {
         &self.config
     }
 
+    /// Returns the loading status of each repository.
+    #[must_use]
+    pub fn repo_statuses(&self) -> &RepoStatuses {
+        &self.repo_statuses
+    }
+
+    /// Returns the log of repository errors.
+    #[must_use]
+    pub fn errors(&self) -> &ErrorLog {
+        &self.errors
+    }
+
+    /// Returns the notification queue and its history.
+    #[must_use]
+    pub fn notifications(&self) -> &Notifications {
+        &self.notifications
+    }
+
+    /// Shows a notification to the user and records it in the history.
+    ///
+    /// Any subsystem with access to the app can report progress or
+    /// failures this way instead of printing to the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - How prominently to show the notification
+    /// * `message` - The message to display
+    pub fn notify(&mut self, severity: Severity, message: impl Into<String>) {
+        self.notifications.push(severity, message);
+    }
+
     /// Returns whether the settings panel is open.
     #[must_use]
     pub fn is_settings_open(&self) -> bool {
@@ -180,17 +357,302 @@ impl App {
 
     /// Replaces the current board with a new one.
     ///
-    /// This is used after refreshing data from external sources.
-    /// Selection state is reset since task IDs may have changed.
+    /// This is used after refreshing data from external sources. The
+    /// selection, the detail view and its scroll position stay on the
+    /// selected task, wherever it moved to; see
+    /// [`AppState::replace_board`].
     pub fn set_board(&mut self, board: KanbanBoard) {
-        self.state = AppState::new(board);
+        self.state.replace_board(board);
+        // The refreshed description may be shorter
+        self.clamp_scroll_to_content();
+    }
+
+    /// Takes the pending credential test request, if any.
+    ///
+    /// Returns the repository to test and the configuration to resolve its
+    /// token from. The caller is expected to run the test and report the
+    /// outcome as a [`RepoEvent::CredentialsChecked`].
+    pub fn take_credential_test(&mut self) -> Option<(Repository, Config)> {
+        self.credential_test.take()
+    }
+
+    /// Takes the repositories that were added to the configuration and have
+    /// not been fetched yet.
+    ///
+    /// The caller is expected to fetch each one and report the results as
+    /// [`RepoEvent`]s.
+    pub fn take_pending_fetches(&mut self) -> Vec<Repository> {
+        std::mem::take(&mut self.pending_fetches)
+    }
+
+    /// Marks every repository as being refreshed.
+    ///
+    /// Call this when starting a new round of background fetches; the board
+    /// keeps showing the current data until fresh results arrive.
+    pub fn mark_repos_refreshing(&mut self) {
+        self.repo_statuses.mark_refreshing();
+    }
+
+    /// Applies an update from a background repository fetch.
+    ///
+    /// Freshly loaded tasks replace every task currently shown for the
+    /// repository. A failure is logged and shown as an error notification;
+    /// the previously shown tasks stay in place. Planned label changes are
+    /// listed in a warning notification, pointing to `whip sync-labels` to
+    /// apply them. Fetch results for repositories that are no longer
+    /// configured (removed by a reload while their fetch ran) are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The repository update to apply.
+    pub fn apply_repo_event(&mut self, event: RepoEvent) {
+        match event {
+            // Applying it would bring back a repository a reload removed
+            RepoEvent::Loaded { repo, .. } | RepoEvent::Failed { repo, .. }
+                if !self.is_configured(&repo) => {}
+            RepoEvent::Loaded { repo, tasks } => {
+                let selected = self.state.selected_task_id();
+                let previous = self.repo_was_shown(&repo).then(|| self.state.board.clone());
+                self.remove_repo_tasks(&repo);
+                for task in tasks {
+                    self.state.board.add_task(task);
+                }
+                self.state.reselect(selected);
+                if let Some(previous) = previous {
+                    let diff = BoardDiff::between(&previous, &self.state.board);
+                    self.state.record_changes(&diff);
+                }
+                self.clamp_scroll_to_content();
+                self.repo_statuses.set(repo, RepoStatus::Fresh);
+            }
+            RepoEvent::Failed { repo, kind, error } => {
+                // Some errors append multi-line context (e.g. backtraces);
+                // only the first line is meaningful on screen
+                let error = error.lines().next().unwrap_or_default().to_string();
+                self.notify(Severity::Error, format!("{repo}: {error}"));
+                self.repo_statuses
+                    .set(repo.clone(), RepoStatus::Failed { kind });
+                self.errors.push(RepoError {
+                    repo,
+                    kind,
+                    message: error,
+                    occurred_at: Local::now(),
+                });
+            }
+            RepoEvent::Authenticated { repo, source } => {
+                self.token_sources.insert(repo, source);
+            }
+            RepoEvent::CredentialsChecked { repo, check } => {
+                if let Some(source) = check.source() {
+                    self.token_sources
+                        .insert(repo.clone(), Some(source.clone()));
+                } else if check == CredentialCheck::Unauthenticated {
+                    self.token_sources.insert(repo.clone(), None);
+                }
+                if let Some(settings) = self.settings_state.as_mut() {
+                    settings.set_credential_check(repo.clone(), check.clone());
+                }
+                self.credential_checks.insert(repo, check);
+            }
+            RepoEvent::LabelSyncPlanned { repo, changes } => {
+                if !changes.is_empty() {
+                    self.notify(
+                        Severity::Warning,
+                        format!(
+                            "{repo}: labels differ ({}); run `whip sync-labels` to review and apply",
+                            changes.join(", ")
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Applies configuration reloaded from disk.
+    ///
+    /// A reloaded configuration replaces the current one: the board takes
+    /// on changed lanes, repositories that were removed disappear from the
+    /// board, and repositories that were added are queued for fetching (see
+    /// [`take_pending_fetches`](Self::take_pending_fetches)). While the
+    /// settings panel is open the reload is held back until it closes, so
+    /// edits in progress are not lost.
+    ///
+    /// A configuration that failed to load is shown as an error notification
+    /// and the current configuration stays in use.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - The outcome of reloading the configuration.
+    pub fn apply_config_change(&mut self, change: ConfigChange) {
+        match change {
+            ConfigChange::Reloaded(config) if self.settings_state.is_some() => {
+                self.deferred_config = Some(*config);
+            }
+            ConfigChange::Reloaded(config) => {
+                if *config == self.config {
+                    return;
+                }
+                let previous = std::mem::replace(&mut self.config, *config);
+                if self.config.lanes != previous.lanes {
+                    self.state.set_lanes(self.config.lane_definitions());
+                }
+                if self.config.filters != previous.filters {
+                    self.refresh_named_filter();
+                }
+                if self.config.keymap != previous.keymap {
+                    self.keymap = self.config.keymap.keymap();
+                }
+                self.sync_repositories(&previous);
+                self.notify(Severity::Info, "Configuration reloaded");
+            }
+            ConfigChange::Invalid(e) => {
+                self.notify(
+                    Severity::Error,
+                    format!("Config not reloaded, keeping the previous one: {e}"),
+                );
+            }
+        }
+    }
+
+    /// Adds `saved` to the configured filters, replacing any with the same
+    /// name (ignoring case), saves the configuration, and makes it the
+    /// active filter.
+    fn save_named_filter(&mut self, saved: NamedFilter) {
+        let loaded = self.config.clone();
+        match self
+            .config
+            .filters
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&saved.name))
+        {
+            Some(existing) => *existing = saved.clone(),
+            None => self.config.filters.push(saved.clone()),
+        }
+        match (self.config_saver)(&loaded, &self.config) {
+            Ok(()) => self.notify(Severity::Success, format!("Saved filter '{}'", saved.name)),
+            Err(e) => self.notify(Severity::Error, format!("Failed to save filter: {e}")),
+        }
+        self.state.set_filter(saved.filter, Some(saved.name));
+    }
+
+    /// Picks up changes to the active named filter after the configuration
+    /// changed. A filter that is no longer configured stays active, shown by
+    /// its expression instead of its name.
+    fn refresh_named_filter(&mut self) {
+        let Some(name) = self.state.filter_name.clone() else {
+            return;
+        };
+        match self.config.named_filter(&name) {
+            Some(saved) if saved.filter != self.state.filter => {
+                self.state
+                    .set_filter(saved.filter.clone(), Some(saved.name.clone()));
+            }
+            Some(_) => {}
+            None => self.state.filter_name = None,
+        }
+    }
+
+    /// Updates the board and repository state after the configured
+    /// repositories changed from `previous`.
+    fn sync_repositories(&mut self, previous: &Config) {
+        let selected = self.state.selected_task_id();
+        let configured = |config: &Config, key: &str| {
+            config
+                .repositories
+                .iter()
+                .any(|repo| config.repo_key(repo) == key)
+        };
+        for repo in &previous.repositories {
+            let key = previous.repo_key(repo);
+            if configured(&self.config, &key) {
+                continue;
+            }
+            self.remove_repo_tasks(&key);
+            self.repo_statuses.remove(&key);
+            self.token_sources.remove(&key);
+            self.credential_checks.remove(&key);
+            self.pending_fetches
+                .retain(|pending| self.config.repo_key(pending) != key);
+        }
+        for repo in &self.config.repositories {
+            let key = self.config.repo_key(repo);
+            if configured(previous, &key) {
+                continue;
+            }
+            self.repo_statuses.set(key, RepoStatus::Loading);
+            self.pending_fetches.push(repo.clone());
+        }
+        self.state.reselect(selected);
+    }
+
+    /// Returns `true` if the repository with key `repo` is configured.
+    fn is_configured(&self, repo: &str) -> bool {
+        self.config
+            .repositories
+            .iter()
+            .any(|configured| self.config.repo_key(configured) == repo)
+    }
+
+    /// Returns `true` if the board showed issues of the repository, cached
+    /// or fetched, so that what a new fetch changes can be told apart.
+    ///
+    /// A repository loaded without issues counts as shown too. Whatever its
+    /// status (a refresh after a failure marks it loading), a repository
+    /// whose issues are on the board was shown.
+    fn repo_was_shown(&self, repo: &str) -> bool {
+        matches!(
+            self.repo_statuses.get(repo),
+            Some(RepoStatus::Stale | RepoStatus::Fresh)
+        ) || self
+            .state
+            .board
+            .lanes
+            .iter()
+            .flat_map(|lane| &lane.tasks)
+            .any(|task| is_repo_task(task, repo))
+    }
+
+    /// Removes every task of a repository from the board.
+    fn remove_repo_tasks(&mut self, repo: &str) {
+        self.state
+            .board
+            .retain_tasks(|task| !is_repo_task(task, repo));
+    }
+
+    /// Applies all configuration changes that have arrived since the last call.
+    fn drain_config_changes(&mut self) {
+        let Some(changes) = self.config_changes.as_mut() else {
+            return;
+        };
+        let mut pending = Vec::new();
+        while let Ok(change) = changes.try_recv() {
+            pending.push(change);
+        }
+        for change in pending {
+            self.apply_config_change(change);
+        }
+    }
+
+    /// Applies all repository updates that have arrived since the last call.
+    fn drain_repo_events(&mut self) {
+        let Some(events) = self.repo_events.as_mut() else {
+            return;
+        };
+        let mut pending = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            pending.push(event);
+        }
+        for event in pending {
+            self.apply_repo_event(event);
+        }
     }
 
     /// Updates the application state based on a message.
     ///
     /// When the help overlay is visible, most messages are intercepted to
     /// dismiss the help instead of their normal action. Only `Quit` and
-    /// `ToggleHelp` work normally when help is shown.
+    /// `ToggleHelp` work normally when help is shown, and clicking an entry
+    /// of the help runs its action.
     ///
     /// # Arguments
     ///
@@ -205,9 +667,25 @@ impl App {
                 Message::CloseSettings | Message::Escape if !settings.is_editing() => {
                     // Close settings and save changes
                     if let Some(settings) = self.settings_state.take() {
-                        self.config = settings.into_config();
+                        let loaded = std::mem::replace(&mut self.config, settings.into_config());
                         // Save to disk using the injected saver
-                        let _ = (self.config_saver)(&self.config);
+                        match (self.config_saver)(&loaded, &self.config) {
+                            Ok(()) => self.notify(Severity::Success, "Settings saved"),
+                            Err(e) => {
+                                self.notify(
+                                    Severity::Error,
+                                    format!("Failed to save settings: {e}"),
+                                );
+                            }
+                        }
+                        self.sync_repositories(&loaded);
+                        // Edits were merged into the file on disk, so a reload
+                        // including them follows; otherwise apply the held one.
+                        if let Some(config) = self.deferred_config.take()
+                            && loaded == self.config
+                        {
+                            self.apply_config_change(ConfigChange::Reloaded(Box::new(config)));
+                        }
                     }
                     self.state.focus = Focus::Board;
                 }
@@ -236,6 +714,19 @@ impl App {
                 Message::SettingsDelete => {
                     let _ = settings.request_delete();
                 }
+                Message::SettingsTestCredentials => {
+                    if let Some(repo) = settings.selected_auth_repository().cloned() {
+                        let key = settings.config().repo_key(&repo);
+                        settings.set_credential_check(key.clone(), CredentialCheck::Pending);
+                        self.credential_checks.insert(key, CredentialCheck::Pending);
+                        // Test against the settings being edited, so changes
+                        // can be checked before they are saved
+                        self.credential_test = Some((repo, settings.config().clone()));
+                    }
+                }
+                Message::SettingsSaveToUserConfig => {
+                    let _ = settings.toggle_save_to_user_config();
+                }
                 Message::SettingsInput { ch } => {
                     // Handle delete confirmation
                     if settings.is_delete_pending() {
@@ -268,11 +759,79 @@ impl App {
                 Message::Select if !settings.is_editing() => {
                     settings.toggle_selected();
                 }
+                Message::ClickAt { column, row }
+                    if !settings.is_editing() && !settings.is_delete_pending() =>
+                {
+                    match settings_target_at(settings, self.last_area, column, row) {
+                        Some(SettingsTarget::Section(section)) => settings.select_section(section),
+                        // Clicking the selected item again acts on it, like Enter
+                        Some(SettingsTarget::Item(index)) if index == settings.selected_item() => {
+                            settings.start_edit();
+                        }
+                        Some(SettingsTarget::Item(index)) => {
+                            let _ = settings.select_item(index);
+                        }
+                        None => {}
+                    }
+                }
+                Message::ScrollAt { delta, .. }
+                    if !settings.is_editing() && !settings.is_delete_pending() =>
+                {
+                    let last = settings.item_count().saturating_sub(1);
+                    let index = settings
+                        .selected_item()
+                        .saturating_add_signed(isize::try_from(delta).unwrap_or_default())
+                        .min(last);
+                    let _ = settings.select_item(index);
+                }
                 _ => {}
             }
             return;
         }
 
+        // When the notification history is visible, Up/Down and the wheel
+        // scroll it and any other key except Quit dismisses it
+        if self.state.notifications_visible {
+            let max_scroll = self
+                .notifications
+                .history_len()
+                .saturating_sub(history_visible_rows(self.last_area));
+            match msg {
+                Message::Quit => {
+                    self.should_quit = true;
+                }
+                Message::NavigateUp => {
+                    self.state.scroll_notifications(-1, max_scroll);
+                }
+                Message::NavigateDown => {
+                    self.state.scroll_notifications(1, max_scroll);
+                }
+                Message::ScrollAt { delta, .. } => {
+                    let delta = isize::try_from(delta).unwrap_or_default();
+                    self.state.scroll_notifications(delta, max_scroll);
+                }
+                _ => {
+                    self.state.toggle_notifications();
+                }
+            }
+            return;
+        }
+
+        // When the errors pane is visible, any key other than Quit dismisses
+        // it; the wheel leaves it open
+        if self.state.errors_visible {
+            match msg {
+                Message::Quit => {
+                    self.should_quit = true;
+                }
+                Message::ScrollAt { .. } => {}
+                _ => {
+                    self.state.toggle_errors();
+                }
+            }
+            return;
+        }
+
         // When help is visible, most keys should dismiss it
         if self.state.help_visible {
             match msg {
@@ -282,6 +841,20 @@ impl App {
                 Message::ToggleHelp | Message::Escape => {
                     self.state.toggle_help();
                 }
+                // Clicking an entry runs its action; clicks elsewhere in the
+                // overlay and the wheel leave it open
+                Message::ClickAt { column, row }
+                    if help_area(&self.keymap, self.last_area).contains((column, row).into()) =>
+                {
+                    if let Some(action) = help_message_at(&self.keymap, self.last_area, column, row)
+                    {
+                        self.state.toggle_help();
+                        if action != Message::ToggleHelp {
+                            self.update(action);
+                        }
+                    }
+                }
+                Message::ScrollAt { .. } => {}
                 // Any other key dismisses help
                 _ => {
                     let _ = self.state.dismiss_help();
@@ -290,32 +863,132 @@ impl App {
             return;
         }
 
+        // While the context menu is open, navigation and the wheel move its
+        // highlight and choosing an entry sends its message; anything else
+        // closes it
+        if let Some(mut menu) = self.state.context_menu.take() {
+            let action = match msg {
+                Message::Quit => {
+                    self.should_quit = true;
+                    None
+                }
+                Message::NavigateUp
+                | Message::NavigateDown
+                | Message::NavigateFirst
+                | Message::NavigateLast
+                | Message::ScrollAt { .. } => {
+                    menu.move_selection(match msg {
+                        Message::NavigateUp => -1,
+                        Message::NavigateFirst => i32::MIN,
+                        Message::NavigateLast => i32::MAX,
+                        Message::ScrollAt { delta, .. } => delta,
+                        _ => 1,
+                    });
+                    self.state.context_menu = Some(menu);
+                    None
+                }
+                Message::Select => menu.selected_message().cloned(),
+                Message::ClickAt { column, row } => {
+                    context_menu_item_at(&menu, &self.keymap, self.last_area, column, row)
+                        .map(|index| menu.items()[index].message.clone())
+                }
+                // A right click elsewhere opens the menu there instead
+                Message::RightClickAt { .. } => Some(msg),
+                _ => None,
+            };
+            if let Some(action) = action {
+                self.update(action);
+            }
+            return;
+        }
+
         match msg {
             Message::Quit => {
                 self.should_quit = true;
             }
             Message::OpenSettings => {
-                self.settings_state = Some(SettingsState::new(self.config.clone()));
+                let mut settings = SettingsState::new(self.config.clone())
+                    .with_token_sources(self.token_sources.clone())
+                    .with_credential_checks(self.credential_checks.clone());
+                if let Some(path) = &self.user_config_path {
+                    settings = settings.with_user_config_path(path);
+                }
+                self.settings_state = Some(settings);
                 self.state.focus = Focus::Settings;
             }
             Message::Escape => {
-                // Contextual escape: close detail panel if open, or clear selection
+                // Contextual escape: close detail panel if open, end the
+                // search if one is active, clear selection, or else dismiss
+                // the changes of the last refreshes
                 if self.state.detail_visible {
                     self.state.toggle_detail();
-                } else {
+                } else if self.state.search.is_active() {
+                    self.state.search.clear();
+                } else if self.state.selected_task.is_some() {
                     self.state.clear_selection();
+                } else {
+                    self.state.changes.clear();
                 }
             }
-            Message::NavigateLeft => {
-                if self.state.focus == Focus::Board {
-                    self.state.navigate_left();
+            Message::StartSearch if !self.state.detail_visible => {
+                self.state.search.start();
+            }
+            Message::SearchInput { ch } => {
+                self.state.search.push(ch);
+                let _ = self.state.select_search_result(true, true);
+            }
+            Message::SearchBackspace => {
+                self.state.search.pop();
+                let _ = self.state.select_search_result(true, true);
+            }
+            Message::SearchConfirm => {
+                self.state.search.confirm();
+            }
+            Message::SearchCancel => {
+                self.state.search.clear();
+            }
+            Message::SearchNext => {
+                let _ = self.state.select_search_result(true, false);
+            }
+            Message::SearchPrevious => {
+                let _ = self.state.select_search_result(false, false);
+            }
+            Message::EditFilter if !self.state.detail_visible => {
+                let current = self.state.filter_label().unwrap_or_default();
+                self.state.filter_bar.start(&current);
+            }
+            Message::FilterInput { ch } => {
+                self.state.filter_bar.push(ch);
+            }
+            Message::FilterBackspace => {
+                self.state.filter_bar.pop();
+            }
+            Message::FilterCycleNamed { delta } => {
+                self.state.filter_bar.cycle(&self.config.filters, delta);
+            }
+            Message::FilterApply if self.state.filter_bar.saving().is_some() => {
+                if let Ok(saved) = self.state.filter_bar.save() {
+                    self.save_named_filter(saved);
                 }
             }
-            Message::NavigateRight => {
-                if self.state.focus == Focus::Board {
-                    self.state.navigate_right();
+            Message::FilterApply => {
+                // Invalid input keeps the bar open, showing the error
+                if let Ok((filter, name)) = self.state.filter_bar.apply(&self.config.filters) {
+                    self.state.set_filter(filter, name);
                 }
             }
+            Message::FilterSave => {
+                self.state.filter_bar.start_save(&self.config.filters);
+            }
+            Message::FilterCancel => {
+                self.state.filter_bar.cancel();
+            }
+            Message::NavigateLeft if self.state.focus == Focus::Board => {
+                self.state.navigate_left();
+            }
+            Message::NavigateRight if self.state.focus == Focus::Board => {
+                self.state.navigate_right();
+            }
             Message::NavigateUp => {
                 if self.state.focus == Focus::Board {
                     self.state.navigate_up();
@@ -332,35 +1005,84 @@ impl App {
                     self.clamp_scroll_to_content();
                 }
             }
-            Message::Select => {
-                // Only open detail if a task is actually selected
-                if self.state.selected_task.is_some() {
-                    self.state.toggle_detail();
+            Message::NavigateFirst => {
+                if self.state.focus == Focus::Board {
+                    self.state.navigate_first();
+                } else if self.state.focus == Focus::Detail {
+                    self.state.scroll_detail(i16::MIN);
                 }
-                // Otherwise do nothing (could ring bell, but simpler to ignore)
             }
-            Message::Back => {
-                if self.state.detail_visible {
-                    self.state.toggle_detail();
+            Message::NavigateLast => {
+                if self.state.focus == Focus::Board {
+                    self.state.navigate_last();
+                } else if self.state.focus == Focus::Detail {
+                    self.state.scroll_detail(i16::MAX);
+                    self.clamp_scroll_to_content();
                 }
             }
+            // Only open detail if a task is actually selected; otherwise do
+            // nothing (could ring bell, but simpler to ignore)
+            Message::Select if self.state.selected_task.is_some() => {
+                self.state.toggle_detail();
+            }
+            Message::Back if self.state.detail_visible => {
+                self.state.toggle_detail();
+            }
             Message::ToggleHelp => {
                 self.state.toggle_help();
             }
+            Message::ToggleErrors => {
+                self.state.toggle_errors();
+            }
+            Message::ToggleNotifications => {
+                self.state.toggle_notifications();
+            }
             Message::Refresh => {
                 self.refresh_requested = true;
+                let count = self.config.repositories.len();
+                if count > 0 {
+                    let noun = if count == 1 {
+                        "repository"
+                    } else {
+                        "repositories"
+                    };
+                    self.notify(Severity::Info, format!("Refreshing {count} {noun}…"));
+                }
             }
             Message::OpenInBrowser => {
                 self.open_selected_in_browser();
             }
+            Message::CycleSort if self.state.focus == Focus::Board => {
+                let _ = self.state.cycle_lane_sort();
+            }
             Message::ClickAt { column, row } => {
                 self.handle_click(column, row);
             }
+            Message::RightClickAt { column, row } => {
+                self.open_context_menu(column, row);
+            }
+            Message::ScrollAt { column, row, delta } => {
+                self.handle_scroll(column, row, delta);
+            }
             // Settings messages are handled above when settings is open
             _ => {}
         }
     }
 
+    /// Returns where keys are pressed, to look them up in the keymap.
+    ///
+    /// The settings panel is handled separately, as its edit modes don't
+    /// use the keymap.
+    fn key_context(&self) -> KeyContext {
+        if self.state.help_visible {
+            KeyContext::Help
+        } else if self.state.detail_visible {
+            KeyContext::Detail
+        } else {
+            KeyContext::Board
+        }
+    }
+
     /// Handles a mouse click at the given coordinates.
     ///
     /// Behavior depends on context:
@@ -378,42 +1100,7 @@ impl App {
             return;
         }
 
-        // Compute the board area (content area below header, if visible)
-        let header_offset = if self.header_visible {
-            HEADER_HEIGHT
-        } else {
-            0
-        };
-        let board_area = Rect {
-            x: self.last_area.x,
-            y: self.last_area.y + header_offset,
-            width: self.last_area.width,
-            height: self.last_area.height.saturating_sub(header_offset),
-        };
-
-        // Check if click is within board area
-        if !board_area.contains((column, row).into()) {
-            return;
-        }
-
-        // Compute which lane was clicked (4 equal columns)
-        let lane_width = board_area.width / 4;
-        if lane_width == 0 {
-            return;
-        }
-        let relative_x = column.saturating_sub(board_area.x);
-        let lane_idx = (relative_x / lane_width).min(3) as usize;
-
-        // Compute which task was clicked within the lane
-        // Account for the lane border (1 row for top border)
-        let relative_y = row.saturating_sub(board_area.y + 1);
-        let task_idx = (relative_y / TASK_CARD_HEIGHT) as usize;
-
-        // Validate the task exists in the clicked lane
-        let Some(lane) = self.state.board.lanes.get(lane_idx) else {
-            return;
-        };
-        if task_idx < lane.len() {
+        if let Some((lane_idx, Some(task_idx))) = self.board_target_at(column, row) {
             // Select the lane and task
             self.state.selected_lane = lane_idx;
             self.state.selected_task = Some(task_idx);
@@ -422,27 +1109,130 @@ impl App {
         }
     }
 
-    /// Opens the currently selected task's GitHub URL in the default browser.
+    /// Opens the context menu of the task under a right click.
     ///
-    /// Does nothing if no task is selected or if the task has no GitHub source.
-    /// Uses the injected `browser_opener` function, which can be customized for testing.
-    fn open_selected_in_browser(&self) {
-        let Some(task) = self.state.selected_task() else {
-            return;
-        };
-        let Some(github) = &task.github else {
+    /// On the board the task under the pointer is selected first; in the
+    /// detail view the menu acts on the task shown. Nothing opens while the
+    /// search line or filter bar takes typed input.
+    fn open_context_menu(&mut self, column: u16, row: u16) {
+        if self.state.search.is_editing() || self.state.filter_bar.is_editing() {
             return;
-        };
-        // Ignore errors - nothing useful we can do if browser fails to open
-        let _ = (self.browser_opener)(&github.url);
+        }
+        if !self.state.detail_visible {
+            if self.state.focus != Focus::Board {
+                return;
+            }
+            let Some((lane_idx, Some(task_idx))) = self.board_target_at(column, row) else {
+                return;
+            };
+            self.state.selected_lane = lane_idx;
+            self.state.selected_task = Some(task_idx);
+        }
+
+        let in_detail = self.state.detail_visible;
+        self.state.context_menu = self
+            .state
+            .selected_task()
+            .map(|task| ContextMenu::for_task(task, in_detail, (column, row)));
     }
 
-    /// Handles clicks in the detail view.
+    /// Handles the mouse wheel at the given coordinates.
     ///
-    /// Clicking on the metadata area (where the GitHub link is displayed) opens
-    /// the issue in the browser.
-    fn handle_detail_click(&mut self, _column: u16, row: u16) {
-        let Some(task) = self.state.selected_task() else {
+    /// The detail view scrolls its description. On the board, the wheel moves
+    /// the selection through the lane under the pointer, which scrolls the
+    /// lane to keep it in view.
+    fn handle_scroll(&mut self, column: u16, row: u16, delta: i32) {
+        if self.state.detail_visible {
+            let lines = delta
+                .saturating_mul(DETAIL_WHEEL_LINES)
+                .clamp(i16::MIN.into(), i16::MAX.into());
+            self.state
+                .scroll_detail(i16::try_from(lines).unwrap_or_default());
+            self.clamp_scroll_to_content();
+            return;
+        }
+        if self.state.focus != Focus::Board {
+            return;
+        }
+        if let Some((lane_idx, _)) = self.board_target_at(column, row) {
+            self.state.scroll_lane(lane_idx, delta);
+        }
+    }
+
+    /// Returns the area the board is drawn in, below the header and above
+    /// the search line or filter bar.
+    fn board_area(&self) -> Rect {
+        let header_offset = if self.header_visible {
+            HEADER_HEIGHT
+        } else {
+            0
+        };
+        // The search line or filter bar takes the bottom row of the board area
+        let search_offset = u16::from(self.bottom_bar_visible());
+        Rect {
+            x: self.last_area.x,
+            y: self.last_area.y + header_offset,
+            width: self.last_area.width,
+            height: self
+                .last_area
+                .height
+                .saturating_sub(header_offset + search_offset),
+        }
+    }
+
+    /// Returns the lane drawn at a position, with the task whose card is
+    /// drawn there, if any.
+    ///
+    /// Uses the same columns and lane scroll as rendering, so the focused
+    /// lane, scrolled to keep its selected task in view, maps rows to the
+    /// cards shown rather than to the first tasks of the lane.
+    fn board_target_at(&self, column: u16, row: u16) -> Option<(usize, Option<usize>)> {
+        let board_area = self.board_area();
+        if !board_area.contains((column, row).into()) {
+            return None;
+        }
+
+        let lane_areas = lane_areas(board_area, self.state.board.lanes.len());
+        let lane_idx = lane_areas
+            .iter()
+            .position(|lane_area| lane_area.contains((column, row).into()))?;
+
+        // Only the focused lane shows a selection, so only it is scrolled
+        let selected = if lane_idx == self.state.selected_lane {
+            self.state.selected_task
+        } else {
+            None
+        };
+        let task_idx = task_index_at(
+            self.state.visible_len(lane_idx),
+            selected,
+            lane_areas[lane_idx],
+            row,
+        );
+        Some((lane_idx, task_idx))
+    }
+
+    /// Opens the currently selected task's GitHub URL in the default browser.
+    ///
+    /// Does nothing if no task is selected or if the task has no GitHub source.
+    /// Uses the injected `browser_opener` function, which can be customized for testing.
+    fn open_selected_in_browser(&self) {
+        let Some(task) = self.state.selected_task() else {
+            return;
+        };
+        let Some(github) = &task.github else {
+            return;
+        };
+        // Ignore errors - nothing useful we can do if browser fails to open
+        let _ = (self.browser_opener)(&github.url);
+    }
+
+    /// Handles clicks in the detail view.
+    ///
+    /// Clicking on the metadata area (where the GitHub link is displayed) opens
+    /// the issue in the browser.
+    fn handle_detail_click(&mut self, _column: u16, row: u16) {
+        let Some(task) = self.state.selected_task() else {
             return;
         };
 
@@ -571,10 +1361,34 @@ impl App {
             self.render_board_area(frame, content_area);
         }
 
+        // Render active notifications over the content, dropping expired ones
+        self.notifications.expire(Instant::now());
+        render_toasts(&self.notifications, content_area, frame.buffer_mut());
+
+        // Render the context menu over the content it was opened on
+        if let Some(menu) = &self.state.context_menu {
+            render_context_menu(menu, &self.keymap, area, frame.buffer_mut());
+        }
+
+        // Render notification history on top if visible
+        if self.state.notifications_visible {
+            render_notification_history(
+                &self.notifications,
+                self.state.notifications_scroll,
+                area,
+                frame.buffer_mut(),
+            );
+        }
+
+        // Render errors pane on top if visible
+        if self.state.errors_visible {
+            render_errors_pane(&self.errors, area, frame.buffer_mut());
+        }
+
         // Render help overlay on top if visible
         if self.state.help_visible {
             let buf = frame.buffer_mut();
-            render_help_overlay(area, buf);
+            render_help_overlay(&self.keymap, area, buf);
         }
 
         // Render settings panel overlay on top if open
@@ -632,6 +1446,8 @@ impl App {
     ///         match app.run(&mut terminal).await? {
     ///             RunResult::Quit => break,
     ///             RunResult::RefreshRequested => { /* refresh data */ }
+    ///             RunResult::CredentialTestRequested => { /* test credentials */ }
+    ///             RunResult::FetchRequested => { /* fetch new repositories */ }
     ///         }
     ///     }
     ///
@@ -643,6 +1459,10 @@ impl App {
         use crossterm::event::Event;
 
         loop {
+            // Apply any fresh data from background fetches
+            self.drain_config_changes();
+            self.drain_repo_events();
+
             // Render
             terminal.draw(|frame| self.view(frame))?;
 
@@ -657,12 +1477,20 @@ impl App {
                             .settings_state
                             .as_ref()
                             .is_some_and(|s| s.is_delete_pending());
-                        key_to_settings_message(key, is_editing, is_delete_pending)
+                        key_to_settings_message(key, is_editing, is_delete_pending, &self.keymap)
                     } else {
-                        event_to_message(&event)
+                        event_to_message(&event, &self.keymap, KeyContext::Settings)
                     }
+                } else if self.state.filter_bar.is_editing()
+                    && let Event::Key(key) = event
+                {
+                    key_to_filter_message(key)
+                } else if self.state.search.is_active()
+                    && let Event::Key(key) = event
+                {
+                    key_to_search_message(key, self.state.search.is_editing(), &self.keymap)
                 } else {
-                    event_to_message(&event)
+                    event_to_message(&event, &self.keymap, self.key_context())
                 };
 
                 if let Some(msg) = msg {
@@ -680,6 +1508,16 @@ impl App {
                 self.refresh_requested = false;
                 return Ok(RunResult::RefreshRequested);
             }
+
+            // Check for credential test request
+            if self.credential_test.is_some() {
+                return Ok(RunResult::CredentialTestRequested);
+            }
+
+            // Check for repositories added to the configuration
+            if !self.pending_fetches.is_empty() {
+                return Ok(RunResult::FetchRequested);
+            }
         }
     }
 
@@ -693,10 +1531,50 @@ impl App {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        // Split inner area: title left, help cue right
-        let [title_area, help_area] = Layout::horizontal([
+        // The active filter and the unseen changes, if any, are shown after
+        // the title
+        let mut context = Vec::new();
+        if let Some(label) = self.state.filter_label() {
+            context.extend([
+                Span::styled("Filter: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(label, Style::default().fg(Color::Yellow)),
+                Span::raw("  "),
+            ]);
+        }
+        if let Some(summary) = self.state.changes.summary() {
+            context.extend([
+                Span::styled("Changes: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(summary, Style::default().fg(Color::LightGreen)),
+                Span::raw("  "),
+            ]);
+        }
+        let context = Line::from(context);
+        // Leave room for the repository status on narrow terminals
+        let context_width = u16::try_from(context.width())
+            .unwrap_or(u16::MAX)
+            .min(inner.width / 2);
+
+        // The help cue names the key bound to the help overlay, if any
+        let help_cue = self
+            .keymap
+            .keys(KeyContext::Board, &Message::ToggleHelp)
+            .next()
+            .map_or_else(Line::default, |chord| {
+                Line::from(vec![
+                    Span::styled("Press ", Style::default().fg(Color::DarkGray)),
+                    Span::styled(chord.label(), Style::default().fg(Color::Yellow)),
+                    Span::styled(" for help", Style::default().fg(Color::DarkGray)),
+                ])
+            });
+        // Leave some padding after the repository status
+        let help_width = u16::try_from(help_cue.width() + 3).unwrap_or(u16::MAX);
+
+        // Split inner area: title left, repository status and help cue right
+        let [title_area, context_area, status_area, help_area] = Layout::horizontal([
+            Constraint::Length(20), // "whip - Kanban Board" = 19 chars + padding
+            Constraint::Length(context_width),
             Constraint::Min(0),
-            Constraint::Length(17), // "Press ? for help" = 16 chars + padding
+            Constraint::Length(help_width),
         ])
         .areas(inner);
 
@@ -713,26 +1591,67 @@ impl App {
         ]));
         frame.render_widget(title, title_area);
 
+        frame.render_widget(Paragraph::new(context), context_area);
+
+        // Render per-repository loading status between title and help cue
+        render_repo_status(&self.repo_statuses, status_area, frame.buffer_mut());
+
         // Render help cue on right
-        let help_cue = Paragraph::new(Line::from(vec![
-            Span::styled("Press ", Style::default().fg(Color::DarkGray)),
-            Span::styled("?", Style::default().fg(Color::Yellow)),
-            Span::styled(" for help", Style::default().fg(Color::DarkGray)),
-        ]))
-        .alignment(Alignment::Right);
-        frame.render_widget(help_cue, help_area);
+        frame.render_widget(
+            Paragraph::new(help_cue).alignment(Alignment::Right),
+            help_area,
+        );
+    }
+
+    /// Returns `true` when a one-line bar (the filter bar or the search
+    /// line) is shown under the board.
+    fn bottom_bar_visible(&self) -> bool {
+        self.state.filter_bar.is_editing() || self.state.search.is_active()
     }
 
-    /// Renders the Kanban board with four lanes.
+    /// Renders the Kanban board with its lanes, and the filter bar or the
+    /// search line under it while one is open.
     fn render_board_area(&self, frame: &mut Frame, area: Rect) {
         let buf = frame.buffer_mut();
-        render_board(
-            &self.state.board,
+        let board = self.state.visible_board();
+        let highlights = Highlights {
+            search: self.state.search.highlight(),
+            changes: Some(&self.state.changes),
+        };
+        if !self.bottom_bar_visible() {
+            render_board_with_highlights(
+                &board,
+                self.state.selected_lane,
+                self.state.selected_task,
+                highlights,
+                area,
+                buf,
+            );
+            return;
+        }
+
+        let [board_area, bar_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        render_board_with_highlights(
+            &board,
             self.state.selected_lane,
             self.state.selected_task,
-            area,
+            highlights,
+            board_area,
             buf,
         );
+
+        if self.state.filter_bar.is_editing() {
+            render_filter_bar(&self.state.filter_bar, &self.config.filters, bar_area, buf);
+            return;
+        }
+        let matches = self.state.search_matches();
+        let current = self.state.selected_task.and_then(|task| {
+            matches
+                .iter()
+                .position(|&pos| pos == (self.state.selected_lane, task))
+        });
+        render_search_bar(&self.state.search, current, matches.len(), bar_area, buf);
     }
 
     /// Renders the task detail panel.
@@ -748,6 +1667,7 @@ impl App {
 mod tests {
     use super::*;
     use std::cell::RefCell;
+    use whip_protocol::{FetchErrorKind, TaskChange};
 
     // Thread-local storage to track browser opener calls
     thread_local! {
@@ -762,7 +1682,7 @@ mod tests {
     }
 
     /// Mock config saver that counts saves instead of writing to disk.
-    fn mock_config_saver(_config: &Config) -> anyhow::Result<()> {
+    fn mock_config_saver(_loaded: &Config, _config: &Config) -> anyhow::Result<()> {
         CONFIG_SAVE_COUNT.with(|count| *count.borrow_mut() += 1);
         Ok(())
     }
@@ -773,7 +1693,7 @@ mod tests {
     }
 
     /// No-op config saver for tests that don't care about config saving.
-    fn noop_config_saver(_config: &Config) -> anyhow::Result<()> {
+    fn noop_config_saver(_loaded: &Config, _config: &Config) -> anyhow::Result<()> {
         Ok(())
     }
 
@@ -951,6 +1871,107 @@ mod tests {
         assert!(!app.should_quit); // Should NOT quit
     }
 
+    fn type_search(app: &mut App, query: &str) {
+        app.update(Message::StartSearch);
+        for ch in query.chars() {
+            app.update(Message::SearchInput { ch });
+        }
+    }
+
+    #[test]
+    fn app_search_selects_first_result_while_typing() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Write specs", "Description"));
+        let mut task = whip_protocol::Task::new("Implement parser", "Description");
+        task.lane = whip_protocol::LaneKind::InProgress;
+        board.add_task(task);
+
+        let mut app = test_app(board);
+        type_search(&mut app, "parser");
+
+        assert!(app.state.search.is_editing());
+        assert_eq!(app.state.selected_lane, 1);
+        assert_eq!(app.state.selected_task, Some(0));
+    }
+
+    #[test]
+    fn app_search_next_and_previous_wrap() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Parser one", "Description"));
+        board.add_task(whip_protocol::Task::new("Other", "Description"));
+        board.add_task(whip_protocol::Task::new("Parser two", "Description"));
+
+        let mut app = test_app(board);
+        type_search(&mut app, "parser");
+        app.update(Message::SearchConfirm);
+        assert!(!app.state.search.is_editing());
+        assert_eq!(app.state.selected_task, Some(0));
+
+        app.update(Message::SearchNext);
+        assert_eq!(app.state.selected_task, Some(2));
+        app.update(Message::SearchNext);
+        assert_eq!(app.state.selected_task, Some(0));
+        app.update(Message::SearchPrevious);
+        assert_eq!(app.state.selected_task, Some(2));
+    }
+
+    #[test]
+    fn app_search_cancel_keeps_selection() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Other", "Description"));
+        board.add_task(whip_protocol::Task::new("Parser", "Description"));
+
+        let mut app = test_app(board);
+        type_search(&mut app, "pars");
+        app.update(Message::SearchBackspace);
+        assert_eq!(app.state.search.query(), "par");
+
+        app.update(Message::SearchCancel);
+        assert!(!app.state.search.is_active());
+        assert_eq!(app.state.selected_task, Some(1));
+    }
+
+    #[test]
+    fn app_escape_clears_search_before_selection() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Parser", "Description"));
+
+        let mut app = test_app(board);
+        type_search(&mut app, "parser");
+        app.update(Message::SearchConfirm);
+
+        app.update(Message::Escape);
+        assert!(!app.state.search.is_active());
+        assert_eq!(app.state.selected_task, Some(0));
+
+        app.update(Message::Escape);
+        assert!(app.state.selected_task.is_none());
+    }
+
+    #[test]
+    fn app_view_shows_search_line() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Parser", "Description"));
+        board.add_task(whip_protocol::Task::new("Lexer", "Description"));
+
+        let mut app = test_app(board);
+        type_search(&mut app, "lex");
+
+        let backend = TestBackend::new(80, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let last_row: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, buffer.area.height - 1)].symbol().to_string())
+            .collect();
+        assert!(last_row.starts_with("/lex"), "got {last_row:?}");
+        assert!(last_row.contains("1/1"), "got {last_row:?}");
+    }
+
     #[test]
     fn app_escape_dismisses_help() {
         let board = KanbanBoard::new();
@@ -1004,6 +2025,25 @@ mod tests {
         assert!(app.state.detail_visible);
     }
 
+    #[test]
+    fn app_click_selects_lane_with_custom_lane_count() {
+        let lanes = (1..=7)
+            .map(|i| whip_protocol::LaneDefinition::new(format!("Lane {i}")))
+            .collect();
+        let mut board = KanbanBoard::with_lanes(lanes);
+        board.lanes[5].add_task(whip_protocol::Task::new("Task 1", "Description"));
+
+        let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 70, 24);
+
+        // Lane 5 is columns 50-59 (70/7 = 10 width per lane)
+        app.update(Message::ClickAt { column: 55, row: 4 });
+
+        assert_eq!(app.state.selected_lane, 5);
+        assert_eq!(app.state.selected_task, Some(0));
+        assert!(app.state.detail_visible);
+    }
+
     #[test]
     fn app_click_on_empty_lane_does_nothing() {
         let board = KanbanBoard::new();
@@ -1050,110 +2090,384 @@ mod tests {
     }
 
     #[test]
-    fn app_scroll_clamped_to_content_bounds() {
+    fn app_click_on_scrolled_lane_selects_task_under_cursor() {
         let mut board = KanbanBoard::new();
-        // Create a task with a short description that won't require scrolling
-        board.add_task(whip_protocol::Task::new("Task 1", "Short description"));
+        for i in 1..=10 {
+            board.add_task(whip_protocol::Task::new(format!("Task {i}"), "Description"));
+        }
 
         let mut app = test_app(board);
-        // Simulate a reasonably sized terminal
         app.last_area = Rect::new(0, 0, 80, 24);
 
-        // Open detail panel
-        app.update(Message::NavigateDown);
-        app.update(Message::Select);
-        assert!(app.state.detail_visible);
-        assert_eq!(app.state.focus, Focus::Detail);
-
-        // Try to scroll down many times - should be clamped
-        for _ in 0..100 {
+        // Selecting the eighth task scrolls the lane, which shows four cards
+        for _ in 0..8 {
             app.update(Message::NavigateDown);
         }
+        assert_eq!(app.state.selected_task, Some(7));
 
-        // With a short description and a 24-line terminal, the max scroll should be 0
-        // The scroll should be clamped to 0 (no scrolling needed)
-        assert_eq!(
-            app.state.detail_scroll, 0,
-            "Scroll should be clamped to 0 for short content"
-        );
+        // The first card shown is the sixth task, not the first
+        app.update(Message::ClickAt { column: 5, row: 4 });
+        assert_eq!(app.state.selected_task, Some(5));
+        assert!(app.state.detail_visible);
     }
 
     #[test]
-    fn app_scroll_allows_scrolling_long_content() {
+    fn app_wheel_moves_selection_in_lane_under_pointer() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task 1", "Description"));
+        board.add_task(whip_protocol::Task::new("Task 2", "Description"));
+
+        let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 80, 24);
+        let scroll = |delta| Message::ScrollAt {
+            column: 5,
+            row: 10,
+            delta,
+        };
+
+        app.update(scroll(1));
+        assert_eq!(app.state.selected_task, Some(0));
+        app.update(scroll(1));
+        app.update(scroll(1));
+        assert_eq!(app.state.selected_task, Some(1));
+        app.update(scroll(-1));
+        assert_eq!(app.state.selected_task, Some(0));
+
+        // The wheel over another lane focuses it
+        app.update(Message::ScrollAt {
+            column: 25,
+            row: 10,
+            delta: 1,
+        });
+        assert_eq!(app.state.selected_lane, 1);
+        assert!(!app.state.detail_visible);
+    }
+
+    #[test]
+    fn app_wheel_scrolls_detail_view() {
         let mut board = KanbanBoard::new();
-        // Create a task with a very long description that will need scrolling
         let long_description = "This is a very long description. ".repeat(50);
         board.add_task(whip_protocol::Task::new("Task 1", &long_description));
 
         let mut app = test_app(board);
-        // Simulate a reasonably sized terminal
         app.last_area = Rect::new(0, 0, 80, 24);
-
-        // Open detail panel
         app.update(Message::NavigateDown);
         app.update(Message::Select);
+
+        let scroll = |delta| Message::ScrollAt {
+            column: 5,
+            row: 10,
+            delta,
+        };
+        app.update(scroll(1));
+        assert_eq!(app.state.detail_scroll, 3);
+        app.update(scroll(-1));
+        assert_eq!(app.state.detail_scroll, 0);
         assert!(app.state.detail_visible);
+    }
 
-        // Scroll down a few times
-        app.update(Message::NavigateDown);
-        app.update(Message::NavigateDown);
-        app.update(Message::NavigateDown);
+    #[test]
+    fn app_right_click_opens_menu_of_task_under_pointer() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task 1", "Description"));
+        board.add_task(whip_protocol::Task::new("Task 2", "Description"));
 
-        // With long content, we should be able to scroll
-        assert!(
-            app.state.detail_scroll > 0,
-            "Should be able to scroll with long content"
-        );
+        let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 80, 24);
 
-        // Try to scroll way past the content
-        for _ in 0..1000 {
-            app.update(Message::NavigateDown);
-        }
+        app.update(Message::RightClickAt { column: 5, row: 8 });
+        assert_eq!(app.state.selected_task, Some(1));
+        let menu = app.state.context_menu.as_ref().expect("menu");
+        assert_eq!(menu.title(), "Task 2");
 
-        // The scroll should be clamped to the maximum valid value
-        // For this test, we just verify it's not absurdly high
-        assert!(
-            app.state.detail_scroll < 1000,
-            "Scroll should be clamped to a reasonable max, got {}",
-            app.state.detail_scroll
-        );
+        // Clicking the first entry, below the menu's top border, opens the task
+        app.update(Message::ClickAt { column: 7, row: 9 });
+        assert!(app.state.context_menu.is_none());
+        assert!(app.state.detail_visible);
+        assert_eq!(app.state.selected_task, Some(1));
     }
 
-    // --- Graceful degradation tests ---
-
     #[test]
-    fn app_view_shows_too_small_message_when_height_below_minimum() {
-        use ratatui::Terminal;
-        use ratatui::backend::TestBackend;
+    fn app_context_menu_follows_keys() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task 1", "Description"));
 
-        let board = KanbanBoard::new();
         let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 80, 24);
 
-        // Create a terminal with height below MIN_HEIGHT (10)
-        let backend = TestBackend::new(80, 8);
-        let mut terminal = Terminal::new(backend).unwrap();
-
-        terminal.draw(|frame| app.view(frame)).unwrap();
+        app.update(Message::RightClickAt { column: 5, row: 4 });
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert!(app.state.context_menu.is_none());
+        assert!(app.state.board.lanes[0].definition.sort.is_some());
 
-        // Verify header is not visible in this mode
-        assert!(!app.header_visible);
+        // Escape closes the menu, keeping the selection
+        app.update(Message::RightClickAt { column: 5, row: 4 });
+        app.update(Message::Escape);
+        assert!(app.state.context_menu.is_none());
+        assert_eq!(app.state.selected_task, Some(0));
 
-        // Check that the buffer contains the "too small" message
-        let content = terminal
-            .backend()
-            .buffer()
-            .content()
-            .iter()
-            .map(|c| c.symbol().chars().next().unwrap_or(' '))
-            .collect::<String>();
-        assert!(
-            content.contains("Terminal too small"),
-            "Buffer should contain 'Terminal too small' message"
-        );
+        // A click outside the menu closes it without acting
+        app.update(Message::RightClickAt { column: 5, row: 4 });
+        app.update(Message::ClickAt {
+            column: 70,
+            row: 20,
+        });
+        assert!(app.state.context_menu.is_none());
+        assert!(!app.state.detail_visible);
     }
 
     #[test]
-    fn app_view_shows_too_small_message_when_width_below_minimum() {
+    fn app_right_click_in_detail_view_and_empty_lanes() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task 1", "Description"));
+
+        let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 80, 24);
+
+        // Nothing to act on in an empty lane
+        app.update(Message::RightClickAt { column: 25, row: 4 });
+        assert!(app.state.context_menu.is_none());
+
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        app.update(Message::RightClickAt {
+            column: 40,
+            row: 12,
+        });
+        let menu = app.state.context_menu.as_ref().expect("menu");
+        assert_eq!(
+            menu.selected_message(),
+            Some(&Message::Escape),
+            "the detail view menu starts at closing it"
+        );
+        app.update(Message::Select);
+        assert!(!app.state.detail_visible);
+    }
+
+    #[test]
+    fn app_view_renders_context_menu() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task 1", "Description"));
+
+        let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 80, 24);
+        app.update(Message::RightClickAt { column: 5, row: 4 });
+
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        assert!(content.contains(" Open details  Enter "), "{content}");
+    }
+
+    /// Renders the app in an 80x24 terminal and returns where `text` is drawn.
+    fn rendered_position(app: &mut App, text: &str) -> (u16, u16) {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        content
+            .lines()
+            .enumerate()
+            .find_map(|(row, line)| {
+                let column = line.find(text)?;
+                let column = line[..column].chars().count();
+                Some((u16::try_from(column).unwrap(), u16::try_from(row).unwrap()))
+            })
+            .unwrap_or_else(|| panic!("{text:?} not rendered:\n{content}"))
+    }
+
+    #[test]
+    fn app_click_on_help_entry_runs_its_action() {
+        let mut app = test_app(KanbanBoard::new());
+
+        app.update(Message::ToggleHelp);
+        let (column, row) = rendered_position(&mut app, "Open settings");
+
+        // The wheel and clicks on headers leave the help open
+        app.update(Message::ScrollAt {
+            column,
+            row,
+            delta: 1,
+        });
+        let (header_column, header_row) = rendered_position(&mut app, "Navigation");
+        app.update(Message::ClickAt {
+            column: header_column,
+            row: header_row,
+        });
+        assert!(app.state.help_visible);
+
+        app.update(Message::ClickAt { column, row });
+        assert!(!app.state.help_visible);
+        assert!(app.is_settings_open());
+    }
+
+    #[test]
+    fn app_click_outside_help_dismisses_it() {
+        let mut app = test_app(KanbanBoard::new());
+        app.last_area = Rect::new(0, 0, 80, 24);
+
+        app.update(Message::ToggleHelp);
+        app.update(Message::ClickAt { column: 0, row: 0 });
+        assert!(!app.state.help_visible);
+    }
+
+    #[test]
+    fn app_click_in_settings_selects_tabs_and_items() {
+        let mut app = test_app(KanbanBoard::new());
+        app.update(Message::OpenSettings);
+
+        let (column, row) = rendered_position(&mut app, "Polling");
+        app.update(Message::ClickAt { column, row });
+        let settings = app.settings_state.as_ref().expect("settings");
+        assert_eq!(
+            settings.section(),
+            crate::settings_state::SettingsSection::Polling
+        );
+        let auto_adjust = settings.config().polling.auto_adjust;
+
+        // The first click selects the item, the second acts on it
+        let (column, row) = rendered_position(&mut app, "Auto-adjust");
+        app.update(Message::ClickAt { column, row });
+        let settings = app.settings_state.as_ref().expect("settings");
+        assert_eq!(settings.selected_item(), 1);
+        assert_eq!(settings.config().polling.auto_adjust, auto_adjust);
+
+        app.update(Message::ClickAt { column, row });
+        let settings = app.settings_state.as_ref().expect("settings");
+        assert_eq!(settings.config().polling.auto_adjust, !auto_adjust);
+
+        // The wheel moves the selection without wrapping
+        app.update(Message::ScrollAt {
+            column,
+            row,
+            delta: 1,
+        });
+        let settings = app.settings_state.as_ref().expect("settings");
+        assert_eq!(settings.selected_item(), 1);
+        app.update(Message::ScrollAt {
+            column,
+            row,
+            delta: -1,
+        });
+        let settings = app.settings_state.as_ref().expect("settings");
+        assert_eq!(settings.selected_item(), 0);
+    }
+
+    #[test]
+    fn app_scroll_clamped_to_content_bounds() {
+        let mut board = KanbanBoard::new();
+        // Create a task with a short description that won't require scrolling
+        board.add_task(whip_protocol::Task::new("Task 1", "Short description"));
+
+        let mut app = test_app(board);
+        // Simulate a reasonably sized terminal
+        app.last_area = Rect::new(0, 0, 80, 24);
+
+        // Open detail panel
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert!(app.state.detail_visible);
+        assert_eq!(app.state.focus, Focus::Detail);
+
+        // Try to scroll down many times - should be clamped
+        for _ in 0..100 {
+            app.update(Message::NavigateDown);
+        }
+
+        // With a short description and a 24-line terminal, the max scroll should be 0
+        // The scroll should be clamped to 0 (no scrolling needed)
+        assert_eq!(
+            app.state.detail_scroll, 0,
+            "Scroll should be clamped to 0 for short content"
+        );
+    }
+
+    #[test]
+    fn app_scroll_allows_scrolling_long_content() {
+        let mut board = KanbanBoard::new();
+        // Create a task with a very long description that will need scrolling
+        let long_description = "This is a very long description. ".repeat(50);
+        board.add_task(whip_protocol::Task::new("Task 1", &long_description));
+
+        let mut app = test_app(board);
+        // Simulate a reasonably sized terminal
+        app.last_area = Rect::new(0, 0, 80, 24);
+
+        // Open detail panel
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert!(app.state.detail_visible);
+
+        // Scroll down a few times
+        app.update(Message::NavigateDown);
+        app.update(Message::NavigateDown);
+        app.update(Message::NavigateDown);
+
+        // With long content, we should be able to scroll
+        assert!(
+            app.state.detail_scroll > 0,
+            "Should be able to scroll with long content"
+        );
+
+        // Try to scroll way past the content
+        for _ in 0..1000 {
+            app.update(Message::NavigateDown);
+        }
+
+        // The scroll should be clamped to the maximum valid value
+        // For this test, we just verify it's not absurdly high
+        assert!(
+            app.state.detail_scroll < 1000,
+            "Scroll should be clamped to a reasonable max, got {}",
+            app.state.detail_scroll
+        );
+    }
+
+    // --- Graceful degradation tests ---
+
+    #[test]
+    fn app_view_shows_too_small_message_when_height_below_minimum() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let board = KanbanBoard::new();
+        let mut app = test_app(board);
+
+        // Create a terminal with height below MIN_HEIGHT (10)
+        let backend = TestBackend::new(80, 8);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        // Verify header is not visible in this mode
+        assert!(!app.header_visible);
+
+        // Check that the buffer contains the "too small" message
+        let content = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol().chars().next().unwrap_or(' '))
+            .collect::<String>();
+        assert!(
+            content.contains("Terminal too small"),
+            "Buffer should contain 'Terminal too small' message"
+        );
+    }
+
+    #[test]
+    fn app_view_shows_too_small_message_when_width_below_minimum() {
         use ratatui::Terminal;
         use ratatui::backend::TestBackend;
 
@@ -1428,6 +2742,7 @@ mod tests {
         let mut board = KanbanBoard::new();
         let mut task = whip_protocol::Task::new("Task 1", "Description");
         task.github = Some(whip_protocol::GitHubSource {
+            host: None,
             owner: "owner".to_string(),
             repo: "repo".to_string(),
             number: 123,
@@ -1504,6 +2819,7 @@ mod tests {
         let mut board = KanbanBoard::new();
         let mut task = whip_protocol::Task::new("Task 1", "Description");
         task.github = Some(whip_protocol::GitHubSource {
+            host: None,
             owner: "owner".to_string(),
             repo: "repo".to_string(),
             number: 123,
@@ -1550,6 +2866,7 @@ mod tests {
         let mut board = KanbanBoard::new();
         let mut task = whip_protocol::Task::new("Task 1", "Description");
         task.github = Some(whip_protocol::GitHubSource {
+            host: None,
             owner: "rust-lang".to_string(),
             repo: "rust".to_string(),
             number: 12345,
@@ -1578,4 +2895,1107 @@ mod tests {
             "Browser should have been opened with the GitHub issue URL when 'o' is pressed"
         );
     }
+
+    fn github_task(title: &str, repo: &str) -> whip_protocol::Task {
+        let mut task = whip_protocol::Task::new(title, "Description");
+        task.github = Some(whip_protocol::GitHubSource {
+            host: None,
+            owner: "owner".to_string(),
+            repo: repo.to_string(),
+            number: 1,
+            url: format!("https://github.com/owner/{repo}/issues/1"),
+            labels: vec![],
+            author: "author".to_string(),
+            comment_count: 0,
+        });
+        task
+    }
+
+    #[test]
+    fn app_repo_loaded_replaces_only_that_repos_tasks() {
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Old A", "a"));
+        board.add_task(github_task("Old B", "b"));
+        board.add_task(whip_protocol::Task::new("Local", "Description"));
+
+        let mut statuses = RepoStatuses::default();
+        statuses.set("owner/a", RepoStatus::Stale);
+        let mut app =
+            test_app_with_config(board, repos_config(&["a", "b"])).with_repo_statuses(statuses);
+
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![github_task("New A", "a")],
+        });
+
+        let titles: Vec<_> = app.state.board.lanes[0]
+            .tasks
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Old B", "Local", "New A"]);
+        assert_eq!(app.repo_statuses().get("owner/a"), Some(&RepoStatus::Fresh));
+    }
+
+    #[test]
+    fn app_repo_loaded_keeps_same_named_repo_on_other_host() {
+        let mut enterprise = github_task("Enterprise", "a");
+        if let Some(github) = enterprise.github.as_mut() {
+            github.host = Some("ghe.example.com".to_string());
+        }
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Public", "a"));
+        board.add_task(enterprise.clone());
+        let mut config = repos_config(&["a"]);
+        config.add_repository(
+            Repository::new("owner", "a").with_api_url("https://ghe.example.com/api/v3"),
+        );
+        let mut app = test_app_with_config(board, config);
+
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "ghe.example.com/owner/a".to_string(),
+            tasks: vec![enterprise],
+        });
+        app.apply_repo_event(RepoEvent::Failed {
+            repo: "owner/a".to_string(),
+            kind: FetchErrorKind::Network,
+            error: "offline".to_string(),
+        });
+
+        assert_eq!(app.state.board.total_tasks(), 2);
+        assert_eq!(
+            app.repo_statuses().get("ghe.example.com/owner/a"),
+            Some(&RepoStatus::Fresh)
+        );
+        assert_eq!(
+            app.repo_statuses().get("owner/a"),
+            Some(&RepoStatus::Failed {
+                kind: FetchErrorKind::Network
+            })
+        );
+    }
+
+    #[test]
+    fn app_repo_failure_keeps_existing_tasks() {
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Cached", "a"));
+        let mut app = test_app_with_config(board, repos_config(&["a"]));
+
+        app.apply_repo_event(RepoEvent::Failed {
+            repo: "owner/a".to_string(),
+            kind: FetchErrorKind::RateLimit,
+            error: "rate limit exceeded\n\nFound at backtrace".to_string(),
+        });
+
+        assert_eq!(app.state.board.total_tasks(), 1);
+        assert_eq!(
+            app.repo_statuses().get("owner/a"),
+            Some(&RepoStatus::Failed {
+                kind: FetchErrorKind::RateLimit
+            })
+        );
+
+        let logged = app.errors().iter().next().expect("error should be logged");
+        assert_eq!(logged.repo, "owner/a");
+        assert_eq!(logged.kind, FetchErrorKind::RateLimit);
+        assert_eq!(logged.message, "rate limit exceeded");
+        let notification = app.notifications().active().next().expect("notified");
+        assert_eq!(notification.severity, Severity::Error);
+        assert_eq!(notification.message, "owner/a: rate limit exceeded");
+    }
+
+    #[test]
+    fn app_label_sync_plan_notifies_only_on_changes() {
+        let mut app = test_app(KanbanBoard::new());
+
+        app.apply_repo_event(RepoEvent::LabelSyncPlanned {
+            repo: "owner/a".to_string(),
+            changes: vec![],
+        });
+        assert_eq!(app.notifications().history_len(), 0);
+
+        app.apply_repo_event(RepoEvent::LabelSyncPlanned {
+            repo: "owner/a".to_string(),
+            changes: vec![
+                "create whip/backlog".to_string(),
+                "rename Whip/Done -> whip/done".to_string(),
+            ],
+        });
+        let notification = app.notifications().active().next().expect("notified");
+        assert_eq!(notification.severity, Severity::Warning);
+        assert_eq!(
+            notification.message,
+            "owner/a: labels differ (create whip/backlog, rename Whip/Done -> whip/done); \
+             run `whip sync-labels` to review and apply"
+        );
+    }
+
+    #[test]
+    fn app_authenticated_event_reaches_settings() {
+        let mut app = test_app(KanbanBoard::new());
+
+        app.apply_repo_event(RepoEvent::Authenticated {
+            repo: "owner/a".to_string(),
+            source: Some(TokenSource::GhCli),
+        });
+        app.apply_repo_event(RepoEvent::Authenticated {
+            repo: "owner/b".to_string(),
+            source: None,
+        });
+        app.update(Message::OpenSettings);
+
+        let settings = app.settings_state.as_ref().expect("settings open");
+        assert_eq!(
+            settings.token_source("owner/a"),
+            Some(&Some(TokenSource::GhCli))
+        );
+        assert_eq!(settings.token_source("owner/b"), Some(&None));
+        assert_eq!(settings.token_source("owner/c"), None);
+    }
+
+    #[test]
+    fn app_test_credentials_requests_test_for_selected_repository() {
+        let config = Config {
+            repositories: vec![Repository::new("owner", "a")],
+            ..Config::default()
+        };
+        let mut app = test_app_with_config(KanbanBoard::new(), config);
+        app.update(Message::OpenSettings);
+        app.update(Message::SettingsPrevSection); // Authentication
+
+        // The global token row has nothing to test
+        app.update(Message::SettingsTestCredentials);
+        assert!(app.take_credential_test().is_none());
+
+        app.update(Message::SettingsNavigate { delta: 1 });
+        app.update(Message::SettingsTestCredentials);
+        let (repo, config) = app.take_credential_test().expect("test requested");
+        assert_eq!(repo.full_name(), "owner/a");
+        assert_eq!(config.repositories.len(), 1);
+        assert!(app.take_credential_test().is_none());
+
+        let settings = app.settings_state.as_ref().expect("settings open");
+        assert_eq!(
+            settings.credential_check("owner/a"),
+            Some(&CredentialCheck::Pending)
+        );
+    }
+
+    #[test]
+    fn app_credentials_checked_updates_open_settings() {
+        let mut app = test_app(KanbanBoard::new());
+        app.update(Message::OpenSettings);
+
+        let check = CredentialCheck::Valid {
+            source: TokenSource::GhCli,
+            login: "octocat".to_string(),
+            scopes: Some(vec!["repo".to_string()]),
+            missing_scopes: Vec::new(),
+        };
+        app.apply_repo_event(RepoEvent::CredentialsChecked {
+            repo: "owner/a".to_string(),
+            check: check.clone(),
+        });
+
+        let settings = app.settings_state.as_ref().expect("settings open");
+        assert_eq!(settings.credential_check("owner/a"), Some(&check));
+        assert_eq!(
+            settings.token_source("owner/a"),
+            Some(&Some(TokenSource::GhCli))
+        );
+
+        // Results survive reopening the panel
+        app.update(Message::CloseSettings);
+        app.update(Message::OpenSettings);
+        let settings = app.settings_state.as_ref().expect("settings open");
+        assert_eq!(settings.credential_check("owner/a"), Some(&check));
+    }
+
+    fn failing_config_saver(_loaded: &Config, _config: &Config) -> anyhow::Result<()> {
+        anyhow::bail!("disk full")
+    }
+
+    #[test]
+    fn app_settings_close_reports_save_result() {
+        let mut app = test_app(KanbanBoard::new());
+        app.update(Message::OpenSettings);
+        app.update(Message::CloseSettings);
+        let notification = app.notifications().history().next().expect("notified");
+        assert_eq!(notification.severity, Severity::Success);
+        assert_eq!(notification.message, "Settings saved");
+
+        let mut app = test_app(KanbanBoard::new()).with_config_saver(failing_config_saver);
+        app.update(Message::OpenSettings);
+        app.update(Message::CloseSettings);
+        let notification = app.notifications().history().next().expect("notified");
+        assert_eq!(notification.severity, Severity::Error);
+        assert_eq!(notification.message, "Failed to save settings: disk full");
+    }
+
+    /// Saver that only accepts a local config saved to the user config.
+    fn user_config_saver(loaded: &Config, config: &Config) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            loaded.source == Some(ConfigLayer::Local("whip.json5".into())),
+            "wrong baseline"
+        );
+        anyhow::ensure!(
+            config.source == Some(ConfigLayer::User("config.json5".into())),
+            "wrong target"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn app_settings_save_to_user_config() {
+        let config = Config {
+            source: Some(ConfigLayer::Local("whip.json5".into())),
+            ..Config::default()
+        };
+        let mut app = App::with_config(KanbanBoard::new(), config)
+            .with_config_saver(user_config_saver)
+            .with_user_config_path("config.json5");
+
+        app.update(Message::OpenSettings);
+        app.update(Message::SettingsSaveToUserConfig);
+        app.update(Message::CloseSettings);
+
+        let notification = app.notifications().history().next().expect("notified");
+        assert_eq!(notification.message, "Settings saved");
+        assert_eq!(
+            app.config().source,
+            Some(ConfigLayer::User("config.json5".into()))
+        );
+    }
+
+    #[test]
+    fn app_refresh_notifies_with_repository_count() {
+        let config = Config {
+            repositories: vec![
+                whip_config::Repository::new("owner", "a"),
+                whip_config::Repository::new("owner", "b"),
+            ],
+            ..Config::default()
+        };
+        let mut app =
+            App::with_config(KanbanBoard::new(), config).with_config_saver(noop_config_saver);
+
+        app.update(Message::Refresh);
+
+        let notification = app.notifications().active().next().expect("notified");
+        assert_eq!(notification.severity, Severity::Info);
+        assert_eq!(notification.message, "Refreshing 2 repositories…");
+    }
+
+    #[test]
+    fn app_notification_history_scrolls_and_closes() {
+        let mut app = test_app(KanbanBoard::new());
+        app.last_area = Rect::new(0, 0, 80, 24);
+        for i in 0..30 {
+            app.notify(Severity::Info, format!("message {i}"));
+        }
+
+        app.update(Message::ToggleNotifications);
+        assert!(app.state.notifications_visible);
+
+        app.update(Message::NavigateDown);
+        app.update(Message::NavigateDown);
+        assert_eq!(app.state.notifications_scroll, 2);
+        app.update(Message::NavigateUp);
+        assert_eq!(app.state.notifications_scroll, 1);
+
+        // Scrolling stops once the last page is shown
+        for _ in 0..100 {
+            app.update(Message::NavigateDown);
+        }
+        let max_scroll = 30 - history_visible_rows(app.last_area);
+        assert_eq!(app.state.notifications_scroll, max_scroll);
+
+        // Board navigation is swallowed; any other key closes the overlay
+        app.update(Message::NavigateRight);
+        assert!(!app.state.notifications_visible);
+        assert_eq!(app.state.selected_lane, 0);
+    }
+
+    #[test]
+    fn app_quit_works_with_notification_history_visible() {
+        let mut app = test_app(KanbanBoard::new());
+        app.update(Message::ToggleNotifications);
+
+        app.update(Message::Quit);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn app_toggle_errors_shows_and_any_key_dismisses() {
+        let mut app = test_app(KanbanBoard::new());
+
+        app.update(Message::ToggleErrors);
+        assert!(app.state.errors_visible);
+
+        // Navigation is swallowed while the pane is open
+        app.update(Message::NavigateRight);
+        assert!(!app.state.errors_visible);
+        assert_eq!(app.state.selected_lane, 0);
+    }
+
+    #[test]
+    fn app_quit_works_with_errors_visible() {
+        let mut app = test_app(KanbanBoard::new());
+        app.update(Message::ToggleErrors);
+
+        app.update(Message::Quit);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn app_view_renders_toast_and_errors_pane() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut app = test_app_with_config(KanbanBoard::new(), repos_config(&["a"]));
+        app.apply_repo_event(RepoEvent::Failed {
+            repo: "owner/a".to_string(),
+            kind: FetchErrorKind::Network,
+            error: "connection refused".to_string(),
+        });
+
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        assert!(content.contains("✗ owner/a: connection refused"));
+
+        app.update(Message::ToggleErrors);
+        terminal.draw(|frame| app.view(frame)).unwrap();
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        assert!(content.contains("network"));
+        assert!(content.contains("Press any key to close"));
+    }
+
+    #[test]
+    fn app_view_renders_notification_history() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut app = test_app(KanbanBoard::new());
+        app.notify(Severity::Warning, "something odd");
+        app.update(Message::ToggleNotifications);
+
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        assert!(content.contains("Notifications"));
+        assert!(content.contains("⚠ something odd"));
+    }
+
+    #[test]
+    fn app_repo_loaded_clamps_selection_and_closes_detail() {
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Task", "a"));
+        let mut app = test_app_with_config(board, repos_config(&["a"]));
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert!(app.state.detail_visible);
+
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![],
+        });
+
+        assert_eq!(app.state.selected_task, None);
+        assert!(!app.state.detail_visible);
+    }
+
+    #[test]
+    fn app_drains_repo_events_from_channel() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app =
+            test_app_with_config(KanbanBoard::new(), repos_config(&["a"])).with_repo_events(rx);
+
+        tx.send(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![github_task("Streamed", "a")],
+        })
+        .unwrap();
+        app.drain_repo_events();
+
+        assert_eq!(app.state.board.total_tasks(), 1);
+    }
+
+    #[test]
+    fn app_ignores_late_fetch_of_removed_repo() {
+        let mut app = test_app_with_config(KanbanBoard::new(), repos_config(&["a", "b"]));
+        app.apply_config_change(reloaded(repos_config(&["a"])));
+
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/b".to_string(),
+            tasks: vec![github_task("Removed", "b")],
+        });
+        app.apply_repo_event(RepoEvent::Failed {
+            repo: "owner/b".to_string(),
+            kind: FetchErrorKind::Network,
+            error: "offline".to_string(),
+        });
+
+        assert_eq!(app.state.board.total_tasks(), 0);
+        assert_eq!(app.repo_statuses().get("owner/b"), None);
+        assert!(app.errors.is_empty());
+    }
+
+    fn repos_config(names: &[&str]) -> Config {
+        Config {
+            repositories: names
+                .iter()
+                .map(|name| Repository::new("owner", *name))
+                .collect(),
+            ..Config::default()
+        }
+    }
+
+    fn reloaded(config: Config) -> ConfigChange {
+        ConfigChange::Reloaded(Box::new(config))
+    }
+
+    #[test]
+    fn app_config_reload_updates_repositories() {
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Kept", "a"));
+        board.add_task(github_task("Removed", "b"));
+        let mut statuses = RepoStatuses::default();
+        statuses.set("owner/a", RepoStatus::Fresh);
+        statuses.set("owner/b", RepoStatus::Fresh);
+        let mut app =
+            test_app_with_config(board, repos_config(&["a", "b"])).with_repo_statuses(statuses);
+
+        app.apply_config_change(reloaded(repos_config(&["a", "c"])));
+
+        assert_eq!(app.config().repositories.len(), 2);
+        assert_eq!(app.state.board.total_tasks(), 1);
+        let names: Vec<_> = app.repo_statuses().iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["owner/a", "owner/c"]);
+        assert_eq!(
+            app.repo_statuses().get("owner/c"),
+            Some(&RepoStatus::Loading)
+        );
+        let fetches = app.take_pending_fetches();
+        assert_eq!(fetches, vec![Repository::new("owner", "c")]);
+        assert!(app.take_pending_fetches().is_empty());
+        let notification = app.notifications().history().next().expect("notified");
+        assert_eq!(notification.message, "Configuration reloaded");
+    }
+
+    #[test]
+    fn app_config_reload_tells_hosts_apart() {
+        let mut enterprise = github_task("Enterprise", "a");
+        if let Some(github) = enterprise.github.as_mut() {
+            github.host = Some("ghe.example.com".to_string());
+        }
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Public", "a"));
+        board.add_task(enterprise);
+        let mut config = repos_config(&["a"]);
+        config
+            .repositories
+            .push(Repository::new("owner", "a").with_api_url("https://ghe.example.com/api/v3"));
+        let mut app = test_app_with_config(board, config);
+
+        app.apply_config_change(reloaded(repos_config(&["a"])));
+
+        let titles: Vec<_> = app
+            .state
+            .board
+            .lanes
+            .iter()
+            .flat_map(|lane| &lane.tasks)
+            .map(|task| task.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Public"]);
+        assert!(app.take_pending_fetches().is_empty());
+    }
+
+    #[test]
+    fn app_config_reload_applies_lanes() {
+        use whip_protocol::{LaneDefinition, TaskState};
+
+        let mut board = KanbanBoard::new();
+        let mut failed = github_task("Failed", "a");
+        failed.lane = whip_protocol::LaneKind::Done;
+        failed.state = TaskState::Failed;
+        board.add_task(failed);
+        board.add_task(github_task("Open", "a"));
+        let mut app = test_app_with_config(board, repos_config(&["a"]));
+        app.state.selected_lane = 3;
+
+        let mut config = repos_config(&["a"]);
+        config.lanes = Some(vec![
+            LaneDefinition::new("Failed").with_states([TaskState::Failed]),
+            LaneDefinition::new("Everything else"),
+        ]);
+        app.apply_config_change(reloaded(config));
+
+        let titles: Vec<_> = app.state.board.lanes.iter().map(|l| l.title()).collect();
+        assert_eq!(titles, ["Failed", "Everything else"]);
+        assert_eq!(app.state.board.lanes[0].tasks[0].title, "Failed");
+        assert_eq!(app.state.board.lanes[1].tasks[0].title, "Open");
+        assert_eq!(app.state.selected_lane, 1);
+        assert!(app.take_pending_fetches().is_empty());
+    }
+
+    #[test]
+    fn app_config_reload_ignores_unchanged_config() {
+        let mut app = test_app_with_config(KanbanBoard::new(), repos_config(&["a"]));
+
+        app.apply_config_change(reloaded(repos_config(&["a"])));
+
+        assert!(app.take_pending_fetches().is_empty());
+        assert_eq!(app.notifications().history().count(), 0);
+    }
+
+    #[test]
+    fn app_invalid_config_reload_keeps_config() {
+        let mut app = test_app_with_config(KanbanBoard::new(), repos_config(&["a"]));
+
+        app.apply_config_change(ConfigChange::Invalid(
+            whip_config::ConfigError::InvalidPollingInterval {
+                reason: "too short".to_string(),
+            },
+        ));
+
+        assert_eq!(app.config(), &repos_config(&["a"]));
+        let notification = app.notifications().history().next().expect("notified");
+        assert_eq!(notification.severity, Severity::Error);
+        assert!(notification.message.contains("too short"));
+    }
+
+    #[test]
+    fn app_config_reload_waits_for_settings_to_close() {
+        let mut app = test_app_with_config(KanbanBoard::new(), repos_config(&["a"]));
+        app.update(Message::OpenSettings);
+
+        app.apply_config_change(reloaded(repos_config(&["a", "b"])));
+        assert_eq!(app.config().repositories.len(), 1);
+
+        app.update(Message::CloseSettings);
+        assert_eq!(app.config().repositories.len(), 2);
+        assert_eq!(
+            app.take_pending_fetches(),
+            vec![Repository::new("owner", "b")]
+        );
+    }
+
+    #[test]
+    fn app_drains_config_changes_from_channel() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut app = test_app(KanbanBoard::new()).with_config_changes(rx);
+
+        tx.send(reloaded(repos_config(&["a"]))).unwrap();
+        app.drain_config_changes();
+
+        assert_eq!(app.config().repositories.len(), 1);
+        assert_eq!(app.take_pending_fetches().len(), 1);
+    }
+
+    #[test]
+    fn app_header_shows_repo_status() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut statuses = RepoStatuses::default();
+        statuses.set("owner/a", RepoStatus::Loading);
+        let mut app = test_app(KanbanBoard::new()).with_repo_statuses(statuses);
+
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        let header = content.lines().nth(1).unwrap_or_default();
+        assert!(header.contains("⟳ owner/a"), "header: {header}");
+        assert!(header.contains("Press ? for help"));
+    }
+
+    fn filter_config() -> Config {
+        Config {
+            filters: vec![whip_protocol::NamedFilter::new(
+                "Only A",
+                whip_protocol::TaskFilter::default().with_repositories(["owner/a"]),
+            )],
+            ..repos_config(&["a", "b"])
+        }
+    }
+
+    fn filter_board() -> KanbanBoard {
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("From B", "b"));
+        board.add_task(github_task("From A", "a"));
+        board
+    }
+
+    fn type_filter(app: &mut App, input: &str) {
+        app.update(Message::EditFilter);
+        for ch in input.chars() {
+            app.update(Message::FilterInput { ch });
+        }
+    }
+
+    #[test]
+    fn app_filter_applies_expression() {
+        let mut app = test_app_with_config(filter_board(), filter_config());
+        type_filter(&mut app, "repo:owner/b");
+        assert!(app.state.filter_bar.is_editing());
+
+        app.update(Message::FilterApply);
+        assert!(!app.state.filter_bar.is_editing());
+        assert_eq!(app.state.visible_len(0), 1);
+        assert_eq!(app.state.filter_label().as_deref(), Some("repo:owner/b"));
+
+        // Editing starts from the active filter; clearing it shows every task
+        app.update(Message::EditFilter);
+        assert_eq!(app.state.filter_bar.input(), "repo:owner/b");
+        for _ in 0.."repo:owner/b".len() {
+            app.update(Message::FilterBackspace);
+        }
+        app.update(Message::FilterApply);
+        assert_eq!(app.state.visible_len(0), 2);
+        assert_eq!(app.state.filter_label(), None);
+    }
+
+    #[test]
+    fn app_filter_cycles_named_filters() {
+        let mut app = test_app_with_config(filter_board(), filter_config());
+        app.update(Message::NavigateDown);
+        app.update(Message::EditFilter);
+        app.update(Message::FilterCycleNamed { delta: 1 });
+        assert_eq!(app.state.filter_bar.input(), "Only A");
+
+        app.update(Message::FilterApply);
+        assert_eq!(app.state.filter_name.as_deref(), Some("Only A"));
+        assert_eq!(
+            app.state.selected_task().map(|task| task.title.as_str()),
+            Some("From A")
+        );
+    }
+
+    #[test]
+    fn app_filter_saves_named_filter() {
+        clear_mocks();
+        let mut app = App::with_config(filter_board(), filter_config())
+            .with_browser_opener(noop_browser_opener)
+            .with_config_saver(mock_config_saver);
+        type_filter(&mut app, "repo:owner/b");
+        app.update(Message::FilterSave);
+        assert_eq!(app.state.filter_bar.input(), "");
+        for ch in "only b".chars() {
+            app.update(Message::FilterInput { ch });
+        }
+
+        app.update(Message::FilterApply);
+        assert!(!app.state.filter_bar.is_editing());
+        assert_eq!(get_config_save_count(), 1);
+        assert_eq!(app.state.filter_name.as_deref(), Some("only b"));
+        assert_eq!(app.state.visible_len(0), 1);
+        let saved = app.config().named_filter("Only B").expect("saved filter");
+        assert_eq!(saved.filter.repositories, ["owner/b"]);
+
+        // Saving under an existing name replaces that filter
+        app.update(Message::EditFilter);
+        assert_eq!(app.state.filter_bar.input(), "only b");
+        for _ in 0.."only b".len() {
+            app.update(Message::FilterBackspace);
+        }
+        for ch in "repo:owner/a".chars() {
+            app.update(Message::FilterInput { ch });
+        }
+        app.update(Message::FilterSave);
+        for ch in "ONLY B".chars() {
+            app.update(Message::FilterInput { ch });
+        }
+        app.update(Message::FilterApply);
+        assert_eq!(get_config_save_count(), 2);
+        assert_eq!(app.config().filters.len(), 2);
+        assert_eq!(
+            app.config().named_filter("only b").map(|f| f.name.as_str()),
+            Some("ONLY B")
+        );
+    }
+
+    #[test]
+    fn app_invalid_filter_keeps_bar_open() {
+        let mut app = test_app_with_config(filter_board(), filter_config());
+        type_filter(&mut app, "colour:red");
+        app.update(Message::FilterApply);
+
+        assert!(app.state.filter_bar.is_editing());
+        assert!(app.state.filter_bar.error().is_some());
+        assert!(app.state.filter.is_empty());
+
+        app.update(Message::FilterCancel);
+        assert!(!app.state.filter_bar.is_editing());
+    }
+
+    #[test]
+    fn app_config_reload_refreshes_named_filter() {
+        let mut app = test_app_with_config(filter_board(), filter_config());
+        type_filter(&mut app, "only a");
+        app.update(Message::FilterApply);
+        assert_eq!(app.state.visible_len(0), 1);
+
+        let mut config = filter_config();
+        config.filters[0].filter = whip_protocol::TaskFilter::default();
+        app.apply_config_change(reloaded(config));
+        assert_eq!(app.state.visible_len(0), 2);
+        assert_eq!(app.state.filter_name.as_deref(), Some("Only A"));
+
+        // A removed filter stays active, shown by its expression
+        let mut app = test_app_with_config(filter_board(), filter_config());
+        type_filter(&mut app, "only a");
+        app.update(Message::FilterApply);
+        let mut config = filter_config();
+        config.filters[0].name = "Renamed".to_string();
+        app.apply_config_change(reloaded(config));
+        assert_eq!(app.state.filter_name, None);
+        assert_eq!(app.state.filter_label().as_deref(), Some("repo:owner/a"));
+        assert_eq!(app.state.visible_len(0), 1);
+    }
+
+    #[test]
+    fn app_header_and_bar_show_filter() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut app = test_app_with_config(filter_board(), filter_config());
+        type_filter(&mut app, "only a");
+        app.update(Message::FilterApply);
+        app.update(Message::EditFilter);
+
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        let header = content.lines().nth(1).unwrap_or_default();
+        assert!(header.contains("Filter: Only A"), "header: {header}");
+        assert!(content.contains("Filter: Only A▏"));
+        assert!(content.contains("From A"));
+        assert!(!content.contains("From B"));
+    }
+
+    #[test]
+    fn app_cycle_sort_sorts_selected_lane() {
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Ok", "a"));
+        let mut failed = github_task("Broken", "a");
+        failed.state = whip_protocol::TaskState::Failed;
+        board.add_task(failed);
+
+        let mut app = test_app(board);
+        app.update(Message::NavigateDown);
+        for _ in whip_protocol::SortKey::all() {
+            app.update(Message::CycleSort);
+        }
+
+        assert_eq!(
+            app.state.board.lanes[0].definition.sort,
+            Some(whip_protocol::SortKey::Severity)
+        );
+        assert_eq!(app.state.board.lanes[0].tasks[0].title, "Broken");
+        assert_eq!(app.state.selected_task, Some(1));
+        assert_eq!(
+            app.state.selected_task().map(|t| t.title.as_str()),
+            Some("Ok")
+        );
+    }
+
+    #[test]
+    fn app_repo_loaded_keeps_selected_task() {
+        let mut first = github_task("First", "a");
+        first.github.as_mut().expect("github").number = 1;
+        let mut second = github_task("Second", "a");
+        second.github.as_mut().expect("github").number = 2;
+
+        let mut board = KanbanBoard::new();
+        board.add_task(first.clone());
+        board.add_task(second.clone());
+        let mut app = test_app_with_config(board, repos_config(&["a"]));
+        app.update(Message::NavigateDown);
+        app.update(Message::NavigateDown);
+        assert_eq!(app.state.selected_task_id(), Some(second.id));
+
+        // The refresh returns the tasks in another order
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![second.clone(), first],
+        });
+
+        assert_eq!(app.state.selected_task, Some(0));
+        assert_eq!(app.state.selected_task_id(), Some(second.id));
+    }
+
+    #[test]
+    fn app_set_board_keeps_view_state() {
+        let task = github_task("Parser", "a");
+        let mut board = KanbanBoard::new();
+        board.add_task(github_task("Other", "b"));
+        board.add_task(task.clone());
+
+        let mut app = test_app(board.clone());
+        type_filter(&mut app, "repo:owner/a");
+        app.update(Message::FilterApply);
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert!(app.state.detail_visible);
+
+        let mut moved = task.clone();
+        moved.lane = whip_protocol::LaneKind::InProgress;
+        let mut refreshed = KanbanBoard::new();
+        refreshed.add_task(github_task("Other", "b"));
+        refreshed.add_task(moved);
+        app.set_board(refreshed);
+
+        assert!(app.state.detail_visible);
+        assert_eq!(app.state.selected_lane, 1);
+        assert_eq!(app.state.selected_task_id(), Some(task.id));
+        assert_eq!(app.state.filter_label().as_deref(), Some("repo:owner/a"));
+    }
+
+    #[test]
+    fn app_set_board_keeps_detail_scroll_within_content() {
+        let long_description = "This is a very long description. ".repeat(50);
+        let task = whip_protocol::Task::new("Task 1", &long_description);
+        let mut board = KanbanBoard::new();
+        board.add_task(task.clone());
+
+        let mut app = test_app(board.clone());
+        app.last_area = Rect::new(0, 0, 80, 24);
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        for _ in 0..3 {
+            app.update(Message::NavigateDown);
+        }
+        let scroll = app.state.detail_scroll;
+        assert!(scroll > 0);
+
+        app.set_board(board);
+        assert_eq!(app.state.detail_scroll, scroll);
+
+        let mut shortened = task;
+        shortened.description = "Short description".to_string();
+        let mut board = KanbanBoard::new();
+        board.add_task(shortened);
+        app.set_board(board);
+        assert!(app.state.detail_visible);
+        assert_eq!(app.state.detail_scroll, 0);
+    }
+
+    /// Creates an app showing repository `owner/a`, already loaded once.
+    fn shown_repo_app(tasks: &[&whip_protocol::Task]) -> App {
+        let mut app = test_app_with_config(KanbanBoard::new(), repos_config(&["a"]));
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: tasks.iter().map(|task| (*task).clone()).collect(),
+        });
+        app
+    }
+
+    #[test]
+    fn app_first_load_marks_nothing() {
+        let app = shown_repo_app(&[&github_task("Task", "a")]);
+        assert!(app.state.changes.is_empty());
+    }
+
+    #[test]
+    fn app_refresh_marks_changed_tasks() {
+        let kept = github_task("Kept", "a");
+        let moved = github_task("Moved", "a");
+        let commented = github_task("Commented", "a");
+        let removed = github_task("Removed", "a");
+        let mut app = shown_repo_app(&[&kept, &moved, &commented, &removed]);
+
+        let added = github_task("Added", "a");
+        let mut moved_after = moved.clone();
+        moved_after.lane = whip_protocol::LaneKind::InProgress;
+        let mut commented_after = commented.clone();
+        commented_after
+            .github
+            .as_mut()
+            .expect("github")
+            .comment_count = 2;
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![kept.clone(), moved_after, commented_after, added.clone()],
+        });
+
+        let changes = &app.state.changes;
+        assert_eq!(changes.get(kept.id), None);
+        assert_eq!(changes.get(added.id), Some(TaskChange::Added));
+        assert_eq!(changes.get(moved.id), Some(TaskChange::Moved));
+        assert_eq!(changes.get(commented.id), Some(TaskChange::Updated));
+        assert_eq!(
+            changes.summary().as_deref(),
+            Some("1 new, 1 moved, 1 updated, 1 removed")
+        );
+    }
+
+    #[test]
+    fn app_refresh_after_failure_marks_changed_tasks() {
+        let cached = github_task("Cached", "a");
+        let mut board = KanbanBoard::new();
+        board.add_task(cached.clone());
+        let mut app = test_app_with_config(board, repos_config(&["a"]));
+        app.apply_repo_event(RepoEvent::Failed {
+            repo: "owner/a".to_string(),
+            kind: FetchErrorKind::Network,
+            error: "offline".to_string(),
+        });
+
+        app.mark_repos_refreshing();
+        assert_eq!(
+            app.repo_statuses().get("owner/a"),
+            Some(&RepoStatus::Loading)
+        );
+        let added = github_task("Added", "a");
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![cached.clone(), added.clone()],
+        });
+
+        assert_eq!(app.state.changes.get(cached.id), None);
+        assert_eq!(app.state.changes.get(added.id), Some(TaskChange::Added));
+    }
+
+    #[test]
+    fn app_viewing_task_clears_its_marker() {
+        let task = github_task("Task", "a");
+        let mut app = shown_repo_app(&[]);
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![task.clone()],
+        });
+        assert_eq!(app.state.changes.get(task.id), Some(TaskChange::Added));
+
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert!(app.state.detail_visible);
+        assert_eq!(app.state.changes.get(task.id), None);
+    }
+
+    #[test]
+    fn app_escape_without_selection_dismisses_changes() {
+        let mut app = shown_repo_app(&[]);
+        app.apply_repo_event(RepoEvent::Loaded {
+            repo: "owner/a".to_string(),
+            tasks: vec![github_task("Task", "a")],
+        });
+        app.update(Message::NavigateDown);
+
+        // The first escape clears the selection, the second the changes
+        app.update(Message::Escape);
+        assert!(!app.state.changes.is_empty());
+        app.update(Message::Escape);
+        assert!(app.state.changes.is_empty());
+    }
+
+    #[test]
+    fn app_header_and_cards_show_changes() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let task = github_task("Task", "a");
+        let mut app = shown_repo_app(&[&task]);
+        let mut moved = task;
+        moved.lane = whip_protocol::LaneKind::InProgress;
+        app.set_board({
+            let mut board = KanbanBoard::new();
+            board.add_task(moved);
+            board
+        });
+
+        let backend = TestBackend::new(120, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        let header = content.lines().nth(1).unwrap_or_default();
+        assert!(header.contains("Changes: 1 moved"), "header: {header}");
+        assert!(content.contains(" moved ┐"));
+    }
+
+    #[test]
+    fn app_navigate_first_and_last() {
+        let long_description = "This is a very long description. ".repeat(50);
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task 1", &long_description));
+        board.add_task(whip_protocol::Task::new("Task 2", "Description"));
+        let mut app = test_app(board);
+        app.last_area = Rect::new(0, 0, 80, 24);
+
+        app.update(Message::NavigateLast);
+        assert_eq!(app.state.selected_task, Some(1));
+        app.update(Message::NavigateFirst);
+        assert_eq!(app.state.selected_task, Some(0));
+
+        // In the detail view they scroll to the top and the bottom
+        app.update(Message::Select);
+        app.update(Message::NavigateLast);
+        let bottom = app.state.detail_scroll;
+        assert!(bottom > 0);
+        app.update(Message::NavigateDown);
+        assert_eq!(app.state.detail_scroll, bottom);
+        app.update(Message::NavigateFirst);
+        assert_eq!(app.state.detail_scroll, 0);
+    }
+
+    #[test]
+    fn app_key_context_follows_view() {
+        let mut board = KanbanBoard::new();
+        board.add_task(whip_protocol::Task::new("Task", "Description"));
+        let mut app = test_app(board);
+        assert_eq!(app.key_context(), KeyContext::Board);
+
+        app.update(Message::NavigateDown);
+        app.update(Message::Select);
+        assert_eq!(app.key_context(), KeyContext::Detail);
+
+        app.update(Message::ToggleHelp);
+        assert_eq!(app.key_context(), KeyContext::Help);
+    }
+
+    #[test]
+    fn app_config_reload_updates_keymap() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
+        let mut app = test_app(KanbanBoard::new());
+        let j = "j".parse().expect("chord");
+        assert_eq!(app.keymap.message(KeyContext::Board, j), None);
+
+        let mut config = Config::default();
+        config.keymap.preset = whip_protocol::KeymapPreset::Vim;
+        config.keymap.bindings.insert(
+            KeyContext::Board,
+            [
+                ("?".parse().expect("chord"), None),
+                ("F1".parse().expect("chord"), Some(Message::ToggleHelp)),
+            ]
+            .into(),
+        );
+        app.apply_config_change(reloaded(config));
+        assert_eq!(
+            app.keymap.message(KeyContext::Board, j),
+            Some(&Message::NavigateDown)
+        );
+
+        // The header and the help overlay show the configured keys
+        app.update(Message::ToggleHelp);
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal.draw(|frame| app.view(frame)).unwrap();
+
+        let content = crate::test_utils::buffer_to_string(terminal.backend().buffer());
+        let header = content.lines().nth(1).unwrap_or_default();
+        assert!(header.contains("Press F1 for help"), "header: {header}");
+        assert!(content.contains("↓ j"));
+        assert!(content.contains("F1           Toggle help"));
+    }
 }
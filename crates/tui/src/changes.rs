@@ -0,0 +1,186 @@
+//! Changes since the last refresh.
+//!
+//! Every refresh is compared with the board it replaces (see
+//! [`BoardDiff`]). The tasks it changed stay marked until the user views
+//! them, and the header sums up the changes still unseen.
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_protocol::{BoardDiff, TaskChange, TaskId};
+//! use whip_tui::changes::BoardChanges;
+//!
+//! let id = TaskId::new_v4();
+//! let mut changes = BoardChanges::default();
+//! changes.record(&BoardDiff {
+//!     added: vec![id],
+//!     ..BoardDiff::default()
+//! });
+//! assert_eq!(changes.get(id), Some(TaskChange::Added));
+//! assert_eq!(changes.summary().as_deref(), Some("1 new"));
+//!
+//! changes.viewed(id);
+//! assert!(changes.is_empty());
+//! ```
+
+use std::collections::HashMap;
+
+use whip_protocol::{BoardDiff, TaskChange, TaskId};
+
+/// The changes the user has not seen yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardChanges {
+    tasks: HashMap<TaskId, TaskChange>,
+    removed: usize,
+}
+
+impl BoardChanges {
+    /// Records the changes of a refresh.
+    ///
+    /// A task keeps its most notable unseen change: new over moved over
+    /// updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `diff` - The changes between the previous and the refreshed board
+    pub fn record(&mut self, diff: &BoardDiff) {
+        let changes = [
+            (&diff.updated, TaskChange::Updated),
+            (&diff.moved, TaskChange::Moved),
+            (&diff.added, TaskChange::Added),
+        ];
+        for (ids, change) in changes {
+            for id in ids {
+                let entry = self.tasks.entry(*id).or_insert(change);
+                *entry = notable(*entry, change);
+            }
+        }
+        for id in &diff.removed {
+            self.tasks.remove(id);
+        }
+        self.removed += diff.removed.len();
+    }
+
+    /// Returns the unseen change of a task, if any.
+    #[must_use]
+    pub fn get(&self, id: TaskId) -> Option<TaskChange> {
+        self.tasks.get(&id).copied()
+    }
+
+    /// Marks a task as seen.
+    pub fn viewed(&mut self, id: TaskId) {
+        self.tasks.remove(&id);
+    }
+
+    /// Marks every change as seen.
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+        self.removed = 0;
+    }
+
+    /// Returns `true` if there is no unseen change.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty() && self.removed == 0
+    }
+
+    /// Sums up the unseen changes, such as `2 new, 1 moved, 1 removed`.
+    ///
+    /// Returns `None` if there is no unseen change.
+    #[must_use]
+    pub fn summary(&self) -> Option<String> {
+        let count = |wanted: TaskChange| {
+            self.tasks
+                .values()
+                .filter(|change| **change == wanted)
+                .count()
+        };
+        let parts: Vec<String> = [
+            (count(TaskChange::Added), "new"),
+            (count(TaskChange::Moved), "moved"),
+            (count(TaskChange::Updated), "updated"),
+            (self.removed, "removed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, name)| format!("{count} {name}"))
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Returns the more notable of two changes.
+const fn notable(a: TaskChange, b: TaskChange) -> TaskChange {
+    match (a, b) {
+        (TaskChange::Added, _) | (_, TaskChange::Added) => TaskChange::Added,
+        (TaskChange::Moved, _) | (_, TaskChange::Moved) => TaskChange::Moved,
+        _ => TaskChange::Updated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_most_notable_change() {
+        let id = TaskId::new_v4();
+        let mut changes = BoardChanges::default();
+        changes.record(&BoardDiff {
+            added: vec![id],
+            ..BoardDiff::default()
+        });
+        changes.record(&BoardDiff {
+            updated: vec![id],
+            ..BoardDiff::default()
+        });
+        assert_eq!(changes.get(id), Some(TaskChange::Added));
+
+        changes.viewed(id);
+        changes.record(&BoardDiff {
+            updated: vec![id],
+            ..BoardDiff::default()
+        });
+        changes.record(&BoardDiff {
+            moved: vec![id],
+            ..BoardDiff::default()
+        });
+        assert_eq!(changes.get(id), Some(TaskChange::Moved));
+    }
+
+    #[test]
+    fn removed_tasks_are_counted() {
+        let id = TaskId::new_v4();
+        let mut changes = BoardChanges::default();
+        changes.record(&BoardDiff {
+            updated: vec![id],
+            ..BoardDiff::default()
+        });
+        changes.record(&BoardDiff {
+            removed: vec![id],
+            ..BoardDiff::default()
+        });
+
+        assert_eq!(changes.get(id), None);
+        assert_eq!(changes.summary().as_deref(), Some("1 removed"));
+
+        changes.clear();
+        assert!(changes.is_empty());
+        assert_eq!(changes.summary(), None);
+    }
+
+    #[test]
+    fn summary_lists_each_kind_of_change() {
+        let mut changes = BoardChanges::default();
+        changes.record(&BoardDiff {
+            added: vec![TaskId::new_v4(), TaskId::new_v4()],
+            moved: vec![TaskId::new_v4()],
+            updated: vec![TaskId::new_v4()],
+            removed: vec![TaskId::new_v4()],
+        });
+        assert_eq!(
+            changes.summary().as_deref(),
+            Some("2 new, 1 moved, 1 updated, 1 removed")
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Context menu state.
+//!
+//! A right click on a task opens a menu of the actions that apply to it.
+//! Each item sends the message one of the board keys would, so the menu
+//! offers the keyboard's actions to the mouse rather than actions of its own.
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_protocol::{Message, Task};
+//! use whip_tui::context_menu::ContextMenu;
+//!
+//! let task = Task::new("Fix login", "Users are logged out");
+//! let mut menu = ContextMenu::for_task(&task, false, (10, 5));
+//! assert_eq!(menu.selected_message(), Some(&Message::Select));
+//!
+//! menu.move_selection(1);
+//! assert_eq!(menu.selected_message(), Some(&Message::CycleSort));
+//! ```
+
+use whip_protocol::{Message, Task};
+
+/// An entry of a context menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    /// The text shown for the entry.
+    pub label: &'static str,
+    /// The message sent when the entry is chosen.
+    pub message: Message,
+}
+
+impl MenuItem {
+    const fn new(label: &'static str, message: Message) -> Self {
+        Self { label, message }
+    }
+}
+
+/// The state of an open context menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenu {
+    title: String,
+    items: Vec<MenuItem>,
+    selected: usize,
+    anchor: (u16, u16),
+}
+
+impl ContextMenu {
+    /// Creates the menu of actions on a task.
+    ///
+    /// On the board the task can be opened and its lane sorted; in the
+    /// detail view it can be closed. Opening in the browser is offered
+    /// only for tasks from GitHub.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task the menu acts on, which must be the selected one
+    /// * `in_detail` - Whether the task is shown in the detail view
+    /// * `anchor` - The (column, row) the menu was opened at
+    #[must_use]
+    pub fn for_task(task: &Task, in_detail: bool, anchor: (u16, u16)) -> Self {
+        let mut items = Vec::with_capacity(4);
+        if !in_detail {
+            items.push(MenuItem::new("Open details", Message::Select));
+        }
+        if task.github.is_some() {
+            items.push(MenuItem::new("Open in browser", Message::OpenInBrowser));
+        }
+        if in_detail {
+            items.push(MenuItem::new("Close details", Message::Escape));
+        } else {
+            items.push(MenuItem::new("Sort lane", Message::CycleSort));
+        }
+        items.push(MenuItem::new("Refresh", Message::Refresh));
+
+        Self {
+            title: task.title.clone(),
+            items,
+            selected: 0,
+            anchor,
+        }
+    }
+
+    /// Returns the title of the menu.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the entries of the menu.
+    #[must_use]
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Returns the index of the highlighted entry.
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns the (column, row) the menu was opened at.
+    #[must_use]
+    pub fn anchor(&self) -> (u16, u16) {
+        self.anchor
+    }
+
+    /// Moves the highlight by `delta` entries, stopping at the first and
+    /// last ones.
+    pub fn move_selection(&mut self, delta: i32) {
+        let last = self.items.len().saturating_sub(1);
+        let step = usize::try_from(delta.unsigned_abs()).unwrap_or(usize::MAX);
+        self.selected = if delta < 0 {
+            self.selected.saturating_sub(step)
+        } else {
+            self.selected.saturating_add(step).min(last)
+        };
+    }
+
+    /// Highlights an entry by index.
+    ///
+    /// Returns `false` and keeps the highlight if there is no such entry.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.items.len() {
+            return false;
+        }
+        self.selected = index;
+        true
+    }
+
+    /// Returns the message of the highlighted entry.
+    #[must_use]
+    pub fn selected_message(&self) -> Option<&Message> {
+        self.items.get(self.selected).map(|item| &item.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use whip_protocol::GitHubSource;
+
+    fn messages(menu: &ContextMenu) -> Vec<Message> {
+        menu.items()
+            .iter()
+            .map(|item| item.message.clone())
+            .collect()
+    }
+
+    fn github_task() -> Task {
+        let mut task = Task::new("Fix login", "");
+        task.github = Some(GitHubSource {
+            host: None,
+            owner: "acme".to_string(),
+            repo: "api".to_string(),
+            number: 7,
+            url: "https://github.com/acme/api/issues/7".to_string(),
+            labels: vec![],
+            author: "author".to_string(),
+            comment_count: 0,
+        });
+        task
+    }
+
+    #[test]
+    fn board_menu_offers_board_actions() {
+        let menu = ContextMenu::for_task(&Task::new("Local", ""), false, (3, 4));
+        assert_eq!(menu.title(), "Local");
+        assert_eq!(menu.anchor(), (3, 4));
+        assert_eq!(
+            messages(&menu),
+            [Message::Select, Message::CycleSort, Message::Refresh]
+        );
+    }
+
+    #[test]
+    fn github_tasks_open_in_browser() {
+        let menu = ContextMenu::for_task(&github_task(), false, (0, 0));
+        assert!(messages(&menu).contains(&Message::OpenInBrowser));
+
+        let menu = ContextMenu::for_task(&github_task(), true, (0, 0));
+        assert_eq!(
+            messages(&menu),
+            [Message::OpenInBrowser, Message::Escape, Message::Refresh]
+        );
+    }
+
+    #[test]
+    fn selection_stops_at_the_ends() {
+        let mut menu = ContextMenu::for_task(&Task::new("Local", ""), false, (0, 0));
+
+        menu.move_selection(-1);
+        assert_eq!(menu.selected(), 0);
+        menu.move_selection(5);
+        assert_eq!(menu.selected(), 2);
+        assert_eq!(menu.selected_message(), Some(&Message::Refresh));
+
+        assert!(menu.select(1));
+        assert!(!menu.select(3));
+        assert_eq!(menu.selected_message(), Some(&Message::CycleSort));
+    }
+}
@@ -0,0 +1,76 @@
+//! Credential checks shown in the settings panel.
+//!
+//! Testing a repository's credentials resolves its token, asks GitHub who
+//! the token belongs to and which scopes it grants, and reports the outcome
+//! as a [`CredentialCheck`]. GitHub App installation tokens belong to no
+//! user, so for them GitHub is asked which permissions the token grants in
+//! the repository instead.
+
+use whip_config::auth::TokenSource;
+
+/// The outcome of testing a repository's credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialCheck {
+    /// The test is in progress.
+    Pending,
+    /// No token was found; the repository is accessed without one.
+    Unauthenticated,
+    /// The test was not run because whip is running offline.
+    Offline,
+    /// GitHub accepted the token.
+    Valid {
+        /// Where the token came from.
+        source: TokenSource,
+        /// The login the token belongs to.
+        login: String,
+        /// The OAuth scopes granted to the token, if GitHub reports them.
+        scopes: Option<Vec<String>>,
+        /// Scopes needed for label sync and lane moves that the token lacks.
+        missing_scopes: Vec<String>,
+    },
+    /// GitHub accepted a GitHub App installation token.
+    ValidInstallation {
+        /// Where the token came from.
+        source: TokenSource,
+        /// The repository permissions granted to the installation, if
+        /// GitHub reports them.
+        permissions: Option<Vec<String>>,
+        /// Permissions needed for label sync and lane moves that the
+        /// installation lacks.
+        missing_permissions: Vec<String>,
+    },
+    /// GitHub rejected the token, or could not be reached.
+    Failed {
+        /// Where the token came from.
+        source: TokenSource,
+        /// A human-readable description of the failure.
+        error: String,
+    },
+}
+
+impl CredentialCheck {
+    /// Returns the source of the tested token, if one was found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::auth::TokenSource;
+    /// use whip_tui::credentials::CredentialCheck;
+    ///
+    /// let check = CredentialCheck::Failed {
+    ///     source: TokenSource::GhCli,
+    ///     error: "Bad credentials".to_string(),
+    /// };
+    /// assert_eq!(check.source(), Some(&TokenSource::GhCli));
+    /// assert_eq!(CredentialCheck::Pending.source(), None);
+    /// ```
+    #[must_use]
+    pub fn source(&self) -> Option<&TokenSource> {
+        match self {
+            Self::Valid { source, .. }
+            | Self::ValidInstallation { source, .. }
+            | Self::Failed { source, .. } => Some(source),
+            Self::Pending | Self::Unauthenticated | Self::Offline => None,
+        }
+    }
+}
@@ -6,7 +6,7 @@
 use std::time::Duration;
 
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
-use whip_protocol::Message;
+use whip_protocol::{Key, KeyChord, KeyContext, Keymap, Message};
 
 /// Default poll timeout for events.
 const POLL_TIMEOUT: Duration = Duration::from_millis(100);
@@ -31,10 +31,16 @@ pub fn poll_event() -> std::io::Result<Option<Event>> {
 ///
 /// Returns `Some(Message)` if the event maps to an action,
 /// or `None` if the event is not handled.
+///
+/// # Arguments
+///
+/// * `event` - The terminal event
+/// * `keymap` - The active key bindings
+/// * `context` - Where keys are pressed
 #[must_use]
-pub fn event_to_message(event: &Event) -> Option<Message> {
+pub fn event_to_message(event: &Event, keymap: &Keymap, context: KeyContext) -> Option<Message> {
     match event {
-        Event::Key(key) => key_to_message(*key),
+        Event::Key(key) => key_to_message(*key, keymap, context),
         Event::Mouse(mouse) => mouse_to_message(mouse),
         _ => None,
     }
@@ -42,107 +48,190 @@ pub fn event_to_message(event: &Event) -> Option<Message> {
 
 /// Converts a mouse event to an application message.
 ///
-/// Only left-click press events are handled, producing a `ClickAt` message
-/// with the click coordinates.
+/// Left and right button presses produce `ClickAt` and `RightClickAt`, and
+/// the wheel produces `ScrollAt`, all with the pointer coordinates. Other
+/// mouse events are ignored.
 #[must_use]
 fn mouse_to_message(mouse: &crossterm::event::MouseEvent) -> Option<Message> {
+    let (column, row) = (mouse.column, mouse.row);
     match mouse.kind {
-        MouseEventKind::Down(MouseButton::Left) => Some(Message::ClickAt {
-            column: mouse.column,
-            row: mouse.row,
+        MouseEventKind::Down(MouseButton::Left) => Some(Message::ClickAt { column, row }),
+        MouseEventKind::Down(MouseButton::Right) => Some(Message::RightClickAt { column, row }),
+        MouseEventKind::ScrollUp => Some(Message::ScrollAt {
+            column,
+            row,
+            delta: -1,
+        }),
+        MouseEventKind::ScrollDown => Some(Message::ScrollAt {
+            column,
+            row,
+            delta: 1,
         }),
         _ => None,
     }
 }
 
+/// Converts a terminal key event to the chord it is bound by.
+///
+/// Returns `None` for keys that cannot be bound, such as media keys.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use whip_tui::event::key_chord;
+///
+/// let key = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+/// assert_eq!(key_chord(key), "G".parse().ok());
+/// ```
+#[must_use]
+pub fn key_chord(key: KeyEvent) -> Option<KeyChord> {
+    let code = match key.code {
+        KeyCode::Char(ch) => Key::Char(ch),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(n @ 1..=12) => Key::F(n),
+        _ => return None,
+    };
+    let mut chord = KeyChord::new(code);
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        chord = chord.with_ctrl();
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        chord = chord.with_alt();
+    }
+    if key.modifiers.contains(KeyModifiers::SHIFT) {
+        chord = chord.with_shift();
+    }
+    Some(chord)
+}
+
 /// Converts a terminal key event to an application message.
 ///
-/// Returns `Some(Message)` if the key event maps to an action,
-/// or `None` if the key is not bound.
+/// Returns `Some(Message)` if the key is bound in `context` (or the
+/// context it falls back to), or `None` if the key is not bound.
+/// `Ctrl+C` always quits, whatever the keymap.
 ///
-/// # Key Bindings
+/// See [`Keymap::preset`] for the default bindings.
 ///
-/// | Key | Action |
-/// |-----|--------|
-/// | `Ctrl+C` | Quit |
-/// | `Esc` | Escape (close panel or clear selection) |
-/// | `Left` | Navigate left |
-/// | `Right` | Navigate right |
-/// | `Up` | Navigate up |
-/// | `Down` | Navigate down |
-/// | `Enter` or `Space` | Select |
-/// | `Backspace` | Back |
-/// | `o` | Open in browser |
-/// | `Ctrl+R` | Refresh |
-/// | `?` | Toggle help |
-/// | `Shift+S` | Open settings |
+/// # Arguments
+///
+/// * `key` - The key event
+/// * `keymap` - The active key bindings
+/// * `context` - Where the key is pressed
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// use whip_protocol::{KeyContext, Keymap, KeymapPreset, Message};
+/// use whip_tui::event::key_to_message;
+///
+/// let key = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+/// assert_eq!(key_to_message(key, &Keymap::default(), KeyContext::Board), None);
+///
+/// let vim = Keymap::preset(KeymapPreset::Vim);
+/// assert_eq!(
+///     key_to_message(key, &vim, KeyContext::Board),
+///     Some(Message::NavigateDown)
+/// );
+/// ```
 #[must_use]
-pub fn key_to_message(key: KeyEvent) -> Option<Message> {
-    // Check for Ctrl+C first
-    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+pub fn key_to_message(key: KeyEvent, keymap: &Keymap, context: KeyContext) -> Option<Message> {
+    // Check for Ctrl+C first, so no keymap can make whip impossible to quit
+    if is_ctrl_c(key) {
         return Some(Message::Quit);
     }
 
-    // Check for Shift+S to open settings
-    if key.modifiers.contains(KeyModifiers::SHIFT) && key.code == KeyCode::Char('S') {
-        return Some(Message::OpenSettings);
-    }
-
-    // Check for Ctrl+R to refresh
-    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('r') {
-        return Some(Message::Refresh);
-    }
-
-    // Regular keys
-    match key.code {
-        // Escape (contextual: close panel or clear selection)
-        KeyCode::Esc => Some(Message::Escape),
-
-        // Navigation (arrow keys only)
-        KeyCode::Left => Some(Message::NavigateLeft),
-        KeyCode::Right => Some(Message::NavigateRight),
-        KeyCode::Up => Some(Message::NavigateUp),
-        KeyCode::Down => Some(Message::NavigateDown),
+    keymap.message(context, key_chord(key)?).cloned()
+}
 
-        // Selection
-        KeyCode::Enter | KeyCode::Char(' ') => Some(Message::Select),
-        KeyCode::Backspace => Some(Message::Back),
+/// Returns `true` for `Ctrl+C`, which quits in every mode.
+fn is_ctrl_c(key: KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c')
+}
 
-        // Other actions
-        KeyCode::Char('o') => Some(Message::OpenInBrowser),
-        KeyCode::Char('?') => Some(Message::ToggleHelp),
+/// Converts a key event to a search-specific message.
+///
+/// This function is used while a search is active. When the query is
+/// being typed, keys edit it; once confirmed, `n`/`N` step through the
+/// results and every other key behaves as on the board.
+///
+/// # Key Bindings (Search Mode)
+///
+/// | Key | Action |
+/// |-----|--------|
+/// | `Enter` | Confirm the query (edit mode) |
+/// | `Esc` | Cancel the search (edit mode) |
+/// | `Backspace` | Backspace (edit mode) |
+/// | `Up`/`Down` | Previous/next result (edit mode) |
+/// | Any char | Input (edit mode) |
+/// | `n`/`N` | Next/previous result |
+#[must_use]
+pub fn key_to_search_message(key: KeyEvent, is_editing: bool, keymap: &Keymap) -> Option<Message> {
+    // Check for Ctrl+C first (always works)
+    if is_ctrl_c(key) {
+        return Some(Message::Quit);
+    }
 
-        _ => None,
+    if is_editing {
+        match key.code {
+            KeyCode::Enter => Some(Message::SearchConfirm),
+            KeyCode::Esc => Some(Message::SearchCancel),
+            KeyCode::Backspace => Some(Message::SearchBackspace),
+            KeyCode::Up => Some(Message::SearchPrevious),
+            KeyCode::Down => Some(Message::SearchNext),
+            KeyCode::Char(ch) => Some(Message::SearchInput { ch }),
+            _ => None,
+        }
+    } else {
+        match key.code {
+            KeyCode::Char('n') => Some(Message::SearchNext),
+            KeyCode::Char('N') => Some(Message::SearchPrevious),
+            _ => key_to_message(key, keymap, KeyContext::Board),
+        }
     }
 }
 
 /// Converts a key event to a settings-specific message.
 ///
-/// This function is used when the settings panel is open to handle
-/// settings-specific key bindings.
+/// This function is used when the settings panel is open. While browsing
+/// the settings, keys are looked up in the [`KeyContext::Settings`]
+/// bindings of the keymap; editing a value and confirming a delete use
+/// fixed keys.
 ///
-/// # Key Bindings (Settings Mode)
+/// # Key Bindings (Edit and Delete Modes)
 ///
 /// | Key | Action |
 /// |-----|--------|
-/// | `Left` | Previous section (nav mode) / Move cursor left (edit mode) |
-/// | `Right` | Next section (nav mode) / Move cursor right (edit mode) |
-/// | `Up` | Navigate up |
-/// | `Down` | Navigate down |
-/// | `Enter` | Edit/confirm |
-/// | `Esc` | Cancel/close |
-/// | `d` | Delete |
+/// | `Left`/`Right` | Move cursor (edit mode) |
+/// | `Enter` | Confirm (edit mode) |
+/// | `Esc` | Cancel |
+/// | `Tab` | Switch field (edit mode) |
 /// | `y`/`n` | Confirm/cancel delete (when delete pending) |
-/// | `Backspace` | Backspace (in edit mode) |
-/// | Any char | Input (in edit mode) |
+/// | `Backspace` | Backspace (edit mode) |
+/// | Any char | Input (edit mode) |
 #[must_use]
 pub fn key_to_settings_message(
     key: KeyEvent,
     is_editing: bool,
     is_delete_pending: bool,
+    keymap: &Keymap,
 ) -> Option<Message> {
     // Check for Ctrl+C first (always works)
-    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+    if is_ctrl_c(key) {
         return Some(Message::Quit);
     }
 
@@ -167,16 +256,42 @@ pub fn key_to_settings_message(
         }
     } else {
         // Navigation mode
-        match key.code {
-            KeyCode::Esc => Some(Message::CloseSettings),
-            KeyCode::Left => Some(Message::SettingsPrevSection),
-            KeyCode::Right => Some(Message::SettingsNextSection),
-            KeyCode::Up => Some(Message::SettingsNavigate { delta: -1 }),
-            KeyCode::Down => Some(Message::SettingsNavigate { delta: 1 }),
-            KeyCode::Enter => Some(Message::SettingsEdit),
-            KeyCode::Char('d') => Some(Message::SettingsDelete),
-            _ => None,
+        key_to_message(key, keymap, KeyContext::Settings)
+    }
+}
+
+/// Converts a key event to a filter bar message.
+///
+/// This function is used while the filter bar is open.
+///
+/// # Key Bindings (Filter Bar)
+///
+/// | Key | Action |
+/// |-----|--------|
+/// | `Enter` | Apply the filter |
+/// | `Esc` | Close without changing the filter |
+/// | `Tab`/`Shift+Tab` | Next/previous saved filter |
+/// | `Ctrl+S` | Save the input as a named filter |
+/// | `Backspace` | Backspace |
+/// | Any char | Input |
+#[must_use]
+pub fn key_to_filter_message(key: KeyEvent) -> Option<Message> {
+    // Check for Ctrl+C first (always works)
+    if is_ctrl_c(key) {
+        return Some(Message::Quit);
+    }
+
+    match key.code {
+        KeyCode::Enter => Some(Message::FilterApply),
+        KeyCode::Esc => Some(Message::FilterCancel),
+        KeyCode::Backspace => Some(Message::FilterBackspace),
+        KeyCode::Tab => Some(Message::FilterCycleNamed { delta: 1 }),
+        KeyCode::BackTab => Some(Message::FilterCycleNamed { delta: -1 }),
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Message::FilterSave)
         }
+        KeyCode::Char(ch) => Some(Message::FilterInput { ch }),
+        _ => None,
     }
 }
 
@@ -185,6 +300,19 @@ mod tests {
     use super::*;
     use crossterm::event::{KeyEventKind, MouseEvent, MouseEventKind};
 
+    /// Converts a key with the default keymap, on the board.
+    fn board(key: KeyEvent) -> Option<Message> {
+        key_to_message(key, &Keymap::default(), KeyContext::Board)
+    }
+
+    fn search(key: KeyEvent, is_editing: bool) -> Option<Message> {
+        key_to_search_message(key, is_editing, &Keymap::default())
+    }
+
+    fn settings(key: KeyEvent, is_editing: bool, is_delete_pending: bool) -> Option<Message> {
+        key_to_settings_message(key, is_editing, is_delete_pending, &Keymap::default())
+    }
+
     fn make_key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
     }
@@ -211,81 +339,197 @@ mod tests {
     fn quit_keys() {
         // Only Ctrl+C quits
         assert_eq!(
-            key_to_message(make_key_with_modifiers(
+            board(make_key_with_modifiers(
                 KeyCode::Char('c'),
                 KeyModifiers::CONTROL
             )),
             Some(Message::Quit)
         );
         // 'q' is no longer a quit key
-        assert_eq!(key_to_message(make_key(KeyCode::Char('q'))), None);
+        assert_eq!(board(make_key(KeyCode::Char('q'))), None);
     }
 
     #[test]
     fn escape_key() {
+        assert_eq!(board(make_key(KeyCode::Esc)), Some(Message::Escape));
+    }
+
+    #[test]
+    fn navigation_keys() {
+        assert_eq!(board(make_key(KeyCode::Left)), Some(Message::NavigateLeft));
         assert_eq!(
-            key_to_message(make_key(KeyCode::Esc)),
-            Some(Message::Escape)
+            board(make_key(KeyCode::Right)),
+            Some(Message::NavigateRight)
         );
+        assert_eq!(board(make_key(KeyCode::Up)), Some(Message::NavigateUp));
+        assert_eq!(board(make_key(KeyCode::Down)), Some(Message::NavigateDown));
     }
 
     #[test]
-    fn navigation_keys() {
+    fn vim_keys_not_mapped() {
+        // Vim-style hjkl should NOT be mapped
+        assert_eq!(board(make_key(KeyCode::Char('h'))), None);
+        assert_eq!(board(make_key(KeyCode::Char('j'))), None);
+        assert_eq!(board(make_key(KeyCode::Char('k'))), None);
+        assert_eq!(board(make_key(KeyCode::Char('l'))), None);
+    }
+
+    #[test]
+    fn selection_keys() {
+        assert_eq!(board(make_key(KeyCode::Enter)), Some(Message::Select));
+        assert_eq!(board(make_key(KeyCode::Char(' '))), Some(Message::Select));
+        assert_eq!(board(make_key(KeyCode::Backspace)), Some(Message::Back));
+    }
+
+    #[test]
+    fn other_action_keys() {
         assert_eq!(
-            key_to_message(make_key(KeyCode::Left)),
-            Some(Message::NavigateLeft)
+            board(make_key(KeyCode::Char('?'))),
+            Some(Message::ToggleHelp)
         );
         assert_eq!(
-            key_to_message(make_key(KeyCode::Right)),
-            Some(Message::NavigateRight)
+            board(make_key(KeyCode::Char('e'))),
+            Some(Message::ToggleErrors)
         );
         assert_eq!(
-            key_to_message(make_key(KeyCode::Up)),
-            Some(Message::NavigateUp)
+            board(make_key(KeyCode::Char('n'))),
+            Some(Message::ToggleNotifications)
         );
+    }
+
+    #[test]
+    fn slash_starts_search() {
         assert_eq!(
-            key_to_message(make_key(KeyCode::Down)),
-            Some(Message::NavigateDown)
+            board(make_key(KeyCode::Char('/'))),
+            Some(Message::StartSearch)
         );
     }
 
     #[test]
-    fn vim_keys_not_mapped() {
-        // Vim-style hjkl should NOT be mapped
-        assert_eq!(key_to_message(make_key(KeyCode::Char('h'))), None);
-        assert_eq!(key_to_message(make_key(KeyCode::Char('j'))), None);
-        assert_eq!(key_to_message(make_key(KeyCode::Char('k'))), None);
-        assert_eq!(key_to_message(make_key(KeyCode::Char('l'))), None);
+    fn sort_key() {
+        assert_eq!(
+            board(make_key(KeyCode::Char('s'))),
+            Some(Message::CycleSort)
+        );
     }
 
     #[test]
-    fn selection_keys() {
+    fn filter_keys() {
         assert_eq!(
-            key_to_message(make_key(KeyCode::Enter)),
-            Some(Message::Select)
+            board(make_key(KeyCode::Char('f'))),
+            Some(Message::EditFilter)
         );
         assert_eq!(
-            key_to_message(make_key(KeyCode::Char(' '))),
-            Some(Message::Select)
+            key_to_filter_message(make_key(KeyCode::Char('f'))),
+            Some(Message::FilterInput { ch: 'f' })
         );
         assert_eq!(
-            key_to_message(make_key(KeyCode::Backspace)),
-            Some(Message::Back)
+            key_to_filter_message(make_key(KeyCode::Enter)),
+            Some(Message::FilterApply)
+        );
+        assert_eq!(
+            key_to_filter_message(make_key(KeyCode::Esc)),
+            Some(Message::FilterCancel)
+        );
+        assert_eq!(
+            key_to_filter_message(make_key(KeyCode::Backspace)),
+            Some(Message::FilterBackspace)
+        );
+        assert_eq!(
+            key_to_filter_message(make_key(KeyCode::Tab)),
+            Some(Message::FilterCycleNamed { delta: 1 })
+        );
+        assert_eq!(
+            key_to_filter_message(make_key_with_modifiers(
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT
+            )),
+            Some(Message::FilterCycleNamed { delta: -1 })
+        );
+        assert_eq!(
+            key_to_filter_message(make_key_with_modifiers(
+                KeyCode::Char('s'),
+                KeyModifiers::CONTROL
+            )),
+            Some(Message::FilterSave)
+        );
+        assert_eq!(
+            key_to_filter_message(make_key_with_modifiers(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            )),
+            Some(Message::Quit)
         );
     }
 
     #[test]
-    fn other_action_keys() {
+    fn search_edit_mode() {
         assert_eq!(
-            key_to_message(make_key(KeyCode::Char('?'))),
-            Some(Message::ToggleHelp)
+            search(make_key(KeyCode::Char('n')), true),
+            Some(Message::SearchInput { ch: 'n' })
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Char('/')), true),
+            Some(Message::SearchInput { ch: '/' })
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Backspace), true),
+            Some(Message::SearchBackspace)
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Enter), true),
+            Some(Message::SearchConfirm)
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Esc), true),
+            Some(Message::SearchCancel)
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Down), true),
+            Some(Message::SearchNext)
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Up), true),
+            Some(Message::SearchPrevious)
+        );
+        assert_eq!(
+            search(
+                make_key_with_modifiers(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                true
+            ),
+            Some(Message::Quit)
+        );
+    }
+
+    #[test]
+    fn search_results_mode() {
+        assert_eq!(
+            search(make_key(KeyCode::Char('n')), false),
+            Some(Message::SearchNext)
+        );
+        assert_eq!(
+            search(
+                make_key_with_modifiers(KeyCode::Char('N'), KeyModifiers::SHIFT),
+                false
+            ),
+            Some(Message::SearchPrevious)
+        );
+        // Other keys fall back to the regular bindings
+        assert_eq!(search(make_key(KeyCode::Esc), false), Some(Message::Escape));
+        assert_eq!(
+            search(make_key(KeyCode::Char('/')), false),
+            Some(Message::StartSearch)
+        );
+        assert_eq!(
+            search(make_key(KeyCode::Down), false),
+            Some(Message::NavigateDown)
         );
     }
 
     #[test]
     fn ctrl_r_refreshes() {
         assert_eq!(
-            key_to_message(make_key_with_modifiers(
+            board(make_key_with_modifiers(
                 KeyCode::Char('r'),
                 KeyModifiers::CONTROL
             )),
@@ -297,7 +541,7 @@ mod tests {
     fn open_settings_key() {
         // Shift+S opens settings
         assert_eq!(
-            key_to_message(make_key_with_modifiers(
+            board(make_key_with_modifiers(
                 KeyCode::Char('S'),
                 KeyModifiers::SHIFT
             )),
@@ -305,10 +549,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn home_and_end_select_first_and_last() {
+        assert_eq!(board(make_key(KeyCode::Home)), Some(Message::NavigateFirst));
+        assert_eq!(board(make_key(KeyCode::End)), Some(Message::NavigateLast));
+    }
+
+    #[test]
+    fn vim_preset_keys() {
+        let vim = Keymap::preset(whip_protocol::KeymapPreset::Vim);
+        let vim_key = |key, context| key_to_message(key, &vim, context);
+
+        assert_eq!(
+            vim_key(make_key(KeyCode::Char('h')), KeyContext::Board),
+            Some(Message::NavigateLeft)
+        );
+        assert_eq!(
+            vim_key(make_key(KeyCode::Char('g')), KeyContext::Board),
+            Some(Message::NavigateFirst)
+        );
+        assert_eq!(
+            vim_key(
+                make_key_with_modifiers(KeyCode::Char('G'), KeyModifiers::SHIFT),
+                KeyContext::Board
+            ),
+            Some(Message::NavigateLast)
+        );
+        assert_eq!(
+            vim_key(make_key(KeyCode::Char('q')), KeyContext::Board),
+            Some(Message::Quit)
+        );
+        assert_eq!(
+            vim_key(make_key(KeyCode::Char('q')), KeyContext::Detail),
+            Some(Message::Back)
+        );
+        assert_eq!(
+            key_to_settings_message(make_key(KeyCode::Char('j')), false, false, &vim),
+            Some(Message::SettingsNavigate { delta: 1 })
+        );
+        // Typing into a settings field is not affected by the keymap
+        assert_eq!(
+            key_to_settings_message(make_key(KeyCode::Char('j')), true, false, &vim),
+            Some(Message::SettingsInput { ch: 'j' })
+        );
+    }
+
+    #[test]
+    fn ctrl_c_quits_even_when_unbound() {
+        let mut keymap = Keymap::default();
+        let ctrl_c = "ctrl+c".parse().expect("chord");
+        keymap.bind(KeyContext::Board, ctrl_c, None);
+        assert_eq!(
+            key_to_message(
+                make_key_with_modifiers(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                &keymap,
+                KeyContext::Board
+            ),
+            Some(Message::Quit)
+        );
+    }
+
+    #[test]
+    fn key_chord_folds_shift_into_characters() {
+        let shifted = make_key_with_modifiers(KeyCode::Char('s'), KeyModifiers::SHIFT);
+        assert_eq!(key_chord(shifted), "S".parse().ok());
+        let back_tab = make_key_with_modifiers(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(key_chord(back_tab), "shift+tab".parse().ok());
+        assert_eq!(key_chord(make_key(KeyCode::CapsLock)), None);
+    }
+
     #[test]
     fn unmapped_keys_return_none() {
-        assert_eq!(key_to_message(make_key(KeyCode::Char('x'))), None);
-        assert_eq!(key_to_message(make_key(KeyCode::F(1))), None);
+        assert_eq!(board(make_key(KeyCode::Char('x'))), None);
+        assert_eq!(board(make_key(KeyCode::F(1))), None);
     }
 
     #[test]
@@ -321,13 +634,53 @@ mod tests {
     }
 
     #[test]
-    fn mouse_right_click_ignored() {
+    fn mouse_right_click_generates_right_click_at() {
         let mouse = MouseEvent {
             kind: MouseEventKind::Down(MouseButton::Right),
             column: 10,
             row: 5,
             modifiers: KeyModifiers::NONE,
         };
+        assert_eq!(
+            mouse_to_message(&mouse),
+            Some(Message::RightClickAt { column: 10, row: 5 })
+        );
+    }
+
+    #[test]
+    fn mouse_wheel_generates_scroll_at() {
+        let scroll = |kind| MouseEvent {
+            kind,
+            column: 10,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(
+            mouse_to_message(&scroll(MouseEventKind::ScrollUp)),
+            Some(Message::ScrollAt {
+                column: 10,
+                row: 5,
+                delta: -1
+            })
+        );
+        assert_eq!(
+            mouse_to_message(&scroll(MouseEventKind::ScrollDown)),
+            Some(Message::ScrollAt {
+                column: 10,
+                row: 5,
+                delta: 1
+            })
+        );
+    }
+
+    #[test]
+    fn mouse_middle_click_ignored() {
+        let mouse = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Middle),
+            column: 10,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        };
         assert_eq!(mouse_to_message(&mouse), None);
     }
 
@@ -356,14 +709,17 @@ mod tests {
     #[test]
     fn event_to_message_handles_key_events() {
         let key_event = Event::Key(make_key(KeyCode::Enter));
-        assert_eq!(event_to_message(&key_event), Some(Message::Select));
+        assert_eq!(
+            event_to_message(&key_event, &Keymap::default(), KeyContext::Board),
+            Some(Message::Select)
+        );
     }
 
     #[test]
     fn event_to_message_handles_mouse_events() {
         let mouse_event = Event::Mouse(make_mouse_click(15, 8));
         assert_eq!(
-            event_to_message(&mouse_event),
+            event_to_message(&mouse_event, &Keymap::default(), KeyContext::Board),
             Some(Message::ClickAt { column: 15, row: 8 })
         );
     }
@@ -371,7 +727,10 @@ mod tests {
     #[test]
     fn event_to_message_ignores_resize_events() {
         let resize_event = Event::Resize(80, 24);
-        assert_eq!(event_to_message(&resize_event), None);
+        assert_eq!(
+            event_to_message(&resize_event, &Keymap::default(), KeyContext::Board),
+            None
+        );
     }
 
     // Settings mode tests
@@ -379,41 +738,53 @@ mod tests {
     fn settings_navigation_mode() {
         // Right moves to next section
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Right), false, false),
+            settings(make_key(KeyCode::Right), false, false),
             Some(Message::SettingsNextSection)
         );
 
         // Left moves to previous section
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Left), false, false),
+            settings(make_key(KeyCode::Left), false, false),
             Some(Message::SettingsPrevSection)
         );
 
         // Up/Down arrow keys navigate within section
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Up), false, false),
+            settings(make_key(KeyCode::Up), false, false),
             Some(Message::SettingsNavigate { delta: -1 })
         );
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Down), false, false),
+            settings(make_key(KeyCode::Down), false, false),
             Some(Message::SettingsNavigate { delta: 1 })
         );
 
         // Enter starts edit
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Enter), false, false),
+            settings(make_key(KeyCode::Enter), false, false),
             Some(Message::SettingsEdit)
         );
 
         // d deletes
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('d')), false, false),
+            settings(make_key(KeyCode::Char('d')), false, false),
             Some(Message::SettingsDelete)
         );
 
+        // t tests credentials
+        assert_eq!(
+            settings(make_key(KeyCode::Char('t')), false, false),
+            Some(Message::SettingsTestCredentials)
+        );
+
+        // u toggles saving to the user config
+        assert_eq!(
+            settings(make_key(KeyCode::Char('u')), false, false),
+            Some(Message::SettingsSaveToUserConfig)
+        );
+
         // Esc closes
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Esc), false, false),
+            settings(make_key(KeyCode::Esc), false, false),
             Some(Message::CloseSettings)
         );
     }
@@ -422,37 +793,37 @@ mod tests {
     fn settings_edit_mode() {
         // Character input
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('a')), true, false),
+            settings(make_key(KeyCode::Char('a')), true, false),
             Some(Message::SettingsInput { ch: 'a' })
         );
 
         // Backspace
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Backspace), true, false),
+            settings(make_key(KeyCode::Backspace), true, false),
             Some(Message::SettingsBackspace)
         );
 
         // Enter confirms
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Enter), true, false),
+            settings(make_key(KeyCode::Enter), true, false),
             Some(Message::SettingsConfirm)
         );
 
         // Esc cancels
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Esc), true, false),
+            settings(make_key(KeyCode::Esc), true, false),
             Some(Message::SettingsCancel)
         );
 
         // Left arrow moves cursor left in edit mode
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Left), true, false),
+            settings(make_key(KeyCode::Left), true, false),
             Some(Message::SettingsCursorLeft)
         );
 
         // Right arrow moves cursor right in edit mode
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Right), true, false),
+            settings(make_key(KeyCode::Right), true, false),
             Some(Message::SettingsCursorRight)
         );
     }
@@ -461,50 +832,44 @@ mod tests {
     fn settings_delete_pending_mode() {
         // y confirms delete
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('y')), false, true),
+            settings(make_key(KeyCode::Char('y')), false, true),
             Some(Message::SettingsInput { ch: 'y' })
         );
 
         // Y also confirms delete
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('Y')), false, true),
+            settings(make_key(KeyCode::Char('Y')), false, true),
             Some(Message::SettingsInput { ch: 'Y' })
         );
 
         // n cancels delete
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('n')), false, true),
+            settings(make_key(KeyCode::Char('n')), false, true),
             Some(Message::SettingsInput { ch: 'n' })
         );
 
         // N also cancels delete
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('N')), false, true),
+            settings(make_key(KeyCode::Char('N')), false, true),
             Some(Message::SettingsInput { ch: 'N' })
         );
 
         // Esc cancels delete
         assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Esc), false, true),
+            settings(make_key(KeyCode::Esc), false, true),
             Some(Message::SettingsCancel)
         );
 
         // Other keys are ignored in delete pending mode
-        assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Char('x')), false, true),
-            None
-        );
-        assert_eq!(
-            key_to_settings_message(make_key(KeyCode::Enter), false, true),
-            None
-        );
+        assert_eq!(settings(make_key(KeyCode::Char('x')), false, true), None);
+        assert_eq!(settings(make_key(KeyCode::Enter), false, true), None);
     }
 
     #[test]
     fn settings_ctrl_c_always_quits() {
         // Ctrl+C works in all modes
         assert_eq!(
-            key_to_settings_message(
+            settings(
                 make_key_with_modifiers(KeyCode::Char('c'), KeyModifiers::CONTROL),
                 false,
                 false
@@ -512,7 +877,7 @@ mod tests {
             Some(Message::Quit)
         );
         assert_eq!(
-            key_to_settings_message(
+            settings(
                 make_key_with_modifiers(KeyCode::Char('c'), KeyModifiers::CONTROL),
                 true,
                 false
@@ -520,7 +885,7 @@ mod tests {
             Some(Message::Quit)
         );
         assert_eq!(
-            key_to_settings_message(
+            settings(
                 make_key_with_modifiers(KeyCode::Char('c'), KeyModifiers::CONTROL),
                 false,
                 true
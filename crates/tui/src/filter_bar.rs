@@ -0,0 +1,379 @@
+//! Filter bar state management.
+//!
+//! The filter bar edits the filter narrowing the board. It takes either a
+//! filter expression, such as `repo:acme/api state:needs_attention`, or the
+//! name of a filter saved in the configuration; `Tab` cycles through the
+//! saved ones. `Ctrl+S` saves the input under a name asked for next (see
+//! [`FilterBarState::start_save`]).
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_protocol::{NamedFilter, TaskFilter};
+//! use whip_tui::filter_bar::FilterBarState;
+//!
+//! let saved = vec![NamedFilter::new("api", TaskFilter::default().with_repositories(["acme/api"]))];
+//!
+//! let mut bar = FilterBarState::default();
+//! bar.start("");
+//! bar.cycle(&saved, 1);
+//! assert_eq!(bar.input(), "api");
+//!
+//! let (filter, name) = bar.apply(&saved).unwrap();
+//! assert_eq!(filter.repositories, ["acme/api"]);
+//! assert_eq!(name.as_deref(), Some("api"));
+//! assert!(!bar.is_editing());
+//! ```
+
+use whip_protocol::{NamedFilter, TaskFilter};
+
+/// The state of the filter bar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterBarState {
+    input: String,
+    editing: bool,
+    error: Option<String>,
+    named: Option<usize>,
+    saving: Option<TaskFilter>,
+}
+
+impl FilterBarState {
+    /// Starts editing, with the given text in the input.
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The active filter, as a name or an expression
+    pub fn start(&mut self, current: &str) {
+        self.input = current.to_string();
+        self.editing = true;
+        self.error = None;
+        self.named = None;
+        self.saving = None;
+    }
+
+    /// Returns the text typed so far.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns `true` while the filter bar is open.
+    #[must_use]
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Returns why the input could not be applied, if it could not.
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the filter being saved while its name is typed, after
+    /// [`start_save`](Self::start_save).
+    #[must_use]
+    pub fn saving(&self) -> Option<&TaskFilter> {
+        self.saving.as_ref()
+    }
+
+    /// Returns the index of the saved filter picked with [`cycle`](Self::cycle),
+    /// until the input is edited.
+    #[must_use]
+    pub fn named_index(&self) -> Option<usize> {
+        self.named
+    }
+
+    /// Appends a character to the input.
+    pub fn push(&mut self, ch: char) {
+        self.input.push(ch);
+        self.edited();
+    }
+
+    /// Removes the last character of the input.
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.edited();
+    }
+
+    fn edited(&mut self) {
+        self.error = None;
+        self.named = None;
+    }
+
+    /// Puts the next (or previous) saved filter in the input, wrapping
+    /// around.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The saved filters
+    /// * `delta` - How many filters to move by (negative moves backwards)
+    pub fn cycle(&mut self, filters: &[NamedFilter], delta: i32) {
+        if filters.is_empty() || self.saving.is_some() {
+            return;
+        }
+        let len = i64::try_from(filters.len()).unwrap_or(i64::MAX);
+        let start = match self.named {
+            Some(idx) => i64::try_from(idx).unwrap_or(0) + i64::from(delta),
+            // The first step lands on the first (or last) filter
+            None if delta < 0 => i64::from(delta),
+            None => i64::from(delta) - 1,
+        };
+        let idx = usize::try_from(start.rem_euclid(len)).unwrap_or(0);
+        self.input.clone_from(&filters[idx].name);
+        self.error = None;
+        self.named = Some(idx);
+    }
+
+    /// Resolves the input to a filter and closes the bar.
+    ///
+    /// Input naming a saved filter (ignoring case) applies that filter;
+    /// anything else is parsed as a filter expression. Empty input clears
+    /// the filter.
+    ///
+    /// Returns the filter and, for a saved filter, its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The saved filters
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error, and keeps the bar open with the error
+    /// shown, if the input is not a valid expression.
+    pub fn apply(
+        &mut self,
+        filters: &[NamedFilter],
+    ) -> Result<(TaskFilter, Option<String>), String> {
+        let resolved = self.resolve(filters);
+        match &resolved {
+            Ok(_) => self.close(),
+            Err(e) => self.error = Some(e.clone()),
+        }
+        resolved
+    }
+
+    fn resolve(&self, filters: &[NamedFilter]) -> Result<(TaskFilter, Option<String>), String> {
+        let input = self.input.trim();
+        match filters
+            .iter()
+            .find(|saved| saved.name.eq_ignore_ascii_case(input))
+        {
+            Some(saved) => Ok((saved.filter.clone(), Some(saved.name.clone()))),
+            None => input
+                .parse::<TaskFilter>()
+                .map(|filter| (filter, None))
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Starts saving the input as a named filter.
+    ///
+    /// The input is resolved as by [`apply`](Self::apply) and, if it is a
+    /// filter, cleared for its name to be typed; [`save`](Self::save) then
+    /// names it. An empty or invalid input shows an error instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The saved filters
+    pub fn start_save(&mut self, filters: &[NamedFilter]) {
+        if self.saving.is_some() {
+            return;
+        }
+        match self.resolve(filters) {
+            Ok((filter, _)) if filter.is_empty() => {
+                self.error = Some("nothing to save".to_string());
+            }
+            Ok((filter, _)) => {
+                self.saving = Some(filter);
+                self.input.clear();
+                self.error = None;
+                self.named = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Names the filter being saved and closes the bar.
+    ///
+    /// Returns the named filter, to be added to the saved filters in place
+    /// of any with the same name (ignoring case).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and keeps the bar open with the error shown, if
+    /// no save was started or the name is empty.
+    pub fn save(&mut self) -> Result<NamedFilter, String> {
+        let Some(filter) = self.saving.clone() else {
+            return Err("no filter to save".to_string());
+        };
+        let name = self.input.trim();
+        if name.is_empty() {
+            let error = "name cannot be empty".to_string();
+            self.error = Some(error.clone());
+            return Err(error);
+        }
+        let saved = NamedFilter::new(name, filter);
+        self.close();
+        Ok(saved)
+    }
+
+    /// Closes the bar, leaving the active filter as it was.
+    ///
+    /// While a filter is being named, this goes back to editing its
+    /// expression instead.
+    pub fn cancel(&mut self) {
+        match self.saving.take() {
+            Some(filter) => {
+                self.input = filter.to_string();
+                self.error = None;
+            }
+            None => self.close(),
+        }
+    }
+
+    fn close(&mut self) {
+        self.input.clear();
+        self.editing = false;
+        self.error = None;
+        self.named = None;
+        self.saving = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use whip_protocol::TaskState;
+
+    fn saved() -> Vec<NamedFilter> {
+        vec![
+            NamedFilter::new("api", TaskFilter::default().with_repositories(["acme/api"])),
+            NamedFilter::new(
+                "Attention",
+                TaskFilter::default().with_states([TaskState::NeedsAttention]),
+            ),
+        ]
+    }
+
+    #[test]
+    fn start_prefills_input() {
+        let mut bar = FilterBarState::default();
+        bar.start("state:failed");
+        assert!(bar.is_editing());
+        assert_eq!(bar.input(), "state:failed");
+    }
+
+    #[test]
+    fn cycle_wraps_both_ways() {
+        let filters = saved();
+        let mut bar = FilterBarState::default();
+        bar.start("");
+
+        bar.cycle(&filters, 1);
+        assert_eq!((bar.input(), bar.named_index()), ("api", Some(0)));
+        bar.cycle(&filters, 1);
+        assert_eq!((bar.input(), bar.named_index()), ("Attention", Some(1)));
+        bar.cycle(&filters, 1);
+        assert_eq!(bar.named_index(), Some(0));
+        bar.cycle(&filters, -1);
+        assert_eq!(bar.named_index(), Some(1));
+
+        bar.start("");
+        bar.cycle(&filters, -1);
+        assert_eq!(bar.named_index(), Some(1));
+
+        bar.push('x');
+        assert_eq!(bar.named_index(), None);
+    }
+
+    #[test]
+    fn cycle_without_saved_filters_keeps_input() {
+        let mut bar = FilterBarState::default();
+        bar.start("label:bug");
+        bar.cycle(&[], 1);
+        assert_eq!(bar.input(), "label:bug");
+    }
+
+    #[test]
+    fn apply_resolves_names_ignoring_case() {
+        let mut bar = FilterBarState::default();
+        bar.start(" attention ");
+        let (filter, name) = bar.apply(&saved()).expect("named filter");
+        assert_eq!(filter.states, [TaskState::NeedsAttention]);
+        assert_eq!(name.as_deref(), Some("Attention"));
+    }
+
+    #[test]
+    fn apply_parses_expressions() {
+        let mut bar = FilterBarState::default();
+        bar.start("author:octocat");
+        let (filter, name) = bar.apply(&saved()).expect("expression");
+        assert_eq!(filter.authors, ["octocat"]);
+        assert_eq!(name, None);
+
+        bar.start("");
+        let (filter, _) = bar.apply(&saved()).expect("empty input");
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn apply_keeps_bar_open_on_error() {
+        let mut bar = FilterBarState::default();
+        bar.start("nonsense");
+        assert!(bar.apply(&saved()).is_err());
+        assert!(bar.is_editing());
+        assert!(bar.error().is_some());
+
+        bar.pop();
+        assert!(bar.error().is_none());
+    }
+
+    #[test]
+    fn save_names_the_input_filter() {
+        let mut bar = FilterBarState::default();
+        bar.start("author:octocat");
+        bar.start_save(&saved());
+        assert_eq!(bar.input(), "");
+        assert_eq!(
+            bar.saving().map(|filter| filter.authors.clone()),
+            Some(vec!["octocat".to_string()])
+        );
+
+        assert!(bar.save().is_err());
+        assert!(bar.is_editing());
+        for ch in " mine ".chars() {
+            bar.push(ch);
+        }
+        let saved = bar.save().expect("named filter");
+        assert_eq!(saved.name, "mine");
+        assert_eq!(saved.filter.authors, ["octocat"]);
+        assert!(!bar.is_editing());
+    }
+
+    #[test]
+    fn save_rejects_empty_and_invalid_input() {
+        let mut bar = FilterBarState::default();
+        bar.start("");
+        bar.start_save(&saved());
+        assert_eq!(bar.saving(), None);
+        assert_eq!(bar.error(), Some("nothing to save"));
+
+        bar.start("nonsense");
+        bar.start_save(&saved());
+        assert_eq!(bar.saving(), None);
+        assert!(bar.error().is_some());
+    }
+
+    #[test]
+    fn cancel_while_naming_returns_to_expression() {
+        let mut bar = FilterBarState::default();
+        bar.start("label:bug");
+        bar.start_save(&saved());
+        bar.push('x');
+
+        bar.cancel();
+        assert!(bar.is_editing());
+        assert_eq!(bar.saving(), None);
+        assert_eq!(bar.input(), "label:bug");
+    }
+}
@@ -10,6 +10,13 @@
 //! - [`app`]: Main application struct and run loop
 //! - [`state`]: Application state management
 //! - [`settings_state`]: Settings panel state management
+//! - [`search`]: Fuzzy search over the tasks of the board
+//! - [`filter_bar`]: Filter bar state, narrowing the board
+//! - [`changes`]: Changes since the last refresh, until they are seen
+//! - [`context_menu`]: Context menu of task actions, opened by a right click
+//! - [`repo_status`]: Per-repository loading status and fetch events
+//! - [`credentials`]: Credential checks shown in the settings panel
+//! - [`notification`]: Error log and toast notifications
 //! - [`terminal`]: Terminal setup, teardown, and panic handling
 //! - [`event`]: Event handling and key mappings
 //!
@@ -33,6 +40,12 @@
 //!             RunResult::RefreshRequested => {
 //!                 // Handle refresh...
 //!             }
+//!             RunResult::CredentialTestRequested => {
+//!                 // Test app.take_credential_test()...
+//!             }
+//!             RunResult::FetchRequested => {
+//!                 // Fetch app.take_pending_fetches()...
+//!             }
 //!         }
 //!     }
 //!
@@ -42,8 +55,15 @@
 //! ```
 
 pub mod app;
+pub mod changes;
+pub mod context_menu;
+pub mod credentials;
 pub mod event;
+pub mod filter_bar;
 pub mod layout;
+pub mod notification;
+pub mod repo_status;
+pub mod search;
 pub mod settings_state;
 pub mod state;
 pub mod terminal;
@@ -54,4 +74,5 @@ pub(crate) mod test_utils;
 
 // Re-export primary types at crate root for convenience
 pub use app::{App, RunResult};
+pub use repo_status::{RepoEvent, RepoStatus, RepoStatuses};
 pub use state::{AppState, Focus};
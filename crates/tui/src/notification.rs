@@ -0,0 +1,328 @@
+//! Error and notification model.
+//!
+//! Output from background work cannot be printed to stderr while the
+//! alternate screen is active. Instead, it is recorded here:
+//!
+//! - Any subsystem can push a [`Notification`] with a [`Severity`] to the
+//!   [`Notifications`] queue; it is shown as a toast until it expires, and
+//!   kept in a history overlay afterwards.
+//! - Repository failures are additionally appended to an [`ErrorLog`],
+//!   shown in the errors pane with their kind and time.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use whip_protocol::FetchErrorKind;
+
+/// Maximum number of notifications shown on screen at once.
+pub const MAX_ACTIVE_NOTIFICATIONS: usize = 3;
+
+/// Maximum number of notifications kept in the history.
+pub const MAX_NOTIFICATION_HISTORY: usize = 100;
+
+/// Maximum number of errors kept in the [`ErrorLog`].
+pub const MAX_LOGGED_ERRORS: usize = 50;
+
+/// A failure that occurred while fetching or syncing a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoError {
+    /// The repository key (see [`Config::repo_key`](whip_config::Config::repo_key)).
+    pub repo: String,
+    /// The broad category of the failure.
+    pub kind: FetchErrorKind,
+    /// A human-readable description of the failure.
+    pub message: String,
+    /// When the failure was recorded.
+    pub occurred_at: DateTime<Local>,
+}
+
+/// A bounded log of repository errors, newest first.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Local;
+/// use whip_protocol::FetchErrorKind;
+/// use whip_tui::notification::{ErrorLog, RepoError};
+///
+/// let mut log = ErrorLog::default();
+/// log.push(RepoError {
+///     repo: "owner/repo".to_string(),
+///     kind: FetchErrorKind::Network,
+///     message: "connection refused".to_string(),
+///     occurred_at: Local::now(),
+/// });
+/// assert_eq!(log.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ErrorLog {
+    entries: VecDeque<RepoError>,
+}
+
+impl ErrorLog {
+    /// Records an error, discarding the oldest once [`MAX_LOGGED_ERRORS`] is reached.
+    pub fn push(&mut self, error: RepoError) {
+        if self.entries.len() == MAX_LOGGED_ERRORS {
+            self.entries.pop_back();
+        }
+        self.entries.push_front(error);
+    }
+
+    /// Returns an iterator over the logged errors, newest first.
+    pub fn iter(&self) -> impl Iterator<Item = &RepoError> {
+        self.entries.iter()
+    }
+
+    /// Returns the number of logged errors.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no errors have been logged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// The severity of a [`Notification`].
+///
+/// Severity determines how a notification is colored and how long it
+/// stays on screen before expiring into the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Informational progress, e.g. a refresh starting.
+    Info,
+    /// A completed action, e.g. settings saved.
+    Success,
+    /// Something worth noticing that did not fail outright.
+    Warning,
+    /// A failed action.
+    Error,
+}
+
+impl Severity {
+    /// Returns how long a notification of this severity stays on screen.
+    ///
+    /// More severe notifications stay longer so they are not missed.
+    #[must_use]
+    pub fn display_duration(&self) -> Duration {
+        match self {
+            Self::Info | Self::Success => Duration::from_secs(3),
+            Self::Warning => Duration::from_secs(5),
+            Self::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+/// A message pushed to the user by any subsystem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// The severity of the notification.
+    pub severity: Severity,
+    /// The message to display.
+    pub message: String,
+    /// When the notification was created, for display in the history.
+    pub created_at: DateTime<Local>,
+    /// When the notification was first shown, for expiry.
+    shown_at: Instant,
+}
+
+impl Notification {
+    /// Creates a notification shown from now.
+    #[must_use]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            created_at: Local::now(),
+            shown_at: Instant::now(),
+        }
+    }
+
+    /// Returns `true` once the notification has been visible for its
+    /// severity's [display duration](Severity::display_duration).
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current instant
+    #[must_use]
+    pub fn is_expired_at(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.shown_at) >= self.severity.display_duration()
+    }
+}
+
+/// A queue of on-screen notifications with a bounded history.
+///
+/// Pushed notifications are shown as toasts until they expire, at most
+/// [`MAX_ACTIVE_NOTIFICATIONS`] at a time. Every notification is also kept
+/// in the history, newest first, up to [`MAX_NOTIFICATION_HISTORY`] entries.
+///
+/// # Examples
+///
+/// ```
+/// use whip_tui::notification::{Notifications, Severity};
+///
+/// let mut notifications = Notifications::default();
+/// notifications.push(Severity::Success, "Settings saved");
+///
+/// assert_eq!(notifications.active().count(), 1);
+/// assert_eq!(notifications.history().count(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Notifications {
+    active: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+}
+
+impl Notifications {
+    /// Pushes a new notification, showing it immediately.
+    ///
+    /// If [`MAX_ACTIVE_NOTIFICATIONS`] are already on screen, the oldest one
+    /// is dismissed early; it remains in the history.
+    ///
+    /// # Arguments
+    ///
+    /// * `severity` - The severity of the notification
+    /// * `message` - The message to display
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        let notification = Notification::new(severity, message);
+
+        if self.history.len() == MAX_NOTIFICATION_HISTORY {
+            self.history.pop_back();
+        }
+        self.history.push_front(notification.clone());
+
+        if self.active.len() == MAX_ACTIVE_NOTIFICATIONS {
+            self.active.pop_front();
+        }
+        self.active.push_back(notification);
+    }
+
+    /// Removes notifications that have been on screen long enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current instant
+    pub fn expire(&mut self, now: Instant) {
+        self.active.retain(|n| !n.is_expired_at(now));
+    }
+
+    /// Returns the notifications currently on screen, oldest first.
+    pub fn active(&self) -> impl Iterator<Item = &Notification> {
+        self.active.iter()
+    }
+
+    /// Returns every notification pushed so far, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    /// Returns the number of notifications in the history.
+    #[must_use]
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(repo: &str) -> RepoError {
+        RepoError {
+            repo: repo.to_string(),
+            kind: FetchErrorKind::Other,
+            message: "boom".to_string(),
+            occurred_at: Local::now(),
+        }
+    }
+
+    #[test]
+    fn error_log_is_newest_first() {
+        let mut log = ErrorLog::default();
+        log.push(error("a/first"));
+        log.push(error("b/second"));
+
+        let repos: Vec<_> = log.iter().map(|e| e.repo.as_str()).collect();
+        assert_eq!(repos, vec!["b/second", "a/first"]);
+    }
+
+    #[test]
+    fn error_log_is_bounded() {
+        let mut log = ErrorLog::default();
+        for i in 0..MAX_LOGGED_ERRORS + 5 {
+            log.push(error(&format!("owner/repo{i}")));
+        }
+
+        assert_eq!(log.len(), MAX_LOGGED_ERRORS);
+        let newest = format!("owner/repo{}", MAX_LOGGED_ERRORS + 4);
+        assert_eq!(
+            log.iter().next().map(|e| e.repo.as_str()),
+            Some(newest.as_str())
+        );
+    }
+
+    #[test]
+    fn notification_expires_after_severity_duration() {
+        let info = Notification::new(Severity::Info, "hello");
+        let error = Notification::new(Severity::Error, "boom");
+
+        assert!(!info.is_expired_at(info.shown_at));
+        assert!(info.is_expired_at(info.shown_at + Severity::Info.display_duration()));
+        assert!(!error.is_expired_at(error.shown_at + Severity::Info.display_duration()));
+        assert!(error.is_expired_at(error.shown_at + Severity::Error.display_duration()));
+    }
+
+    #[test]
+    fn errors_stay_on_screen_longer_than_info() {
+        assert!(Severity::Error.display_duration() > Severity::Warning.display_duration());
+        assert!(Severity::Warning.display_duration() > Severity::Info.display_duration());
+    }
+
+    #[test]
+    fn expire_removes_only_expired_notifications() {
+        let mut notifications = Notifications::default();
+        notifications.push(Severity::Info, "short");
+        notifications.push(Severity::Error, "long");
+
+        let shown_at = notifications.active[0].shown_at;
+        notifications.expire(shown_at + Severity::Info.display_duration());
+
+        let active: Vec<_> = notifications.active().map(|n| n.message.as_str()).collect();
+        assert_eq!(active, vec!["long"]);
+        assert_eq!(notifications.history_len(), 2);
+    }
+
+    #[test]
+    fn push_limits_active_notifications() {
+        let mut notifications = Notifications::default();
+        for i in 0..MAX_ACTIVE_NOTIFICATIONS + 2 {
+            notifications.push(Severity::Info, format!("n{i}"));
+        }
+
+        assert_eq!(notifications.active().count(), MAX_ACTIVE_NOTIFICATIONS);
+        assert_eq!(
+            notifications.active().next().map(|n| n.message.as_str()),
+            Some("n2")
+        );
+        assert_eq!(notifications.history_len(), MAX_ACTIVE_NOTIFICATIONS + 2);
+    }
+
+    #[test]
+    fn history_is_newest_first_and_bounded() {
+        let mut notifications = Notifications::default();
+        for i in 0..MAX_NOTIFICATION_HISTORY + 1 {
+            notifications.push(Severity::Info, format!("n{i}"));
+        }
+
+        assert_eq!(notifications.history_len(), MAX_NOTIFICATION_HISTORY);
+        let newest = format!("n{MAX_NOTIFICATION_HISTORY}");
+        assert_eq!(
+            notifications.history().next().map(|n| n.message.as_str()),
+            Some(newest.as_str())
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Per-repository loading status.
+//!
+//! The board is rendered immediately from cached data while fresh results
+//! are fetched in the background. This module tracks where each configured
+//! repository stands in that process, and defines the [`RepoEvent`] type
+//! used to stream fetch results into the running [`App`](crate::App).
+
+use whip_config::auth::TokenSource;
+use whip_protocol::{FetchErrorKind, Task};
+
+use crate::credentials::CredentialCheck;
+
+/// The loading status of a single repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoStatus {
+    /// No data is available yet; a fetch is in progress.
+    Loading,
+    /// Cached data is shown but is older than the polling interval; a fetch
+    /// is in progress.
+    Stale,
+    /// The displayed data is up to date.
+    Fresh,
+    /// The last fetch failed. Any previously loaded data is kept on the board.
+    ///
+    /// Details are recorded in the app's error log.
+    Failed {
+        /// The broad category of the failure.
+        kind: FetchErrorKind,
+    },
+}
+
+impl RepoStatus {
+    /// Returns `true` if a fetch is in progress for the repository.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Loading | Self::Stale)
+    }
+}
+
+/// An update about a repository, sent from a background fetch to the [`App`](crate::App).
+///
+/// Repositories are identified by their
+/// [`Config::repo_key`](whip_config::Config::repo_key), so same-named
+/// repositories on different hosts are kept apart.
+#[derive(Debug, Clone)]
+pub enum RepoEvent {
+    /// Fresh tasks were fetched for the repository.
+    ///
+    /// These replace any tasks currently shown for the repository.
+    Loaded {
+        /// The repository key.
+        repo: String,
+        /// The tasks for the repository.
+        tasks: Vec<Task>,
+    },
+    /// Fetching or syncing the repository failed.
+    Failed {
+        /// The repository key.
+        repo: String,
+        /// The broad category of the failure.
+        kind: FetchErrorKind,
+        /// A human-readable description of the failure.
+        error: String,
+    },
+    /// The GitHub token for the repository was resolved.
+    ///
+    /// Sent before the repository is fetched, so the settings panel can show
+    /// which credential source is in use.
+    Authenticated {
+        /// The repository key.
+        repo: String,
+        /// The source that provided the token, or `None` if the repository
+        /// is accessed without a token.
+        source: Option<TokenSource>,
+    },
+    /// The repository's credentials were tested from the settings panel.
+    CredentialsChecked {
+        /// The repository key.
+        repo: String,
+        /// The outcome of the test.
+        check: CredentialCheck,
+    },
+    /// The repository's labels differ from the configured status labels.
+    ///
+    /// Label changes are visible to everyone on the repository, so they are
+    /// only planned here and applied with `whip sync-labels` after review.
+    LabelSyncPlanned {
+        /// The repository key.
+        repo: String,
+        /// A one-line summary of each planned change, e.g.
+        /// `"create whip/backlog"`.
+        changes: Vec<String>,
+    },
+}
+
+impl RepoEvent {
+    /// Returns the key of the repository this event is about.
+    #[must_use]
+    pub fn repo(&self) -> &str {
+        match self {
+            Self::Loaded { repo, .. }
+            | Self::Failed { repo, .. }
+            | Self::Authenticated { repo, .. }
+            | Self::CredentialsChecked { repo, .. }
+            | Self::LabelSyncPlanned { repo, .. } => repo,
+        }
+    }
+}
+
+/// The loading status of every configured repository, in configuration order.
+///
+/// # Examples
+///
+/// ```
+/// use whip_tui::{RepoStatus, RepoStatuses};
+///
+/// let mut statuses = RepoStatuses::default();
+/// statuses.set("rust-lang/rust", RepoStatus::Stale);
+/// assert_eq!(statuses.get("rust-lang/rust"), Some(&RepoStatus::Stale));
+///
+/// statuses.mark_refreshing();
+/// assert!(statuses.iter().all(|(_, status)| status.is_pending()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoStatuses {
+    entries: Vec<(String, RepoStatus)>,
+}
+
+impl RepoStatuses {
+    /// Sets the status of a repository, appending it if not yet tracked.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The repository key (see [`Config::repo_key`](whip_config::Config::repo_key))
+    /// * `status` - The new status
+    pub fn set(&mut self, repo: impl Into<String>, status: RepoStatus) {
+        let repo = repo.into();
+        match self.entries.iter_mut().find(|(name, _)| *name == repo) {
+            Some((_, existing)) => *existing = status,
+            None => self.entries.push((repo, status)),
+        }
+    }
+
+    /// Returns the status of a repository, if tracked.
+    #[must_use]
+    pub fn get(&self, repo: &str) -> Option<&RepoStatus> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == repo)
+            .map(|(_, status)| status)
+    }
+
+    /// Stops tracking a repository.
+    ///
+    /// Returns `true` if the repository was tracked.
+    pub fn remove(&mut self, repo: &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(name, _)| name != repo);
+        self.entries.len() != len
+    }
+
+    /// Marks every tracked repository as being refreshed.
+    ///
+    /// Repositories that were loaded successfully become [`RepoStatus::Stale`];
+    /// all others become [`RepoStatus::Loading`].
+    pub fn mark_refreshing(&mut self) {
+        for (_, status) in &mut self.entries {
+            *status = match status {
+                RepoStatus::Fresh | RepoStatus::Stale => RepoStatus::Stale,
+                RepoStatus::Loading | RepoStatus::Failed { .. } => RepoStatus::Loading,
+            };
+        }
+    }
+
+    /// Returns an iterator over `(repo, status)` pairs in configuration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &RepoStatus)> {
+        self.entries
+            .iter()
+            .map(|(name, status)| (name.as_str(), status))
+    }
+
+    /// Returns the number of tracked repositories.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no repositories are tracked.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_appends_and_updates_in_order() {
+        let mut statuses = RepoStatuses::default();
+        statuses.set("a/one", RepoStatus::Loading);
+        statuses.set("b/two", RepoStatus::Fresh);
+        statuses.set("a/one", RepoStatus::Fresh);
+
+        let names: Vec<_> = statuses.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["a/one", "b/two"]);
+        assert_eq!(statuses.get("a/one"), Some(&RepoStatus::Fresh));
+        assert_eq!(statuses.len(), 2);
+    }
+
+    #[test]
+    fn remove_keeps_order_of_others() {
+        let mut statuses = RepoStatuses::default();
+        statuses.set("a/one", RepoStatus::Fresh);
+        statuses.set("b/two", RepoStatus::Loading);
+        statuses.set("c/three", RepoStatus::Stale);
+
+        assert!(statuses.remove("b/two"));
+        assert!(!statuses.remove("b/two"));
+
+        let names: Vec<_> = statuses.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["a/one", "c/three"]);
+    }
+
+    #[test]
+    fn get_unknown_repo_returns_none() {
+        let statuses = RepoStatuses::default();
+        assert!(statuses.get("missing/repo").is_none());
+        assert!(statuses.is_empty());
+    }
+
+    #[test]
+    fn mark_refreshing_keeps_loaded_data_as_stale() {
+        let mut statuses = RepoStatuses::default();
+        statuses.set("a/fresh", RepoStatus::Fresh);
+        statuses.set(
+            "b/failed",
+            RepoStatus::Failed {
+                kind: FetchErrorKind::Network,
+            },
+        );
+        statuses.set("c/loading", RepoStatus::Loading);
+
+        statuses.mark_refreshing();
+
+        assert_eq!(statuses.get("a/fresh"), Some(&RepoStatus::Stale));
+        assert_eq!(statuses.get("b/failed"), Some(&RepoStatus::Loading));
+        assert_eq!(statuses.get("c/loading"), Some(&RepoStatus::Loading));
+    }
+
+    #[test]
+    fn is_pending_only_for_in_flight_statuses() {
+        assert!(RepoStatus::Loading.is_pending());
+        assert!(RepoStatus::Stale.is_pending());
+        assert!(!RepoStatus::Fresh.is_pending());
+        assert!(
+            !RepoStatus::Failed {
+                kind: FetchErrorKind::Other
+            }
+            .is_pending()
+        );
+    }
+
+    #[test]
+    fn repo_event_exposes_repo_name() {
+        let event = RepoEvent::Failed {
+            repo: "owner/repo".to_string(),
+            kind: FetchErrorKind::Other,
+            error: "boom".to_string(),
+        };
+        assert_eq!(event.repo(), "owner/repo");
+    }
+}
@@ -0,0 +1,434 @@
+//! Fuzzy search over the tasks of the board.
+//!
+//! A query is split into words, and every word has to fuzzy-match at least
+//! one field of a task: its title, description, `owner/repo#number`, labels,
+//! or author. A word matches a field when its characters appear in the
+//! field in order, ignoring case; consecutive characters and characters at
+//! the start of a word score higher.
+//!
+//! # Examples
+//!
+//! ```
+//! use whip_protocol::Task;
+//! use whip_tui::search::{fuzzy_match, task_score};
+//!
+//! let matched = fuzzy_match("impar", "Implement parser").unwrap();
+//! assert_eq!(matched.positions, vec![0, 1, 2, 11, 12]);
+//!
+//! let task = Task::new("Implement parser", "Build the JSON parser");
+//! assert!(task_score("json prs", &task).is_some());
+//! assert!(task_score("yaml", &task).is_none());
+//! ```
+
+use whip_protocol::{KanbanBoard, Task};
+
+/// Score for each matched character.
+const SCORE_MATCH: i64 = 16;
+/// Bonus for a character matched right after the previous one.
+const BONUS_CONSECUTIVE: i64 = 12;
+/// Bonus for a character matched at the start of a word.
+const BONUS_WORD_START: i64 = 8;
+/// Penalty for each character skipped between two matched characters.
+const PENALTY_GAP: i64 = 1;
+
+/// A fuzzy match of a query in a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// How well the query matched; higher is better.
+    pub score: i64,
+    /// Character (not byte) positions of the matched characters in the text.
+    pub positions: Vec<usize>,
+}
+
+/// Fuzzy-matches `query` against `text`, ignoring case.
+///
+/// Returns `None` unless every character of the query appears in the text,
+/// in order. An empty query matches anything with a score of zero.
+///
+/// # Arguments
+///
+/// * `query` - The characters to look for
+/// * `text` - The text to search
+///
+/// # Examples
+///
+/// ```
+/// use whip_tui::search::fuzzy_match;
+///
+/// assert!(fuzzy_match("ci", "Setup CI/CD").is_some());
+/// assert!(fuzzy_match("scd", "Setup CI/CD").is_some());
+/// assert!(fuzzy_match("xyz", "Setup CI/CD").is_none());
+///
+/// // Contiguous matches score higher than scattered ones
+/// let contiguous = fuzzy_match("set", "Setup").unwrap();
+/// let scattered = fuzzy_match("set", "Slow eventual tests").unwrap();
+/// assert!(contiguous.score > scattered.score);
+/// ```
+#[must_use]
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+    let text: Vec<char> = text.chars().collect();
+    let lowered: Vec<char> = text
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    // Try every possible start, keeping the best: a greedy match from the
+    // first occurrence alone would miss "parser" in "part of the parser"
+    let mut best: Option<FuzzyMatch> = None;
+    for start in (0..lowered.len()).filter(|&i| lowered[i] == query[0]) {
+        let Some(positions) = match_from(&query, &lowered, start) else {
+            // Later starts cannot match either
+            break;
+        };
+        let score = score_positions(&text, &positions);
+        if best.as_ref().is_none_or(|best| score > best.score) {
+            best = Some(FuzzyMatch { score, positions });
+        }
+    }
+    best
+}
+
+/// Greedily matches the query from `start`, whose character already matches.
+fn match_from(query: &[char], text: &[char], start: usize) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut next = start;
+    for &wanted in query {
+        let found = next + text[next..].iter().position(|&c| c == wanted)?;
+        positions.push(found);
+        next = found + 1;
+    }
+    Some(positions)
+}
+
+/// Scores matched positions, rewarding runs and word starts.
+fn score_positions(text: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    for &position in positions {
+        score += SCORE_MATCH;
+        let word_start = position == 0 || !text[position - 1].is_alphanumeric();
+        if word_start {
+            score += BONUS_WORD_START;
+        }
+        match previous {
+            Some(previous) if position == previous + 1 => score += BONUS_CONSECUTIVE,
+            Some(previous) => {
+                let gap = i64::try_from(position - previous - 1).unwrap_or(i64::MAX);
+                score -= gap.saturating_mul(PENALTY_GAP);
+            }
+            None => {}
+        }
+        previous = Some(position);
+    }
+    score
+}
+
+/// Returns the fields of a task that a search looks at.
+fn searchable_fields(task: &Task) -> Vec<String> {
+    let mut fields = vec![task.title.clone(), task.description.clone()];
+    if let Some(github) = &task.github {
+        fields.push(format!(
+            "{}/{}#{}",
+            github.owner, github.repo, github.number
+        ));
+        fields.extend(github.labels.iter().cloned());
+        fields.push(github.author.clone());
+    }
+    fields
+}
+
+/// Scores a task against a search query.
+///
+/// Every word of the query must fuzzy-match some field of the task; the
+/// score adds up the best match of each word. Returns `None` if a word
+/// matches nothing, or if the query has no words.
+///
+/// # Arguments
+///
+/// * `query` - The search query, as typed
+/// * `task` - The task to score
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{GitHubSource, Task};
+/// use whip_tui::search::task_score;
+///
+/// let mut task = Task::new("Fix login bug", "Users couldn't log in");
+/// task.github = Some(GitHubSource {
+///     host: None,
+///     owner: "acme".into(),
+///     repo: "web".into(),
+///     number: 42,
+///     url: "https://github.com/acme/web/issues/42".into(),
+///     labels: vec!["whip/failed".into()],
+///     author: "octocat".into(),
+///     comment_count: 0,
+/// });
+///
+/// assert!(task_score("acme/web#42", &task).is_some());
+/// assert!(task_score("octo failed", &task).is_some());
+/// assert!(task_score("login acme#7", &task).is_none());
+/// ```
+#[must_use]
+pub fn task_score(query: &str, task: &Task) -> Option<i64> {
+    let fields = searchable_fields(task);
+    let mut total = None;
+    for word in query.split_whitespace() {
+        let best = fields
+            .iter()
+            .filter_map(|field| fuzzy_match(word, field))
+            .map(|matched| matched.score)
+            .max()?;
+        total = Some(total.unwrap_or(0) + best);
+    }
+    total
+}
+
+/// Returns the character positions of `text` matched by the words of a query.
+///
+/// Used to highlight matches when rendering; words that do not match
+/// `text` are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use whip_tui::search::highlight_positions;
+///
+/// assert_eq!(highlight_positions("fix bug", "Fix login bug"), vec![0, 1, 2, 10, 11, 12]);
+/// assert!(highlight_positions("yaml", "Fix login bug").is_empty());
+/// ```
+#[must_use]
+pub fn highlight_positions(query: &str, text: &str) -> Vec<usize> {
+    let mut positions: Vec<usize> = query
+        .split_whitespace()
+        .filter_map(|word| fuzzy_match(word, text))
+        .flat_map(|matched| matched.positions)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+/// Finds the tasks of the board matching a query.
+///
+/// Returns the `(lane, task)` index of every match, in board order: lane
+/// by lane, top to bottom.
+///
+/// # Examples
+///
+/// ```
+/// use whip_protocol::{KanbanBoard, Task};
+/// use whip_tui::search::find_matches;
+///
+/// let mut board = KanbanBoard::new();
+/// board.add_task(Task::new("Write specs", "Document requirements"));
+/// board.add_task(Task::new("Design UI", "Wireframes"));
+///
+/// assert_eq!(find_matches("design", &board), vec![(0, 1)]);
+/// ```
+#[must_use]
+pub fn find_matches(query: &str, board: &KanbanBoard) -> Vec<(usize, usize)> {
+    board
+        .lanes
+        .iter()
+        .enumerate()
+        .flat_map(|(lane_idx, lane)| {
+            lane.tasks
+                .iter()
+                .enumerate()
+                .filter(|(_, task)| task_score(query, task).is_some())
+                .map(move |(task_idx, _)| (lane_idx, task_idx))
+        })
+        .collect()
+}
+
+/// The state of the search line.
+///
+/// A search is active from the moment `/` is pressed until it is cleared.
+/// While editing, typed characters go to the query; once confirmed, the
+/// query stays active so results can be stepped through.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchState {
+    query: String,
+    editing: bool,
+}
+
+impl SearchState {
+    /// Starts editing a new, empty query.
+    pub fn start(&mut self) {
+        self.query.clear();
+        self.editing = true;
+    }
+
+    /// Returns the query typed so far.
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns `true` while the query is being typed.
+    #[must_use]
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Returns `true` while the search line is shown: when the query is
+    /// being typed, or a confirmed query is highlighting results.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.editing || !self.query.is_empty()
+    }
+
+    /// Returns the query to highlight, if it has any words.
+    #[must_use]
+    pub fn highlight(&self) -> Option<&str> {
+        (!self.query.trim().is_empty()).then_some(self.query.as_str())
+    }
+
+    /// Appends a character to the query.
+    pub fn push(&mut self, ch: char) {
+        self.query.push(ch);
+    }
+
+    /// Removes the last character of the query.
+    pub fn pop(&mut self) {
+        self.query.pop();
+    }
+
+    /// Stops editing, keeping the query active. An empty query ends the
+    /// search.
+    pub fn confirm(&mut self) {
+        self.editing = false;
+        if self.query.trim().is_empty() {
+            self.query.clear();
+        }
+    }
+
+    /// Ends the search.
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.editing = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use whip_protocol::GitHubSource;
+
+    fn github_task(title: &str, labels: &[&str]) -> Task {
+        let mut task = Task::new(title, "Description");
+        task.github = Some(GitHubSource {
+            host: None,
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            number: 7,
+            url: "https://github.com/owner/repo/issues/7".to_string(),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            author: "alice".to_string(),
+            comment_count: 0,
+        });
+        task
+    }
+
+    #[test]
+    fn fuzzy_match_is_case_insensitive() {
+        let matched = fuzzy_match("PARSER", "Implement parser").expect("match");
+        assert_eq!(matched.positions, vec![10, 11, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_the_best_start() {
+        let matched = fuzzy_match("parser", "part of the parser").expect("match");
+        assert_eq!(matched.positions, vec![12, 13, 14, 15, 16, 17]);
+    }
+
+    #[test]
+    fn fuzzy_match_requires_order() {
+        assert!(fuzzy_match("ba", "ab").is_none());
+        assert!(fuzzy_match("ab", "ab").is_some());
+    }
+
+    #[test]
+    fn fuzzy_match_counts_characters_not_bytes() {
+        let matched = fuzzy_match("é", "café").expect("match");
+        assert_eq!(matched.positions, vec![3]);
+    }
+
+    #[test]
+    fn word_starts_score_higher() {
+        let word_start = fuzzy_match("p", "a parser").expect("match");
+        let inner = fuzzy_match("p", "apparser").expect("match");
+        assert!(word_start.score > inner.score);
+    }
+
+    #[test]
+    fn task_score_searches_every_field() {
+        let task = github_task("Title", &["whip/in-progress"]);
+        assert!(task_score("title", &task).is_some());
+        assert!(task_score("description", &task).is_some());
+        assert!(task_score("owner/repo#7", &task).is_some());
+        assert!(task_score("in-progress", &task).is_some());
+        assert!(task_score("alice", &task).is_some());
+        assert!(task_score("bob", &task).is_none());
+    }
+
+    #[test]
+    fn task_score_requires_every_word() {
+        let task = github_task("Title", &[]);
+        assert!(task_score("title alice", &task).is_some());
+        assert!(task_score("title bob", &task).is_none());
+        assert!(task_score("   ", &task).is_none());
+    }
+
+    #[test]
+    fn find_matches_follows_board_order() {
+        let mut board = KanbanBoard::new();
+        board.add_task(Task::new("Parser one", "Description"));
+        board.add_task(Task::new("Other", "Description"));
+        let mut later = Task::new("Parser two", "Description");
+        later.lane = whip_protocol::LaneKind::Done;
+        board.add_task(later);
+
+        assert_eq!(find_matches("parser", &board), vec![(0, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn search_state_lifecycle() {
+        let mut search = SearchState::default();
+        assert!(!search.is_active());
+
+        search.start();
+        assert!(search.is_active() && search.is_editing());
+        assert_eq!(search.highlight(), None);
+
+        search.push('a');
+        search.push('b');
+        search.pop();
+        assert_eq!(search.query(), "a");
+        assert_eq!(search.highlight(), Some("a"));
+
+        search.confirm();
+        assert!(search.is_active() && !search.is_editing());
+
+        search.clear();
+        assert!(!search.is_active());
+    }
+
+    #[test]
+    fn confirming_an_empty_query_ends_the_search() {
+        let mut search = SearchState::default();
+        search.start();
+        search.push(' ');
+        search.confirm();
+        assert!(!search.is_active());
+    }
+}
@@ -3,7 +3,13 @@
 //! This module provides state management for the settings UI, including
 //! section navigation, item selection, and edit mode handling.
 
-use whip_config::{Config, Repository};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use whip_config::auth::TokenSource;
+use whip_config::{Config, ConfigLayer, Repository};
+
+use crate::credentials::CredentialCheck;
 
 /// Sections in the settings panel.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -336,6 +342,14 @@ pub struct SettingsState {
     edit_mode: EditMode,
     /// Index of item pending deletion (waiting for confirmation).
     pending_delete: Option<usize>,
+    /// The token source that won for each repository, keyed by repository key.
+    token_sources: HashMap<String, Option<TokenSource>>,
+    /// The latest credential test of each repository, keyed by repository key.
+    credential_checks: HashMap<String, CredentialCheck>,
+    /// The file the configuration was loaded from.
+    loaded_source: Option<ConfigLayer>,
+    /// The user config file, offered as an alternative place to save to.
+    user_config_path: Option<PathBuf>,
 }
 
 impl SettingsState {
@@ -357,15 +371,166 @@ impl SettingsState {
     /// ```
     #[must_use]
     pub fn new(config: Config) -> Self {
+        let loaded_source = config.source.clone();
         Self {
             config,
             section: SettingsSection::default(),
             selected_item: 0,
             edit_mode: EditMode::None,
             pending_delete: None,
+            token_sources: HashMap::new(),
+            credential_checks: HashMap::new(),
+            loaded_source,
+            user_config_path: None,
         }
     }
 
+    /// Sets the token sources last resolved for each repository.
+    ///
+    /// These are shown in the Authentication section.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_sources` - The winning source per repository key, or
+    ///   `None` for repositories accessed without a token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use whip_config::Config;
+    /// use whip_config::auth::TokenSource;
+    /// use whip_tui::settings_state::SettingsState;
+    ///
+    /// let sources = HashMap::from([("owner/repo".to_string(), Some(TokenSource::GhCli))]);
+    /// let state = SettingsState::new(Config::default()).with_token_sources(sources);
+    /// assert_eq!(state.token_source("owner/repo"), Some(&Some(TokenSource::GhCli)));
+    /// ```
+    #[must_use]
+    pub fn with_token_sources(
+        mut self,
+        token_sources: HashMap<String, Option<TokenSource>>,
+    ) -> Self {
+        self.token_sources = token_sources;
+        self
+    }
+
+    /// Sets the user config file that settings can be saved to instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The user config file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::{Config, ConfigLayer};
+    /// use whip_tui::settings_state::SettingsState;
+    ///
+    /// let config = Config {
+    ///     source: Some(ConfigLayer::Local("whip.json5".into())),
+    ///     ..Default::default()
+    /// };
+    /// let mut state = SettingsState::new(config).with_user_config_path("config.json5");
+    ///
+    /// assert!(state.toggle_save_to_user_config());
+    /// assert_eq!(state.save_target(), Some(&ConfigLayer::User("config.json5".into())));
+    /// ```
+    #[must_use]
+    pub fn with_user_config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_config_path = Some(path.into());
+        self
+    }
+
+    /// Sets the latest credential test results for each repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `credential_checks` - The latest test result per repository key
+    #[must_use]
+    pub fn with_credential_checks(
+        mut self,
+        credential_checks: HashMap<String, CredentialCheck>,
+    ) -> Self {
+        self.credential_checks = credential_checks;
+        self
+    }
+
+    /// Records the outcome of testing a repository's credentials.
+    ///
+    /// The token source shown for the repository is updated to match.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The repository key (see [`Config::repo_key`](whip_config::Config::repo_key))
+    /// * `check` - The test outcome
+    pub fn set_credential_check(&mut self, repo: impl Into<String>, check: CredentialCheck) {
+        let repo = repo.into();
+        match &check {
+            CredentialCheck::Pending | CredentialCheck::Offline => {}
+            CredentialCheck::Unauthenticated => {
+                self.token_sources.insert(repo.clone(), None);
+            }
+            CredentialCheck::Valid { source, .. }
+            | CredentialCheck::ValidInstallation { source, .. }
+            | CredentialCheck::Failed { source, .. } => {
+                self.token_sources
+                    .insert(repo.clone(), Some(source.clone()));
+            }
+        }
+        self.credential_checks.insert(repo, check);
+    }
+
+    /// Returns the latest credential test of a repository, if any.
+    #[must_use]
+    pub fn credential_check(&self, repo: &str) -> Option<&CredentialCheck> {
+        self.credential_checks.get(repo)
+    }
+
+    /// Returns the repository selected in the Authentication section, if any.
+    ///
+    /// The first Authentication item is the global token; the following
+    /// items are the configured repositories, whose credentials can be
+    /// tested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_config::{Config, Repository};
+    /// use whip_tui::settings_state::SettingsState;
+    ///
+    /// let config = Config {
+    ///     repositories: vec![Repository::new("owner", "repo")],
+    ///     ..Default::default()
+    /// };
+    /// let mut state = SettingsState::new(config);
+    /// state.prev_section(); // Authentication
+    /// assert!(state.selected_auth_repository().is_none());
+    ///
+    /// state.navigate(1);
+    /// let repo = state.selected_auth_repository().unwrap();
+    /// assert_eq!(repo.full_name(), "owner/repo");
+    /// ```
+    #[must_use]
+    pub fn selected_auth_repository(&self) -> Option<&Repository> {
+        if self.section != SettingsSection::Authentication {
+            return None;
+        }
+        self.selected_item
+            .checked_sub(1)
+            .and_then(|index| self.config.repositories.get(index))
+    }
+
+    /// Returns the token source last resolved for a repository.
+    ///
+    /// Returns `None` if no token has been resolved for the repository yet,
+    /// and `Some(None)` if it is accessed without a token.
+    #[must_use]
+    pub fn token_source(&self, repo: &str) -> Option<&Option<TokenSource>> {
+        self.token_sources.get(repo)
+    }
+
     /// Returns a reference to the configuration.
     #[must_use]
     pub fn config(&self) -> &Config {
@@ -413,6 +578,27 @@ impl SettingsState {
         self.selected_item = 0;
     }
 
+    /// Moves to a section, as when its tab is clicked.
+    ///
+    /// Selects the first item, unless the section is already the current one.
+    pub fn select_section(&mut self, section: SettingsSection) {
+        if self.section != section {
+            self.section = section;
+            self.selected_item = 0;
+        }
+    }
+
+    /// Selects an item of the current section by index.
+    ///
+    /// Returns `false` and keeps the selection if there is no such item.
+    pub fn select_item(&mut self, index: usize) -> bool {
+        if index >= self.item_count() {
+            return false;
+        }
+        self.selected_item = index;
+        true
+    }
+
     /// Navigates within the current section.
     ///
     /// # Arguments
@@ -446,8 +632,8 @@ impl SettingsState {
             SettingsSection::Repositories => self.config.repositories.len() + 1,
             // Polling interval, auto-adjust toggle
             SettingsSection::Polling => 2,
-            // Global token field
-            SettingsSection::Authentication => 1,
+            // Global token field, then one entry per repository
+            SettingsSection::Authentication => 1 + self.config.repositories.len(),
         }
     }
 
@@ -488,13 +674,15 @@ impl SettingsState {
                     self.config.polling.auto_adjust = !self.config.polling.auto_adjust;
                 }
             }
-            SettingsSection::Authentication => {
+            SettingsSection::Authentication if self.selected_item == 0 => {
                 // Edit global token
                 self.edit_mode = EditMode::Text {
                     value: self.config.github_token.clone().unwrap_or_default(),
                     cursor: self.config.github_token.as_ref().map_or(0, |t| t.len()),
                 };
             }
+            // Repository entries are tested, not edited
+            SettingsSection::Authentication => {}
         }
     }
 
@@ -538,8 +726,18 @@ impl SettingsState {
                     if !token.is_empty() {
                         repo = Repository::with_token(repo.owner(), repo.repo(), token);
                     }
-                    if *index < self.config.repositories.len() {
-                        self.config.repositories[*index] = repo;
+                    if let Some(existing) = self.config.repositories.get_mut(*index) {
+                        // The panel doesn't edit these fields; keep them as configured
+                        if let Some(api_url) = existing.api_url() {
+                            repo = repo.with_api_url(api_url);
+                        }
+                        if let Some(name) = existing.token_env() {
+                            repo = repo.with_token_env(name);
+                        }
+                        if let Some(command) = existing.token_command() {
+                            repo = repo.with_token_command(command);
+                        }
+                        *existing = repo;
                     }
                 }
                 self.edit_mode = EditMode::None;
@@ -559,13 +757,11 @@ impl SettingsState {
     #[must_use]
     pub fn request_delete(&mut self) -> bool {
         match self.section {
-            SettingsSection::Repositories => {
-                if self.selected_item < self.config.repositories.len() {
-                    self.pending_delete = Some(self.selected_item);
-                    true
-                } else {
-                    false
-                }
+            SettingsSection::Repositories
+                if self.selected_item < self.config.repositories.len() =>
+            {
+                self.pending_delete = Some(self.selected_item);
+                true
             }
             _ => false,
         }
@@ -620,10 +816,12 @@ impl SettingsState {
             }
             SettingsSection::Authentication => {
                 // Can delete the token if it's set
-                self.config
-                    .github_token
-                    .as_ref()
-                    .is_some_and(|t| !t.is_empty())
+                self.selected_item == 0
+                    && self
+                        .config
+                        .github_token
+                        .as_ref()
+                        .is_some_and(|t| !t.is_empty())
             }
             SettingsSection::Polling => {
                 // Polling settings cannot be deleted
@@ -674,6 +872,49 @@ impl SettingsState {
     pub fn into_config(self) -> Config {
         self.config
     }
+
+    /// Returns the file settings will be saved to.
+    ///
+    /// `None` means the default user config file.
+    #[must_use]
+    pub fn save_target(&self) -> Option<&ConfigLayer> {
+        self.config.source.as_ref()
+    }
+
+    /// Returns `true` if settings are saved to the user config instead of
+    /// the file they were loaded from.
+    #[must_use]
+    pub fn is_saving_to_user_config(&self) -> bool {
+        self.config.source != self.loaded_source
+    }
+
+    /// Returns `true` if settings can be saved to the user config instead
+    /// of the file they were loaded from.
+    #[must_use]
+    pub fn can_save_to_user_config(&self) -> bool {
+        self.user_config_path.is_some()
+            && self
+                .loaded_source
+                .as_ref()
+                .is_some_and(|source| !matches!(source, ConfigLayer::User(_)))
+    }
+
+    /// Switches between saving to the loaded file and to the user config.
+    ///
+    /// Returns `false` if there is nothing to switch to.
+    pub fn toggle_save_to_user_config(&mut self) -> bool {
+        if self.is_saving_to_user_config() {
+            self.config.source.clone_from(&self.loaded_source);
+            true
+        } else if self.can_save_to_user_config()
+            && let Some(path) = &self.user_config_path
+        {
+            self.config.source = Some(ConfigLayer::User(path.clone()));
+            true
+        } else {
+            false
+        }
+    }
 }
 
 #[cfg(test)]
@@ -737,8 +978,8 @@ mod tests {
         assert_eq!(state.item_count(), 2);
 
         state.next_section();
-        // Authentication: global token
-        assert_eq!(state.item_count(), 1);
+        // Authentication: global token + 1 repo
+        assert_eq!(state.item_count(), 2);
     }
 
     #[test]
@@ -765,6 +1006,26 @@ mod tests {
         assert_eq!(state.selected_item(), 2);
     }
 
+    #[test]
+    fn settings_state_select_section_and_item() {
+        let mut config = Config::default();
+        config.repositories.push(Repository::new("owner", "repo"));
+        let mut state = SettingsState::new(config);
+
+        assert!(state.select_item(1));
+        assert_eq!(state.selected_item(), 1);
+        assert!(!state.select_item(2));
+        assert_eq!(state.selected_item(), 1);
+
+        // Reselecting the current section keeps the item
+        state.select_section(SettingsSection::Repositories);
+        assert_eq!(state.selected_item(), 1);
+
+        state.select_section(SettingsSection::Polling);
+        assert_eq!(state.section(), SettingsSection::Polling);
+        assert_eq!(state.selected_item(), 0);
+    }
+
     #[test]
     fn edit_mode_text_input() {
         let mut edit = EditMode::Text {
@@ -808,6 +1069,27 @@ mod tests {
         assert_eq!(state.config().repositories[0].full_name(), "owner/repo");
     }
 
+    #[test]
+    fn settings_state_edit_repository_keeps_api_url() {
+        let mut config = Config::default();
+        config.repositories.push(
+            Repository::new("owner", "repo")
+                .with_api_url("https://ghe.example.com/api/v3")
+                .with_token_env("ORG_TOKEN"),
+        );
+
+        let mut state = SettingsState::new(config);
+        state.start_edit();
+        assert!(state.is_editing());
+        state.input_char('2');
+        state.confirm_edit();
+
+        let repo = &state.config().repositories[0];
+        assert_eq!(repo.full_name(), "owner/repo2");
+        assert_eq!(repo.api_url(), Some("https://ghe.example.com/api/v3"));
+        assert_eq!(repo.token_env(), Some("ORG_TOKEN"));
+    }
+
     #[test]
     fn settings_state_delete_repository() {
         let mut config = Config::default();
@@ -1278,6 +1560,69 @@ mod tests {
         assert!(!state.is_delete_pending());
     }
 
+    fn auth_state_with_repo() -> SettingsState {
+        let config = Config {
+            repositories: vec![Repository::new("owner", "repo")],
+            github_token: Some("ghp_global".to_string()),
+            ..Default::default()
+        };
+        let mut state = SettingsState::new(config);
+        state.prev_section(); // Authentication
+        state
+    }
+
+    #[test]
+    fn settings_state_auth_repository_selection() {
+        let mut state = auth_state_with_repo();
+        assert!(state.selected_auth_repository().is_none());
+        assert!(state.can_delete_selected());
+
+        state.navigate(1);
+        let repo = state
+            .selected_auth_repository()
+            .expect("repository selected");
+        assert_eq!(repo.full_name(), "owner/repo");
+        assert!(!state.can_delete_selected());
+
+        // Repository entries are not editable
+        state.start_edit();
+        assert!(!state.is_editing());
+    }
+
+    #[test]
+    fn settings_state_credential_check_updates_source() {
+        let mut state = auth_state_with_repo();
+        state.set_credential_check("owner/repo", CredentialCheck::Pending);
+        assert_eq!(
+            state.credential_check("owner/repo"),
+            Some(&CredentialCheck::Pending)
+        );
+        assert_eq!(state.token_source("owner/repo"), None);
+
+        state.set_credential_check(
+            "owner/repo",
+            CredentialCheck::Failed {
+                source: TokenSource::ConfigToken,
+                error: "Bad credentials".to_string(),
+            },
+        );
+        assert_eq!(
+            state.token_source("owner/repo"),
+            Some(&Some(TokenSource::ConfigToken))
+        );
+
+        state.set_credential_check("owner/repo", CredentialCheck::Unauthenticated);
+        assert_eq!(state.token_source("owner/repo"), Some(&None));
+
+        // An offline test resolves the check but says nothing about the token
+        state.set_credential_check("owner/repo", CredentialCheck::Offline);
+        assert_eq!(
+            state.credential_check("owner/repo"),
+            Some(&CredentialCheck::Offline)
+        );
+        assert_eq!(state.token_source("owner/repo"), Some(&None));
+    }
+
     #[test]
     fn settings_state_request_delete_add_repository_item() {
         // request_delete should return false when on "Add repository..." item
@@ -1319,4 +1664,55 @@ mod tests {
         assert_eq!(field.next(), RepoEditField::Token);
         assert_eq!(field.next().next(), RepoEditField::Path);
     }
+
+    fn local_config() -> Config {
+        Config {
+            source: Some(ConfigLayer::Local(PathBuf::from("whip.json5"))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn save_target_defaults_to_loaded_file() {
+        let state = SettingsState::new(local_config()).with_user_config_path("config.json5");
+        assert_eq!(
+            state.save_target(),
+            Some(&ConfigLayer::Local(PathBuf::from("whip.json5")))
+        );
+        assert!(!state.is_saving_to_user_config());
+        assert!(state.can_save_to_user_config());
+    }
+
+    #[test]
+    fn toggle_save_to_user_config_switches_back_and_forth() {
+        let mut state = SettingsState::new(local_config()).with_user_config_path("config.json5");
+
+        assert!(state.toggle_save_to_user_config());
+        assert!(state.is_saving_to_user_config());
+        assert_eq!(
+            state.clone().into_config().source,
+            Some(ConfigLayer::User(PathBuf::from("config.json5")))
+        );
+
+        assert!(state.toggle_save_to_user_config());
+        assert!(!state.is_saving_to_user_config());
+        assert_eq!(state.into_config(), local_config());
+    }
+
+    #[test]
+    fn toggle_save_to_user_config_needs_another_file() {
+        let user = Config {
+            source: Some(ConfigLayer::User(PathBuf::from("config.json5"))),
+            ..Default::default()
+        };
+        let mut state = SettingsState::new(user).with_user_config_path("config.json5");
+        assert!(!state.can_save_to_user_config());
+        assert!(!state.toggle_save_to_user_config());
+
+        let mut state = SettingsState::new(Config::default()).with_user_config_path("config.json5");
+        assert!(!state.toggle_save_to_user_config());
+
+        let mut state = SettingsState::new(local_config());
+        assert!(!state.toggle_save_to_user_config());
+    }
 }
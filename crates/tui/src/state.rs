@@ -3,7 +3,16 @@
 //! This module defines the core state structures for the TUI application,
 //! including focus management and selection tracking.
 
-use whip_protocol::{KanbanBoard, Lane, Task};
+use std::borrow::Cow;
+
+use whip_protocol::{
+    BoardDiff, KanbanBoard, Lane, LaneDefinition, SortKey, Task, TaskFilter, TaskId,
+};
+
+use crate::changes::BoardChanges;
+use crate::context_menu::ContextMenu;
+use crate::filter_bar::FilterBarState;
+use crate::search::{self, SearchState};
 
 /// The current focus area in the UI.
 ///
@@ -23,13 +32,16 @@ pub enum Focus {
 ///
 /// Contains all mutable state for the TUI application including
 /// the board data, focus state, and selection tracking.
+///
+/// The board always holds every task; the active `filter` narrows what is
+/// shown. Task selection indices count only the tasks the filter shows.
 #[derive(Debug, Clone)]
 pub struct AppState {
     /// The Kanban board being displayed.
     pub board: KanbanBoard,
     /// Current focus area.
     pub focus: Focus,
-    /// Index of the currently selected lane (0-3).
+    /// Index of the currently selected lane.
     pub selected_lane: usize,
     /// Index of the selected task within the current lane, if any.
     pub selected_task: Option<usize>,
@@ -39,37 +51,27 @@ pub struct AppState {
     pub detail_scroll: u16,
     /// Whether the help overlay is visible.
     pub help_visible: bool,
+    /// Whether the errors pane is visible.
+    pub errors_visible: bool,
+    /// Whether the notification history overlay is visible.
+    pub notifications_visible: bool,
+    /// Index of the first entry shown in the notification history.
+    pub notifications_scroll: usize,
+    /// The search line state.
+    pub search: SearchState,
+    /// The filter narrowing the tasks shown on the board.
+    pub filter: TaskFilter,
+    /// The name of the active filter, when it is a named filter.
+    pub filter_name: Option<String>,
+    /// The filter bar state.
+    pub filter_bar: FilterBarState,
+    /// Changes since the last refresh that the user has not seen.
+    pub changes: BoardChanges,
+    /// The context menu opened by a right click, if open.
+    pub context_menu: Option<ContextMenu>,
 }
 
 impl AppState {
-    /// Returns a reference to the currently selected lane.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `selected_lane` is out of bounds. This should never occur
-    /// if navigation methods are used correctly, as they maintain the invariant
-    /// that `selected_lane` is always in the range `0..4`.
-    fn selected_lane_ref(&self) -> &Lane {
-        self.board
-            .lanes
-            .get(self.selected_lane)
-            .expect("selected_lane should always be in bounds (0..4)")
-    }
-
-    /// Returns a mutable reference to the currently selected lane.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `selected_lane` is out of bounds. This should never occur
-    /// if navigation methods are used correctly.
-    #[allow(dead_code)] // Provided for future use and API symmetry
-    fn selected_lane_mut(&mut self) -> &mut Lane {
-        self.board
-            .lanes
-            .get_mut(self.selected_lane)
-            .expect("selected_lane should always be in bounds (0..4)")
-    }
-
     /// Creates a new application state with the given board.
     ///
     /// Initializes with focus on the board, selecting the first lane.
@@ -98,6 +100,15 @@ impl AppState {
             detail_visible: false,
             detail_scroll: 0,
             help_visible: false,
+            errors_visible: false,
+            notifications_visible: false,
+            notifications_scroll: 0,
+            search: SearchState::default(),
+            filter: TaskFilter::default(),
+            filter_name: None,
+            filter_bar: FilterBarState::default(),
+            changes: BoardChanges::default(),
+            context_menu: None,
         }
     }
 
@@ -109,6 +120,30 @@ impl AppState {
         self.help_visible = !self.help_visible;
     }
 
+    /// Toggles the errors pane visibility.
+    pub fn toggle_errors(&mut self) {
+        self.errors_visible = !self.errors_visible;
+    }
+
+    /// Toggles the notification history overlay, scrolling back to the newest entry.
+    pub fn toggle_notifications(&mut self) {
+        self.notifications_visible = !self.notifications_visible;
+        self.notifications_scroll = 0;
+    }
+
+    /// Scrolls the notification history by `delta` entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - Entries to scroll (positive scrolls towards older entries)
+    /// * `max_scroll` - The largest allowed scroll offset
+    pub fn scroll_notifications(&mut self, delta: isize, max_scroll: usize) {
+        self.notifications_scroll = self
+            .notifications_scroll
+            .saturating_add_signed(delta)
+            .min(max_scroll);
+    }
+
     /// Dismisses the help overlay if it is visible.
     ///
     /// Returns `true` if help was visible and has been dismissed,
@@ -128,14 +163,15 @@ impl AppState {
         if self.selected_lane > 0 {
             self.selected_lane -= 1;
         } else {
-            self.selected_lane = 3; // Wrap to last lane
+            // Wrap to last lane
+            self.selected_lane = self.board.lanes.len().saturating_sub(1);
         }
         self.clamp_task_selection();
     }
 
     /// Moves the lane selection to the right, wrapping around if needed.
     pub fn navigate_right(&mut self) {
-        if self.selected_lane < 3 {
+        if self.selected_lane + 1 < self.board.lanes.len() {
             self.selected_lane += 1;
         } else {
             self.selected_lane = 0; // Wrap to first lane
@@ -143,10 +179,134 @@ impl AppState {
         self.clamp_task_selection();
     }
 
+    /// Replaces the lanes of the board, keeping the selection valid.
+    ///
+    /// Tasks are placed again into the new lanes. The selected lane is
+    /// kept if it still exists, and otherwise moves to the last lane.
+    ///
+    /// # Arguments
+    ///
+    /// * `definitions` - The new lanes, in display order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, LaneDefinition};
+    /// use whip_tui::AppState;
+    ///
+    /// let mut state = AppState::new(KanbanBoard::new());
+    /// state.selected_lane = 3;
+    ///
+    /// state.set_lanes(vec![LaneDefinition::new("Open"), LaneDefinition::new("Closed")]);
+    /// assert_eq!(state.selected_lane, 1);
+    /// ```
+    pub fn set_lanes(&mut self, definitions: Vec<LaneDefinition>) {
+        let selected = self.selected_task_id();
+        self.board.set_lanes(definitions);
+        self.selected_lane = self
+            .selected_lane
+            .min(self.board.lanes.len().saturating_sub(1));
+        self.reselect(selected);
+    }
+
+    /// Replaces the board, keeping the view on the selected task.
+    ///
+    /// The selection follows the selected task, by ID, even into another
+    /// lane; its detail view stays open at the same scroll position. Lanes
+    /// keep the sort picked with [`cycle_lane_sort`](Self::cycle_lane_sort)
+    /// when the new board has a lane of the same title. What changed from
+    /// the previous board is [recorded](Self::record_changes).
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The new board
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, LaneKind, Task};
+    /// use whip_tui::AppState;
+    ///
+    /// let task = Task::new("Task", "Description");
+    /// let mut board = KanbanBoard::new();
+    /// board.add_task(task.clone());
+    /// let mut state = AppState::new(board);
+    /// state.selected_task = Some(0);
+    ///
+    /// let mut moved = task.clone();
+    /// moved.lane = LaneKind::Done;
+    /// let mut board = KanbanBoard::new();
+    /// board.add_task(moved);
+    ///
+    /// state.replace_board(board);
+    /// assert_eq!((state.selected_lane, state.selected_task), (3, Some(0)));
+    /// ```
+    pub fn replace_board(&mut self, mut board: KanbanBoard) {
+        let selected = self.selected_task_id();
+        for lane in &mut board.lanes {
+            let sort = self
+                .board
+                .lanes
+                .iter()
+                .find(|old| old.title() == lane.title())
+                .map(|old| old.definition.sort);
+            if let Some(sort) = sort.filter(|sort| *sort != lane.definition.sort) {
+                lane.set_sort(sort);
+            }
+        }
+        let diff = BoardDiff::between(&self.board, &board);
+        self.board = board;
+        self.selected_lane = self
+            .selected_lane
+            .min(self.board.lanes.len().saturating_sub(1));
+        self.reselect(selected);
+        self.record_changes(&diff);
+    }
+
+    /// Records the changes of a refresh, to be marked until they are seen.
+    ///
+    /// The task whose detail view is open counts as seen.
+    ///
+    /// # Arguments
+    ///
+    /// * `diff` - The changes between the previous and the refreshed board
+    pub fn record_changes(&mut self, diff: &BoardDiff) {
+        self.changes.record(diff);
+        if self.detail_visible
+            && let Some(id) = self.selected_task_id()
+        {
+            self.changes.viewed(id);
+        }
+    }
+
+    /// Sorts the selected lane by the next sort key, cycling through every
+    /// key and back to arrival order. The selected task stays selected.
+    ///
+    /// Returns the new sort key of the lane.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, SortKey};
+    /// use whip_tui::AppState;
+    ///
+    /// let mut state = AppState::new(KanbanBoard::new());
+    /// assert_eq!(state.cycle_lane_sort(), Some(SortKey::UpdatedAt));
+    /// assert_eq!(state.board.lanes[0].definition.sort, Some(SortKey::UpdatedAt));
+    /// ```
+    pub fn cycle_lane_sort(&mut self) -> Option<SortKey> {
+        let selected = self.selected_task_id();
+        let lane = self.board.lane_mut(self.selected_lane)?;
+        let sort = SortKey::next(lane.definition.sort);
+        lane.set_sort(sort);
+        self.reselect(selected);
+        sort
+    }
+
     /// Moves the task selection up within the current lane.
     pub fn navigate_up(&mut self) {
-        let lane = self.selected_lane_ref();
-        if lane.is_empty() {
+        let len = self.visible_len(self.selected_lane);
+        if len == 0 {
             self.selected_task = None;
             return;
         }
@@ -157,7 +317,7 @@ impl AppState {
             }
             Some(_) => {
                 // Wrap to bottom
-                self.selected_task = Some(lane.len().saturating_sub(1));
+                self.selected_task = Some(len - 1);
             }
             None => {
                 // Select first task
@@ -166,15 +326,25 @@ impl AppState {
         }
     }
 
+    /// Selects the first task of the current lane.
+    pub fn navigate_first(&mut self) {
+        self.selected_task = (self.visible_len(self.selected_lane) > 0).then_some(0);
+    }
+
+    /// Selects the last task of the current lane.
+    pub fn navigate_last(&mut self) {
+        self.selected_task = self.visible_len(self.selected_lane).checked_sub(1);
+    }
+
     /// Moves the task selection down within the current lane.
     pub fn navigate_down(&mut self) {
-        let lane = self.selected_lane_ref();
-        if lane.is_empty() {
+        let len = self.visible_len(self.selected_lane);
+        if len == 0 {
             self.selected_task = None;
             return;
         }
 
-        let max_idx = lane.len().saturating_sub(1);
+        let max_idx = len - 1;
         match self.selected_task {
             Some(idx) if idx < max_idx => {
                 self.selected_task = Some(idx + 1);
@@ -190,9 +360,45 @@ impl AppState {
         }
     }
 
+    /// Moves the selection `delta` tasks through a lane, as the mouse wheel
+    /// does, focusing the lane first.
+    ///
+    /// The selection stops at the first and last tasks rather than wrapping.
+    /// A lane without a selection starts at its first task.
+    ///
+    /// # Arguments
+    ///
+    /// * `lane` - The index of the lane under the pointer
+    /// * `delta` - Tasks to move (positive moves down)
+    pub fn scroll_lane(&mut self, lane: usize, delta: i32) {
+        if lane >= self.board.lanes.len() {
+            return;
+        }
+        if lane != self.selected_lane {
+            self.selected_lane = lane;
+            self.selected_task = None;
+        }
+
+        let Some(max_idx) = self.visible_len(lane).checked_sub(1) else {
+            self.selected_task = None;
+            return;
+        };
+        self.selected_task = Some(match self.selected_task {
+            Some(idx) => idx
+                .saturating_add_signed(isize::try_from(delta).unwrap_or_default())
+                .min(max_idx),
+            None => 0,
+        });
+    }
+
     /// Toggles the detail panel visibility.
     pub fn toggle_detail(&mut self) {
         self.detail_visible = !self.detail_visible;
+        if self.detail_visible
+            && let Some(id) = self.selected_task_id()
+        {
+            self.changes.viewed(id);
+        }
         self.focus = if self.detail_visible {
             Focus::Detail
         } else {
@@ -270,8 +476,213 @@ impl AppState {
     #[must_use]
     pub fn selected_task(&self) -> Option<&Task> {
         let task_idx = self.selected_task?;
-        let lane = self.selected_lane_ref();
-        lane.tasks.get(task_idx)
+        self.visible_tasks(self.selected_lane).nth(task_idx)
+    }
+
+    /// Returns the tasks of a lane that the active filter shows.
+    ///
+    /// # Arguments
+    ///
+    /// * `lane` - Index of the lane
+    pub fn visible_tasks(&self, lane: usize) -> impl Iterator<Item = &Task> {
+        self.board
+            .lanes
+            .get(lane)
+            .into_iter()
+            .flat_map(|lane| lane.tasks.iter())
+            .filter(|task| self.filter.matches(task))
+    }
+
+    /// Returns the number of tasks of a lane that the active filter shows.
+    ///
+    /// # Arguments
+    ///
+    /// * `lane` - Index of the lane
+    #[must_use]
+    pub fn visible_len(&self, lane: usize) -> usize {
+        if self.filter.is_empty() {
+            return self.board.lanes.get(lane).map_or(0, Lane::len);
+        }
+        self.visible_tasks(lane).count()
+    }
+
+    /// Returns the board as shown: only the tasks the active filter shows.
+    ///
+    /// Borrows the board when no filter is active.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, Task, TaskFilter, TaskState};
+    /// use whip_tui::AppState;
+    ///
+    /// let mut board = KanbanBoard::new();
+    /// board.add_task(Task::new("Idle", "Description"));
+    /// let mut failed = Task::new("Failed", "Description");
+    /// failed.state = TaskState::Failed;
+    /// board.add_task(failed);
+    ///
+    /// let mut state = AppState::new(board);
+    /// assert_eq!(state.visible_board().total_tasks(), 2);
+    ///
+    /// state.set_filter(TaskFilter::default().with_states([TaskState::Failed]), None);
+    /// assert_eq!(state.visible_board().total_tasks(), 1);
+    /// assert_eq!(state.board.total_tasks(), 2);
+    /// ```
+    #[must_use]
+    pub fn visible_board(&self) -> Cow<'_, KanbanBoard> {
+        if self.filter.is_empty() {
+            return Cow::Borrowed(&self.board);
+        }
+        let mut board = self.board.clone();
+        board.retain_tasks(|task| self.filter.matches(task));
+        Cow::Owned(board)
+    }
+
+    /// Applies a filter to the board, keeping the selected task selected
+    /// when the filter still shows it.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter to apply; an empty filter shows every task
+    /// * `name` - The name of the filter, when it is a named filter
+    pub fn set_filter(&mut self, filter: TaskFilter, name: Option<String>) {
+        let selected = self.selected_task_id();
+        self.filter = filter;
+        self.filter_name = name;
+        self.reselect(selected);
+    }
+
+    /// Returns how the active filter is shown in the header: its name, or
+    /// else its expression. Returns `None` when no filter is active.
+    #[must_use]
+    pub fn filter_label(&self) -> Option<String> {
+        match &self.filter_name {
+            Some(name) => Some(name.clone()),
+            None if self.filter.is_empty() => None,
+            None => Some(self.filter.to_string()),
+        }
+    }
+
+    /// Returns the ID of the selected task, if any.
+    #[must_use]
+    pub fn selected_task_id(&self) -> Option<TaskId> {
+        self.selected_task().map(|task| task.id)
+    }
+
+    /// Selects a task again after the board changed, wherever it moved to.
+    ///
+    /// If the board no longer shows the task, the selection stays at the
+    /// same position, within bounds, and the detail view showing the task
+    /// closes.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the task selected before the change, if any
+    pub fn reselect(&mut self, id: Option<TaskId>) {
+        if id.is_some_and(|id| self.select_task_id(id)) {
+            return;
+        }
+        self.clamp_task_selection();
+        if self.detail_visible {
+            self.toggle_detail();
+        }
+    }
+
+    /// Selects the task with the given ID, if the board shows it.
+    ///
+    /// Returns `true` if the task was found and selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the task to select
+    pub fn select_task_id(&mut self, id: TaskId) -> bool {
+        let found = (0..self.board.lanes.len()).find_map(|lane| {
+            self.visible_tasks(lane)
+                .position(|task| task.id == id)
+                .map(|idx| (lane, idx))
+        });
+        let Some((lane, idx)) = found else {
+            return false;
+        };
+        self.selected_lane = lane;
+        self.selected_task = Some(idx);
+        true
+    }
+
+    /// Returns the `(lane, task)` index of every task matching the search.
+    ///
+    /// Returns an empty list when no search query is active.
+    #[must_use]
+    pub fn search_matches(&self) -> Vec<(usize, usize)> {
+        self.search
+            .highlight()
+            .map(|query| search::find_matches(query, &self.visible_board()))
+            .unwrap_or_default()
+    }
+
+    /// Selects the next or previous search result, wrapping around the board.
+    ///
+    /// Results are ordered lane by lane, top to bottom. The search starts
+    /// from the current selection, which is itself a candidate when
+    /// `include_current` is set; this keeps the selection in place while
+    /// the query is refined.
+    ///
+    /// Returns `true` if a result was selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `forward` - Whether to move to the next (or previous) result
+    /// * `include_current` - Whether the selected task may be selected again
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whip_protocol::{KanbanBoard, Task};
+    /// use whip_tui::AppState;
+    ///
+    /// let mut board = KanbanBoard::new();
+    /// board.add_task(Task::new("Parser", "Description"));
+    /// board.add_task(Task::new("Lexer", "Description"));
+    /// board.add_task(Task::new("Parser tests", "Description"));
+    ///
+    /// let mut state = AppState::new(board);
+    /// state.search.start();
+    /// "parser".chars().for_each(|ch| state.search.push(ch));
+    ///
+    /// assert!(state.select_search_result(true, true));
+    /// assert_eq!(state.selected_task, Some(0));
+    /// assert!(state.select_search_result(true, false));
+    /// assert_eq!(state.selected_task, Some(2));
+    /// ```
+    pub fn select_search_result(&mut self, forward: bool, include_current: bool) -> bool {
+        let matches = self.search_matches();
+        let cursor = (self.selected_lane, self.selected_task.unwrap_or(0));
+        let target = if forward {
+            // Nothing is selected yet: the top of the lane comes next
+            let inclusive = include_current || self.selected_task.is_none();
+            matches
+                .iter()
+                .find(|&&pos| pos > cursor || (inclusive && pos == cursor))
+                .or_else(|| matches.first())
+        } else {
+            let inclusive = include_current && self.selected_task.is_some();
+            matches
+                .iter()
+                .rev()
+                .find(|&&pos| pos < cursor || (inclusive && pos == cursor))
+                .or_else(|| matches.last())
+        };
+
+        let Some(&(lane, task)) = target else {
+            return false;
+        };
+        if (lane, Some(task)) != (self.selected_lane, self.selected_task) {
+            self.selected_lane = lane;
+            self.selected_task = Some(task);
+            self.detail_scroll = 0;
+        }
+        true
     }
 
     /// Clears the current task selection.
@@ -282,14 +693,14 @@ impl AppState {
     }
 
     /// Ensures the task selection is valid for the current lane.
-    fn clamp_task_selection(&mut self) {
-        let lane = self.selected_lane_ref();
-        if lane.is_empty() {
+    pub(crate) fn clamp_task_selection(&mut self) {
+        let len = self.visible_len(self.selected_lane);
+        if len == 0 {
             self.selected_task = None;
         } else if let Some(idx) = self.selected_task
-            && idx >= lane.len()
+            && idx >= len
         {
-            self.selected_task = Some(lane.len().saturating_sub(1));
+            self.selected_task = Some(len - 1);
         }
     }
 }
@@ -297,7 +708,7 @@ impl AppState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use whip_protocol::Task;
+    use whip_protocol::{LaneKind, SortKey, Task, TaskChange, TaskState};
 
     #[test]
     fn new_state_has_correct_defaults() {
@@ -334,6 +745,40 @@ mod tests {
         assert_eq!(state.selected_lane, 0);
     }
 
+    #[test]
+    fn navigation_wraps_for_any_lane_count() {
+        let lanes = (1..=7)
+            .map(|i| LaneDefinition::new(format!("Lane {i}")))
+            .collect();
+        let mut state = AppState::new(KanbanBoard::with_lanes(lanes));
+
+        state.navigate_left();
+        assert_eq!(state.selected_lane, 6);
+
+        state.navigate_right();
+        assert_eq!(state.selected_lane, 0);
+
+        for _ in 0..5 {
+            state.navigate_right();
+        }
+        assert_eq!(state.selected_lane, 5);
+    }
+
+    #[test]
+    fn set_lanes_keeps_selection_in_bounds() {
+        let mut board = KanbanBoard::new();
+        board.add_task(Task::new("Task 1", "Desc 1"));
+        let mut state = AppState::new(board);
+        state.selected_lane = 3;
+        state.selected_task = Some(0);
+
+        state.set_lanes(vec![LaneDefinition::new("All")]);
+
+        assert_eq!(state.selected_lane, 0);
+        assert_eq!(state.selected_task, Some(0));
+        assert_eq!(state.selected_task().unwrap().title, "Task 1");
+    }
+
     #[test]
     fn navigate_up_down_in_empty_lane() {
         let board = KanbanBoard::new();
@@ -374,6 +819,53 @@ mod tests {
         assert_eq!(state.selected_task, Some(2));
     }
 
+    #[test]
+    fn navigate_first_and_last() {
+        let mut board = KanbanBoard::new();
+        board.add_task(Task::new("Task 1", "Desc 1"));
+        board.add_task(Task::new("Task 2", "Desc 2"));
+        board.add_task(Task::new("Task 3", "Desc 3"));
+
+        let mut state = AppState::new(board);
+        state.navigate_last();
+        assert_eq!(state.selected_task, Some(2));
+        state.navigate_first();
+        assert_eq!(state.selected_task, Some(0));
+
+        // An empty lane has nothing to select
+        state.navigate_right();
+        state.navigate_last();
+        assert_eq!(state.selected_task, None);
+    }
+
+    #[test]
+    fn scroll_lane_focuses_lane_and_stops_at_ends() {
+        let mut board = KanbanBoard::new();
+        board.add_task(Task::new("Task 1", "Desc 1"));
+        board.add_task(Task::new("Task 2", "Desc 2"));
+        board.add_task(Task::new("Task 3", "Desc 3"));
+
+        let mut state = AppState::new(board);
+        state.navigate_right();
+
+        // Scrolling another lane focuses it, starting at its first task
+        state.scroll_lane(0, 1);
+        assert_eq!(state.selected_lane, 0);
+        assert_eq!(state.selected_task, Some(0));
+
+        state.scroll_lane(0, 5);
+        assert_eq!(state.selected_task, Some(2));
+        state.scroll_lane(0, -1);
+        assert_eq!(state.selected_task, Some(1));
+        state.scroll_lane(0, -5);
+        assert_eq!(state.selected_task, Some(0));
+
+        // An empty lane is focused with nothing selected
+        state.scroll_lane(1, 1);
+        assert_eq!(state.selected_lane, 1);
+        assert_eq!(state.selected_task, None);
+    }
+
     #[test]
     fn toggle_detail_changes_focus() {
         let board = KanbanBoard::new();
@@ -508,6 +1000,47 @@ mod tests {
         assert!(!state.help_visible);
     }
 
+    #[test]
+    fn toggle_errors_visibility() {
+        let board = KanbanBoard::new();
+        let mut state = AppState::new(board);
+
+        assert!(!state.errors_visible);
+
+        state.toggle_errors();
+        assert!(state.errors_visible);
+
+        state.toggle_errors();
+        assert!(!state.errors_visible);
+    }
+
+    #[test]
+    fn toggle_notifications_resets_scroll() {
+        let board = KanbanBoard::new();
+        let mut state = AppState::new(board);
+
+        state.toggle_notifications();
+        assert!(state.notifications_visible);
+        state.scroll_notifications(3, 10);
+        assert_eq!(state.notifications_scroll, 3);
+
+        state.toggle_notifications();
+        assert!(!state.notifications_visible);
+        assert_eq!(state.notifications_scroll, 0);
+    }
+
+    #[test]
+    fn scroll_notifications_is_clamped() {
+        let board = KanbanBoard::new();
+        let mut state = AppState::new(board);
+
+        state.scroll_notifications(-1, 10);
+        assert_eq!(state.notifications_scroll, 0);
+
+        state.scroll_notifications(20, 10);
+        assert_eq!(state.notifications_scroll, 10);
+    }
+
     #[test]
     fn dismiss_help_when_visible() {
         let board = KanbanBoard::new();
@@ -544,4 +1077,219 @@ mod tests {
         state.clear_selection();
         assert!(state.selected_task.is_none());
     }
+
+    fn search_state(titles: &[(&str, LaneKind)], query: &str) -> AppState {
+        let mut board = KanbanBoard::new();
+        for (title, lane) in titles {
+            let mut task = Task::new(*title, "Description");
+            task.lane = *lane;
+            board.add_task(task);
+        }
+        let mut state = AppState::new(board);
+        state.search.start();
+        query.chars().for_each(|ch| state.search.push(ch));
+        state
+    }
+
+    #[test]
+    fn search_results_wrap_across_lanes() {
+        let mut state = search_state(
+            &[
+                ("Parser", LaneKind::Backlog),
+                ("Lexer", LaneKind::Backlog),
+                ("Parser docs", LaneKind::Done),
+            ],
+            "parser",
+        );
+        assert_eq!(state.search_matches(), vec![(0, 0), (3, 0)]);
+
+        assert!(state.select_search_result(true, true));
+        assert_eq!((state.selected_lane, state.selected_task), (0, Some(0)));
+
+        assert!(state.select_search_result(true, false));
+        assert_eq!((state.selected_lane, state.selected_task), (3, Some(0)));
+
+        // Wraps back to the first result
+        assert!(state.select_search_result(true, false));
+        assert_eq!((state.selected_lane, state.selected_task), (0, Some(0)));
+
+        // And backwards to the last one
+        assert!(state.select_search_result(false, false));
+        assert_eq!((state.selected_lane, state.selected_task), (3, Some(0)));
+    }
+
+    #[test]
+    fn search_result_keeps_matching_selection() {
+        let mut state = search_state(
+            &[
+                ("Parser", LaneKind::Backlog),
+                ("Parser docs", LaneKind::Backlog),
+            ],
+            "parser",
+        );
+        state.selected_task = Some(1);
+
+        assert!(state.select_search_result(true, true));
+        assert_eq!(state.selected_task, Some(1));
+    }
+
+    #[test]
+    fn search_result_starts_from_unselected_lane_top() {
+        let mut state = search_state(
+            &[
+                ("Parser", LaneKind::Backlog),
+                ("Parser docs", LaneKind::Done),
+            ],
+            "parser",
+        );
+        state.selected_lane = 3;
+
+        assert!(state.select_search_result(true, false));
+        assert_eq!((state.selected_lane, state.selected_task), (3, Some(0)));
+    }
+
+    #[test]
+    fn search_without_results_keeps_selection() {
+        let mut state = search_state(&[("Parser", LaneKind::Backlog)], "yaml");
+        state.selected_task = Some(0);
+
+        assert!(state.search_matches().is_empty());
+        assert!(!state.select_search_result(true, true));
+        assert_eq!(state.selected_task, Some(0));
+    }
+
+    fn filter_state() -> AppState {
+        let mut board = KanbanBoard::new();
+        for (title, state) in [
+            ("First", TaskState::Failed),
+            ("Second", TaskState::Idle),
+            ("Third", TaskState::Failed),
+        ] {
+            let mut task = Task::new(title, "Description");
+            task.state = state;
+            board.add_task(task);
+        }
+        AppState::new(board)
+    }
+
+    #[test]
+    fn filter_limits_navigation_to_visible_tasks() {
+        let mut state = filter_state();
+        state.set_filter(TaskFilter::default().with_states([TaskState::Failed]), None);
+        assert_eq!(state.visible_len(0), 2);
+        assert_eq!(state.visible_board().lanes[0].tasks.len(), 2);
+
+        state.navigate_down();
+        state.navigate_down();
+        assert_eq!(state.selected_task, Some(1));
+        assert_eq!(
+            state.selected_task().map(|t| t.title.as_str()),
+            Some("Third")
+        );
+        assert_eq!(state.filter_label().as_deref(), Some("state:failed"));
+    }
+
+    #[test]
+    fn set_filter_keeps_selected_task() {
+        let mut state = filter_state();
+        state.selected_task = Some(2);
+
+        state.set_filter(
+            TaskFilter::default().with_states([TaskState::Failed]),
+            Some("Broken".to_string()),
+        );
+        assert_eq!(state.selected_task, Some(1));
+        assert_eq!(state.filter_label().as_deref(), Some("Broken"));
+
+        // A hidden selection is clamped to the visible tasks
+        state.set_filter(TaskFilter::default().with_states([TaskState::Idle]), None);
+        assert_eq!(state.selected_task, Some(0));
+
+        state.set_filter(TaskFilter::default(), None);
+        assert_eq!(
+            state.selected_task().map(|t| t.title.as_str()),
+            Some("Second")
+        );
+        assert_eq!(state.filter_label(), None);
+    }
+
+    #[test]
+    fn cycle_lane_sort_keeps_selected_task() {
+        let mut state = filter_state();
+        state.selected_task = Some(1);
+
+        assert_eq!(state.cycle_lane_sort(), Some(SortKey::UpdatedAt));
+        assert_eq!(state.cycle_lane_sort(), Some(SortKey::CreatedAt));
+        assert_eq!(state.cycle_lane_sort(), Some(SortKey::Number));
+        assert_eq!(state.cycle_lane_sort(), Some(SortKey::Priority));
+        assert_eq!(state.cycle_lane_sort(), Some(SortKey::Severity));
+        let titles: Vec<_> = state.visible_tasks(0).map(|t| t.title.as_str()).collect();
+        assert_eq!(titles[2], "Second");
+        assert_eq!(state.selected_task, Some(2));
+
+        assert_eq!(state.cycle_lane_sort(), None);
+        assert_eq!(
+            state.selected_task().map(|t| t.title.as_str()),
+            Some("Second")
+        );
+    }
+
+    #[test]
+    fn replace_board_follows_selected_task() {
+        let mut state = filter_state();
+        state.selected_task = Some(1);
+        state.toggle_detail();
+        state.scroll_detail(4);
+        let _ = state.cycle_lane_sort();
+
+        let mut board = KanbanBoard::new();
+        for task in &state.board.lanes[0].tasks {
+            let mut task = task.clone();
+            if task.title == "Second" {
+                task.lane = LaneKind::UnderReview;
+            }
+            board.add_task(task);
+        }
+        state.replace_board(board);
+
+        assert_eq!((state.selected_lane, state.selected_task), (2, Some(0)));
+        assert!(state.detail_visible);
+        assert_eq!(state.detail_scroll, 4);
+        assert_eq!(
+            state.board.lanes[0].definition.sort,
+            Some(SortKey::UpdatedAt)
+        );
+    }
+
+    #[test]
+    fn replace_board_closes_detail_of_removed_task() {
+        let mut state = filter_state();
+        state.selected_task = Some(2);
+        state.toggle_detail();
+
+        let mut board = KanbanBoard::new();
+        board.add_task(state.board.lanes[0].tasks[0].clone());
+        state.replace_board(board);
+
+        assert_eq!(state.selected_task, Some(0));
+        assert!(!state.detail_visible);
+        assert_eq!(state.focus, Focus::Board);
+    }
+
+    #[test]
+    fn record_changes_skips_task_in_detail_view() {
+        let mut state = filter_state();
+        state.selected_task = Some(0);
+        state.toggle_detail();
+        let open = state.board.lanes[0].tasks[0].id;
+        let other = state.board.lanes[0].tasks[1].id;
+
+        state.record_changes(&BoardDiff {
+            updated: vec![open, other],
+            ..BoardDiff::default()
+        });
+
+        assert_eq!(state.changes.get(open), None);
+        assert_eq!(state.changes.get(other), Some(TaskChange::Updated));
+    }
 }
@@ -1,26 +1,29 @@
 //! Kanban board rendering widget.
 //!
 //! This module provides functions for rendering the complete Kanban board
-//! with its four lanes arranged horizontally.
+//! with its lanes arranged horizontally.
+
+use std::rc::Rc;
 
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
 };
-use whip_protocol::{KanbanBoard, LaneKind};
+use whip_protocol::KanbanBoard;
 
-use super::lane::{LanePosition, render_lane};
+use super::lane::{LanePosition, render_lane_with_highlights};
+use super::task_card::Highlights;
 
 /// Renders the complete Kanban board to the buffer.
 ///
-/// The board displays four lanes (Backlog, In Progress, Under Review, Done)
-/// arranged horizontally with equal widths. Each lane shows its tasks with
+/// The board displays its lanes (by default Backlog, In Progress, Under
+/// Review, and Done) arranged horizontally with equal widths. Each lane shows its tasks with
 /// the selected lane and task highlighted.
 ///
 /// # Arguments
 ///
 /// * `board` - The Kanban board containing all tasks
-/// * `selected_lane` - Index of the currently focused lane (0-3)
+/// * `selected_lane` - Index of the currently focused lane
 /// * `selected_task` - Index of the selected task within the focused lane, if any
 /// * `area` - The rectangular area to render into
 /// * `buf` - The buffer to render into
@@ -60,21 +63,62 @@ pub fn render_board(
     area: Rect,
     buf: &mut Buffer,
 ) {
-    // Split into 4 equal columns for the lanes
-    let lane_areas = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-        ])
-        .split(area);
+    render_board_with_highlights(
+        board,
+        selected_lane,
+        selected_task,
+        Highlights::default(),
+        area,
+        buf,
+    );
+}
+
+/// Renders the Kanban board, highlighting search matches and unseen
+/// changes.
+///
+/// Like [`render_board`], with matched characters highlighted on the
+/// cards, non-matching cards dimmed, and changed cards marked.
+///
+/// # Arguments
+///
+/// * `highlights` - The search query and unseen changes
+///
+/// See [`render_board`] for the other arguments.
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_protocol::{KanbanBoard, Task};
+/// use whip_tui::widgets::board::render_board_with_highlights;
+/// use whip_tui::widgets::task_card::Highlights;
+///
+/// let mut board = KanbanBoard::new();
+/// board.add_task(Task::new("Task 1", "Description"));
+///
+/// let area = Rect::new(0, 0, 80, 20);
+/// let mut buf = Buffer::empty(area);
+///
+/// let highlights = Highlights {
+///     search: Some("task"),
+///     ..Highlights::default()
+/// };
+/// render_board_with_highlights(&board, 0, Some(0), highlights, area, &mut buf);
+/// ```
+pub fn render_board_with_highlights(
+    board: &KanbanBoard,
+    selected_lane: usize,
+    selected_task: Option<usize>,
+    highlights: Highlights<'_>,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let lane_areas = lane_areas(area, board.lanes.len());
 
     // Render each lane
-    let lane_count = LaneKind::all().len();
-    for (i, kind) in LaneKind::all().iter().enumerate() {
-        let lane = board.lane(*kind);
+    let lane_count = board.lanes.len();
+    for (i, lane) in board.lanes.iter().enumerate() {
         let is_focused = selected_lane == i;
 
         // Only show task selection in focused lane
@@ -92,10 +136,11 @@ pub fn render_board(
         // Check if the previous lane is focused (for shared border coloring)
         let prev_focused = i > 0 && selected_lane == i - 1;
 
-        render_lane(
+        render_lane_with_highlights(
             lane,
             is_focused,
             task_selection,
+            highlights,
             lane_areas[i],
             buf,
             position,
@@ -104,11 +149,23 @@ pub fn render_board(
     }
 }
 
+/// Splits the board area into equal columns, one per lane.
+///
+/// Rendering and mouse handling both use this, so clicks land on the lane
+/// drawn under the cursor.
+pub(crate) fn lane_areas(area: Rect, lane_count: usize) -> Rc<[Rect]> {
+    let lane_count = u32::try_from(lane_count.max(1)).unwrap_or(u32::MAX);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints((0..lane_count).map(|_| Constraint::Ratio(1, lane_count)))
+        .split(area)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_utils::buffer_to_string;
-    use whip_protocol::Task;
+    use whip_protocol::{LaneDefinition, LaneKind, Task, TaskState};
 
     #[test]
     fn render_empty_board() {
@@ -119,7 +176,7 @@ mod tests {
         render_board(&board, 0, None, area, &mut buf);
 
         let content = buffer_to_string(&buf);
-        // All four lanes should be rendered
+        // All four standard lanes should be rendered
         assert!(content.contains("Backlog"));
         assert!(content.contains("In Progress"));
         assert!(content.contains("Under Review"));
@@ -141,6 +198,41 @@ mod tests {
         assert!(content.contains("Backlog (2)"));
     }
 
+    #[test]
+    fn render_board_with_custom_lanes() {
+        let mut board = KanbanBoard::with_lanes(vec![
+            LaneDefinition::new("Todo").with_kinds([LaneKind::Backlog]),
+            LaneDefinition::new("Doing").with_kinds([LaneKind::InProgress]),
+            LaneDefinition::new("Review").with_kinds([LaneKind::UnderReview]),
+            LaneDefinition::new("Blocked").with_states([TaskState::NeedsAttention]),
+            LaneDefinition::new("Failed").with_states([TaskState::Failed]),
+            LaneDefinition::new("Done"),
+        ]);
+        let mut task = Task::new("Task 1", "First task");
+        task.lane = LaneKind::Done;
+        task.state = TaskState::Failed;
+        board.add_task(task);
+
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+
+        render_board(&board, 4, Some(0), area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        for title in ["Todo", "Doing", "Review", "Blocked", "Failed (1)", "Done"] {
+            assert!(content.contains(title), "missing lane {title}");
+        }
+    }
+
+    #[test]
+    fn lane_areas_cover_the_board() {
+        let area = Rect::new(0, 0, 100, 10);
+        let areas = lane_areas(area, 7);
+        assert_eq!(areas.len(), 7);
+        assert_eq!(areas[0].x, 0);
+        assert_eq!(areas[6].x + areas[6].width, 100);
+    }
+
     #[test]
     fn render_board_narrow_terminal() {
         let board = KanbanBoard::new();
@@ -0,0 +1,234 @@
+//! Context menu widget.
+//!
+//! This module provides the small menu of task actions opened by a right
+//! click, drawn at the pointer.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+use whip_protocol::{KeyContext, Keymap};
+
+use crate::context_menu::ContextMenu;
+
+/// Renders a context menu at the position it was opened at.
+///
+/// Each entry shows the first board key bound to its action, so the menu
+/// also teaches the keys. The menu is moved left or up where it would
+/// otherwise run past the edge of `area`.
+///
+/// # Arguments
+///
+/// * `menu` - The context menu state
+/// * `keymap` - The active key bindings
+/// * `area` - The full terminal area
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// ╭ Fix login ──────────╮
+/// │ Open details  Enter │
+/// │ Sort lane         s │
+/// │ Refresh      Ctrl+R │
+/// ╰─────────────────────╯
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_protocol::{Keymap, Task};
+/// use whip_tui::context_menu::ContextMenu;
+/// use whip_tui::widgets::render_context_menu;
+///
+/// let menu = ContextMenu::for_task(&Task::new("Fix login", ""), false, (10, 5));
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+///
+/// render_context_menu(&menu, &Keymap::default(), area, &mut buf);
+/// ```
+pub fn render_context_menu(menu: &ContextMenu, keymap: &Keymap, area: Rect, buf: &mut Buffer) {
+    let menu_area = context_menu_area(menu, keymap, area);
+    let rows = menu_rows(menu, keymap);
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let inner_width = usize::from(menu_area.width.saturating_sub(2));
+
+    let selected_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, keys))| {
+            // Keys are right-aligned against the border
+            let key_width = inner_width.saturating_sub(label_width + 3);
+            let text = format!(" {label:<label_width$} {keys:>key_width$} ");
+            if i == menu.selected() {
+                Line::from(Span::styled(text, selected_style))
+            } else {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {label:<label_width$} "),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("{keys:>key_width$} "),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            }
+        })
+        .collect();
+
+    Clear.render(menu_area, buf);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(
+                " {} ",
+                truncate(menu.title(), inner_width.saturating_sub(2))
+            ),
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    Paragraph::new(lines).block(block).render(menu_area, buf);
+}
+
+/// Returns the area of a context menu rendered in `area`.
+///
+/// Rendering and mouse handling both use this, so clicks land on the entry
+/// drawn under the cursor.
+pub(crate) fn context_menu_area(menu: &ContextMenu, keymap: &Keymap, area: Rect) -> Rect {
+    let rows = menu_rows(menu, keymap);
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let key_width = rows
+        .iter()
+        .map(|(_, keys)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    // Borders, padding, and a gap between the label and key columns
+    let width = u16::try_from(label_width + key_width + 5)
+        .unwrap_or(u16::MAX)
+        .min(area.width);
+    let height = u16::try_from(rows.len() + 2)
+        .unwrap_or(u16::MAX)
+        .min(area.height);
+
+    let (column, row) = menu.anchor();
+    let x = column.min(area.right().saturating_sub(width)).max(area.x);
+    let y = row.min(area.bottom().saturating_sub(height)).max(area.y);
+    Rect::new(x, y, width, height)
+}
+
+/// Returns the index of the context menu entry drawn at a position.
+///
+/// Returns `None` on the border and outside the menu.
+pub(crate) fn context_menu_item_at(
+    menu: &ContextMenu,
+    keymap: &Keymap,
+    area: Rect,
+    column: u16,
+    row: u16,
+) -> Option<usize> {
+    let inner = Block::default()
+        .borders(Borders::ALL)
+        .inner(context_menu_area(menu, keymap, area));
+    if !inner.contains((column, row).into()) {
+        return None;
+    }
+    let index = usize::from(row - inner.y);
+    (index < menu.items().len()).then_some(index)
+}
+
+/// Pairs the label of each entry with the first board key of its action.
+fn menu_rows(menu: &ContextMenu, keymap: &Keymap) -> Vec<(&'static str, String)> {
+    menu.items()
+        .iter()
+        .map(|item| {
+            let keys = keymap
+                .keys(KeyContext::Board, &item.message)
+                .next()
+                .map(|chord| chord.label())
+                .unwrap_or_default();
+            (item.label, keys)
+        })
+        .collect()
+}
+
+/// Shortens text to at most `width` characters, ending it with `…` when cut.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::buffer_to_string;
+    use whip_protocol::{Message, Task};
+
+    fn menu_at(anchor: (u16, u16)) -> ContextMenu {
+        ContextMenu::for_task(&Task::new("Fix login", ""), false, anchor)
+    }
+
+    #[test]
+    fn menu_opens_at_the_pointer() {
+        let area = Rect::new(0, 0, 80, 24);
+        let menu_area = context_menu_area(&menu_at((10, 5)), &Keymap::default(), area);
+        assert_eq!((menu_area.x, menu_area.y), (10, 5));
+        assert_eq!(menu_area.height, 5);
+    }
+
+    #[test]
+    fn menu_stays_inside_the_area() {
+        let area = Rect::new(0, 0, 80, 24);
+        let menu_area = context_menu_area(&menu_at((79, 23)), &Keymap::default(), area);
+        assert_eq!(menu_area.right(), 80);
+        assert_eq!(menu_area.bottom(), 24);
+    }
+
+    #[test]
+    fn render_context_menu_shows_entries_and_keys() {
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+
+        render_context_menu(&menu_at((2, 1)), &Keymap::default(), area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.contains("Fix login"), "{content}");
+        assert!(content.contains(" Open details  Enter "), "{content}");
+        assert!(content.contains(" Sort lane         s "), "{content}");
+        assert!(content.contains(" Refresh      Ctrl+R "), "{content}");
+    }
+
+    #[test]
+    fn item_at_maps_rows_to_entries() {
+        let keymap = Keymap::default();
+        let area = Rect::new(0, 0, 80, 24);
+        let menu = menu_at((10, 5));
+
+        // The first entry is below the top border
+        assert_eq!(context_menu_item_at(&menu, &keymap, area, 12, 5), None);
+        assert_eq!(context_menu_item_at(&menu, &keymap, area, 12, 6), Some(0));
+        assert_eq!(context_menu_item_at(&menu, &keymap, area, 12, 8), Some(2));
+        assert_eq!(context_menu_item_at(&menu, &keymap, area, 12, 9), None);
+        assert_eq!(context_menu_item_at(&menu, &keymap, area, 5, 6), None);
+        assert_eq!(menu.items()[2].message, Message::Refresh);
+    }
+}
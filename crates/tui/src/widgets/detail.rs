@@ -1037,6 +1037,7 @@ mod tests {
     fn render_detail_panel_with_github_metadata() {
         let mut task = Task::new("Test Task", "A test description");
         task.github = Some(GitHubSource {
+            host: None,
             owner: "rust-lang".to_string(),
             repo: "rust".to_string(),
             number: 12345,
@@ -1120,6 +1121,7 @@ mod tests {
     fn calculate_metadata_height_with_github() {
         let mut task = Task::new("Test Task", "Description");
         task.github = Some(GitHubSource {
+            host: None,
             owner: "owner".to_string(),
             repo: "repo".to_string(),
             number: 123,
@@ -1148,6 +1150,7 @@ mod tests {
     fn build_description_lines_includes_labels() {
         let mut task = Task::new("Test Task", "Description text");
         task.github = Some(GitHubSource {
+            host: None,
             owner: "owner".to_string(),
             repo: "repo".to_string(),
             number: 123,
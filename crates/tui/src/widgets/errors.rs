@@ -0,0 +1,193 @@
+//! Errors pane widget.
+//!
+//! This module provides the overlay listing recent repository errors,
+//! opened with `e`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+use whip_protocol::FetchErrorKind;
+
+use super::help::centered_rect;
+use crate::notification::ErrorLog;
+
+/// The width of the errors pane.
+const ERRORS_WIDTH: u16 = 72;
+
+/// The height of the errors pane.
+const ERRORS_HEIGHT: u16 = 20;
+
+/// Returns the display color for an error kind.
+fn kind_color(kind: FetchErrorKind) -> Color {
+    match kind {
+        FetchErrorKind::Auth => Color::Magenta,
+        FetchErrorKind::RateLimit => Color::Yellow,
+        FetchErrorKind::Network => Color::Blue,
+        FetchErrorKind::Parse => Color::Cyan,
+        FetchErrorKind::Other => Color::Red,
+    }
+}
+
+/// Renders a centered overlay listing logged repository errors, newest first.
+///
+/// Each error occupies two lines: the time, kind, and repository, followed
+/// by the error message. Errors that do not fit are omitted.
+///
+/// # Arguments
+///
+/// * `errors` - The error log to display
+/// * `area` - The full terminal area (the overlay will be centered within it)
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// ╭─ Errors ─────────────────────────────────────────╮
+/// │                                                  │
+/// │  14:02:11  rate limit  rust-lang/rust            │
+/// │            API rate limit exceeded               │
+/// │  14:01:57  network     tokio-rs/tokio            │
+/// │            error sending request                 │
+/// │                                                  │
+/// │  Press any key to close                          │
+/// ╰──────────────────────────────────────────────────╯
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_tui::notification::ErrorLog;
+/// use whip_tui::widgets::render_errors_pane;
+///
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+///
+/// render_errors_pane(&ErrorLog::default(), area, &mut buf);
+/// ```
+pub fn render_errors_pane(errors: &ErrorLog, area: Rect, buf: &mut Buffer) {
+    let popup_area = centered_rect(ERRORS_WIDTH, ERRORS_HEIGHT, area);
+
+    Clear.render(popup_area, buf);
+
+    let border_style = Style::default().fg(Color::LightRed);
+    let block = Block::default()
+        .title(Span::styled(
+            " Errors ",
+            border_style.add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style);
+
+    // Inner height minus blank first line, blank separator, and close hint
+    let available = popup_area.height.saturating_sub(2 + 3) as usize;
+    let lines = build_error_lines(errors, available / 2);
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Left)
+        .render(popup_area, buf);
+}
+
+/// Builds the lines of the errors pane, showing at most `max_errors` entries.
+fn build_error_lines(errors: &ErrorLog, max_errors: usize) -> Vec<Line<'static>> {
+    let time_style = Style::default().fg(Color::DarkGray);
+    let repo_style = Style::default().fg(Color::White);
+    let message_style = Style::default().fg(Color::Gray);
+    let hint_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC);
+
+    let mut lines = vec![Line::from("")];
+
+    if errors.is_empty() {
+        lines.push(Line::from(Span::styled("  No errors", message_style)));
+    }
+
+    for error in errors.iter().take(max_errors) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {}  ", error.occurred_at.format("%H:%M:%S")),
+                time_style,
+            ),
+            Span::styled(
+                format!("{:<12}", error.kind.label()),
+                Style::default().fg(kind_color(error.kind)),
+            ),
+            Span::styled(error.repo.clone(), repo_style),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("            {}", error.message),
+            message_style,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        hint_style,
+    )));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::RepoError;
+    use crate::test_utils::buffer_to_string;
+    use chrono::{Local, TimeZone};
+
+    fn render(errors: &ErrorLog) -> String {
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        render_errors_pane(errors, area, &mut buf);
+        buffer_to_string(&buf)
+    }
+
+    #[test]
+    fn empty_log_shows_placeholder() {
+        let content = render(&ErrorLog::default());
+        assert!(content.contains("Errors"));
+        assert!(content.contains("No errors"));
+    }
+
+    #[test]
+    fn lists_time_kind_repo_and_message() {
+        let mut log = ErrorLog::default();
+        log.push(RepoError {
+            repo: "owner/repo".to_string(),
+            kind: FetchErrorKind::RateLimit,
+            message: "API rate limit exceeded".to_string(),
+            occurred_at: Local.with_ymd_and_hms(2026, 1, 2, 14, 2, 11).unwrap(),
+        });
+
+        let content = render(&log);
+        assert!(content.contains("14:02:11  rate limit  owner/repo"));
+        assert!(content.contains("API rate limit exceeded"));
+        assert!(!content.contains("No errors"));
+    }
+
+    #[test]
+    fn omits_errors_that_do_not_fit() {
+        let mut log = ErrorLog::default();
+        for i in 0..20 {
+            log.push(RepoError {
+                repo: format!("owner/repo{i:02}"),
+                kind: FetchErrorKind::Network,
+                message: "unreachable".to_string(),
+                occurred_at: Local::now(),
+            });
+        }
+
+        let content = render(&log);
+        assert!(content.contains("owner/repo19"));
+        assert!(!content.contains("owner/repo00"));
+        assert!(content.contains("Press any key to close"));
+    }
+}
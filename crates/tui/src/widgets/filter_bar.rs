@@ -0,0 +1,177 @@
+//! Filter bar rendering widget.
+//!
+//! This module provides the one-line input shown under the board while the
+//! board filter is being edited.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use whip_protocol::NamedFilter;
+
+use crate::filter_bar::FilterBarState;
+
+/// Renders the filter bar.
+///
+/// The input is shown after a `Filter:` prompt, or a `Save as:` prompt
+/// while a filter is being named. The right side shows why the input could
+/// not be applied, the expression of the saved filter picked with `Tab`,
+/// or the expression being saved, followed by key hints.
+///
+/// # Arguments
+///
+/// * `bar` - The filter bar state
+/// * `filters` - The saved filters
+/// * `area` - The rectangular area to render into
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// Filter: mine▏  author:octocat  Tab saved  ^S save  Enter apply  Esc cancel
+/// Save as: mine▏                  author:octocat  Enter save  Esc back
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_tui::filter_bar::FilterBarState;
+/// use whip_tui::widgets::render_filter_bar;
+///
+/// let mut bar = FilterBarState::default();
+/// bar.start("repo:acme/api");
+///
+/// let area = Rect::new(0, 0, 80, 1);
+/// let mut buf = Buffer::empty(area);
+///
+/// render_filter_bar(&bar, &[], area, &mut buf);
+/// ```
+pub fn render_filter_bar(
+    bar: &FilterBarState,
+    filters: &[NamedFilter],
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let key_style = Style::default().fg(Color::Yellow);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    let prompt = if bar.saving().is_some() {
+        "Save as: "
+    } else {
+        "Filter: "
+    };
+    let input = Line::from(vec![
+        Span::styled(prompt, key_style.add_modifier(Modifier::BOLD)),
+        Span::styled(bar.input(), Style::default().fg(Color::White)),
+        Span::styled("▏", key_style),
+    ]);
+
+    let mut status = Vec::new();
+    let shown = bar
+        .saving()
+        .or_else(|| Some(&filters.get(bar.named_index()?)?.filter));
+    if let Some(error) = bar.error() {
+        status.push(Span::styled(error, Style::default().fg(Color::Red)));
+    } else if let Some(filter) = shown {
+        status.push(Span::styled(
+            filter.to_string(),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if bar.saving().is_some() {
+        status.push(Span::styled("  Enter save  Esc back", hint_style));
+    } else {
+        if !filters.is_empty() {
+            status.push(Span::styled("  Tab saved", hint_style));
+        }
+        status.push(Span::styled(
+            "  ^S save  Enter apply  Esc cancel",
+            hint_style,
+        ));
+    }
+
+    let status = Line::from(status);
+    let status_width = u16::try_from(status.width()).unwrap_or(u16::MAX);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(status_width)])
+        .split(area);
+
+    Paragraph::new(input).render(chunks[0], buf);
+    Paragraph::new(status).render(chunks[1], buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::buffer_to_string;
+    use whip_protocol::TaskFilter;
+
+    #[test]
+    fn shows_input_and_hints() {
+        let mut bar = FilterBarState::default();
+        bar.start("label:bug");
+        let area = Rect::new(0, 0, 80, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_filter_bar(&bar, &[], area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.starts_with("Filter: label:bug▏"));
+        assert!(content.contains("Enter apply"));
+        assert!(!content.contains("Tab saved"));
+    }
+
+    #[test]
+    fn shows_picked_saved_filter() {
+        let filters = vec![NamedFilter::new(
+            "mine",
+            TaskFilter::default().with_authors(["octocat"]),
+        )];
+        let mut bar = FilterBarState::default();
+        bar.start("");
+        bar.cycle(&filters, 1);
+        let area = Rect::new(0, 0, 80, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_filter_bar(&bar, &filters, area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.starts_with("Filter: mine▏"));
+        assert!(content.contains("author:octocat  Tab saved"));
+    }
+
+    #[test]
+    fn shows_save_prompt() {
+        let mut bar = FilterBarState::default();
+        bar.start("label:bug");
+        bar.start_save(&[]);
+        bar.push('b');
+        let area = Rect::new(0, 0, 80, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_filter_bar(&bar, &[], area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.starts_with("Save as: b▏"));
+        assert!(content.contains("label:bug  Enter save  Esc back"));
+    }
+
+    #[test]
+    fn shows_errors() {
+        let mut bar = FilterBarState::default();
+        bar.start("nonsense");
+        let _ = bar.apply(&[]);
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_filter_bar(&bar, &[], area, &mut buf);
+
+        assert!(buffer_to_string(&buf).contains("invalid filter 'nonsense'"));
+    }
+}
@@ -1,7 +1,7 @@
 //! Help overlay widget.
 //!
-//! This module provides the help overlay that displays all available keybindings
-//! when the user presses `?`.
+//! This module provides the help overlay that displays the keybindings of
+//! the active keymap when the user presses `?`.
 
 use ratatui::{
     buffer::Buffer,
@@ -10,44 +10,94 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
 };
+use whip_protocol::{KeyContext, Keymap, Message};
 
 /// The width of the help overlay panel.
 const HELP_WIDTH: u16 = 35;
 
-/// The height of the help overlay panel.
-const HELP_HEIGHT: u16 = 20;
+/// The width of the key column, keys beyond it are left out.
+const KEY_COLUMN_WIDTH: usize = 12;
 
-/// Renders a centered help overlay displaying all keybindings.
+/// The keys shown for a help entry.
+enum HelpKeys {
+    /// The board keys bound to a message.
+    Bound(Message),
+    /// Keys not in the keymap, such as those of search mode.
+    Fixed(&'static str),
+}
+
+/// The entries of the help overlay, by section.
+const SECTIONS: [(&str, &[(HelpKeys, &str)]); 3] = [
+    (
+        "Navigation",
+        &[
+            (HelpKeys::Bound(Message::NavigateLeft), "Move left"),
+            (HelpKeys::Bound(Message::NavigateRight), "Move right"),
+            (HelpKeys::Bound(Message::NavigateUp), "Select previous"),
+            (HelpKeys::Bound(Message::NavigateDown), "Select next"),
+            (HelpKeys::Bound(Message::NavigateFirst), "Select first"),
+            (HelpKeys::Bound(Message::NavigateLast), "Select last"),
+        ],
+    ),
+    (
+        "Actions",
+        &[
+            (HelpKeys::Bound(Message::Select), "Open details"),
+            (HelpKeys::Bound(Message::Escape), "Close panel"),
+            (HelpKeys::Bound(Message::OpenSettings), "Open settings"),
+            (HelpKeys::Bound(Message::Refresh), "Refresh"),
+            (HelpKeys::Bound(Message::CycleSort), "Sort lane"),
+            (HelpKeys::Bound(Message::ToggleErrors), "Show errors"),
+            (
+                HelpKeys::Bound(Message::ToggleNotifications),
+                "Notifications",
+            ),
+            (HelpKeys::Bound(Message::Quit), "Quit"),
+            (HelpKeys::Bound(Message::ToggleHelp), "Toggle help"),
+        ],
+    ),
+    (
+        "Search",
+        &[
+            (HelpKeys::Bound(Message::StartSearch), "Search tasks"),
+            (HelpKeys::Fixed("n / N"), "Next/prev match"),
+            (HelpKeys::Bound(Message::EditFilter), "Filter board"),
+        ],
+    ),
+];
+
+/// Renders a centered help overlay displaying the keybindings of a keymap.
 ///
 /// The overlay is rendered on top of the existing content, with a semi-transparent
-/// background effect achieved by clearing the area first.
+/// background effect achieved by clearing the area first. Actions without
+/// a key are left out.
 ///
 /// # Arguments
 ///
+/// * `keymap` - The active key bindings
 /// * `area` - The full terminal area (the overlay will be centered within it)
 /// * `buf` - The buffer to render into
 ///
 /// # Layout
 ///
 /// ```text
-/// +-- Help ------------------------+
-/// |                                |
-/// |  Navigation                    |
-/// |  ←          Move left          |
-/// |  →          Move right         |
-/// |  ↑          Select previous    |
-/// |  ↓          Select next        |
-/// |                                |
-/// |  Actions                       |
-/// |  Enter      Open details       |
-/// |  Esc        Close panel        |
-/// |  Shift+S    Open settings      |
-/// |  Ctrl+R     Refresh            |
-/// |  Ctrl+C     Quit               |
-/// |  ?          Toggle help        |
-/// |                                |
-/// |  Press any key to close        |
-/// +---------------------------------+
+/// ╭ Help ───────────────────────────╮
+/// │  Navigation                     │
+/// │  ←            Move left         │
+/// │  →            Move right        │
+/// │  ↑            Select previous   │
+/// │  ↓            Select next       │
+/// │  Home         Select first      │
+/// │  End          Select last       │
+/// │  Actions                        │
+/// │  Enter Space  Open details      │
+/// │  Esc          Close panel       │
+/// │  ...                            │
+/// │  Search                         │
+/// │  /            Search tasks      │
+/// │  n / N        Next/prev match   │
+/// │  f            Filter board      │
+/// ╰ Press any key to close ─────────╯
 /// ```
 ///
 /// # Examples
@@ -55,23 +105,25 @@ const HELP_HEIGHT: u16 = 20;
 /// ```
 /// use ratatui::buffer::Buffer;
 /// use ratatui::layout::Rect;
+/// use whip_protocol::Keymap;
 /// use whip_tui::widgets::render_help_overlay;
 ///
 /// let area = Rect::new(0, 0, 80, 24);
 /// let mut buf = Buffer::empty(area);
 ///
-/// render_help_overlay(area, &mut buf);
+/// render_help_overlay(&Keymap::default(), area, &mut buf);
 /// ```
-pub fn render_help_overlay(area: Rect, buf: &mut Buffer) {
-    // Calculate centered position
-    let popup_area = centered_rect(HELP_WIDTH, HELP_HEIGHT, area);
+pub fn render_help_overlay(keymap: &Keymap, area: Rect, buf: &mut Buffer) {
+    // Build the help content
+    let lines: Vec<_> = build_help_lines(keymap)
+        .into_iter()
+        .map(|(line, _)| line)
+        .collect();
+    let popup_area = overlay_area(lines.len(), area);
 
     // Clear the area behind the popup for a clean look
     Clear.render(popup_area, buf);
 
-    // Build the help content
-    let lines = build_help_lines();
-
     let help_block = Block::default()
         .title(Span::styled(
             " Help ",
@@ -79,6 +131,12 @@ pub fn render_help_overlay(area: Rect, buf: &mut Buffer) {
                 .fg(Color::LightYellow)
                 .add_modifier(Modifier::BOLD),
         ))
+        .title_bottom(Span::styled(
+            " Press any key to close ",
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        ))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(Color::LightYellow));
@@ -90,72 +148,111 @@ pub fn render_help_overlay(area: Rect, buf: &mut Buffer) {
     help_text.render(popup_area, buf);
 }
 
-/// Builds the lines of help content.
-fn build_help_lines() -> Vec<Line<'static>> {
+/// Returns the area of the help overlay rendered in `area`.
+///
+/// Rendering and mouse handling both use this, so clicks land on the entry
+/// drawn under the cursor.
+pub(crate) fn help_area(keymap: &Keymap, area: Rect) -> Rect {
+    overlay_area(build_help_lines(keymap).len(), area)
+}
+
+/// Returns the action of the help entry drawn at a position.
+///
+/// Returns `None` outside the entries, on section headers, and on entries
+/// whose keys are not in the keymap, such as those of search mode.
+pub(crate) fn help_message_at(
+    keymap: &Keymap,
+    area: Rect,
+    column: u16,
+    row: u16,
+) -> Option<Message> {
+    let lines = build_help_lines(keymap);
+    let inner = Block::default()
+        .borders(Borders::ALL)
+        .inner(overlay_area(lines.len(), area));
+    if !inner.contains((column, row).into()) {
+        return None;
+    }
+    lines
+        .into_iter()
+        .nth(usize::from(row - inner.y))
+        .and_then(|(_, message)| message)
+}
+
+/// Centers the overlay for `line_count` lines of content, leaving room for
+/// the border.
+fn overlay_area(line_count: usize, area: Rect) -> Rect {
+    let height = u16::try_from(line_count + 2).unwrap_or(u16::MAX);
+    centered_rect(HELP_WIDTH, height, area)
+}
+
+/// Builds the lines of help content, each with the action of its entry.
+fn build_help_lines(keymap: &Keymap) -> Vec<(Line<'static>, Option<Message>)> {
     let header_style = Style::default()
         .fg(Color::Yellow)
         .add_modifier(Modifier::BOLD);
     let key_style = Style::default().fg(Color::Green);
     let text_style = Style::default().fg(Color::White);
-    let hint_style = Style::default()
-        .fg(Color::DarkGray)
-        .add_modifier(Modifier::ITALIC);
-
-    vec![
-        Line::from(""),
-        Line::from(Span::styled("  Navigation", header_style)),
-        Line::from(vec![
-            Span::styled("  ←          ", key_style),
-            Span::styled("Move left", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  →          ", key_style),
-            Span::styled("Move right", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↑          ", key_style),
-            Span::styled("Select previous", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↓          ", key_style),
-            Span::styled("Select next", text_style),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled("  Actions", header_style)),
-        Line::from(vec![
-            Span::styled("  Enter      ", key_style),
-            Span::styled("Open details", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  Esc        ", key_style),
-            Span::styled("Close panel", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  Shift+S    ", key_style),
-            Span::styled("Open settings", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+R     ", key_style),
-            Span::styled("Refresh", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  Ctrl+C     ", key_style),
-            Span::styled("Quit", text_style),
-        ]),
-        Line::from(vec![
-            Span::styled("  ?          ", key_style),
-            Span::styled("Toggle help", text_style),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled("  Press any key to close", hint_style)),
-    ]
+
+    let mut lines = Vec::new();
+    for (title, entries) in SECTIONS {
+        let entries: Vec<_> = entries
+            .iter()
+            .filter_map(|(keys, description)| match keys {
+                HelpKeys::Bound(message) => Some((
+                    key_labels(keymap, message)?,
+                    *description,
+                    Some(message.clone()),
+                )),
+                HelpKeys::Fixed(keys) => Some(((*keys).to_string(), *description, None)),
+            })
+            .collect();
+        // Sections are set apart by their headers alone, so the overlay
+        // fits a 24-row terminal
+        if entries.is_empty() {
+            continue;
+        }
+        lines.push((
+            Line::from(Span::styled(format!("  {title}"), header_style)),
+            None,
+        ));
+        for (keys, description, message) in entries {
+            let line = Line::from(vec![
+                Span::styled(format!("  {keys:<KEY_COLUMN_WIDTH$} "), key_style),
+                Span::styled(description, text_style),
+            ]);
+            lines.push((line, message));
+        }
+    }
+    lines
+}
+
+/// Joins the labels of the board keys bound to a message, as many as fit
+/// the key column (and at least one).
+///
+/// Returns `None` if no key is bound to the message.
+fn key_labels(keymap: &Keymap, message: &Message) -> Option<String> {
+    let mut labels = String::new();
+    for chord in keymap.keys(KeyContext::Board, message) {
+        let label = chord.label();
+        let width =
+            labels.chars().count() + usize::from(!labels.is_empty()) + label.chars().count();
+        if !labels.is_empty() && width > KEY_COLUMN_WIDTH {
+            break;
+        }
+        if !labels.is_empty() {
+            labels.push(' ');
+        }
+        labels.push_str(&label);
+    }
+    (!labels.is_empty()).then_some(labels)
 }
 
 /// Creates a centered rectangle within a given area.
 ///
 /// If the requested dimensions exceed the available area, the rectangle
 /// will be clamped to fit.
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+pub(crate) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     // Clamp dimensions to available area
     let popup_width = width.min(area.width);
     let popup_height = height.min(area.height);
@@ -201,7 +298,7 @@ mod tests {
         let area = Rect::new(0, 0, 80, 24);
         let mut buf = Buffer::empty(area);
 
-        render_help_overlay(area, &mut buf);
+        render_help_overlay(&Keymap::default(), area, &mut buf);
 
         // Verify the help title is rendered
         let content = buffer_to_string(&buf);
@@ -216,16 +313,16 @@ mod tests {
         let mut buf = Buffer::empty(area);
 
         // Should not panic with small area
-        render_help_overlay(area, &mut buf);
+        render_help_overlay(&Keymap::default(), area, &mut buf);
     }
 
     #[test]
     fn build_help_lines_contains_all_keybindings() {
-        let lines = build_help_lines();
+        let lines = build_help_lines(&Keymap::default());
 
         let content: String = lines
             .iter()
-            .map(|l| {
+            .map(|(l, _)| {
                 l.spans
                     .iter()
                     .map(|s| s.content.as_ref())
@@ -245,5 +342,75 @@ mod tests {
         assert!(content.contains("Esc"));
         assert!(content.contains("Quit"));
         assert!(content.contains("?"));
+
+        // Check search keys
+        assert!(content.contains("Search tasks"));
+        assert!(content.contains("n / N"));
+        assert!(content.contains("Filter board"));
+        assert!(content.contains("Sort lane"));
+    }
+
+    fn help_text(keymap: &Keymap) -> String {
+        build_help_lines(keymap)
+            .iter()
+            .map(|(line, _)| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn help_lists_keys_of_the_keymap() {
+        let vim = Keymap::preset(whip_protocol::KeymapPreset::Vim);
+        let content = help_text(&vim);
+        assert!(content.contains("  ← h          Move left"));
+        assert!(content.contains("  End Shift+G  Select last"));
+        assert!(content.contains("  Ctrl+C q     Quit"));
+        assert!(content.contains("  Enter Space  Open details"));
+    }
+
+    #[test]
+    fn help_leaves_out_unbound_actions() {
+        let mut keymap = Keymap::default();
+        let s = "s".parse().expect("chord");
+        keymap.bind(KeyContext::Board, s, None);
+        let content = help_text(&keymap);
+        assert!(!content.contains("Sort lane"));
+        assert!(content.contains("Show errors"));
+    }
+
+    #[test]
+    fn help_message_at_finds_entry_actions() {
+        let keymap = Keymap::default();
+        let area = Rect::new(0, 0, 80, 24);
+        let popup = help_area(&keymap, area);
+        let mut buf = Buffer::empty(area);
+        render_help_overlay(&keymap, area, &mut buf);
+        let rows: Vec<String> = buffer_to_string(&buf).lines().map(String::from).collect();
+        let row_of = |text: &str| {
+            let row = rows.iter().position(|row| row.contains(text)).expect(text);
+            u16::try_from(row).expect("row")
+        };
+        let column = popup.x + 4;
+
+        assert_eq!(
+            help_message_at(&keymap, area, column, row_of("Open settings")),
+            Some(Message::OpenSettings)
+        );
+        assert_eq!(
+            help_message_at(&keymap, area, column, row_of("Select last")),
+            Some(Message::NavigateLast)
+        );
+        // Section headers and keys outside the keymap have no action
+        assert_eq!(
+            help_message_at(&keymap, area, column, row_of("Navigation")),
+            None
+        );
+        assert_eq!(
+            help_message_at(&keymap, area, column, row_of("Next/prev match")),
+            None
+        );
+        // The border and outside the overlay
+        assert_eq!(help_message_at(&keymap, area, column, popup.y), None);
+        assert_eq!(help_message_at(&keymap, area, 0, row_of("Quit")), None);
     }
 }
@@ -63,7 +63,7 @@ const BORDER_SET_LAST: border::Set = border::Set {
     horizontal_bottom: "─",
 };
 
-use super::task_card::render_task_card;
+use super::task_card::{Highlights, render_task_card_with_highlights};
 use crate::layout::TASK_CARD_HEIGHT;
 
 /// Renders a single lane to the buffer.
@@ -95,7 +95,7 @@ use crate::layout::TASK_CARD_HEIGHT;
 /// | | Task 2     | |
 /// | | desc...    | |
 /// | +------------+ |
-/// +----------------+
+/// +---- ↓ updated -+  <- Sort key, when the lane is sorted
 /// ```
 ///
 /// # Examples
@@ -122,6 +122,39 @@ pub fn render_lane(
     buf: &mut Buffer,
     position: LanePosition,
     prev_focused: bool,
+) {
+    render_lane_with_highlights(
+        lane,
+        is_focused,
+        selected_idx,
+        Highlights::default(),
+        area,
+        buf,
+        position,
+        prev_focused,
+    );
+}
+
+/// Renders a single lane, highlighting search matches and unseen changes.
+///
+/// Like [`render_lane`], with task cards rendered by
+/// [`render_task_card_with_highlights`].
+///
+/// # Arguments
+///
+/// * `highlights` - The search query and unseen changes
+///
+/// See [`render_lane`] for the other arguments.
+#[allow(clippy::too_many_arguments)]
+pub fn render_lane_with_highlights(
+    lane: &Lane,
+    is_focused: bool,
+    selected_idx: Option<usize>,
+    highlights: Highlights<'_>,
+    area: Rect,
+    buf: &mut Buffer,
+    position: LanePosition,
+    prev_focused: bool,
 ) {
     // Determine border style based on focus.
     // For the left border (shared with previous lane), highlight if either lane is focused.
@@ -133,7 +166,7 @@ pub fn render_lane(
     };
 
     // Create the lane header
-    let title = format!("{} ({})", lane.kind.display_name(), lane.len());
+    let title = format!("{} ({})", lane.title(), lane.len());
     let title_style = if is_focused {
         Style::default()
             .fg(Color::Cyan)
@@ -160,12 +193,18 @@ pub fn render_lane(
         LanePosition::Last => BORDER_SET_LAST,
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .title(Span::styled(title, title_style))
         .borders(borders)
         .border_set(border_set)
         .border_style(border_style);
 
+    // A sorted lane names its sort key in the bottom border
+    if let Some(sort) = lane.definition.sort {
+        let label = format!(" ↓ {} ", sort.display_name());
+        block = block.title_bottom(Line::from(Span::styled(label, border_style)).right_aligned());
+    }
+
     // Render the outer block
     let inner_area = block.inner(area);
     block.render(area, buf);
@@ -189,7 +228,7 @@ pub fn render_lane(
     }
 
     // Calculate how many tasks can fit in the visible area
-    let visible_tasks = (inner_area.height / TASK_CARD_HEIGHT).max(1) as usize;
+    let visible_tasks = visible_task_count(inner_area.height);
 
     // Determine scroll offset to keep selected task visible
     let scroll_offset = calculate_scroll_offset(selected_idx, lane.len(), visible_tasks);
@@ -216,7 +255,7 @@ pub fn render_lane(
         let task = &lane.tasks[task_idx];
         let is_selected = is_focused && selected_idx == Some(task_idx);
 
-        render_task_card(task, is_selected, *task_area, buf);
+        render_task_card_with_highlights(task, is_selected, highlights, *task_area, buf);
     }
 }
 
@@ -232,6 +271,40 @@ fn render_empty_placeholder(area: Rect, buf: &mut Buffer) {
     placeholder.render(area, buf);
 }
 
+/// Returns the index of the task whose card is drawn at `row`, in a lane
+/// of `task_count` tasks rendered in `area`.
+///
+/// Rendering and mouse handling both use this, so clicks land on the card
+/// drawn under the cursor even when the lane is scrolled. Returns `None` on
+/// the lane borders and below the last card.
+pub(crate) fn task_index_at(
+    task_count: usize,
+    selected_idx: Option<usize>,
+    area: Rect,
+    row: u16,
+) -> Option<usize> {
+    // Lanes always have a top and a bottom border
+    let inner_height = area.height.saturating_sub(2);
+    let relative_y = row.checked_sub(area.y + 1)?;
+    if relative_y >= inner_height {
+        return None;
+    }
+
+    let visible_tasks = visible_task_count(inner_height);
+    let card = usize::from(relative_y / TASK_CARD_HEIGHT);
+    if card >= visible_tasks {
+        return None;
+    }
+
+    let task_idx = calculate_scroll_offset(selected_idx, task_count, visible_tasks) + card;
+    (task_idx < task_count).then_some(task_idx)
+}
+
+/// Returns how many task cards fit in a lane's inner height.
+fn visible_task_count(inner_height: u16) -> usize {
+    usize::from((inner_height / TASK_CARD_HEIGHT).max(1))
+}
+
 /// Calculates the scroll offset to keep the selected task visible.
 fn calculate_scroll_offset(
     selected_idx: Option<usize>,
@@ -305,6 +378,32 @@ mod tests {
         let content = buffer_to_string(&buf);
         assert!(content.contains("In Progress"));
         assert!(content.contains("(2)"));
+        assert!(!content.contains("↓"));
+    }
+
+    #[test]
+    fn render_sorted_lane_shows_sort_key() {
+        let mut lane = Lane::new(LaneKind::Done);
+        lane.set_sort(Some(whip_protocol::SortKey::Severity));
+        let area = Rect::new(0, 0, 25, 10);
+        let mut buf = Buffer::empty(area);
+
+        render_lane(
+            &lane,
+            false,
+            None,
+            area,
+            &mut buf,
+            LanePosition::Last,
+            false,
+        );
+
+        let bottom = buffer_to_string(&buf)
+            .lines()
+            .last()
+            .unwrap_or_default()
+            .to_string();
+        assert!(bottom.contains("↓ severity"), "bottom: {bottom}");
     }
 
     #[test]
@@ -328,4 +427,33 @@ mod tests {
         assert!(offset > 0);
         assert!(offset <= 7);
     }
+
+    #[test]
+    fn task_index_follows_scroll_offset() {
+        // Room for three cards between the borders
+        let area = Rect::new(0, 0, 30, TASK_CARD_HEIGHT * 3 + 2);
+        let first_card = 1;
+        let second_card = first_card + TASK_CARD_HEIGHT;
+
+        assert_eq!(task_index_at(10, None, area, first_card), Some(0));
+        assert_eq!(task_index_at(10, None, area, second_card), Some(1));
+        // Selecting the sixth task scrolls the lane to keep it centered
+        let offset = calculate_scroll_offset(Some(5), 10, 3);
+        assert_eq!(task_index_at(10, Some(5), area, first_card), Some(offset));
+        assert_eq!(
+            task_index_at(10, Some(5), area, second_card),
+            Some(offset + 1)
+        );
+    }
+
+    #[test]
+    fn task_index_outside_cards() {
+        let area = Rect::new(0, 2, 30, TASK_CARD_HEIGHT * 3 + 2);
+
+        // Top and bottom borders
+        assert_eq!(task_index_at(10, None, area, 2), None);
+        assert_eq!(task_index_at(10, None, area, area.bottom() - 1), None);
+        // Below the last task of a short lane
+        assert_eq!(task_index_at(1, None, area, 3 + TASK_CARD_HEIGHT), None);
+    }
 }
@@ -11,10 +11,17 @@
 //!
 //! # Modules
 //!
-//! - [`board`]: Renders the complete Kanban board with four lanes
+//! - [`board`]: Renders the complete Kanban board with its lanes
 //! - [`lane`]: Renders individual lanes with task lists
 //! - [`task_card`]: Renders task cards with color coding based on state
 //! - [`status_bar`]: Renders the footer with keybinding hints
+//! - [`search`]: Renders the search line under the board
+//! - [`filter_bar`]: Renders the filter bar under the board
+//! - [`repo_status`]: Renders the per-repository loading summary in the header
+//! - [`toast`]: Renders short-lived notification toasts
+//! - [`notifications`]: Renders the notification history overlay
+//! - [`errors`]: Renders the errors pane overlay
+//! - [`context_menu`]: Renders the context menu of task actions
 //!
 //! # Color Coding
 //!
@@ -46,25 +53,39 @@
 //! ```
 
 pub mod board;
+pub mod context_menu;
 pub mod detail;
+pub mod errors;
+pub mod filter_bar;
 pub mod help;
 pub mod lane;
 pub mod markdown;
+pub mod notifications;
+pub mod repo_status;
+pub mod search;
 pub mod settings;
 pub mod status_bar;
 pub mod task_card;
+pub mod toast;
 
 // Re-export primary rendering functions for convenience
 pub use board::render_board;
+pub use context_menu::render_context_menu;
 pub use detail::{
     calculate_metadata_height, description_area_dimensions, label_color, max_scroll_offset,
     render_detail_panel, state_indicator,
 };
+pub use errors::render_errors_pane;
+pub use filter_bar::render_filter_bar;
 pub use help::render_help_overlay;
 pub use lane::{LanePosition, render_lane};
+pub use notifications::render_notification_history;
+pub use repo_status::render_repo_status;
+pub use search::render_search_bar;
 pub use settings::render_settings_panel;
 pub use status_bar::render_status_bar;
-pub use task_card::{render_task_card, state_color};
+pub use task_card::{Highlights, render_task_card, state_color};
+pub use toast::{render_toasts, severity_color, severity_icon};
 
 #[cfg(test)]
 mod tests;
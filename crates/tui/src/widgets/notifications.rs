@@ -0,0 +1,198 @@
+//! Notification history overlay widget.
+//!
+//! This module provides the scrollable overlay listing every notification
+//! pushed during the session, opened with `n`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+use super::help::centered_rect;
+use super::toast::{severity_color, severity_icon};
+use crate::notification::Notifications;
+
+/// The width of the history overlay.
+const HISTORY_WIDTH: u16 = 72;
+
+/// The height of the history overlay.
+const HISTORY_HEIGHT: u16 = 20;
+
+/// Number of rows inside the overlay not used by history entries
+/// (borders, leading blank line, trailing blank line, and hint).
+const HISTORY_CHROME_HEIGHT: u16 = 5;
+
+/// Returns the number of history entries visible at once for `area`.
+///
+/// Used to clamp the scroll offset so the last page stays full.
+#[must_use]
+pub fn history_visible_rows(area: Rect) -> usize {
+    centered_rect(HISTORY_WIDTH, HISTORY_HEIGHT, area)
+        .height
+        .saturating_sub(HISTORY_CHROME_HEIGHT) as usize
+}
+
+/// Renders a centered, scrollable overlay listing past notifications, newest first.
+///
+/// # Arguments
+///
+/// * `notifications` - The notification queue whose history is displayed
+/// * `scroll` - Index of the first history entry to display
+/// * `area` - The full terminal area (the overlay will be centered within it)
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// ╭─ Notifications ──────────────────────────────────╮
+/// │                                                  │
+/// │  14:02:11  ✗ owner/repo: rate limit exceeded     │
+/// │  14:01:57  ✓ Settings saved                      │
+/// │                                                  │
+/// │  ↑↓ scroll  Esc close                            │
+/// ╰───────────────────────────────────── 1-2 of 2 ───╯
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_tui::notification::{Notifications, Severity};
+/// use whip_tui::widgets::render_notification_history;
+///
+/// let mut notifications = Notifications::default();
+/// notifications.push(Severity::Success, "Settings saved");
+///
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+///
+/// render_notification_history(&notifications, 0, area, &mut buf);
+/// ```
+pub fn render_notification_history(
+    notifications: &Notifications,
+    scroll: usize,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let popup_area = centered_rect(HISTORY_WIDTH, HISTORY_HEIGHT, area);
+    let visible = history_visible_rows(area);
+    let total = notifications.history_len();
+    let scroll = scroll.min(total.saturating_sub(visible));
+
+    Clear.render(popup_area, buf);
+
+    let border_style = Style::default().fg(Color::LightCyan);
+    let mut block = Block::default()
+        .title(Span::styled(
+            " Notifications ",
+            border_style.add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style);
+    if total > 0 {
+        let last = (scroll + visible).min(total);
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {}-{last} of {total} ", scroll + 1),
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    let time_style = Style::default().fg(Color::DarkGray);
+    let hint_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC);
+
+    let mut lines = vec![Line::from("")];
+    if total == 0 {
+        lines.push(Line::from(Span::styled(
+            "  No notifications",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    for notification in notifications.history().skip(scroll).take(visible) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {}  ", notification.created_at.format("%H:%M:%S")),
+                time_style,
+            ),
+            Span::styled(
+                format!(
+                    "{} {}",
+                    severity_icon(notification.severity),
+                    notification.message
+                ),
+                Style::default().fg(severity_color(notification.severity)),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  ↑↓ scroll  Esc close",
+        hint_style,
+    )));
+
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Left)
+        .render(popup_area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::Severity;
+    use crate::test_utils::buffer_to_string;
+
+    fn render(notifications: &Notifications, scroll: usize) -> String {
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        render_notification_history(notifications, scroll, area, &mut buf);
+        buffer_to_string(&buf)
+    }
+
+    #[test]
+    fn empty_history_shows_placeholder() {
+        let content = render(&Notifications::default(), 0);
+        assert!(content.contains("Notifications"));
+        assert!(content.contains("No notifications"));
+    }
+
+    #[test]
+    fn lists_notifications_newest_first() {
+        let mut notifications = Notifications::default();
+        notifications.push(Severity::Success, "Settings saved");
+        notifications.push(Severity::Error, "owner/repo: boom");
+
+        let content = render(&notifications, 0);
+        let error_pos = content.find("✗ owner/repo: boom").expect("error shown");
+        let saved_pos = content.find("✓ Settings saved").expect("success shown");
+        assert!(error_pos < saved_pos);
+        assert!(content.contains("1-2 of 2"));
+    }
+
+    #[test]
+    fn scroll_skips_newer_entries_and_is_clamped() {
+        let mut notifications = Notifications::default();
+        for i in 0..30 {
+            notifications.push(Severity::Info, format!("message {i:02}"));
+        }
+        let visible = history_visible_rows(Rect::new(0, 0, 80, 24));
+
+        let content = render(&notifications, 5);
+        assert!(!content.contains("message 29"));
+        assert!(content.contains("message 24"));
+
+        // Scrolling past the end keeps the last page full
+        let content = render(&notifications, 1000);
+        assert!(content.contains("message 00"));
+        assert!(content.contains(&format!("{}-30 of 30", 30 - visible + 1)));
+    }
+}
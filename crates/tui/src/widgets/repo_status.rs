@@ -0,0 +1,148 @@
+//! Repository status indicator widget.
+//!
+//! This module renders a single-line summary of per-repository loading
+//! status, shown in the application header.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::repo_status::{RepoStatus, RepoStatuses};
+
+/// Renders the repository status summary, right-aligned in `area`.
+///
+/// Repositories with a fetch in flight or a failed fetch are listed
+/// individually. When every repository is up to date, a single count is
+/// shown instead. Nothing is rendered when no repositories are tracked.
+///
+/// # Arguments
+///
+/// * `statuses` - The per-repository statuses
+/// * `area` - The rectangular area to render into
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// ✗ org/api (rate limit)  ⟳ org/web (stale)  ⟳ org/cli
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_tui::{RepoStatus, RepoStatuses};
+/// use whip_tui::widgets::render_repo_status;
+///
+/// let mut statuses = RepoStatuses::default();
+/// statuses.set("owner/repo", RepoStatus::Loading);
+///
+/// let area = Rect::new(0, 0, 40, 1);
+/// let mut buf = Buffer::empty(area);
+/// render_repo_status(&statuses, area, &mut buf);
+/// ```
+pub fn render_repo_status(statuses: &RepoStatuses, area: Rect, buf: &mut Buffer) {
+    if statuses.is_empty() {
+        return;
+    }
+
+    let mut spans = Vec::new();
+    let mut push = |span: Span<'static>| {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(span);
+    };
+
+    // Failures first, as they are the most important to notice
+    for (repo, status) in statuses.iter() {
+        if let RepoStatus::Failed { kind } = status {
+            push(Span::styled(
+                format!("✗ {repo} ({kind})"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+    }
+
+    for (repo, status) in statuses.iter() {
+        match status {
+            RepoStatus::Loading => push(Span::styled(
+                format!("⟳ {repo}"),
+                Style::default().fg(Color::Yellow),
+            )),
+            RepoStatus::Stale => push(Span::styled(
+                format!("⟳ {repo} (stale)"),
+                Style::default().fg(Color::DarkGray),
+            )),
+            RepoStatus::Fresh | RepoStatus::Failed { .. } => {}
+        }
+    }
+
+    if spans.is_empty() {
+        let count = statuses.len();
+        let noun = if count == 1 { "repo" } else { "repos" };
+        spans.push(Span::styled(
+            format!("✓ {count} {noun}"),
+            Style::default().fg(Color::Green),
+        ));
+    }
+
+    Paragraph::new(Line::from(spans))
+        .alignment(Alignment::Right)
+        .render(area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::buffer_to_string;
+    use whip_protocol::FetchErrorKind;
+
+    fn render(statuses: &RepoStatuses) -> String {
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        render_repo_status(statuses, area, &mut buf);
+        buffer_to_string(&buf)
+    }
+
+    #[test]
+    fn renders_nothing_without_repositories() {
+        let content = render(&RepoStatuses::default());
+        assert!(content.trim().is_empty());
+    }
+
+    #[test]
+    fn renders_count_when_all_fresh() {
+        let mut statuses = RepoStatuses::default();
+        statuses.set("a/one", RepoStatus::Fresh);
+        statuses.set("b/two", RepoStatus::Fresh);
+
+        assert!(render(&statuses).contains("✓ 2 repos"));
+    }
+
+    #[test]
+    fn lists_pending_and_failed_repositories() {
+        let mut statuses = RepoStatuses::default();
+        statuses.set("a/fresh", RepoStatus::Fresh);
+        statuses.set("b/stale", RepoStatus::Stale);
+        statuses.set("c/loading", RepoStatus::Loading);
+        statuses.set(
+            "d/failed",
+            RepoStatus::Failed {
+                kind: FetchErrorKind::RateLimit,
+            },
+        );
+
+        let content = render(&statuses);
+        assert!(!content.contains("a/fresh"));
+        assert!(content.contains("b/stale (stale)"));
+        assert!(content.contains("c/loading"));
+        assert!(content.contains("✗ d/failed (rate limit)"));
+        assert!(content.find("d/failed") < content.find("b/stale"));
+    }
+}
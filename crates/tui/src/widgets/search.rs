@@ -0,0 +1,151 @@
+//! Search line rendering widget.
+//!
+//! This module provides the one-line input shown under the board while a
+//! search is active.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::search::SearchState;
+
+/// Renders the search line.
+///
+/// The query is shown after a `/` prompt, with a cursor while it is being
+/// typed. The right side counts the results, and tells which one is
+/// selected.
+///
+/// # Arguments
+///
+/// * `search` - The search state
+/// * `current` - Index of the selected result among `total`, if a result is selected
+/// * `total` - Number of tasks matching the query
+/// * `area` - The rectangular area to render into
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// /parser▏                               2/5  n/N next/prev
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_tui::search::SearchState;
+/// use whip_tui::widgets::render_search_bar;
+///
+/// let mut search = SearchState::default();
+/// search.start();
+/// search.push('a');
+///
+/// let area = Rect::new(0, 0, 60, 1);
+/// let mut buf = Buffer::empty(area);
+///
+/// render_search_bar(&search, Some(0), 3, area, &mut buf);
+/// ```
+pub fn render_search_bar(
+    search: &SearchState,
+    current: Option<usize>,
+    total: usize,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let key_style = Style::default().fg(Color::Yellow);
+    let text_style = Style::default().fg(Color::White);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    let mut query = vec![
+        Span::styled("/", key_style.add_modifier(Modifier::BOLD)),
+        Span::styled(search.query(), text_style),
+    ];
+    if search.is_editing() {
+        query.push(Span::styled("▏", key_style));
+    }
+
+    let mut status = Vec::new();
+    if search.highlight().is_some() {
+        if total == 0 {
+            status.push(Span::styled("No matches", Style::default().fg(Color::Red)));
+        } else {
+            let position = current.map_or_else(|| "-".to_string(), |idx| (idx + 1).to_string());
+            status.push(Span::styled(format!("{position}/{total}"), text_style));
+        }
+    }
+    let hint = if search.is_editing() {
+        "  Enter done  Esc cancel"
+    } else {
+        "  n/N next/prev  Esc clear"
+    };
+    status.push(Span::styled(hint, hint_style));
+
+    let status = Line::from(status);
+    let status_width = u16::try_from(status.width()).unwrap_or(u16::MAX);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(status_width)])
+        .split(area);
+
+    Paragraph::new(Line::from(query)).render(chunks[0], buf);
+    Paragraph::new(status).render(chunks[1], buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::buffer_to_string;
+
+    fn editing(query: &str) -> SearchState {
+        let mut search = SearchState::default();
+        search.start();
+        query.chars().for_each(|ch| search.push(ch));
+        search
+    }
+
+    #[test]
+    fn shows_query_and_position() {
+        let search = editing("parser");
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_search_bar(&search, Some(1), 5, area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.starts_with("/parser▏"));
+        assert!(content.contains("2/5"));
+        assert!(content.contains("Enter done"));
+    }
+
+    #[test]
+    fn shows_missing_matches() {
+        let mut search = editing("yaml");
+        search.confirm();
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_search_bar(&search, None, 0, area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.starts_with("/yaml "));
+        assert!(content.contains("No matches"));
+        assert!(content.contains("n/N next/prev"));
+    }
+
+    #[test]
+    fn shows_unselected_result() {
+        let mut search = editing("parser");
+        search.confirm();
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+
+        render_search_bar(&search, None, 3, area, &mut buf);
+
+        assert!(buffer_to_string(&buf).contains("-/3"));
+    }
+}
@@ -3,6 +3,8 @@
 //! This module provides the settings panel overlay that allows users to
 //! view and modify application configuration.
 
+use std::rc::Rc;
+
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -11,6 +13,7 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph, Widget},
 };
 
+use crate::credentials::CredentialCheck;
 use crate::settings_state::{EditMode, RepoEditField, SettingsSection, SettingsState};
 
 /// Formats a text field value with a cursor indicator at the specified position.
@@ -87,11 +90,29 @@ pub fn render_settings_panel(state: &SettingsState, area: Rect, buf: &mut Buffer
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(Color::Cyan));
 
-    let inner = block.inner(popup_area);
     block.render(popup_area, buf);
 
-    // Split inner area into sections
-    let chunks = Layout::default()
+    let chunks = panel_chunks(area);
+
+    // Render section tabs
+    render_section_tabs(state, chunks[0], buf);
+
+    // Render section content
+    render_section_content(state, chunks[2], buf);
+
+    // Render help/status bar
+    render_settings_help(state, chunks[4], buf);
+}
+
+/// Splits the inside of the settings panel into its rows.
+///
+/// Rendering and mouse handling both use this, so clicks land on the part
+/// of the panel drawn under the cursor.
+fn panel_chunks(area: Rect) -> Rc<[Rect]> {
+    let popup_area = centered_rect(SETTINGS_WIDTH, SETTINGS_HEIGHT, area);
+    let inner = Block::default().borders(Borders::ALL).inner(popup_area);
+
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1), // Section tabs
@@ -100,16 +121,104 @@ pub fn render_settings_panel(state: &SettingsState, area: Rect, buf: &mut Buffer
             Constraint::Length(1), // Separator
             Constraint::Length(2), // Help/status bar
         ])
-        .split(inner);
+        .split(inner)
+}
 
-    // Render section tabs
-    render_section_tabs(state, chunks[0], buf);
+/// A part of the settings panel that responds to clicks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SettingsTarget {
+    /// The tab of a section.
+    Section(SettingsSection),
+    /// An item of the current section, by index.
+    Item(usize),
+}
 
-    // Render section content
-    render_section_content(state, chunks[2], buf);
+/// Returns the part of the settings panel drawn at a position.
+///
+/// Returns `None` outside the tabs and items, and on the items while one is
+/// being edited.
+///
+/// # Arguments
+///
+/// * `state` - The settings state
+/// * `area` - The full terminal area, as passed to [`render_settings_panel`]
+/// * `column` - The column of the position
+/// * `row` - The row of the position
+pub(crate) fn settings_target_at(
+    state: &SettingsState,
+    area: Rect,
+    column: u16,
+    row: u16,
+) -> Option<SettingsTarget> {
+    let chunks = panel_chunks(area);
+    let position = (column, row).into();
+
+    if chunks[0].contains(position) {
+        return section_tab_at(chunks[0], column).map(SettingsTarget::Section);
+    }
+    if state.is_editing() || !chunks[2].contains(position) {
+        return None;
+    }
 
-    // Render help/status bar
-    render_settings_help(state, chunks[4], buf);
+    let offset = usize::from(row - chunks[2].y);
+    let item = match state.section() {
+        SettingsSection::Repositories => offset,
+        // Each polling setting takes two rows
+        SettingsSection::Polling => offset / 2,
+        SettingsSection::Authentication => auth_item_at(state, chunks[2], offset)?,
+    };
+    (item < state.item_count()).then_some(SettingsTarget::Item(item))
+}
+
+/// Returns the section whose centered tab is drawn at a column.
+fn section_tab_at(area: Rect, column: u16) -> Option<SettingsSection> {
+    let sections = SettingsSection::all();
+    let separators = " | ".len() * sections.len().saturating_sub(1);
+    let width = sections
+        .iter()
+        .map(|section| section.name().chars().count())
+        .sum::<usize>()
+        + separators;
+
+    let mut x = usize::from(area.x) + usize::from(area.width).saturating_sub(width) / 2;
+    let column = usize::from(column);
+    for section in sections {
+        let end = x + section.name().chars().count();
+        if (x..end).contains(&column) {
+            return Some(*section);
+        }
+        x = end + " | ".len();
+    }
+    None
+}
+
+/// Returns the authentication item drawn `offset` rows into the content.
+///
+/// Mirrors the rows of [`render_authentication_section`]: the token, two
+/// notes, a blank row, a heading, then one row per repository followed by
+/// its credential test, if any.
+fn auth_item_at(state: &SettingsState, area: Rect, offset: usize) -> Option<usize> {
+    if offset == 0 {
+        return Some(0);
+    }
+    if area.height <= 5 {
+        return None;
+    }
+
+    let mut line = 6;
+    for (index, repo) in state.config().repositories.iter().enumerate() {
+        if offset == line {
+            return Some(index + 1);
+        }
+        line += 1;
+        if state
+            .credential_check(&state.config().repo_key(repo))
+            .is_some()
+        {
+            line += 1;
+        }
+    }
+    None
 }
 
 /// Renders the section tabs at the top of the settings panel.
@@ -199,7 +308,7 @@ fn render_repositories_section(state: &SettingsState, area: Rect, buf: &mut Buff
                 // Show confirmation prompt
                 ListItem::new(Line::from(vec![
                     Span::styled(prefix, style),
-                    Span::styled(repo.full_name(), style),
+                    Span::styled(config.repo_key(repo), style),
                     Span::styled(" Delete? (y/n)", Style::default().fg(Color::Red)),
                 ]))
             } else {
@@ -211,7 +320,7 @@ fn render_repositories_section(state: &SettingsState, area: Rect, buf: &mut Buff
 
                 ListItem::new(Line::from(vec![
                     Span::styled(prefix, style),
-                    Span::styled(repo.full_name(), style),
+                    Span::styled(config.repo_key(repo), style),
                     Span::styled(token_indicator, Style::default().fg(Color::DarkGray)),
                 ]))
             }
@@ -455,18 +564,141 @@ fn render_authentication_section(state: &SettingsState, area: Rect, buf: &mut Bu
             height: area.height.saturating_sub(2),
             ..area
         };
-        let note = Paragraph::new(Line::from(vec![Span::styled(
-            "  (Falls back to `gh auth token` if not set)",
-            Style::default().fg(Color::DarkGray),
-        )]));
+        let note_style = Style::default().fg(Color::DarkGray);
+        let note = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "  (Falls back to `gh auth token` if not set)",
+                note_style,
+            )),
+            // Tokens are split out of the config file when saving
+            Line::from(Span::styled(
+                "  (Tokens are saved to credentials.json, readable only by you)",
+                note_style,
+            )),
+        ]);
         note.render(note_area, buf);
     }
+
+    // List the credential source each repository ended up using
+    if area.height > 5 && !config.repositories.is_empty() {
+        let sources_area = Rect {
+            y: area.y + 5,
+            height: area.height - 5,
+            ..area
+        };
+        let name_width = config
+            .repositories
+            .iter()
+            .map(|repo| config.repo_key(repo).chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = vec![Line::from(Span::styled(
+            "  Credentials in use:",
+            Style::default().fg(Color::Gray),
+        ))];
+        for (index, repo) in config.repositories.iter().enumerate() {
+            let name = config.repo_key(repo);
+            let is_selected = selected == index + 1;
+            let (prefix, name_style) = if is_selected {
+                (
+                    "  > ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("    ", Style::default().fg(Color::White))
+            };
+            let (source, style) = match state.token_source(&name) {
+                Some(Some(source)) => (source.to_string(), Style::default().fg(Color::Green)),
+                Some(None) => (
+                    "none (unauthenticated)".to_string(),
+                    Style::default().fg(Color::Yellow),
+                ),
+                None => (
+                    "not resolved yet".to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{prefix}{name:<name_width$}  "), name_style),
+                Span::styled(source, style),
+            ]));
+            if let Some(check) = state.credential_check(&name) {
+                lines.push(credential_check_line(check));
+            }
+        }
+        Paragraph::new(lines).render(sources_area, buf);
+    }
+}
+
+/// Builds the line describing the outcome of a credential test.
+fn credential_check_line(check: &CredentialCheck) -> Line<'static> {
+    let indent = "      ";
+    let (text, color) = match check {
+        CredentialCheck::Pending => ("… testing credentials".to_string(), Color::DarkGray),
+        CredentialCheck::Unauthenticated => ("no token to test".to_string(), Color::Yellow),
+        CredentialCheck::Offline => ("not tested while offline".to_string(), Color::Yellow),
+        CredentialCheck::Failed { error, .. } => (format!("✗ {error}"), Color::Red),
+        CredentialCheck::Valid {
+            login,
+            scopes,
+            missing_scopes,
+            ..
+        } => {
+            let scopes = match scopes {
+                Some(scopes) if scopes.is_empty() => "no scopes".to_string(),
+                Some(scopes) => format!("scopes: {}", scopes.join(", ")),
+                None => "scopes not reported".to_string(),
+            };
+            if missing_scopes.is_empty() {
+                (format!("✓ {login} · {scopes}"), Color::Green)
+            } else {
+                (
+                    format!(
+                        "⚠ {login} · missing scopes: {} (label sync, lane moves)",
+                        missing_scopes.join(", ")
+                    ),
+                    Color::Yellow,
+                )
+            }
+        }
+        CredentialCheck::ValidInstallation {
+            permissions,
+            missing_permissions,
+            ..
+        } => {
+            let permissions = match permissions {
+                Some(permissions) if permissions.is_empty() => "no permissions".to_string(),
+                Some(permissions) => format!("permissions: {}", permissions.join(", ")),
+                None => "permissions not reported".to_string(),
+            };
+            if missing_permissions.is_empty() {
+                (format!("✓ installation · {permissions}"), Color::Green)
+            } else {
+                (
+                    format!(
+                        "⚠ installation · missing: {} (label sync, lane moves)",
+                        missing_permissions.join(", ")
+                    ),
+                    Color::Yellow,
+                )
+            }
+        }
+    };
+    Line::from(Span::styled(
+        format!("{indent}{text}"),
+        Style::default().fg(color),
+    ))
 }
 
 /// Renders the help/status bar at the bottom of the settings panel.
 fn render_settings_help(state: &SettingsState, area: Rect, buf: &mut Buffer) {
     let help_text = if state.is_editing() {
         "←→: move cursor | Enter: confirm | Esc: cancel".to_string()
+    } else if state.selected_auth_repository().is_some() {
+        "←→: sections | ↑↓: navigate | t: test credentials | Esc: close".to_string()
     } else if state.can_delete_selected() {
         "←→: sections | ↑↓: navigate | Enter: edit | d: delete | Esc: close".to_string()
     } else {
@@ -475,15 +707,37 @@ fn render_settings_help(state: &SettingsState, area: Rect, buf: &mut Buffer) {
         "←→: sections | ↑↓: navigate | Enter: edit |           | Esc: close".to_string()
     };
 
-    let help = Paragraph::new(Line::from(Span::styled(
-        help_text,
-        Style::default().fg(Color::DarkGray),
-    )))
+    let help = Paragraph::new(vec![
+        Line::from(Span::styled(
+            help_text,
+            Style::default().fg(Color::DarkGray),
+        )),
+        save_target_line(state),
+    ])
     .alignment(Alignment::Center);
 
     help.render(area, buf);
 }
 
+/// Describes where settings are saved, and how to switch to the user config.
+fn save_target_line(state: &SettingsState) -> Line<'static> {
+    let target = state
+        .save_target()
+        .map_or_else(|| "user config".to_string(), ToString::to_string);
+    let hint = if state.is_saving_to_user_config() {
+        " (u: undo)"
+    } else if state.can_save_to_user_config() {
+        " (u: save to user config)"
+    } else {
+        ""
+    };
+    Line::from(vec![
+        Span::styled("Saves to: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(target, Style::default().fg(Color::Gray)),
+        Span::styled(hint, Style::default().fg(Color::DarkGray)),
+    ])
+}
+
 /// Creates a centered rectangle within a given area.
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let popup_width = width.min(area.width);
@@ -498,6 +752,9 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+
+    use whip_config::auth::TokenSource;
     use whip_config::{Config, Repository};
 
     /// Extracts all characters from a buffer as a single string for content assertions.
@@ -750,6 +1007,53 @@ mod tests {
         assert!(content.contains("gh auth token"));
     }
 
+    #[test]
+    fn render_authentication_section_shows_credentials_storage_note() {
+        let mut state = SettingsState::new(Config::default());
+        state.prev_section(); // Authentication
+
+        let area = Rect::new(0, 0, 70, 10);
+        let mut buf = Buffer::empty(area);
+
+        render_authentication_section(&state, area, &mut buf);
+
+        assert!(buffer_content(&buf).contains("saved to credentials.json"));
+    }
+
+    #[test]
+    fn render_authentication_section_shows_token_sources() {
+        let config = Config {
+            repositories: vec![
+                Repository::new("owner", "env"),
+                Repository::new("owner", "anonymous"),
+                Repository::new("owner", "pending"),
+            ],
+            ..Default::default()
+        };
+        let sources = HashMap::from([
+            (
+                "owner/env".to_string(),
+                Some(TokenSource::Env("GITHUB_TOKEN".to_string())),
+            ),
+            ("owner/anonymous".to_string(), None),
+        ]);
+        let mut state = SettingsState::new(config).with_token_sources(sources);
+        state.next_section(); // Polling
+        state.next_section(); // Authentication
+
+        let area = Rect::new(0, 0, 60, 10);
+        let mut buf = Buffer::empty(area);
+
+        render_authentication_section(&state, area, &mut buf);
+
+        let content = buffer_content(&buf);
+
+        assert!(content.contains("Credentials in use"));
+        assert!(content.contains("owner/env        $GITHUB_TOKEN"));
+        assert!(content.contains("owner/anonymous  none (unauthenticated)"));
+        assert!(content.contains("owner/pending    not resolved yet"));
+    }
+
     // Tests for render_repo_edit_mode
 
     #[test]
@@ -869,4 +1173,130 @@ mod tests {
         // Token field is active, should show cursor in the token value
         assert!(content.contains("a_bc"));
     }
+
+    #[test]
+    fn render_settings_help_shows_save_target() {
+        use whip_config::ConfigLayer;
+
+        let config = Config {
+            source: Some(ConfigLayer::Local("whip.json5".into())),
+            ..Default::default()
+        };
+        let mut state = SettingsState::new(config).with_user_config_path("config.json5");
+        let area = Rect::new(0, 0, 80, 2);
+
+        let mut buf = Buffer::empty(area);
+        render_settings_help(&state, area, &mut buf);
+        let content = buffer_content(&buf);
+        assert!(content.contains("Saves to: local config (whip.json5) (u: save to user config)"));
+
+        assert!(state.toggle_save_to_user_config());
+        let mut buf = Buffer::empty(area);
+        render_settings_help(&state, area, &mut buf);
+        let content = buffer_content(&buf);
+        assert!(content.contains("Saves to: user config (config.json5) (u: undo)"));
+    }
+
+    /// Returns the position of the first cell of `text` in a buffer.
+    fn position_of(buf: &Buffer, text: &str) -> (u16, u16) {
+        let area = buf.area;
+        let text: Vec<char> = text.chars().collect();
+        for y in area.top()..area.bottom() {
+            let row: Vec<char> = (area.left()..area.right())
+                .map(|x| buf[(x, y)].symbol().chars().next().unwrap_or(' '))
+                .collect();
+            if let Some(x) = row.windows(text.len()).position(|cells| cells == text) {
+                return (area.x + u16::try_from(x).expect("column"), y);
+            }
+        }
+        panic!("{text:?} not rendered");
+    }
+
+    #[test]
+    fn settings_target_at_finds_tabs_and_items() {
+        let config = Config {
+            repositories: vec![
+                Repository::new("rust-lang", "rust"),
+                Repository::new("tokio-rs", "tokio"),
+            ],
+            ..Default::default()
+        };
+        let state = SettingsState::new(config);
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        render_settings_panel(&state, area, &mut buf);
+
+        let (x, y) = position_of(&buf, "Polling");
+        assert_eq!(
+            settings_target_at(&state, area, x + 3, y),
+            Some(SettingsTarget::Section(SettingsSection::Polling))
+        );
+        let (x, y) = position_of(&buf, "Authentication");
+        assert_eq!(
+            settings_target_at(&state, area, x, y),
+            Some(SettingsTarget::Section(SettingsSection::Authentication))
+        );
+        // The separator between tabs
+        assert_eq!(settings_target_at(&state, area, x - 2, y), None);
+
+        let (x, y) = position_of(&buf, "tokio-rs/tokio");
+        assert_eq!(
+            settings_target_at(&state, area, x, y),
+            Some(SettingsTarget::Item(1))
+        );
+        let (x, y) = position_of(&buf, "+ Add repository");
+        assert_eq!(
+            settings_target_at(&state, area, x, y),
+            Some(SettingsTarget::Item(2))
+        );
+        assert_eq!(settings_target_at(&state, area, x, y + 1), None);
+        // Outside the panel
+        assert_eq!(settings_target_at(&state, area, 0, 0), None);
+    }
+
+    #[test]
+    fn settings_target_at_skips_credential_test_rows() {
+        let config = Config {
+            repositories: vec![
+                Repository::new("owner", "tested"),
+                Repository::new("owner", "other"),
+            ],
+            ..Default::default()
+        };
+        let mut state = SettingsState::new(config);
+        state.prev_section(); // Authentication
+        state.set_credential_check("owner/tested", CredentialCheck::Pending);
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        render_settings_panel(&state, area, &mut buf);
+
+        let (x, y) = position_of(&buf, "GitHub Token");
+        assert_eq!(
+            settings_target_at(&state, area, x, y),
+            Some(SettingsTarget::Item(0))
+        );
+        let (x, y) = position_of(&buf, "owner/tested");
+        assert_eq!(
+            settings_target_at(&state, area, x, y),
+            Some(SettingsTarget::Item(1))
+        );
+        assert_eq!(settings_target_at(&state, area, x, y + 1), None);
+        let (x, y) = position_of(&buf, "owner/other");
+        assert_eq!(
+            settings_target_at(&state, area, x, y),
+            Some(SettingsTarget::Item(2))
+        );
+    }
+
+    #[test]
+    fn settings_target_at_ignores_items_while_editing() {
+        let mut state = SettingsState::new(Config::default());
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        render_settings_panel(&state, area, &mut buf);
+        let (x, y) = position_of(&buf, "+ Add repository");
+
+        state.start_edit();
+        assert_eq!(settings_target_at(&state, area, x, y), None);
+    }
 }
@@ -1,7 +1,7 @@
 //! Status bar rendering widget.
 //!
 //! This module provides functions for rendering the footer status bar
-//! with keybinding hints.
+//! with keybinding hints, taken from the active keymap.
 
 use ratatui::{
     buffer::Buffer,
@@ -10,14 +10,17 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
+use whip_protocol::{KeyChord, KeyContext, Keymap, Message};
 
 /// Renders the status bar with keybinding hints.
 ///
 /// The status bar displays available keyboard shortcuts to help users
-/// navigate and interact with the application.
+/// navigate and interact with the application. Hints for actions without
+/// a key are left out.
 ///
 /// # Arguments
 ///
+/// * `keymap` - The active key bindings
 /// * `area` - The rectangular area to render into
 /// * `buf` - The buffer to render into
 ///
@@ -25,7 +28,7 @@ use ratatui::{
 ///
 /// ```text
 /// +----------------------------------------------------+
-/// | [Ctrl+C] Quit  [←→↑↓] Navigate  [Enter] Select     |
+/// | Ctrl+C Quit  ←→↑↓ Navigate  Enter Select           |
 /// +----------------------------------------------------+
 /// ```
 ///
@@ -34,27 +37,32 @@ use ratatui::{
 /// ```
 /// use ratatui::buffer::Buffer;
 /// use ratatui::layout::Rect;
+/// use whip_protocol::Keymap;
 /// use whip_tui::widgets::render_status_bar;
 ///
 /// let area = Rect::new(0, 0, 80, 3);
 /// let mut buf = Buffer::empty(area);
 ///
-/// render_status_bar(area, &mut buf);
+/// render_status_bar(&Keymap::default(), area, &mut buf);
 /// ```
-pub fn render_status_bar(area: Rect, buf: &mut Buffer) {
-    let key_style = Style::default().fg(Color::Yellow);
-    let text_style = Style::default().fg(Color::White);
-
-    let hints = Line::from(vec![
-        Span::styled("Ctrl+C", key_style),
-        Span::styled(" Quit  ", text_style),
-        Span::styled("←→↑↓", key_style),
-        Span::styled(" Navigate  ", text_style),
-        Span::styled("Enter", key_style),
-        Span::styled(" Select", text_style),
-    ]);
-
-    let status_bar = Paragraph::new(hints).block(Block::default().borders(Borders::ALL));
+pub fn render_status_bar(keymap: &Keymap, area: Rect, buf: &mut Buffer) {
+    let navigate: String = [
+        Message::NavigateLeft,
+        Message::NavigateRight,
+        Message::NavigateUp,
+        Message::NavigateDown,
+    ]
+    .iter()
+    .filter_map(|message| first_key(keymap, message))
+    .collect();
+    let hints = [
+        (first_key(keymap, &Message::Quit), "Quit"),
+        ((!navigate.is_empty()).then_some(navigate), "Navigate"),
+        (first_key(keymap, &Message::Select), "Select"),
+    ];
+
+    let status_bar =
+        Paragraph::new(hint_line(hints, Vec::new())).block(Block::default().borders(Borders::ALL));
 
     status_bar.render(area, buf);
 }
@@ -67,6 +75,7 @@ pub fn render_status_bar(area: Rect, buf: &mut Buffer) {
 /// # Arguments
 ///
 /// * `message` - Custom status message to display
+/// * `keymap` - The active key bindings
 /// * `area` - The rectangular area to render into
 /// * `buf` - The buffer to render into
 ///
@@ -75,32 +84,67 @@ pub fn render_status_bar(area: Rect, buf: &mut Buffer) {
 /// ```
 /// use ratatui::buffer::Buffer;
 /// use ratatui::layout::Rect;
+/// use whip_protocol::Keymap;
 /// use whip_tui::widgets::status_bar::render_status_bar_with_message;
 ///
 /// let area = Rect::new(0, 0, 80, 3);
 /// let mut buf = Buffer::empty(area);
 ///
-/// render_status_bar_with_message("Task moved to In Progress", area, &mut buf);
+/// render_status_bar_with_message("Task moved to In Progress", &Keymap::default(), area, &mut buf);
 /// ```
-pub fn render_status_bar_with_message(message: &str, area: Rect, buf: &mut Buffer) {
-    let key_style = Style::default().fg(Color::Yellow);
+pub fn render_status_bar_with_message(
+    message: &str,
+    keymap: &Keymap,
+    area: Rect,
+    buf: &mut Buffer,
+) {
     let text_style = Style::default().fg(Color::White);
     let message_style = Style::default().fg(Color::Cyan);
 
-    let hints = Line::from(vec![
-        Span::styled(message, message_style),
+    let hints = [
+        (first_key(keymap, &Message::Quit), "Quit"),
+        (first_key(keymap, &Message::ToggleHelp), "Help"),
+    ];
+    let prefix = vec![
+        Span::styled(message.to_string(), message_style),
         Span::styled("  |  ", text_style),
-        Span::styled("Ctrl+C", key_style),
-        Span::styled(" Quit  ", text_style),
-        Span::styled("?", key_style),
-        Span::styled(" Help", text_style),
-    ]);
+    ];
 
-    let status_bar = Paragraph::new(hints).block(Block::default().borders(Borders::ALL));
+    let status_bar =
+        Paragraph::new(hint_line(hints, prefix)).block(Block::default().borders(Borders::ALL));
 
     status_bar.render(area, buf);
 }
 
+/// Returns the label of the first board key bound to a message.
+fn first_key(keymap: &Keymap, message: &Message) -> Option<String> {
+    keymap
+        .keys(KeyContext::Board, message)
+        .next()
+        .map(KeyChord::label)
+}
+
+/// Appends `key action` hints to `spans`, skipping actions without a key.
+fn hint_line<'a>(
+    hints: impl IntoIterator<Item = (Option<String>, &'a str)>,
+    mut spans: Vec<Span<'a>>,
+) -> Line<'a> {
+    let key_style = Style::default().fg(Color::Yellow);
+    let text_style = Style::default().fg(Color::White);
+
+    let hints: Vec<_> = hints
+        .into_iter()
+        .filter_map(|(key, action)| Some((key?, action)))
+        .collect();
+    let count = hints.len();
+    for (idx, (key, action)) in hints.into_iter().enumerate() {
+        spans.push(Span::styled(key, key_style));
+        let separator = if idx + 1 < count { "  " } else { "" };
+        spans.push(Span::styled(format!(" {action}{separator}"), text_style));
+    }
+    Line::from(spans)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,7 +155,7 @@ mod tests {
         let area = Rect::new(0, 0, 80, 3);
         let mut buf = Buffer::empty(area);
 
-        render_status_bar(area, &mut buf);
+        render_status_bar(&Keymap::default(), area, &mut buf);
 
         let content = buffer_to_string(&buf);
         assert!(content.contains("Quit"));
@@ -123,9 +167,27 @@ mod tests {
         let area = Rect::new(0, 0, 80, 3);
         let mut buf = Buffer::empty(area);
 
-        render_status_bar_with_message("Test message", area, &mut buf);
+        render_status_bar_with_message("Test message", &Keymap::default(), area, &mut buf);
 
         let content = buffer_to_string(&buf);
         assert!(content.contains("Test message"));
+        assert!(content.contains("? Help"));
+    }
+
+    #[test]
+    fn render_status_bar_follows_keymap() {
+        let area = Rect::new(0, 0, 80, 3);
+        let mut buf = Buffer::empty(area);
+
+        let mut keymap = Keymap::default();
+        for (chord, message) in [("left", None), ("q", Some(Message::Quit))] {
+            let chord = chord.parse().expect("chord");
+            keymap.bind(KeyContext::Board, chord, message);
+        }
+        keymap.bind(KeyContext::Board, "ctrl+c".parse().expect("chord"), None);
+        render_status_bar(&keymap, area, &mut buf);
+
+        let content = buffer_to_string(&buf);
+        assert!(content.contains("q Quit  →↑↓ Navigate  Enter Select"));
     }
 }
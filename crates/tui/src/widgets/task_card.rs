@@ -10,7 +10,22 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
-use whip_protocol::{Task, TaskState};
+use whip_protocol::{Task, TaskChange, TaskState};
+
+use crate::changes::BoardChanges;
+use crate::search::{highlight_positions, task_score};
+
+/// What makes task cards stand out on the board.
+///
+/// The default highlights nothing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Highlights<'a> {
+    /// The active search query: matched characters are highlighted and
+    /// cards that do not match are dimmed.
+    pub search: Option<&'a str>,
+    /// The changes the user has not seen: changed cards are marked.
+    pub changes: Option<&'a BoardChanges>,
+}
 
 /// Returns the color associated with a task state.
 ///
@@ -46,6 +61,15 @@ pub const fn state_color(state: TaskState) -> Color {
     }
 }
 
+/// Returns the color marking a task changed by a refresh.
+const fn change_color(change: TaskChange) -> Color {
+    match change {
+        TaskChange::Added => Color::LightGreen,
+        TaskChange::Moved => Color::LightCyan,
+        TaskChange::Updated => Color::LightMagenta,
+    }
+}
+
 /// Returns a brighter version of the state color for selected items.
 ///
 /// Used to highlight selected task cards with more vivid colors.
@@ -99,13 +123,75 @@ const fn state_color_bright(state: TaskState) -> Color {
 /// render_task_card(&task, false, area, &mut buf);
 /// ```
 pub fn render_task_card(task: &Task, is_selected: bool, area: Rect, buf: &mut Buffer) {
+    render_task_card_with_highlights(task, is_selected, Highlights::default(), area, buf);
+}
+
+/// Renders a task card, highlighting search matches and unseen changes.
+///
+/// Characters of the title and description matched by the search query
+/// are highlighted. Cards that do not match the query are dimmed, so
+/// results stand out on the board. A card changed by a refresh the user
+/// has not seen names the change (`new`, `moved`, or `updated`) in its top
+/// border. Without highlights, this is [`render_task_card`].
+///
+/// # Arguments
+///
+/// * `task` - The task to render
+/// * `is_selected` - Whether this card is currently selected
+/// * `highlights` - The search query and unseen changes
+/// * `area` - The rectangular area to render into
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+/// +---------- new -+
+/// | Title          |
+/// | description... |
+/// +----------------+
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_protocol::Task;
+/// use whip_tui::widgets::task_card::{Highlights, render_task_card_with_highlights};
+///
+/// let task = Task::new("Implement feature", "Add new functionality");
+/// let area = Rect::new(0, 0, 20, 5);
+/// let mut buf = Buffer::empty(area);
+///
+/// let highlights = Highlights {
+///     search: Some("feat"),
+///     ..Highlights::default()
+/// };
+/// render_task_card_with_highlights(&task, false, highlights, area, &mut buf);
+/// ```
+pub fn render_task_card_with_highlights(
+    task: &Task,
+    is_selected: bool,
+    highlights: Highlights<'_>,
+    area: Rect,
+    buf: &mut Buffer,
+) {
     // Skip rendering if area is too small
     if area.width < 4 || area.height < 3 {
         return;
     }
 
+    let search = highlights.search;
+
+    let is_match = search.is_none_or(|query| task_score(query, task).is_some());
     let base_color = state_color(task.state);
-    let (border_color, title_style, desc_style) = if is_selected {
+    let (border_color, title_style, desc_style) = if !is_match && !is_selected {
+        (
+            Color::DarkGray,
+            Style::default().fg(Color::DarkGray),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else if is_selected {
         (
             state_color_bright(task.state),
             Style::default()
@@ -125,12 +211,18 @@ pub fn render_task_card(task: &Task, is_selected: bool, area: Rect, buf: &mut Bu
     let inner_width = area.width.saturating_sub(2) as usize;
     let truncated_desc = truncate_string(&task.description, inner_width);
 
-    let content = vec![
-        Line::from(Span::styled(&task.title, title_style)),
-        Line::from(Span::styled(truncated_desc, desc_style)),
-    ];
+    let content = match search.filter(|_| is_match) {
+        Some(query) => vec![
+            highlighted_line(&task.title, query, title_style),
+            highlighted_line(&truncated_desc, query, desc_style),
+        ],
+        None => vec![
+            Line::from(Span::styled(task.title.as_str(), title_style)),
+            Line::from(Span::styled(truncated_desc, desc_style)),
+        ],
+    };
 
-    let block = if is_selected {
+    let mut block = if is_selected {
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color))
@@ -141,6 +233,16 @@ pub fn render_task_card(task: &Task, is_selected: bool, area: Rect, buf: &mut Bu
             .border_style(Style::default().fg(border_color))
     };
 
+    if let Some(change) = highlights.changes.and_then(|changes| changes.get(task.id)) {
+        let marker = Span::styled(
+            format!(" {} ", change.display_name()),
+            Style::default()
+                .fg(change_color(change))
+                .add_modifier(Modifier::BOLD),
+        );
+        block = block.title_top(Line::from(marker).right_aligned());
+    }
+
     let card = Paragraph::new(content)
         .block(block)
         .wrap(Wrap { trim: true });
@@ -148,6 +250,32 @@ pub fn render_task_card(task: &Task, is_selected: bool, area: Rect, buf: &mut Bu
     card.render(area, buf);
 }
 
+/// Builds a line where the characters matched by a search query stand out.
+fn highlighted_line<'a>(text: &str, query: &str, style: Style) -> Line<'a> {
+    let positions = highlight_positions(query, text);
+    let highlight = style
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (idx, ch) in text.chars().enumerate() {
+        let highlighted = positions.binary_search(&idx).is_ok();
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted { highlight } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(ch);
+    }
+    if !run.is_empty() {
+        let style = if run_highlighted { highlight } else { style };
+        spans.push(Span::styled(run, style));
+    }
+    Line::from(spans)
+}
+
 /// Truncates a string to fit within a given width, adding ellipsis if needed.
 fn truncate_string(s: &str, max_width: usize) -> String {
     if s.chars().count() <= max_width {
@@ -163,6 +291,7 @@ fn truncate_string(s: &str, max_width: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::buffer_to_string;
 
     #[test]
     fn state_color_mapping() {
@@ -210,6 +339,92 @@ mod tests {
         assert_ne!(cell.symbol(), " ");
     }
 
+    #[test]
+    fn highlighted_line_splits_matches() {
+        let line = highlighted_line("Fix login", "fix", Style::default());
+        let texts: Vec<&str> = line.spans.iter().map(|span| &*span.content).collect();
+        assert_eq!(texts, vec!["Fix", " login"]);
+        assert!(
+            line.spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+        assert!(
+            !line.spans[1]
+                .style
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+    }
+
+    fn searching(query: &str) -> Highlights<'_> {
+        Highlights {
+            search: Some(query),
+            ..Highlights::default()
+        }
+    }
+
+    #[test]
+    fn render_task_card_marks_unseen_changes() {
+        let task = Task::new("Test Task", "A description");
+        let mut changes = BoardChanges::default();
+        changes.record(&whip_protocol::BoardDiff {
+            moved: vec![task.id],
+            ..Default::default()
+        });
+        let highlights = Highlights {
+            changes: Some(&changes),
+            ..Highlights::default()
+        };
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+
+        render_task_card_with_highlights(&task, false, highlights, area, &mut buf);
+        let top = buffer_to_string(&buf)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        assert!(top.ends_with(" moved ┐"), "top: {top}");
+
+        changes.viewed(task.id);
+        let highlights = Highlights {
+            changes: Some(&changes),
+            ..Highlights::default()
+        };
+        render_task_card_with_highlights(&task, false, highlights, area, &mut buf);
+        assert!(!buffer_to_string(&buf).contains("moved"));
+    }
+
+    #[test]
+    fn render_task_card_dims_non_matching_cards() {
+        let task = Task::new("Test Task", "A description");
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+
+        render_task_card_with_highlights(&task, false, searching("unrelated"), area, &mut buf);
+
+        let cell = buf.cell((1, 1)).expect("cell should exist");
+        assert_eq!(cell.symbol(), "T");
+        assert_eq!(cell.fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn render_task_card_highlights_matches() {
+        let task = Task::new("Test Task", "A description");
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+
+        render_task_card_with_highlights(&task, false, searching("task"), area, &mut buf);
+
+        let plain = buf.cell((1, 1)).expect("cell should exist");
+        assert_eq!(plain.fg, Color::White);
+        let matched = buf.cell((6, 1)).expect("cell should exist");
+        assert_eq!(matched.symbol(), "T");
+        assert_eq!(matched.fg, Color::Yellow);
+    }
+
     #[test]
     fn render_task_card_handles_small_area() {
         let task = Task::new("Test Task", "A description");
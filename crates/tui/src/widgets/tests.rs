@@ -4,7 +4,7 @@
 
 use chrono::{TimeZone, Utc};
 use ratatui::{buffer::Buffer, layout::Rect};
-use whip_protocol::{KanbanBoard, LaneKind, Task, TaskState};
+use whip_protocol::{KanbanBoard, Keymap, LaneDefinition, LaneKind, Task, TaskState};
 
 use crate::test_utils::buffer_to_string;
 
@@ -19,9 +19,11 @@ fn test_task(title: &str, description: &str) -> Task {
 }
 
 use super::{
-    LanePosition, render_board, render_detail_panel, render_help_overlay, render_lane,
-    render_settings_panel, render_status_bar, render_task_card,
+    LanePosition, render_board, render_context_menu, render_detail_panel, render_help_overlay,
+    render_lane, render_search_bar, render_settings_panel, render_status_bar, render_task_card,
 };
+use crate::context_menu::ContextMenu;
+use crate::search::SearchState;
 use crate::settings_state::SettingsState;
 use whip_config::{Config, PollingConfig, Repository};
 
@@ -42,24 +44,24 @@ fn create_sample_board() -> KanbanBoard {
     let mut task3 = Task::new("Implement parser", "Build JSON parser module");
     task3.state = TaskState::InFlight;
     task3.lane = LaneKind::InProgress;
-    board.lane_mut(LaneKind::InProgress).add_task(task3);
+    board.add_task(task3);
 
     // Under Review tasks
     let mut task4 = Task::new("Code review: auth", "Review authentication PR");
     task4.state = TaskState::Idle;
     task4.lane = LaneKind::UnderReview;
-    board.lane_mut(LaneKind::UnderReview).add_task(task4);
+    board.add_task(task4);
 
     // Done tasks
     let mut task5 = Task::new("Setup CI/CD", "Configure GitHub Actions");
     task5.state = TaskState::Success;
     task5.lane = LaneKind::Done;
-    board.lane_mut(LaneKind::Done).add_task(task5);
+    board.add_task(task5);
 
     let mut task6 = Task::new("Fix login bug", "Users couldn't log in");
     task6.state = TaskState::Failed;
     task6.lane = LaneKind::Done;
-    board.lane_mut(LaneKind::Done).add_task(task6);
+    board.add_task(task6);
 
     board
 }
@@ -97,6 +99,26 @@ fn snapshot_board_different_lane_selected() {
     insta::assert_snapshot!(buffer_to_string(&buf));
 }
 
+#[test]
+fn snapshot_board_custom_lanes() {
+    let mut board = create_sample_board();
+    board.set_lanes(vec![
+        LaneDefinition::for_kind(LaneKind::Backlog),
+        LaneDefinition::new("Planning").with_labels(["whip/status/planning"]),
+        LaneDefinition::new("Implementing").with_kinds([LaneKind::InProgress]),
+        LaneDefinition::new("Review").with_kinds([LaneKind::UnderReview]),
+        LaneDefinition::new("Blocked").with_states([TaskState::NeedsAttention]),
+        LaneDefinition::new("Failed").with_states([TaskState::Failed]),
+        LaneDefinition::for_kind(LaneKind::Done),
+    ]);
+    let area = Rect::new(0, 0, 140, 24);
+    let mut buf = Buffer::empty(area);
+
+    render_board(&board, 5, Some(0), area, &mut buf);
+
+    insta::assert_snapshot!(buffer_to_string(&buf));
+}
+
 #[test]
 fn snapshot_lane_empty() {
     let lane = whip_protocol::Lane::new(LaneKind::Backlog);
@@ -227,7 +249,20 @@ fn snapshot_status_bar() {
     let area = Rect::new(0, 0, 80, 3);
     let mut buf = Buffer::empty(area);
 
-    render_status_bar(area, &mut buf);
+    render_status_bar(&Keymap::default(), area, &mut buf);
+
+    insta::assert_snapshot!(buffer_to_string(&buf));
+}
+
+#[test]
+fn snapshot_search_bar() {
+    let mut search = SearchState::default();
+    search.start();
+    "parser".chars().for_each(|ch| search.push(ch));
+    let area = Rect::new(0, 0, 80, 1);
+    let mut buf = Buffer::empty(area);
+
+    render_search_bar(&search, Some(1), 4, area, &mut buf);
 
     insta::assert_snapshot!(buffer_to_string(&buf));
 }
@@ -389,7 +424,19 @@ fn snapshot_help_overlay() {
     let area = Rect::new(0, 0, 80, 24);
     let mut buf = Buffer::empty(area);
 
-    render_help_overlay(area, &mut buf);
+    render_help_overlay(&Keymap::default(), area, &mut buf);
+
+    insta::assert_snapshot!(buffer_to_string(&buf));
+}
+
+#[test]
+fn snapshot_context_menu() {
+    let area = Rect::new(0, 0, 40, 10);
+    let mut buf = Buffer::empty(area);
+    let mut menu = ContextMenu::for_task(&test_task("Fix login redirect", ""), false, (4, 2));
+    menu.move_selection(1);
+
+    render_context_menu(&menu, &Keymap::default(), area, &mut buf);
 
     insta::assert_snapshot!(buffer_to_string(&buf));
 }
@@ -399,7 +446,7 @@ fn snapshot_help_overlay_small_terminal() {
     let area = Rect::new(0, 0, 40, 15);
     let mut buf = Buffer::empty(area);
 
-    render_help_overlay(area, &mut buf);
+    render_help_overlay(&Keymap::default(), area, &mut buf);
 
     insta::assert_snapshot!(buffer_to_string(&buf));
 }
@@ -479,6 +526,108 @@ fn snapshot_settings_panel_authentication_section() {
     insta::assert_snapshot!(buffer_to_string(&buf));
 }
 
+#[test]
+fn snapshot_settings_panel_authentication_credentials() {
+    use std::collections::HashMap;
+
+    use whip_config::Repository;
+    use whip_config::auth::TokenSource;
+
+    use crate::credentials::CredentialCheck;
+
+    let config = Config {
+        repositories: vec![
+            Repository::new("owner", "valid"),
+            Repository::new("owner", "narrow"),
+            Repository::new("owner", "rejected"),
+            Repository::new("owner", "pending"),
+        ],
+        ..Default::default()
+    };
+
+    let mut state = SettingsState::new(config).with_token_sources(HashMap::from([(
+        "owner/pending".to_string(),
+        Some(TokenSource::Env("GITHUB_TOKEN".to_string())),
+    )]));
+    state.set_credential_check(
+        "owner/valid",
+        CredentialCheck::Valid {
+            source: TokenSource::GhCli,
+            login: "octocat".to_string(),
+            scopes: Some(vec!["repo".to_string(), "read:org".to_string()]),
+            missing_scopes: Vec::new(),
+        },
+    );
+    state.set_credential_check(
+        "owner/narrow",
+        CredentialCheck::Valid {
+            source: TokenSource::Command,
+            login: "octocat".to_string(),
+            scopes: Some(vec!["gist".to_string()]),
+            missing_scopes: vec!["repo".to_string()],
+        },
+    );
+    state.set_credential_check(
+        "owner/rejected",
+        CredentialCheck::Failed {
+            source: TokenSource::ConfigToken,
+            error: "token validation failed: Bad credentials".to_string(),
+        },
+    );
+    state.set_credential_check("owner/pending", CredentialCheck::Pending);
+    state.prev_section(); // Authentication
+    state.navigate(2); // owner/narrow
+
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+
+    render_settings_panel(&state, area, &mut buf);
+
+    insta::assert_snapshot!(buffer_to_string(&buf));
+}
+
+#[test]
+fn snapshot_settings_panel_authentication_installation() {
+    use whip_config::Repository;
+    use whip_config::auth::TokenSource;
+
+    use crate::credentials::CredentialCheck;
+
+    let config = Config {
+        repositories: vec![
+            Repository::new("owner", "app"),
+            Repository::new("owner", "readonly"),
+        ],
+        ..Default::default()
+    };
+
+    let mut state = SettingsState::new(config);
+    state.set_credential_check(
+        "owner/app",
+        CredentialCheck::ValidInstallation {
+            source: TokenSource::GitHubApp,
+            permissions: Some(vec!["push".to_string(), "pull".to_string()]),
+            missing_permissions: Vec::new(),
+        },
+    );
+    state.set_credential_check(
+        "owner/readonly",
+        CredentialCheck::ValidInstallation {
+            source: TokenSource::GitHubApp,
+            permissions: Some(vec!["pull".to_string()]),
+            missing_permissions: vec!["push".to_string()],
+        },
+    );
+    state.prev_section(); // Authentication
+
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+
+    render_settings_panel(&state, area, &mut buf);
+
+    insta::assert_snapshot!(buffer_to_string(&buf));
+}
+
 #[test]
 fn snapshot_settings_panel_small_terminal() {
     let config = Config::default();
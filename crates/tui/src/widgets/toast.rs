@@ -0,0 +1,198 @@
+//! Toast notification widget.
+//!
+//! This module renders the active notifications as a stack of short-lived
+//! toasts in the bottom-right corner of the screen, on top of the board.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::notification::{Notification, Notifications, Severity};
+
+/// Maximum width of a toast, including borders.
+const TOAST_MAX_WIDTH: u16 = 60;
+
+/// Height of a toast, including borders.
+const TOAST_HEIGHT: u16 = 3;
+
+/// Returns the display color for a notification severity.
+#[must_use]
+pub fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::Cyan,
+        Severity::Success => Color::Green,
+        Severity::Warning => Color::Yellow,
+        Severity::Error => Color::Red,
+    }
+}
+
+/// Returns the icon shown before a notification of the given severity.
+#[must_use]
+pub fn severity_icon(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "ℹ",
+        Severity::Success => "✓",
+        Severity::Warning => "⚠",
+        Severity::Error => "✗",
+    }
+}
+
+/// Renders the active notifications as toasts in the bottom-right corner of `area`.
+///
+/// Toasts are stacked upwards with the newest at the bottom. Those that do
+/// not fit vertically are skipped. The bottom toast carries a hint to open
+/// the notification history.
+///
+/// # Arguments
+///
+/// * `notifications` - The notification queue
+/// * `area` - The area to position the toasts within
+/// * `buf` - The buffer to render into
+///
+/// # Layout
+///
+/// ```text
+///                              ╭────────────────────────────╮
+///                              │ ℹ Refreshing 2 repositories │
+///                              ╰────────────────────────────╯
+///                        ╭─────────────────────── n: history ─╮
+///                        │ ✗ owner/repo: rate limit exceeded  │
+///                        ╰────────────────────────────────────╯
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use whip_tui::notification::{Notifications, Severity};
+/// use whip_tui::widgets::render_toasts;
+///
+/// let mut notifications = Notifications::default();
+/// notifications.push(Severity::Error, "owner/repo: rate limit exceeded");
+///
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+///
+/// render_toasts(&notifications, area, &mut buf);
+/// ```
+pub fn render_toasts(notifications: &Notifications, area: Rect, buf: &mut Buffer) {
+    let mut bottom = area.y + area.height;
+    let mut is_newest = true;
+
+    for notification in notifications.active().collect::<Vec<_>>().into_iter().rev() {
+        if bottom < area.y + TOAST_HEIGHT {
+            break;
+        }
+        let toast_area = Rect {
+            y: bottom - TOAST_HEIGHT,
+            height: TOAST_HEIGHT,
+            ..area
+        };
+        render_toast(notification, is_newest, toast_area, buf);
+        bottom -= TOAST_HEIGHT;
+        is_newest = false;
+    }
+}
+
+/// Renders a single toast, right-aligned within `area`.
+fn render_toast(notification: &Notification, show_hint: bool, area: Rect, buf: &mut Buffer) {
+    let text = format!(
+        "{} {}",
+        severity_icon(notification.severity),
+        notification.message
+    );
+    // Borders plus one column of padding on each side
+    let wanted = u16::try_from(text.chars().count())
+        .unwrap_or(u16::MAX)
+        .saturating_add(4);
+    let width = wanted.min(TOAST_MAX_WIDTH).min(area.width);
+
+    let toast_area = Rect {
+        x: area.x + area.width.saturating_sub(width),
+        width,
+        ..area
+    };
+
+    Clear.render(toast_area, buf);
+
+    let style = Style::default().fg(severity_color(notification.severity));
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(style);
+    if show_hint {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                " n: history ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    Paragraph::new(Line::from(Span::styled(format!(" {text}"), style)))
+        .block(block)
+        .render(toast_area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::buffer_to_string;
+
+    fn render(notifications: &Notifications, area: Rect) -> Vec<String> {
+        let mut buf = Buffer::empty(area);
+        render_toasts(notifications, area, &mut buf);
+        buffer_to_string(&buf).lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn toast_renders_in_bottom_right() {
+        let mut notifications = Notifications::default();
+        notifications.push(Severity::Error, "owner/repo: boom");
+
+        let lines = render(&notifications, Rect::new(0, 0, 80, 10));
+        assert!(lines[8].contains("✗ owner/repo: boom"));
+        assert!(lines[8].ends_with('│'));
+        assert!(lines[9].contains("n: history"));
+        assert!(lines[0].trim().is_empty());
+    }
+
+    #[test]
+    fn toasts_stack_newest_at_bottom() {
+        let mut notifications = Notifications::default();
+        notifications.push(Severity::Info, "first");
+        notifications.push(Severity::Success, "second");
+
+        let lines = render(&notifications, Rect::new(0, 0, 80, 10));
+        assert!(lines[5].contains("ℹ first"));
+        assert!(lines[8].contains("✓ second"));
+        assert!(!lines[6].contains("n: history"));
+    }
+
+    #[test]
+    fn toasts_that_do_not_fit_are_skipped() {
+        let mut notifications = Notifications::default();
+        notifications.push(Severity::Info, "first");
+        notifications.push(Severity::Info, "second");
+
+        let lines = render(&notifications, Rect::new(0, 0, 80, 4));
+        let content = lines.join("\n");
+        assert!(content.contains("second"));
+        assert!(!content.contains("first"));
+    }
+
+    #[test]
+    fn toast_is_clamped_to_max_width() {
+        let mut notifications = Notifications::default();
+        notifications.push(Severity::Warning, "x".repeat(150));
+
+        let lines = render(&notifications, Rect::new(0, 0, 200, 5));
+        assert_eq!(lines[4].trim().chars().count(), TOAST_MAX_WIDTH as usize);
+    }
+}
@@ -1,46 +1,222 @@
 //! whip - An AI Agent orchestrator using Claude Code.
 //!
-//! This is the main binary that launches the TUI application.
+//! This is the main binary. Without a subcommand it launches the TUI; the
+//! subcommands run headless, for scripts and cron jobs.
 
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use anyhow::Context;
+use clap::{Parser, Subcommand};
 use secrecy::SecretString;
-use whip_config::Config;
-use whip_config::auth::resolve_token;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use whip_config::auth::{
+    ResolvedToken, TokenSource, forget_command_tokens, forget_rejected_token, resolve_token,
+    resolve_token_with_source,
+};
+use whip_config::check::check_file;
+use whip_config::migrate::{CURRENT_VERSION, MigrationPlan};
+use whip_config::persistence::{
+    default_credentials_path, default_user_config_path, find_local_config_file,
+    find_user_config_file,
+};
+use whip_config::schema::config_schema_json;
+use whip_config::watch::ConfigWatcher;
+use whip_config::{Config, ConfigError, ConfigLoader, LayeredConfig, Provenance, Repository};
 use whip_github::{
-    CachedIssues, FetchOptions, GitHubClient, IssueCache, issue_to_task, sync_labels,
+    CachedIssues, FetchOptions, GitHubClient, IssueCache, api_host, issue_to_task, plan_label_sync,
 };
-use whip_protocol::KanbanBoard;
-use whip_tui::{App, RunResult, terminal};
+use whip_protocol::{FetchErrorKind, KanbanBoard, TaskFilter};
+use whip_tui::credentials::CredentialCheck;
+use whip_tui::notification::Severity;
+use whip_tui::{App, RepoEvent, RepoStatus, RepoStatuses, RunResult, terminal};
+
+/// An AI agent orchestrator, driven by a Kanban board of GitHub issues.
+///
+/// Runs the TUI unless a subcommand is given.
+#[derive(Debug, Parser)]
+#[command(name = "whip", version)]
+struct Cli {
+    /// Use this config file instead of the user and local config files
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Only use cached issues; never contact GitHub
+    #[arg(long, global = true)]
+    offline: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the board and exit
+    Board {
+        /// Print the board as JSON
+        #[arg(long)]
+        json: bool,
+        /// Only show tasks matching a saved filter, or a filter expression
+        /// such as `repo:acme/api state:needs_attention`
+        #[arg(long, value_name = "NAME|EXPRESSION")]
+        filter: Option<String>,
+    },
+    /// Create or update the whip status labels on every repository
+    SyncLabels {
+        /// Show what would change without changing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Apply the changes without asking for confirmation
+        #[arg(long, short, conflicts_with = "dry_run")]
+        yes: bool,
+    },
+    /// Manage the configured repositories
+    #[command(subcommand)]
+    Repo(RepoCommand),
+    /// Manage the issue cache
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Inspect and upgrade config files
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum RepoCommand {
+    /// Add a repository to the config file
+    Add {
+        /// The repository, as `owner/repo`
+        repository: String,
+        /// The GitHub API URL for the repository, for GitHub Enterprise Server
+        #[arg(long, value_name = "URL")]
+        api_url: Option<String>,
+    },
+    /// Remove a repository from the config file
+    Remove {
+        /// The repository, as `owner/repo`, or `host/owner/repo` to remove
+        /// it from one GitHub Enterprise host only
+        repository: String,
+    },
+    /// List the configured repositories
+    List,
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    /// Remove every cached issue
+    Clear,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommand {
+    /// Print the config file settings are read from and saved to
+    Path,
+    /// Check config files and report every problem with its location
+    Check {
+        /// The file to check, instead of the config files in use
+        path: Option<PathBuf>,
+    },
+    /// Upgrade config files written for an older whip
+    Migrate {
+        /// The file to upgrade, instead of the config files in use
+        path: Option<PathBuf>,
+    },
+    /// Print the JSON Schema of config files
+    Schema,
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Load configuration
-    let config = Config::load().await.unwrap_or_else(|e| {
-        eprintln!("Warning: failed to load config: {e}");
-        Config::default()
-    });
+    let cli = Cli::parse();
+    let config_path = cli.config.as_deref();
+    match cli.command {
+        None => run_tui(config_path, cli.offline).await,
+        Some(Command::Board { json, filter }) => {
+            let config = load_config(config_path)?;
+            let filter = filter
+                .map(|filter| resolve_filter(&config, &filter))
+                .transpose()?;
+            print_board(&config, cli.offline, json, filter.as_ref()).await
+        }
+        Some(Command::SyncLabels { dry_run, yes }) => {
+            if cli.offline {
+                anyhow::bail!("syncing labels needs GitHub, so it cannot run with --offline");
+            }
+            sync_labels_headless(&load_config(config_path)?, dry_run, yes).await
+        }
+        Some(Command::Repo(command)) => repo_command(config_path, command),
+        Some(Command::Cache(CacheCommand::Clear)) => {
+            let removed = IssueCache::new()?.clear()?;
+            println!("Removed {removed} cached repositories");
+            Ok(())
+        }
+        Some(Command::Config(command)) => match command {
+            ConfigCommand::Path => {
+                println!("{}", settings_file(config_path)?.display());
+                Ok(())
+            }
+            ConfigCommand::Check { path } => config_check(path.or_else(|| cli.config.clone())),
+            ConfigCommand::Migrate { path } => {
+                offer_migrations(&config_files(path.or_else(|| cli.config.clone())))
+            }
+            ConfigCommand::Schema => {
+                print!("{}", config_schema_json());
+                Ok(())
+            }
+        },
+    }
+}
 
-    // Load board from GitHub BEFORE terminal setup so errors are visible
-    let board = if config.has_repositories() {
-        // Run label sync and board loading concurrently
-        let (sync_result, board_result) = tokio::join!(
-            sync_labels_for_repositories(&config),
-            load_github_board(&config)
-        );
+/// Runs the TUI until the user quits.
+///
+/// With `offline`, the board is built from the cache alone: nothing is
+/// fetched, labels are not synced, and refreshes are ignored.
+async fn run_tui(config_path: Option<&Path>, offline: bool) -> anyhow::Result<()> {
+    // Load configuration; files written for an older whip are upgraded in
+    // memory, and only `whip config migrate` writes the upgrade back
+    let loader = config_loader(config_path);
+    let config = loader
+        .load()
+        .map(|loaded| loaded.config)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: failed to load config: {e}");
+            Config::default()
+        });
+
+    if !config.has_repositories() {
+        eprintln!("No repositories configured. Add one with `whip repo add <owner/repo>`");
+    }
 
-        // Report any sync errors but continue
-        if let Err(e) = sync_result {
-            eprintln!("Warning: failed to sync labels: {e}");
+    // Build the initial board from the cache so the TUI opens immediately;
+    // anything missing or stale is fetched in the background.
+    let (board, mut statuses, to_fetch) = load_cached_board(&config);
+
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+    if offline {
+        // Cached data is all there is; stop waiting for fetches
+        for repo in to_fetch {
+            let key = config.repo_key(&repo);
+            if statuses.get(&key) == Some(&RepoStatus::Loading) {
+                statuses.remove(&key);
+            } else {
+                statuses.set(key, RepoStatus::Fresh);
+            }
         }
-
-        board_result.unwrap_or_else(|e| {
-            eprintln!("Warning: failed to load GitHub issues: {e}");
-            KanbanBoard::new()
-        })
     } else {
-        eprintln!("No repositories configured. Add repositories to ~/.config/whip/config.json5");
-        KanbanBoard::new()
+        for repo in to_fetch {
+            spawn_fetch(&config, repo, events_tx.clone());
+        }
+        if config.sync_labels {
+            spawn_label_plan(&config, events_tx.clone());
+        }
+    }
+
+    // Reload the configuration whenever one of its files changes
+    let (config_tx, config_rx) = mpsc::unbounded_channel();
+    match config_path {
+        Some(_) => ConfigWatcher::new(loader).spawn(config_tx),
+        None => ConfigWatcher::discover().spawn(config_tx),
     };
 
     // Install panic hook to restore terminal on panic
@@ -49,43 +225,49 @@ async fn main() -> anyhow::Result<()> {
     // Setup terminal
     let mut terminal = terminal::setup_terminal()?;
 
-    let mut app = App::with_config(board, config.clone());
+    let mut app = App::with_config(board, config)
+        .with_repo_statuses(statuses)
+        .with_repo_events(events_rx)
+        .with_config_changes(config_rx);
 
     // Run the main loop, handling refresh requests
     loop {
         match app.run(&mut terminal).await? {
             RunResult::Quit => break,
+            RunResult::CredentialTestRequested => {
+                if let Some((repo, config)) = app.take_credential_test() {
+                    if offline {
+                        // Resolve the pending test rather than leave it waiting
+                        let _ = events_tx.send(RepoEvent::CredentialsChecked {
+                            repo: config.repo_key(&repo),
+                            check: CredentialCheck::Offline,
+                        });
+                    } else {
+                        spawn_credential_test(&config, repo, events_tx.clone());
+                    }
+                }
+            }
+            RunResult::FetchRequested => {
+                // Repositories added to the configuration while running
+                for repo in app.take_pending_fetches() {
+                    if !offline {
+                        spawn_fetch(app.config(), repo, events_tx.clone());
+                    }
+                }
+            }
+            RunResult::RefreshRequested if offline => {
+                app.notify(Severity::Warning, "Offline: showing cached issues only");
+            }
             RunResult::RefreshRequested => {
-                // Show loading indicator
-                terminal.draw(|frame| {
-                    app.view(frame);
-                    // Draw a loading message at the bottom
-                    let area = frame.area();
-                    let loading_area = ratatui::layout::Rect {
-                        x: 0,
-                        y: area.height.saturating_sub(1),
-                        width: area.width,
-                        height: 1,
-                    };
-                    let loading = ratatui::widgets::Paragraph::new(" Refreshing GitHub issues...")
-                        .style(
-                            ratatui::style::Style::default()
-                                .fg(ratatui::style::Color::Yellow)
-                                .add_modifier(ratatui::style::Modifier::BOLD),
-                        );
-                    frame.render_widget(loading, loading_area);
-                })?;
-
-                // Force refresh from GitHub (bypass cache) using the CURRENT config
+                // Force refresh from GitHub (bypass cache) using the CURRENT
+                // config. Results stream in while the board stays interactive.
+                app.mark_repos_refreshing();
+                // Token commands are run again, in case their tokens changed
+                forget_command_tokens();
                 let current_config = app.config();
-                let board = refresh_github_board(current_config)
-                    .await
-                    .unwrap_or_else(|e| {
-                        // On error, keep the current board
-                        eprintln!("\rRefresh failed: {e}");
-                        KanbanBoard::new()
-                    });
-                app.set_board(board);
+                for repo in &current_config.repositories {
+                    spawn_fetch(current_config, repo.clone(), events_tx.clone());
+                }
             }
         }
     }
@@ -96,193 +278,688 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Syncs whip labels to all configured repositories.
+/// Returns the loader for the config layers in use.
 ///
-/// This ensures that all repositories have the standard `whip/*` labels
-/// with consistent colors and descriptions. Skipped if `sync_labels` is
-/// disabled in the configuration.
-async fn sync_labels_for_repositories(config: &Config) -> anyhow::Result<()> {
-    if !config.sync_labels {
-        return Ok(());
+/// `path` replaces the user and local config files; the credentials file
+/// and environment overrides still apply.
+fn config_loader(path: Option<&Path>) -> ConfigLoader {
+    let Some(path) = path else {
+        return ConfigLoader::discover();
+    };
+    let loader = ConfigLoader::new().with_user_file(path);
+    match default_credentials_path() {
+        Ok(credentials) => loader.with_credentials_file(credentials),
+        Err(_) => loader,
     }
+    .with_process_env()
+}
 
-    for repo in &config.repositories {
-        let owner = repo.owner();
-        let repo_name = repo.repo();
+/// Loads the configuration for a headless command.
+fn load_config(path: Option<&Path>) -> anyhow::Result<Config> {
+    Ok(config_loader(path)
+        .load()
+        .context("failed to load config")?
+        .config)
+}
+
+/// Returns the config file settings are read from and saved to.
+///
+/// This is `path` if given, otherwise the highest-priority config file that
+/// exists, falling back to where the user config file would be created.
+fn settings_file(path: Option<&Path>) -> anyhow::Result<PathBuf> {
+    match path {
+        Some(path) => Ok(path.to_path_buf()),
+        None => match find_local_config_file().or_else(find_user_config_file) {
+            Some(path) => Ok(path),
+            None => Ok(default_user_config_path()?),
+        },
+    }
+}
+
+/// Resolves a `--filter` argument: the name of a saved filter, or else a
+/// filter expression.
+fn resolve_filter(config: &Config, filter: &str) -> anyhow::Result<TaskFilter> {
+    if let Some(saved) = config.named_filter(filter) {
+        return Ok(saved.filter.clone());
+    }
+    Ok(filter.parse()?)
+}
+
+/// Prints the board, fetching repositories whose cache is missing or stale.
+///
+/// A repository that fails to fetch is reported on stderr and shown from
+/// its cache, if any. With a filter, only the tasks it matches are shown.
+async fn print_board(
+    config: &Config,
+    offline: bool,
+    json: bool,
+    filter: Option<&TaskFilter>,
+) -> anyhow::Result<()> {
+    let (mut board, _, to_fetch) = load_cached_board(config);
+    if !offline {
+        for repo in to_fetch {
+            let token = match resolve_token(&repo, config).await {
+                Ok(token) => token,
+                Err(e) => {
+                    eprintln!("Warning: {}: {e}", repo.full_name());
+                    continue;
+                }
+            };
+            match fetch_repository(config, &repo, token).await {
+                Ok(tasks) => {
+                    let key = config.repo_key(&repo);
+                    board.retain_tasks(|task| {
+                        task.github
+                            .as_ref()
+                            .is_none_or(|github| github.repo_key() != key)
+                    });
+                    for task in tasks {
+                        board.add_task(task);
+                    }
+                }
+                Err(e) => eprintln!("Warning: {}: {e}", repo.full_name()),
+            }
+        }
+    }
+    if let Some(filter) = filter {
+        board.retain_tasks(|task| filter.matches(task));
+    }
+
+    // A reader that stops early (`whip board --json | head`) is not an error
+    match write_board(&mut std::io::stdout().lock(), &board, json) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Writes the board as JSON, or as a plain list of tasks per lane.
+fn write_board(out: &mut impl Write, board: &KanbanBoard, json: bool) -> std::io::Result<()> {
+    if json {
+        serde_json::to_writer_pretty(&mut *out, board)?;
+        return writeln!(out);
+    }
+    for lane in &board.lanes {
+        writeln!(out, "{} ({})", lane.title(), lane.len())?;
+        for task in &lane.tasks {
+            match &task.github {
+                Some(github) => writeln!(
+                    out,
+                    "  {}#{}  {}",
+                    github.repo_key(),
+                    github.number,
+                    task.title
+                )?,
+                None => writeln!(out, "  {}", task.title)?,
+            }
+        }
+    }
+    Ok(())
+}
 
+/// Syncs whip labels to every repository, reporting each outcome.
+///
+/// The changes for every repository are planned and shown as a diff first.
+/// With `dry_run` nothing more happens; otherwise they are applied once
+/// confirmed, or right away with `yes`. Exits with status 1 if any
+/// repository fails.
+async fn sync_labels_headless(config: &Config, dry_run: bool, yes: bool) -> anyhow::Result<()> {
+    let mut failed = false;
+    let mut plans = Vec::new();
+    for repo in &config.repositories {
+        let name = repo.full_name();
         // Need a token to sync labels (write access required)
-        let token = resolve_token(repo, config.github_token.as_deref()).await;
-        if token.is_none() {
-            eprintln!(
-                "Skipping label sync for {owner}/{repo_name}: no authentication token available"
-            );
-            continue;
+        let token = match resolve_token(repo, config).await {
+            Ok(Some(token)) => token,
+            Ok(None) => {
+                println!("{name}: skipped, no token");
+                continue;
+            }
+            Err(e) => {
+                eprintln!("{name}: {e}");
+                failed = true;
+                continue;
+            }
+        };
+        let planned = match GitHubClient::with_base_url(
+            Some(SecretString::from(token)),
+            config.api_url_for(repo),
+        )
+        .await
+        {
+            Ok(client) => plan_label_sync(
+                &client,
+                repo.owner(),
+                repo.repo(),
+                &config.status_labels_for(repo),
+            )
+            .await
+            .map(|plan| (client, plan)),
+            Err(e) => Err(e),
+        };
+        match planned {
+            Ok((_, plan)) if plan.is_empty() => println!("{name}: up to date"),
+            Ok((client, plan)) => {
+                println!("{name}:");
+                for change in plan.changes() {
+                    for line in change.to_string().lines() {
+                        println!("  {line}");
+                    }
+                }
+                plans.push((client, plan));
+            }
+            Err(e) => {
+                eprintln!("{name}: {e}");
+                failed = true;
+            }
         }
-        let token = token.map(SecretString::from);
-
-        match GitHubClient::new(token).await {
-            Ok(client) => match sync_labels(&client, owner, repo_name).await {
-                Ok(result) => {
-                    if result.created > 0 || result.updated > 0 {
-                        eprintln!(
-                            "Synced labels for {owner}/{repo_name}: {} created, {} updated",
-                            result.created, result.updated
-                        );
+    }
+
+    if !dry_run && !plans.is_empty() {
+        let apply = if yes {
+            true
+        } else if std::io::stdin().is_terminal() {
+            confirm("Apply these label changes? They are visible to everyone on the repository")?
+        } else {
+            anyhow::bail!("not applying label changes without confirmation; pass --yes");
+        };
+        if apply {
+            for (client, plan) in &plans {
+                let name = format!("{}/{}", plan.owner(), plan.repo());
+                match plan.apply(client).await {
+                    Ok(synced) => println!(
+                        "{name}: created {}, updated {}",
+                        synced.created, synced.updated
+                    ),
+                    Err(e) => {
+                        eprintln!("{name}: {e}");
+                        failed = true;
                     }
                 }
-                Err(e) => {
-                    eprintln!("Warning: failed to sync labels for {owner}/{repo_name}: {e}");
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Adds, removes, or lists repositories.
+fn repo_command(config_path: Option<&Path>, command: RepoCommand) -> anyhow::Result<()> {
+    match command {
+        RepoCommand::Add {
+            repository,
+            api_url,
+        } => {
+            let mut repo = Repository::parse_short(&repository)?;
+            if let Some(api_url) = api_url {
+                repo = repo.with_api_url(api_url);
+            }
+            edit_repositories(config_path, |config, _, _| {
+                let key = config.repo_key(&repo);
+                if config
+                    .repositories
+                    .iter()
+                    .any(|existing| config.repo_key(existing) == key)
+                {
+                    anyhow::bail!("{key} is already configured");
+                }
+                config.add_repository(repo);
+                Ok(())
+            })
+        }
+        RepoCommand::Remove { repository } => {
+            edit_repositories(config_path, |config, provenance, path| {
+                // Removing it from the settings file would not unconfigure
+                // a repository another layer lists
+                let elsewhere = config
+                    .repositories
+                    .iter()
+                    .filter(|repo| {
+                        repo.full_name() == repository || config.repo_key(repo) == repository
+                    })
+                    .filter_map(|repo| provenance.repository_source(repo))
+                    .find(|layer| layer.path() != Some(path));
+                if let Some(layer) = elsewhere {
+                    anyhow::bail!(
+                        "{repository} is defined in the {layer}, not in {}; remove it there",
+                        path.display()
+                    );
+                }
+                if !config.remove_repository(&repository) {
+                    anyhow::bail!("{repository} is not configured");
+                }
+                Ok(())
+            })
+        }
+        RepoCommand::List => {
+            for repo in &load_config(config_path)?.repositories {
+                match repo.api_url() {
+                    Some(api_url) => println!("{} ({api_url})", repo.full_name()),
+                    None => println!("{}", repo.full_name()),
                 }
-            },
-            Err(e) => {
-                eprintln!("Warning: failed to create client for label sync: {e}");
             }
+            Ok(())
         }
     }
+}
+
+/// Applies `edit` to the configuration and saves the change.
+///
+/// The change is saved to the [settings file](settings_file), keeping its
+/// comments and formatting. A file given with `--config` is created if it
+/// does not exist yet. `edit` is also given where each value came from and
+/// the file the change will be saved to.
+fn edit_repositories(
+    config_path: Option<&Path>,
+    edit: impl FnOnce(&mut Config, &Provenance, &Path) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let path = settings_file(config_path)?;
+    let loader = match config_path {
+        Some(path) if !path.exists() => ConfigLoader::new().with_process_env(),
+        _ => config_loader(config_path),
+    };
+    let LayeredConfig {
+        config: loaded,
+        provenance,
+    } = loader.load().context("failed to load config")?;
+
+    let mut config = loaded.clone();
+    edit(&mut config, &provenance, &path)?;
+    config.validate()?;
+    config.save_changes(&loaded, &path, default_credentials_path()?)?;
+    println!("Saved {}", path.display());
+    Ok(())
+}
+
+/// Returns `path`, or the local and user config files that exist.
+fn config_files(path: Option<PathBuf>) -> Vec<PathBuf> {
+    match path {
+        Some(path) => vec![path],
+        None => find_local_config_file()
+            .into_iter()
+            .chain(find_user_config_file())
+            .collect(),
+    }
+}
+
+/// Checks config files and prints every problem with its location.
+///
+/// Checks `path`, or the local and user config files that exist. Exits with
+/// status 1 if any problem is found.
+fn config_check(path: Option<PathBuf>) -> anyhow::Result<()> {
+    let paths = config_files(path);
+    if paths.is_empty() {
+        anyhow::bail!("no config file found");
+    }
 
+    let mut problems = 0;
+    for path in &paths {
+        let diagnostics = check_file(path)?;
+        if diagnostics.is_empty() {
+            println!("{}: ok", path.display());
+        }
+        for diagnostic in &diagnostics {
+            eprintln!("{}:{diagnostic}", path.display());
+        }
+        problems += diagnostics.len();
+    }
+    if problems > 0 {
+        std::process::exit(1);
+    }
     Ok(())
 }
 
-/// Loads a KanbanBoard from GitHub issues.
+/// Upgrades config files written for an older format version.
 ///
-/// Uses caching with the following strategy:
-/// 1. Load from cache immediately (fast startup)
-/// 2. If cache is stale or missing, fetch from GitHub
-/// 3. Save to cache for next startup
+/// Each outdated file is only written after the user confirms; the
+/// original is kept as a backup next to it.
+fn offer_migrations(paths: &[PathBuf]) -> anyhow::Result<()> {
+    for path in paths {
+        let Some(plan) = MigrationPlan::for_file(path)? else {
+            continue;
+        };
+        println!(
+            "{} uses config format version {}; this whip uses version {CURRENT_VERSION}:",
+            path.display(),
+            plan.from_version()
+        );
+        for step in plan.steps() {
+            println!("  - {step}");
+        }
+        let question = format!(
+            "Upgrade it? The original is kept as {}",
+            plan.backup_path().display()
+        );
+        if confirm(&question)? {
+            plan.apply()?;
+            println!("Upgraded {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Asks a yes/no question on the terminal; anything but "y" is a no.
+fn confirm(question: &str) -> std::io::Result<bool> {
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Builds the initial board from cached issues.
 ///
-/// Only includes issues that have a `whip/*` status label.
-async fn load_github_board(config: &Config) -> anyhow::Result<KanbanBoard> {
-    let cache = IssueCache::new()?;
-    let mut board = KanbanBoard::new();
+/// Returns the board, the initial status of each repository, and the
+/// repositories that must be fetched because their cache is missing or
+/// older than the polling interval. Only cached data is used, so this
+/// never touches the network.
+fn load_cached_board(config: &Config) -> (KanbanBoard, RepoStatuses, Vec<Repository>) {
+    let mut board = KanbanBoard::with_lanes(config.lane_definitions());
+    let mut statuses = RepoStatuses::default();
+    let mut to_fetch = Vec::new();
+
+    let cache = IssueCache::new().ok();
 
     // Determine cache staleness threshold from config
     let max_age = Duration::from_secs(u64::from(config.polling.effective_interval(true)));
 
     for repo in &config.repositories {
-        let owner = repo.owner();
-        let repo_name = repo.repo();
-
-        eprint!("Loading issues from {owner}/{repo_name}... ");
-
-        // Try to load from cache first
-        if let Ok(Some(cached)) = cache.load(owner, repo_name)
-            && !cached.is_older_than(max_age)
-        {
-            // Cache is fresh, use it
-            let count = cached.tasks.len();
-            for task in cached.tasks {
-                board.add_task(task);
+        let host = api_host(config.api_url_for(repo));
+        let cached = cache.as_ref().and_then(|cache| {
+            cache
+                .clone()
+                .for_host(&host)
+                .load(repo.owner(), repo.repo())
+                .ok()
+                .flatten()
+        });
+
+        let status = match cached {
+            Some(cached) => {
+                let status = if cached.is_older_than(max_age) {
+                    RepoStatus::Stale
+                } else {
+                    RepoStatus::Fresh
+                };
+                for task in cached.tasks {
+                    board.add_task(task);
+                }
+                status
             }
-            eprintln!("{count} issues (from cache)");
-            continue;
+            None => RepoStatus::Loading,
+        };
+
+        if status.is_pending() {
+            to_fetch.push(repo.clone());
         }
+        statuses.set(config.repo_key(repo), status);
+    }
 
-        // Cache is stale or missing, fetch from GitHub
-        let token = resolve_token(repo, config.github_token.as_deref()).await;
-        let authenticated = token.is_some();
-        let token = token.map(SecretString::from);
+    (board, statuses, to_fetch)
+}
 
-        match GitHubClient::new(token).await {
-            Ok(client) => {
-                let options = FetchOptions::default();
-                match client.fetch_issues(owner, repo_name, &options).await {
-                    Ok(issues) => {
-                        // Only include issues with whip/* labels
-                        let tasks: Vec<_> = issues
-                            .iter()
-                            .filter_map(|issue| issue_to_task(issue, owner, repo_name))
-                            .collect();
-
-                        let count = tasks.len();
-
-                        // Save to cache
-                        let cached = CachedIssues::new(tasks.clone(), None);
-                        let _ = cache.save(owner, repo_name, &cached);
-
-                        for task in tasks {
-                            board.add_task(task);
-                        }
-
-                        let auth_note = if authenticated {
-                            ""
-                        } else {
-                            " (unauthenticated)"
-                        };
-                        eprintln!("{count} whip-labeled issues{auth_note}");
-                    }
-                    Err(e) => {
-                        eprintln!("failed: {e}");
-                        // Try to use stale cache as fallback
-                        if let Ok(Some(cached)) = cache.load(owner, repo_name) {
-                            let count = cached.tasks.len();
-                            eprintln!("  Using stale cache: {count} issues");
-                            for task in cached.tasks {
-                                board.add_task(task);
-                            }
-                        }
-                    }
+/// Fetches a repository's issues in the background.
+///
+/// The source of the resolved token is reported to the app first, so the
+/// settings panel can show it. On success the cache is updated and the
+/// tasks are sent to the app; on failure the error is reported and the app
+/// keeps its current data. A configured token source that fails is
+/// reported as a failed credential check and an authentication error,
+/// without fetching. A token command's cached token that GitHub rejects is
+/// dropped, so the command runs again on the next fetch.
+fn spawn_fetch(config: &Config, repo: Repository, events: UnboundedSender<RepoEvent>) {
+    let config = config.clone();
+    tokio::spawn(async move {
+        let key = config.repo_key(&repo);
+        let resolved = match resolve_token_with_source(&repo, &config).await {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                if let Some(check) = failed_token_check(&e) {
+                    let _ = events.send(RepoEvent::CredentialsChecked {
+                        repo: key.clone(),
+                        check,
+                    });
                 }
+                let _ = events.send(RepoEvent::Failed {
+                    repo: key,
+                    kind: FetchErrorKind::Auth,
+                    error: e.to_string(),
+                });
+                return;
             }
+        };
+        // The app may already have exited; nothing left to update then
+        let _ = events.send(RepoEvent::Authenticated {
+            repo: key.clone(),
+            source: resolved.as_ref().map(|resolved| resolved.source.clone()),
+        });
+        let (token, source) = resolved
+            .map(|resolved| (resolved.token, resolved.source))
+            .unzip();
+
+        let event = match fetch_repository(&config, &repo, token).await {
+            Ok(tasks) => RepoEvent::Loaded { repo: key, tasks },
             Err(e) => {
-                eprintln!("failed to create client: {e}");
+                // A rotated or expired token is fetched afresh next time
+                if e.kind() == FetchErrorKind::Auth
+                    && let Some(source) = &source
+                {
+                    forget_rejected_token(&repo, &config, source);
+                }
+                RepoEvent::Failed {
+                    repo: key,
+                    kind: e.kind(),
+                    error: e.to_string(),
+                }
+            }
+        };
+        let _ = events.send(event);
+    });
+}
+
+/// Tests a repository's credentials in the background.
+///
+/// Resolves the repository's token and asks GitHub who it belongs to and
+/// which scopes it grants. GitHub App installation tokens belong to no
+/// user, so for them GitHub is asked which permissions they grant in the
+/// repository instead. The outcome is sent to the app for display in the
+/// settings panel.
+fn spawn_credential_test(config: &Config, repo: Repository, events: UnboundedSender<RepoEvent>) {
+    let config = config.clone();
+    tokio::spawn(async move {
+        let check = match resolve_token_with_source(&repo, &config).await {
+            // Resolution only fails with the error of a token source
+            Err(e) => failed_token_check(&e).unwrap_or(CredentialCheck::Unauthenticated),
+            Ok(None) => CredentialCheck::Unauthenticated,
+            Ok(Some(ResolvedToken { token, source })) => {
+                let api_url = config.api_url_for(&repo);
+                match GitHubClient::with_base_url(Some(SecretString::from(token)), api_url).await {
+                    Ok(client) => check_credentials(&client, &repo, source).await,
+                    Err(e) => CredentialCheck::Failed {
+                        source,
+                        error: first_line(&e),
+                    },
+                }
             }
+        };
+        // The app may already have exited; nothing left to update then
+        let _ = events.send(RepoEvent::CredentialsChecked {
+            repo: config.repo_key(&repo),
+            check,
+        });
+    });
+}
+
+/// Asks GitHub what a resolved token may do.
+async fn check_credentials(
+    client: &GitHubClient,
+    repo: &Repository,
+    source: TokenSource,
+) -> CredentialCheck {
+    if source == TokenSource::GitHubApp {
+        match client.repository_access(repo.owner(), repo.repo()).await {
+            Ok(Some(access)) => CredentialCheck::ValidInstallation {
+                source,
+                missing_permissions: access
+                    .missing_permissions()
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                permissions: access.permissions,
+            },
+            // A resolved token always yields an authenticated client
+            Ok(None) => CredentialCheck::Unauthenticated,
+            Err(e) => CredentialCheck::Failed {
+                source,
+                error: first_line(&e),
+            },
+        }
+    } else {
+        match client.token_info().await {
+            Ok(Some(info)) => CredentialCheck::Valid {
+                source,
+                missing_scopes: info
+                    .missing_scopes()
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                login: info.login,
+                scopes: info.scopes,
+            },
+            // A resolved token always yields an authenticated client
+            Ok(None) => CredentialCheck::Unauthenticated,
+            Err(e) => CredentialCheck::Failed {
+                source,
+                error: first_line(&e),
+            },
         }
     }
+}
 
-    Ok(board)
+/// Returns the first line of an error's message, for display on one line.
+fn first_line(error: &impl std::fmt::Display) -> String {
+    error
+        .to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
 }
 
-/// Force-refreshes issues from GitHub, bypassing the cache.
+/// Returns the failed credential check for a token source that failed.
 ///
-/// Used when the user explicitly requests a refresh (Ctrl+R).
-async fn refresh_github_board(config: &Config) -> anyhow::Result<KanbanBoard> {
-    let cache = IssueCache::new()?;
-    let mut board = KanbanBoard::new();
+/// Returns `None` if the error did not come from a token source.
+fn failed_token_check(error: &ConfigError) -> Option<CredentialCheck> {
+    match error {
+        ConfigError::TokenSourceFailed {
+            token_source,
+            error,
+        } => Some(CredentialCheck::Failed {
+            source: token_source.clone(),
+            error: first_line(error),
+        }),
+        _ => None,
+    }
+}
+
+/// Plans label syncs in the background, reporting the plans to the app.
+fn spawn_label_plan(config: &Config, events: UnboundedSender<RepoEvent>) {
+    let config = config.clone();
+    tokio::spawn(async move {
+        plan_labels_for_repositories(&config, &events).await;
+    });
+}
 
+/// Plans the label changes for all configured repositories.
+///
+/// Checks that all repositories have their status labels (the configured
+/// ones, or the standard `whip/*` labels) with consistent colors and
+/// descriptions. Nothing is written: label changes are visible to everyone
+/// on the repository, so the app lists them and `whip sync-labels` applies
+/// them after showing the diff and asking. Repositories without a token are
+/// skipped, since applying the changes requires write access; so are those
+/// whose token source fails, which the fetch already reports.
+async fn plan_labels_for_repositories(config: &Config, events: &UnboundedSender<RepoEvent>) {
     for repo in &config.repositories {
         let owner = repo.owner();
         let repo_name = repo.repo();
 
-        // Always fetch from GitHub, ignore cache
-        let token = resolve_token(repo, config.github_token.as_deref()).await;
-        let token = token.map(SecretString::from);
+        // Need a token to sync labels (write access required)
+        let Ok(Some(token)) = resolve_token(repo, config).await else {
+            continue;
+        };
 
-        match GitHubClient::new(token).await {
+        let api_url = config.api_url_for(repo);
+        let result = match GitHubClient::with_base_url(Some(SecretString::from(token)), api_url)
+            .await
+        {
             Ok(client) => {
-                let options = FetchOptions::default();
-                match client.fetch_issues(owner, repo_name, &options).await {
-                    Ok(issues) => {
-                        // Only include issues with whip/* labels
-                        let tasks: Vec<_> = issues
-                            .iter()
-                            .filter_map(|issue| issue_to_task(issue, owner, repo_name))
-                            .collect();
-
-                        // Update cache
-                        let cached = CachedIssues::new(tasks.clone(), None);
-                        let _ = cache.save(owner, repo_name, &cached);
-
-                        for task in tasks {
-                            board.add_task(task);
-                        }
-                    }
-                    Err(e) => {
-                        // On refresh failure, try stale cache
-                        if let Ok(Some(cached)) = cache.load(owner, repo_name) {
-                            for task in cached.tasks {
-                                board.add_task(task);
-                            }
-                        } else {
-                            return Err(e.into());
-                        }
-                    }
-                }
+                plan_label_sync(&client, owner, repo_name, &config.status_labels_for(repo)).await
             }
-            Err(e) => {
-                return Err(e.into());
-            }
-        }
+            Err(e) => Err(e),
+        };
+
+        let event = match result {
+            Ok(plan) => RepoEvent::LabelSyncPlanned {
+                repo: config.repo_key(repo),
+                changes: plan
+                    .changes()
+                    .iter()
+                    .map(|change| {
+                        change
+                            .to_string()
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                    .collect(),
+            },
+            Err(e) => RepoEvent::Failed {
+                repo: config.repo_key(repo),
+                kind: e.kind(),
+                error: format!("label sync failed: {e}"),
+            },
+        };
+        let _ = events.send(event);
+    }
+}
+
+/// Fetches issues for a repository from GitHub, bypassing the cache.
+///
+/// Only includes issues that have one of the repository's status labels.
+/// The fetched tasks are saved to the cache for the next startup, keyed by
+/// the repository's API host. Requests are authenticated with `token` if
+/// one was resolved.
+async fn fetch_repository(
+    config: &Config,
+    repo: &Repository,
+    token: Option<String>,
+) -> whip_github::Result<Vec<whip_protocol::Task>> {
+    let owner = repo.owner();
+    let repo_name = repo.repo();
+
+    let api_url = config.api_url_for(repo);
+    let client = GitHubClient::with_base_url(token.map(SecretString::from), api_url).await?;
+
+    let issues = client
+        .fetch_issues(owner, repo_name, &FetchOptions::default())
+        .await?;
+
+    // Only include issues with a status label
+    let status_labels = config.status_labels_for(repo);
+    let tasks: Vec<_> = issues
+        .iter()
+        .filter_map(|issue| issue_to_task(issue, client.host(), owner, repo_name, &status_labels))
+        .collect();
+
+    // Update cache; a failure here only costs a slower next startup
+    if let Ok(cache) = IssueCache::new().map(|cache| cache.for_host(client.host())) {
+        let cached = CachedIssues::new(tasks.clone(), None);
+        let _ = cache.save(owner, repo_name, &cached);
     }
 
-    Ok(board)
+    Ok(tasks)
 }
@@ -2,7 +2,8 @@
 
 use std::fs;
 use tempfile::TempDir;
-use whip_config::{Config, PollingConfig, Repository};
+use whip_config::migrate::CURRENT_VERSION;
+use whip_config::{Config, Credentials, PollingConfig, Repository};
 
 #[tokio::test]
 async fn config_load_from_json5_file() {
@@ -44,17 +45,33 @@ async fn config_save_and_reload() {
     let config_path = dir.path().join("config.json");
 
     let original = Config {
+        version: CURRENT_VERSION,
         repositories: vec![
             Repository::new("owner1", "repo1"),
             Repository::with_token("owner2", "repo2", "ghp_secret"),
         ],
         polling: PollingConfig::with_interval(90),
         github_token: Some("ghp_global".to_string()),
+        github_token_env: None,
+        github_token_command: None,
+        github_api_url: None,
+        github_app: None,
+        labels: None,
+        lanes: None,
+        filters: Vec::new(),
+        keymap: Default::default(),
         sync_labels: true,
+        source: None,
     };
 
-    original.save_to(&config_path).unwrap();
-    let loaded = Config::load_from(&config_path).unwrap();
+    let credentials_path = dir.path().join("credentials.json");
+    original
+        .save_with_credentials(&config_path, &credentials_path)
+        .unwrap();
+    assert!(!fs::read_to_string(&config_path).unwrap().contains("ghp_"));
+
+    let mut loaded = Config::load_from(&config_path).unwrap();
+    loaded.apply_credentials(&Credentials::load_from(&credentials_path).unwrap());
 
     assert_eq!(original.repositories.len(), loaded.repositories.len());
     assert_eq!(
@@ -159,10 +176,20 @@ fn config_add_remove_repository() {
 fn config_validation() {
     // Valid config
     let valid = Config {
+        version: CURRENT_VERSION,
         repositories: vec![Repository::new("owner", "repo")],
         polling: PollingConfig::with_interval(60),
         github_token: Some("ghp_xxx".to_string()),
+        github_token_env: None,
+        github_token_command: None,
+        github_api_url: None,
+        github_app: None,
+        labels: None,
+        lanes: None,
+        filters: Vec::new(),
+        keymap: Default::default(),
         sync_labels: true,
+        source: None,
     };
     assert!(valid.validate().is_ok());
 